@@ -68,6 +68,12 @@
 //!
 //! This will select a field on a component and pass it to a [`Curve`] with a type that matches the field.
 //!
+//! ## Reflected Fields
+//!
+//! When the component isn't known at compile time, for example in animations authored in data
+//! files, [`ReflectedField`] selects a field by the type path of the component and a reflection
+//! path, resolved through the type registry. It is used with [`AnimatableCurve`] as well.
+//!
 //! ## Animatable Properties
 //!
 //! Animation of arbitrary aspects of entities can be accomplished using [`AnimatableProperty`] in
@@ -84,7 +90,7 @@
 //! [`animated_field`]: crate::animated_field
 
 use core::{
-    any::TypeId,
+    any::{Any, TypeId},
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
 };
@@ -96,14 +102,21 @@ use crate::{
     prelude::{Animatable, BlendInput},
     AnimationEntityMut, AnimationEvaluationError,
 };
-use bevy_ecs::component::{Component, Mutable};
+use alloc::sync::Arc;
+use bevy_ecs::{
+    component::{Component, Mutable},
+    reflect::ReflectComponent,
+};
 use bevy_math::curve::{
     cores::{UnevenCore, UnevenCoreError},
     Curve, Interval,
 };
 use bevy_platform::hash::Hashed;
-use bevy_reflect::{FromReflect, Reflect, Reflectable, TypeInfo, Typed};
+use bevy_reflect::{
+    FromReflect, GetPath, ParsedPath, Reflect, Reflectable, TypeInfo, TypeRegistry, Typed,
+};
 use downcast_rs::{impl_downcast, Downcast};
+use thiserror::Error;
 
 /// A trait for exposing a value in an entity so that it can be animated.
 ///
@@ -272,6 +285,130 @@ impl<C: Typed, P, F: Fn(&mut C) -> &mut P + 'static> AnimatedField<C, P, F> {
     }
 }
 
+/// A field of a reflected [`Component`] that can be animated, identified by the
+/// type path of the component and a [`ParsedPath`] to the field.
+///
+/// Unlike [`AnimatedField`], this doesn't require knowing the component type at
+/// compile time: the component is resolved through its [`ReflectComponent`]
+/// registration in the [`TypeRegistry`]. This allows animation clips authored
+/// in data files or by an editor to animate arbitrary fields, including nested
+/// ones such as `stats.speed`. The animated value type `A` must match the type
+/// of the field, and values are blended with [`Animatable`].
+///
+/// Curves animating the same field with an [`AnimatedField`] and a
+/// [`ReflectedField`] use separate evaluators, and so aren't blended together.
+///
+///     # use bevy_animation::animation_curves::*;
+///     # use bevy_ecs::{component::Component, reflect::ReflectComponent};
+///     # use bevy_reflect::{Reflect, TypePath, TypeRegistry};
+///     #[derive(Reflect)]
+///     struct Stats {
+///         speed: f32,
+///     }
+///
+///     #[derive(Component, Reflect)]
+///     #[reflect(Component)]
+///     struct Character {
+///         stats: Stats,
+///     }
+///
+///     # let mut registry = TypeRegistry::new();
+///     # registry.register::<Character>();
+///     let speed = ReflectedField::<f32>::new(
+///         &registry,
+///         Character::type_path(),
+///         "stats.speed",
+///     )
+///     .unwrap();
+///     let curve = AnimatableCurve::new(
+///         speed,
+///         AnimatableKeyframeCurve::new([(0.0, 1.0), (1.0, 2.0)]).unwrap(),
+///     );
+#[derive(Clone)]
+pub struct ReflectedField<A> {
+    reflect_component: ReflectComponent,
+    /// A pre-hashed (component-type-id, field-path) pair, uniquely identifying
+    /// a component field.
+    evaluator_id: Hashed<(TypeId, Arc<ParsedPath>)>,
+    marker: PhantomData<fn() -> A>,
+}
+
+/// An error that occurs when resolving a [`ReflectedField`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReflectedFieldError {
+    /// No type with the given type path is registered.
+    #[error("no type with the type path `{0}` is registered")]
+    UnregisteredType(String),
+    /// The type with the given type path isn't registered as a component.
+    #[error("the type `{0}` is not registered with `ReflectComponent`")]
+    NotAComponent(String),
+    /// The path to the field couldn't be parsed.
+    #[error("invalid field path `{path}`: {error}")]
+    InvalidPath {
+        /// The path to the field.
+        path: String,
+        /// The error that occurred when parsing the path.
+        error: String,
+    },
+}
+
+impl<A: Animatable> ReflectedField<A> {
+    /// Resolves the field at `field_path` of the component with the given
+    /// `type_path`, such as `my_game::Character` and `stats.speed`.
+    ///
+    /// The format of `field_path` is described in [`GetPath`].
+    ///
+    /// [`GetPath`]: bevy_reflect::GetPath
+    pub fn new(
+        registry: &TypeRegistry,
+        type_path: &str,
+        field_path: &str,
+    ) -> Result<Self, ReflectedFieldError> {
+        let registration = registry
+            .get_with_type_path(type_path)
+            .ok_or_else(|| ReflectedFieldError::UnregisteredType(type_path.to_owned()))?;
+        let reflect_component = registration
+            .data::<ReflectComponent>()
+            .ok_or_else(|| ReflectedFieldError::NotAComponent(type_path.to_owned()))?
+            .clone();
+        let path =
+            ParsedPath::parse(field_path).map_err(|error| ReflectedFieldError::InvalidPath {
+                path: field_path.to_owned(),
+                error: error.to_string(),
+            })?;
+        Ok(Self {
+            reflect_component,
+            evaluator_id: Hashed::new((registration.type_id(), Arc::new(path))),
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<A> AnimatableProperty for ReflectedField<A>
+where
+    A: Animatable,
+{
+    type Property = A;
+
+    fn get_mut<'a>(
+        &self,
+        entity: &'a mut AnimationEntityMut,
+    ) -> Result<&'a mut A, AnimationEvaluationError> {
+        let (component_type, path) = &*self.evaluator_id;
+        let component = self.reflect_component.reflect_mut(entity).ok_or(
+            AnimationEvaluationError::ComponentNotPresent(*component_type),
+        )?;
+        component
+            .into_inner()
+            .path_mut::<A>(&**path)
+            .map_err(|_| AnimationEvaluationError::PropertyNotPresent(TypeId::of::<A>()))
+    }
+
+    fn evaluator_id(&self) -> EvaluatorId<'_> {
+        EvaluatorId::ReflectedField(&self.evaluator_id)
+    }
+}
+
 /// This trait collects the additional requirements on top of [`Curve<T>`] needed for a
 /// curve to be used as an [`AnimationCurve`].
 pub trait AnimationCompatibleCurve<T>: Curve<T> + Debug + Clone + Reflectable {}
@@ -389,6 +526,16 @@ where
             });
         Ok(())
     }
+
+    fn sample_into(&self, t: f32, value: &mut dyn Any) -> bool {
+        match value.downcast_mut::<P::Property>() {
+            Some(value) => {
+                *value = self.curve.sample_clamped(t);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl<A: Animatable> AnimationCurveEvaluator for AnimatableCurveEvaluator<A> {
@@ -422,7 +569,7 @@ impl<A: Animatable> AnimationCurveEvaluator for AnimatableCurveEvaluator<A> {
 }
 
 #[derive(Reflect)]
-struct BasicAnimationCurveEvaluator<A>
+pub(crate) struct BasicAnimationCurveEvaluator<A>
 where
     A: Animatable,
 {
@@ -456,7 +603,24 @@ impl<A> BasicAnimationCurveEvaluator<A>
 where
     A: Animatable,
 {
-    fn combine(
+    /// Pushes the value of a clip node onto the stack.
+    pub(crate) fn push(&mut self, value: A, weight: f32, graph_node: AnimationNodeIndex) {
+        self.stack.push(BasicAnimationCurveEvaluatorStackElement {
+            value,
+            weight,
+            graph_node,
+        });
+    }
+
+    /// Pops the evaluated value off the stack, and clears it.
+    pub(crate) fn pop(&mut self) -> Option<A> {
+        let value = self.stack.pop().map(|element| element.value);
+        self.stack.clear();
+        self.blend_register = None;
+        value
+    }
+
+    pub(crate) fn combine(
         &mut self,
         graph_node: AnimationNodeIndex,
         additive: bool,
@@ -528,7 +692,7 @@ where
         }
     }
 
-    fn push_blend_register(
+    pub(crate) fn push_blend_register(
         &mut self,
         weight: f32,
         graph_node: AnimationNodeIndex,
@@ -600,6 +764,17 @@ pub trait AnimationCurve: Debug + Send + Sync + 'static {
         weight: f32,
         graph_node: AnimationNodeIndex,
     ) -> Result<(), AnimationEvaluationError>;
+
+    /// Samples the curve at the given time `t` into `value`, if `value` has
+    /// the type of the values of the curve, and returns whether it did.
+    ///
+    /// This reads curves outside of the animation graph, for example to
+    /// extract [root motion](crate::root_motion). The default implementation
+    /// returns `false`.
+    fn sample_into(&self, t: f32, value: &mut dyn Any) -> bool {
+        let _ = (t, value);
+        false
+    }
 }
 
 /// The [`EvaluatorId`] is used to look up the [`AnimationCurveEvaluator`] for an [`AnimatableProperty`].
@@ -613,6 +788,9 @@ pub enum EvaluatorId<'a> {
     // IMPLEMENTATION NOTE: The Hashed<(TypeId, usize) is intentionally cheap to clone, as it will be cloned per frame by the evaluator
     // Switching the field index `usize` for something like a field name `String` would probably be too expensive to justify
     ComponentField(&'a Hashed<(TypeId, usize)>),
+    /// Corresponds to a field of a component type at a reflection path, as
+    /// animated by a [`ReflectedField`].
+    ReflectedField(&'a Hashed<(TypeId, Arc<ParsedPath>)>),
     /// Corresponds to a custom property of a given type. This should be the [`TypeId`]
     /// of the custom [`AnimatableProperty`].
     Type(TypeId),
@@ -793,6 +971,12 @@ macro_rules! animated_field {
 
 #[cfg(test)]
 mod tests {
+    use bevy_ecs::{
+        system::{Query, RunSystemOnce},
+        world::World,
+    };
+    use bevy_reflect::TypePath;
+
     use super::*;
 
     #[test]
@@ -807,4 +991,130 @@ mod tests {
         let _ = AnimatedField::new_unchecked("1", |b: &mut B| &mut b.1);
         let _ = AnimatedField::new_unchecked("2", |b: &mut B| &mut b.2);
     }
+
+    #[derive(Reflect)]
+    struct Stats {
+        speed: f32,
+    }
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Character {
+        stats: Stats,
+    }
+
+    #[test]
+    fn reflected_field_resolves_nested_fields() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Character>();
+
+        assert_eq!(
+            ReflectedField::<f32>::new(&registry, "my_game::Unknown", "speed").err(),
+            Some(ReflectedFieldError::UnregisteredType(
+                "my_game::Unknown".to_owned()
+            ))
+        );
+        assert!(matches!(
+            ReflectedField::<f32>::new(&registry, Character::type_path(), "stats..speed"),
+            Err(ReflectedFieldError::InvalidPath { .. })
+        ));
+
+        let speed =
+            ReflectedField::<f32>::new(&registry, Character::type_path(), "stats.speed").unwrap();
+        let wrong_type =
+            ReflectedField::<bool>::new(&registry, Character::type_path(), "stats.speed").unwrap();
+
+        let mut world = World::new();
+        let entity = world
+            .spawn(Character {
+                stats: Stats { speed: 1.0 },
+            })
+            .id();
+        world
+            .run_system_once(move |mut entities: Query<AnimationEntityMut>| {
+                let mut entity = entities.get_mut(entity).unwrap();
+                *speed.get_mut(&mut entity).unwrap() = 2.0;
+                assert!(matches!(
+                    wrong_type.get_mut(&mut entity),
+                    Err(AnimationEvaluationError::PropertyNotPresent(_))
+                ));
+            })
+            .unwrap();
+        assert_eq!(world.get::<Character>(entity).unwrap().stats.speed, 2.0);
+    }
+
+    #[test]
+    fn reflected_field_curves_blend() {
+        use bevy_app::{App, TaskPoolPlugin};
+        use bevy_asset::{AssetPlugin, Assets};
+        use bevy_ecs::{name::Name, reflect::AppTypeRegistry};
+        use bevy_time::Time;
+
+        use crate::{
+            graph::{AnimationGraph, AnimationGraphHandle},
+            AnimatedBy, AnimationClip, AnimationPlayer, AnimationPlugin, AnimationTargetId,
+        };
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            AnimationPlugin,
+        ))
+        .init_resource::<Time>()
+        .register_type::<Character>();
+
+        // Each clip resolves the field separately, so their evaluators must
+        // still be keyed by the same component and path.
+        let target_id = AnimationTargetId::from_name(&Name::new("character"));
+        let clip = |speed: f32| {
+            let registry = app.world().resource::<AppTypeRegistry>().read();
+            let field =
+                ReflectedField::<f32>::new(&registry, Character::type_path(), "stats.speed")
+                    .unwrap();
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_target(
+                target_id,
+                AnimatableCurve::new(
+                    field,
+                    AnimatableKeyframeCurve::new([(0.0, speed), (1.0, speed)]).unwrap(),
+                ),
+            );
+            clip
+        };
+        let (slow, fast) = (clip(1.0), clip(3.0));
+        let mut clips = app.world_mut().resource_mut::<Assets<AnimationClip>>();
+        let (slow, fast) = (clips.add(slow), clips.add(fast));
+
+        let mut graph = AnimationGraph::new();
+        let slow = graph.add_clip(slow, 0.75, graph.root);
+        let fast = graph.add_clip(fast, 0.25, graph.root);
+        let graph = app
+            .world_mut()
+            .resource_mut::<Assets<AnimationGraph>>()
+            .add(graph);
+
+        let mut player = AnimationPlayer::default();
+        player.play(slow).repeat();
+        player.play(fast).repeat();
+        let player = app
+            .world_mut()
+            .spawn((player, AnimationGraphHandle(graph)))
+            .id();
+        let character = app
+            .world_mut()
+            .spawn((
+                Character {
+                    stats: Stats { speed: 0.0 },
+                },
+                target_id,
+                AnimatedBy(player),
+            ))
+            .id();
+
+        app.update();
+        app.update();
+        let speed = app.world().get::<Character>(character).unwrap().stats.speed;
+        assert!((speed - 1.5).abs() < 1e-5, "blended speed was {speed}");
+    }
 }
@@ -13,10 +13,13 @@ use bevy_asset::{
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     component::Component,
+    entity::Entity,
+    hierarchy::Children,
     message::MessageReader,
+    name::Name,
     reflect::ReflectComponent,
     resource::Resource,
-    system::{Res, ResMut},
+    system::{Query, Res, ResMut, SystemParam},
 };
 use bevy_platform::collections::HashMap;
 use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
@@ -199,6 +202,17 @@ pub struct AnimationGraphNode {
     /// [Add]: AnimationNodeType::Add
     /// [active animation weight]: crate::ActiveAnimation::weight
     pub weight: f32,
+
+    /// Per-target factors multiplied with the [`weight`] of this node.
+    ///
+    /// Animation targets that aren't in this map use a factor of 1.0. A factor
+    /// of 0.0 behaves like masking the target out, and fractional factors let
+    /// a layer fade out gradually along a skeleton, for example from the upper
+    /// body into the lower body. Like masks, the factors of a node also apply
+    /// to its descendants.
+    ///
+    /// [`weight`]: Self::weight
+    pub target_weights: HashMap<AnimationTargetId, f32>,
 }
 
 /// Animation node data specific to the type of node (clip, blend, or add).
@@ -368,6 +382,13 @@ pub struct ThreadedAnimationGraph {
     /// A 1 in bit position N indicates that this node doesn't animate any
     /// targets of mask group N.
     pub computed_masks: Vec<u64>,
+
+    /// A mapping from node index to the [`AnimationGraphNode::target_weights`]
+    /// of that node and its ancestors, multiplied together.
+    ///
+    /// Animation targets that aren't in the map of a node have a factor of
+    /// 1.0. Clips whose factor for a target is 0.0 don't animate that target.
+    pub computed_target_weights: Vec<HashMap<AnimationTargetId, f32>>,
 }
 
 /// A version of [`AnimationGraph`] suitable for serializing as an asset.
@@ -400,6 +421,9 @@ pub struct SerializedAnimationGraphNode {
     pub mask: AnimationMask,
     /// Corresponds to the `weight` field on [`AnimationGraphNode`].
     pub weight: f32,
+    /// Corresponds to the `target_weights` field on [`AnimationGraphNode`].
+    #[serde(default)]
+    pub target_weights: HashMap<AnimationTargetId, f32>,
 }
 
 /// A version of [`AnimationNodeType`] suitable for serializing as part of a
@@ -477,6 +501,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Clip(clip),
             mask: 0,
             weight,
+            target_weights: HashMap::default(),
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -497,6 +522,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Clip(clip),
             mask,
             weight,
+            target_weights: HashMap::default(),
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -536,6 +562,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Blend,
             mask: 0,
             weight,
+            target_weights: HashMap::default(),
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -559,6 +586,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Blend,
             mask,
             weight,
+            target_weights: HashMap::default(),
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -580,6 +608,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Add,
             mask: 0,
             weight,
+            target_weights: HashMap::default(),
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -603,6 +632,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Add,
             mask,
             weight,
+            target_weights: HashMap::default(),
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -669,6 +699,21 @@ impl AnimationGraph {
     pub fn add_target_to_mask_group(&mut self, target: AnimationTargetId, mask_group: u32) {
         *self.mask_groups.entry(target).or_default() |= 1 << mask_group;
     }
+
+    /// Adds multiple animation targets (bones) to the mask group with the
+    /// given ID.
+    ///
+    /// This is typically used with the targets of a skeleton sub-tree, as
+    /// returned by [`AnimationTargetHierarchy::subtree_of`].
+    pub fn add_targets_to_mask_group(
+        &mut self,
+        targets: impl IntoIterator<Item = AnimationTargetId>,
+        mask_group: u32,
+    ) {
+        for target in targets {
+            self.add_target_to_mask_group(target, mask_group);
+        }
+    }
 }
 
 impl AnimationGraphNode {
@@ -709,6 +754,102 @@ impl AnimationGraphNode {
     pub fn remove_mask_group(&mut self, group: u32) -> &mut Self {
         self.remove_mask(1 << group)
     }
+
+    /// Returns the factor multiplied with the weight of this node when
+    /// animating the given `target`.
+    ///
+    /// See [`AnimationGraphNode::target_weights`].
+    pub fn target_weight(&self, target: AnimationTargetId) -> f32 {
+        self.target_weights.get(&target).copied().unwrap_or(1.0)
+    }
+
+    /// Sets the factor multiplied with the weight of this node and its
+    /// descendants when animating the given `target`.
+    pub fn set_target_weight(&mut self, target: AnimationTargetId, weight: f32) -> &mut Self {
+        self.target_weights.insert(target, weight);
+        self
+    }
+
+    /// Sets the same factor for all of the given `targets`.
+    ///
+    /// This is typically used with the targets of a skeleton sub-tree, as
+    /// returned by [`AnimationTargetHierarchy::subtree_of`].
+    pub fn set_target_weights(
+        &mut self,
+        targets: impl IntoIterator<Item = AnimationTargetId>,
+        weight: f32,
+    ) -> &mut Self {
+        for target in targets {
+            self.set_target_weight(target, weight);
+        }
+        self
+    }
+}
+
+/// A [`SystemParam`] that resolves skeleton sub-trees to the
+/// [`AnimationTargetId`]s of their bones, for building masks and per-target
+/// weights.
+///
+/// Bones are found by their [`Name`] among the descendants of an entity, such
+/// as the root of a glTF scene:
+///
+/// ```
+/// # use bevy_animation::{graph::{AnimationGraph, AnimationTargetHierarchy}};
+/// # use bevy_ecs::prelude::*;
+/// fn mask_upper_body(
+///     In((scene, mut graph)): In<(Entity, AnimationGraph)>,
+///     hierarchy: AnimationTargetHierarchy,
+/// ) -> AnimationGraph {
+///     if let Some(spine) = hierarchy.find_by_name(scene, "Spine2") {
+///         graph.add_targets_to_mask_group(hierarchy.subtree_of(spine), 0);
+///     }
+///     graph
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct AnimationTargetHierarchy<'w, 's> {
+    names: Query<'w, 's, &'static Name>,
+    targets: Query<'w, 's, &'static AnimationTargetId>,
+    children: Query<'w, 's, &'static Children>,
+}
+
+impl AnimationTargetHierarchy<'_, '_> {
+    /// Returns the first entity named `name` among `root` and its
+    /// descendants, in depth-first order.
+    pub fn find_by_name(&self, root: Entity, name: &str) -> Option<Entity> {
+        iter::once(root)
+            .chain(self.children.iter_descendants_depth_first(root))
+            .find(|&entity| {
+                self.names
+                    .get(entity)
+                    .is_ok_and(|entity_name| entity_name.as_str() == name)
+            })
+    }
+
+    /// Returns the [`AnimationTargetId`]s of `entity` and all of its
+    /// descendants.
+    ///
+    /// Entities without an [`AnimationTargetId`] are skipped, but their
+    /// descendants are still visited.
+    pub fn subtree_of(&self, entity: Entity) -> impl Iterator<Item = AnimationTargetId> + '_ {
+        iter::once(entity)
+            .chain(self.children.iter_descendants(entity))
+            .filter_map(|entity| self.targets.get(entity).ok().copied())
+    }
+
+    /// Returns the [`AnimationTargetId`]s of the sub-tree rooted at the first
+    /// entity named `name` among `root` and its descendants.
+    ///
+    /// The iterator is empty if no such entity exists.
+    pub fn subtree_by_name(
+        &self,
+        root: Entity,
+        name: &str,
+    ) -> impl Iterator<Item = AnimationTargetId> + '_ {
+        self.find_by_name(root, name)
+            .into_iter()
+            .flat_map(|entity| self.subtree_of(entity))
+    }
 }
 
 impl Index<AnimationNodeIndex> for AnimationGraph {
@@ -731,6 +872,7 @@ impl Default for AnimationGraphNode {
             node_type: Default::default(),
             mask: 0,
             weight: 1.0,
+            target_weights: HashMap::default(),
         }
     }
 }
@@ -782,6 +924,7 @@ impl AssetLoader for AnimationGraphAssetLoader {
                 },
                 mask: serialized_node.mask,
                 weight: serialized_node.weight,
+                target_weights: serialized_node.target_weights.clone(),
             });
         }
         for edge in serialized_animation_graph.graph.raw_edges() {
@@ -815,6 +958,7 @@ impl TryFrom<AnimationGraph> for SerializedAnimationGraph {
             serialized_graph.add_node(SerializedAnimationGraphNode {
                 weight: node.weight,
                 mask: node.mask,
+                target_weights: node.target_weights.clone(),
                 node_type: match node.node_type {
                     AnimationNodeType::Clip(ref clip) => match clip.path() {
                         Some(path) => SerializedAnimationNodeType::Clip(path.clone()),
@@ -872,6 +1016,7 @@ pub(crate) fn thread_animation_graphs(
                     &animation_graph.graph,
                     animation_graph.root,
                     0,
+                    None,
                 );
 
                 // Write in the threaded graph.
@@ -911,6 +1056,10 @@ impl ThreadedAnimationGraph {
 
         self.computed_masks.clear();
         self.computed_masks.extend(iter::repeat_n(0, node_count));
+
+        self.computed_target_weights.clear();
+        self.computed_target_weights
+            .resize_with(node_count, HashMap::default);
     }
 
     /// Recursively constructs the [`ThreadedAnimationGraph`] for the subtree
@@ -918,17 +1067,30 @@ impl ThreadedAnimationGraph {
     ///
     /// `mask` specifies the computed mask of the parent node. (It could be
     /// fetched from the [`Self::computed_masks`] field, but we pass it
-    /// explicitly as a micro-optimization.)
+    /// explicitly as a micro-optimization.) `parent` is the parent node, if
+    /// any, whose computed target weights are inherited.
     fn build_from(
         &mut self,
         graph: &AnimationDiGraph,
         node_index: AnimationNodeIndex,
         mut mask: u64,
+        parent: Option<AnimationNodeIndex>,
     ) {
+        let node = graph.node_weight(node_index).unwrap();
+
         // Accumulate the mask.
-        mask |= graph.node_weight(node_index).unwrap().mask;
+        mask |= node.mask;
         self.computed_masks[node_index.index()] = mask;
 
+        // Accumulate the target weights.
+        let mut target_weights = parent
+            .map(|parent| self.computed_target_weights[parent.index()].clone())
+            .unwrap_or_default();
+        for (&target, &weight) in &node.target_weights {
+            *target_weights.entry(target).or_insert(1.0) *= weight;
+        }
+        self.computed_target_weights[node_index.index()] = target_weights;
+
         // Gather up the indices of our children, and sort them.
         let mut kids: SmallVec<[AnimationNodeIndex; 8]> = graph
             .neighbors_directed(node_index, Direction::Outgoing)
@@ -942,10 +1104,72 @@ impl ThreadedAnimationGraph {
 
         // Recurse. (This is a postorder traversal.)
         for kid in kids.into_iter().rev() {
-            self.build_from(graph, kid, mask);
+            self.build_from(graph, kid, mask, Some(node_index));
         }
 
         // Finally, push our index.
         self.threaded_graph.push(node_index);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{hierarchy::ChildOf, system::RunSystemOnce, world::World};
+
+    use super::*;
+
+    #[test]
+    fn target_weights_accumulate_down_the_graph() {
+        let spine = AnimationTargetId::from_name(&Name::new("Spine"));
+        let head = AnimationTargetId::from_name(&Name::new("Head"));
+
+        let mut graph = AnimationGraph::new();
+        let layer = graph.add_blend(1.0, graph.root);
+        let clip = graph.add_clip(Handle::default(), 1.0, layer);
+        graph[layer].set_target_weights([spine, head], 0.5);
+        graph[clip].set_target_weight(spine, 0.5);
+
+        let mut threaded_graph = ThreadedAnimationGraph::default();
+        threaded_graph.init(&graph);
+        threaded_graph.build_from(&graph.graph, graph.root, 0, None);
+
+        let weights = &threaded_graph.computed_target_weights;
+        assert!(weights[graph.root.index()].is_empty());
+        assert_eq!(weights[layer.index()].get(&spine), Some(&0.5));
+        assert_eq!(weights[clip.index()].get(&spine), Some(&0.25));
+        assert_eq!(weights[clip.index()].get(&head), Some(&0.5));
+        assert_eq!(graph[clip].target_weight(head), 1.0);
+    }
+
+    #[test]
+    fn subtree_targets_are_resolved_by_name() {
+        let mut world = World::new();
+        let target = |name: &str| AnimationTargetId::from_name(&Name::new(name.to_owned()));
+        let root = world.spawn(Name::new("Root")).id();
+        let hips = world
+            .spawn((Name::new("Hips"), target("Hips"), ChildOf(root)))
+            .id();
+        let spine = world
+            .spawn((Name::new("Spine2"), target("Spine2"), ChildOf(hips)))
+            .id();
+        let neck = world.spawn((Name::new("Neck"), ChildOf(spine))).id();
+        world.spawn((Name::new("Head"), target("Head"), ChildOf(neck)));
+        world.spawn((Name::new("Leg"), target("Leg"), ChildOf(hips)));
+
+        let targets = world
+            .run_system_once(move |hierarchy: AnimationTargetHierarchy| {
+                hierarchy
+                    .subtree_by_name(root, "Spine2")
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        assert_eq!(targets, [target("Spine2"), target("Head")]);
+
+        let missing = world
+            .run_system_once(move |hierarchy: AnimationTargetHierarchy| {
+                hierarchy.find_by_name(root, "Tail")
+            })
+            .unwrap();
+        assert_eq!(missing, None);
+    }
+}
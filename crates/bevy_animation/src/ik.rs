@@ -0,0 +1,581 @@
+//! Inverse kinematics constraints, which adjust the animated pose of bone
+//! chains so that they reach targets.
+//!
+//! Constraints are components placed on the last bone of the chain they solve,
+//! such as a foot or a hand. They run after animations are applied and before
+//! transforms are propagated, and their `weight` blends their result with the
+//! animated pose.
+//!
+//! The solvers themselves are exposed as functions working on joint positions,
+//! so that they can be used without constraints.
+
+use bevy_ecs::{
+    component::Component, entity::Entity, hierarchy::ChildOf, reflect::ReflectComponent,
+    system::Query,
+};
+use bevy_math::{Dir3, Quat, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::Transform;
+use core::f32::consts::PI;
+use smallvec::SmallVec;
+
+/// The target of an inverse kinematics constraint.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum IkTarget {
+    /// The position of an entity.
+    Entity(Entity),
+    /// A position in world space.
+    Position(Vec3),
+}
+
+impl From<Entity> for IkTarget {
+    fn from(entity: Entity) -> Self {
+        Self::Entity(entity)
+    }
+}
+
+impl From<Vec3> for IkTarget {
+    fn from(position: Vec3) -> Self {
+        Self::Position(position)
+    }
+}
+
+/// Rotates the two parent bones of this bone, such as the hip and knee of a
+/// foot, so that this bone reaches a target.
+///
+/// The chain bends in the plane containing the pole target, or in its current
+/// plane if there's none.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct TwoBoneIk {
+    /// The position this bone reaches.
+    pub target: IkTarget,
+    /// The position that the middle joint of the chain, such as the knee,
+    /// points towards.
+    pub pole: Option<IkTarget>,
+    /// How much the constraint overrides the animated pose, from `0.0` to
+    /// `1.0`.
+    pub weight: f32,
+}
+
+impl TwoBoneIk {
+    /// Creates a constraint reaching the given target, with a weight of `1.0`.
+    pub fn new(target: impl Into<IkTarget>) -> Self {
+        Self {
+            target: target.into(),
+            pole: None,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the pole target of the constraint.
+    pub fn with_pole(mut self, pole: impl Into<IkTarget>) -> Self {
+        self.pole = Some(pole.into());
+        self
+    }
+
+    /// Sets the weight of the constraint.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// An iterative solver used by an [`IkChain`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+pub enum IkSolver {
+    /// Forward And Backward Reaching Inverse Kinematics, see [`solve_fabrik`].
+    #[default]
+    Fabrik,
+    /// Cyclic Coordinate Descent, see [`solve_ccd`].
+    Ccd,
+}
+
+/// Rotates a chain of parent bones of this bone, such as a spine or a tail, so
+/// that this bone reaches a target.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct IkChain {
+    /// The position this bone reaches.
+    pub target: IkTarget,
+    /// The number of parent bones rotated by the constraint.
+    pub bones: usize,
+    /// The solver used.
+    pub solver: IkSolver,
+    /// The maximum number of iterations of the solver.
+    pub iterations: u32,
+    /// The distance to the target under which the solver stops iterating.
+    pub tolerance: f32,
+    /// How much the constraint overrides the animated pose, from `0.0` to
+    /// `1.0`.
+    pub weight: f32,
+}
+
+impl IkChain {
+    /// Creates a constraint rotating the given number of parent bones to reach
+    /// the target, with a weight of `1.0`.
+    pub fn new(target: impl Into<IkTarget>, bones: usize) -> Self {
+        Self {
+            target: target.into(),
+            bones,
+            solver: IkSolver::default(),
+            iterations: 10,
+            tolerance: 0.001,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the solver used.
+    pub fn with_solver(mut self, solver: IkSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    /// Sets the maximum number of iterations of the solver.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the weight of the constraint.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Rotates this bone, such as a head or an eye, so that it looks at a target.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct LookAtIk {
+    /// The position this bone looks at.
+    pub target: IkTarget,
+    /// The local direction this bone looks in.
+    pub forward: Dir3,
+    /// The maximum angle in radians by which the constraint rotates the
+    /// animated pose.
+    pub max_angle: f32,
+    /// How much the constraint overrides the animated pose, from `0.0` to
+    /// `1.0`.
+    pub weight: f32,
+}
+
+impl LookAtIk {
+    /// Creates a constraint looking at the given target along
+    /// [`Dir3::NEG_Z`], without angle limit and with a weight of `1.0`.
+    pub fn new(target: impl Into<IkTarget>) -> Self {
+        Self {
+            target: target.into(),
+            forward: Dir3::NEG_Z,
+            max_angle: PI,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the local direction this bone looks in.
+    pub fn with_forward(mut self, forward: Dir3) -> Self {
+        self.forward = forward;
+        self
+    }
+
+    /// Sets the maximum angle in radians by which the constraint rotates the
+    /// animated pose.
+    pub fn with_max_angle(mut self, max_angle: f32) -> Self {
+        self.max_angle = max_angle;
+        self
+    }
+
+    /// Sets the weight of the constraint.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Solves a chain of two bones analytically, returning the new positions of
+/// its three joints.
+///
+/// The root joint doesn't move, and the end joint is placed as close to the
+/// `target` as the lengths of the bones allow. The middle joint bends towards
+/// the `pole`, or in the current plane of the chain if there's none.
+pub fn solve_two_bone(positions: [Vec3; 3], target: Vec3, pole: Option<Vec3>) -> [Vec3; 3] {
+    let [root, mid, _] = positions;
+    let upper = root.distance(mid);
+    let lower = mid.distance(positions[2]);
+    let Ok(direction) = Dir3::new(target - root) else {
+        return positions;
+    };
+    let distance = root
+        .distance(target)
+        .clamp((upper - lower).abs(), upper + lower);
+    if upper * distance <= 0.0 {
+        return positions;
+    }
+
+    // The direction in which the middle joint bends, perpendicular to the
+    // direction of the target.
+    let bend = (pole.unwrap_or(mid) - root)
+        .reject_from_normalized(*direction)
+        .try_normalize()
+        .unwrap_or_else(|| direction.any_orthonormal_vector());
+    // The law of cosines gives the angle between the upper bone and the target.
+    let cos = ((upper * upper + distance * distance - lower * lower) / (2.0 * upper * distance))
+        .clamp(-1.0, 1.0);
+    let sin = (1.0 - cos * cos).sqrt();
+
+    [
+        root,
+        root + (direction * cos + bend * sin) * upper,
+        root + direction * distance,
+    ]
+}
+
+/// Solves a chain of joints with Forward And Backward Reaching Inverse
+/// Kinematics, moving the last joint towards the `target`.
+///
+/// The first joint doesn't move, and the distances between joints are kept.
+/// The solver stops after `iterations`, or once the last joint is within
+/// `tolerance` of the target.
+pub fn solve_fabrik(positions: &mut [Vec3], target: Vec3, iterations: u32, tolerance: f32) {
+    let count = positions.len();
+    if count < 2 {
+        return;
+    }
+    let lengths: SmallVec<[f32; 8]> = positions
+        .windows(2)
+        .map(|joints| joints[0].distance(joints[1]))
+        .collect();
+    let root = positions[0];
+
+    // Stretch the chain towards targets out of reach.
+    if root.distance(target) >= lengths.iter().sum::<f32>() {
+        for i in 1..count {
+            let direction = (target - positions[i - 1]).normalize_or_zero();
+            positions[i] = positions[i - 1] + direction * lengths[i - 1];
+        }
+        return;
+    }
+
+    for _ in 0..iterations {
+        if positions[count - 1].distance(target) <= tolerance {
+            break;
+        }
+        positions[count - 1] = target;
+        for i in (0..count - 1).rev() {
+            let direction = (positions[i] - positions[i + 1]).normalize_or_zero();
+            positions[i] = positions[i + 1] + direction * lengths[i];
+        }
+        positions[0] = root;
+        for i in 1..count {
+            let direction = (positions[i] - positions[i - 1]).normalize_or_zero();
+            positions[i] = positions[i - 1] + direction * lengths[i - 1];
+        }
+    }
+}
+
+/// Solves a chain of joints with Cyclic Coordinate Descent, moving the last
+/// joint towards the `target`.
+///
+/// The first joint doesn't move, and the distances between joints are kept.
+/// The solver stops after `iterations`, or once the last joint is within
+/// `tolerance` of the target.
+pub fn solve_ccd(positions: &mut [Vec3], target: Vec3, iterations: u32, tolerance: f32) {
+    let count = positions.len();
+    if count < 2 {
+        return;
+    }
+
+    for _ in 0..iterations {
+        if positions[count - 1].distance(target) <= tolerance {
+            break;
+        }
+        // Rotate the chain around each joint, from the last to the first, so
+        // that the last joint points towards the target.
+        for joint in (0..count - 1).rev() {
+            let pivot = positions[joint];
+            let (Some(end), Some(target)) = (
+                (positions[count - 1] - pivot).try_normalize(),
+                (target - pivot).try_normalize(),
+            ) else {
+                continue;
+            };
+            let rotation = Quat::from_rotation_arc(end, target);
+            for position in &mut positions[joint + 1..] {
+                *position = pivot + rotation * (*position - pivot);
+            }
+        }
+    }
+}
+
+/// Returns the rotation that makes a bone with the given `rotation` look in
+/// the `direction`, where `forward` is the direction the bone looks in
+/// locally.
+///
+/// The bone is rotated by at most `max_angle` radians.
+pub fn look_at_rotation(rotation: Quat, forward: Dir3, direction: Dir3, max_angle: f32) -> Quat {
+    let look_at = Quat::from_rotation_arc(rotation * *forward, *direction) * rotation;
+    rotation.rotate_towards(look_at, max_angle)
+}
+
+/// Returns the transform of an entity in world space, computed from the
+/// [`Transform`]s of its ancestors.
+///
+/// [`GlobalTransform`](bevy_transform::components::GlobalTransform)s can't be
+/// used, since they haven't been propagated since animations were applied.
+fn world_transform(
+    entity: Entity,
+    transforms: &Query<&mut Transform>,
+    parents: &Query<&ChildOf>,
+) -> Transform {
+    let mut transform = transforms.get(entity).copied().unwrap_or_default();
+    let mut current = entity;
+    while let Ok(child_of) = parents.get(current) {
+        current = child_of.parent();
+        if let Ok(parent) = transforms.get(current) {
+            transform = *parent * transform;
+        }
+    }
+    transform
+}
+
+fn target_position(
+    target: IkTarget,
+    transforms: &Query<&mut Transform>,
+    parents: &Query<&ChildOf>,
+) -> Vec3 {
+    match target {
+        IkTarget::Entity(entity) => world_transform(entity, transforms, parents).translation,
+        IkTarget::Position(position) => position,
+    }
+}
+
+/// A chain of bones, from its root to its end.
+struct Chain {
+    bones: SmallVec<[Entity; 8]>,
+    /// The transforms of the bones in world space.
+    world: SmallVec<[Transform; 8]>,
+    /// The rotation in world space of the parent of the root bone.
+    parent_rotation: Quat,
+}
+
+impl Chain {
+    /// Gathers the chain made of `end` and `bones` of its ancestors.
+    fn new(
+        end: Entity,
+        bones: usize,
+        transforms: &Query<&mut Transform>,
+        parents: &Query<&ChildOf>,
+    ) -> Option<Self> {
+        let mut chain: SmallVec<[Entity; 8]> = SmallVec::with_capacity(bones + 1);
+        chain.push(end);
+        for _ in 0..bones {
+            chain.push(parents.get(*chain.last()?).ok()?.parent());
+        }
+        chain.reverse();
+
+        let parent = parents.get(chain[0]).ok().map(ChildOf::parent);
+        let parent = parent
+            .map(|parent| world_transform(parent, transforms, parents))
+            .unwrap_or_default();
+        let mut world = SmallVec::with_capacity(chain.len());
+        let mut transform = parent;
+        for &bone in &chain {
+            transform = transform * *transforms.get(bone).ok()?;
+            world.push(transform);
+        }
+
+        Some(Self {
+            bones: chain,
+            world,
+            parent_rotation: parent.rotation,
+        })
+    }
+
+    fn positions(&self) -> SmallVec<[Vec3; 8]> {
+        self.world
+            .iter()
+            .map(|transform| transform.translation)
+            .collect()
+    }
+
+    /// Rotates the bones so that their joints move to the solved positions,
+    /// blending with their current rotations by `weight`.
+    fn apply(&self, solved: &[Vec3], weight: f32, transforms: &mut Query<&mut Transform>) {
+        let mut parent_rotation = self.parent_rotation;
+        for (i, &bone) in self.bones[..self.bones.len() - 1].iter().enumerate() {
+            let current = self.world[i + 1].translation - self.world[i].translation;
+            let solved_direction = solved[i + 1] - solved[i];
+            let delta = match (current.try_normalize(), solved_direction.try_normalize()) {
+                (Some(current), Some(solved)) => Quat::from_rotation_arc(current, solved),
+                _ => Quat::IDENTITY,
+            };
+            let Ok(mut transform) = transforms.get_mut(bone) else {
+                return;
+            };
+            let local = parent_rotation.inverse() * delta * self.world[i].rotation;
+            transform.rotation = transform.rotation.slerp(local, weight).normalize();
+            parent_rotation *= transform.rotation;
+        }
+    }
+}
+
+/// A system that applies [`TwoBoneIk`], [`IkChain`] and [`LookAtIk`]
+/// constraints to the animated pose.
+///
+/// Two-bone constraints are solved first, then chains, and finally look-at
+/// constraints.
+pub fn solve_ik_constraints(
+    two_bone_constraints: Query<(Entity, &TwoBoneIk)>,
+    chain_constraints: Query<(Entity, &IkChain)>,
+    look_at_constraints: Query<(Entity, &LookAtIk)>,
+    parents: Query<&ChildOf>,
+    mut transforms: Query<&mut Transform>,
+) {
+    for (entity, constraint) in &two_bone_constraints {
+        if constraint.weight <= 0.0 {
+            continue;
+        }
+        let Some(chain) = Chain::new(entity, 2, &transforms, &parents) else {
+            continue;
+        };
+        let target = target_position(constraint.target, &transforms, &parents);
+        let pole = constraint
+            .pole
+            .map(|pole| target_position(pole, &transforms, &parents));
+        let positions = chain.positions();
+        let solved = solve_two_bone([positions[0], positions[1], positions[2]], target, pole);
+        chain.apply(&solved, constraint.weight.min(1.0), &mut transforms);
+    }
+
+    for (entity, constraint) in &chain_constraints {
+        if constraint.weight <= 0.0 {
+            continue;
+        }
+        let Some(chain) = Chain::new(entity, constraint.bones, &transforms, &parents) else {
+            continue;
+        };
+        let target = target_position(constraint.target, &transforms, &parents);
+        let mut positions = chain.positions();
+        match constraint.solver {
+            IkSolver::Fabrik => solve_fabrik(
+                &mut positions,
+                target,
+                constraint.iterations,
+                constraint.tolerance,
+            ),
+            IkSolver::Ccd => solve_ccd(
+                &mut positions,
+                target,
+                constraint.iterations,
+                constraint.tolerance,
+            ),
+        }
+        chain.apply(&positions, constraint.weight.min(1.0), &mut transforms);
+    }
+
+    for (entity, constraint) in &look_at_constraints {
+        if constraint.weight <= 0.0 {
+            continue;
+        }
+        let target = target_position(constraint.target, &transforms, &parents);
+        let world = world_transform(entity, &transforms, &parents);
+        let Ok(direction) = Dir3::new(target - world.translation) else {
+            continue;
+        };
+        let rotation = look_at_rotation(
+            world.rotation,
+            constraint.forward,
+            direction,
+            constraint.max_angle,
+        );
+        let Ok(mut transform) = transforms.get_mut(entity) else {
+            continue;
+        };
+        // Apply the change of world rotation in the space of the parent.
+        let parent_rotation = world.rotation * transform.rotation.inverse();
+        let local = parent_rotation.inverse() * rotation;
+        transform.rotation = transform
+            .rotation
+            .slerp(local, constraint.weight.min(1.0))
+            .normalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    fn assert_lengths_kept(before: &[Vec3], after: &[Vec3]) {
+        for (before, after) in before.windows(2).zip(after.windows(2)) {
+            assert!((before[0].distance(before[1]) - after[0].distance(after[1])).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn two_bone() {
+        let positions = [Vec3::ZERO, Vec3::Y, Vec3::Y * 2.0];
+        let target = Vec3::new(1.0, 1.0, 0.0);
+        let solved = solve_two_bone(positions, target, None);
+        assert!(solved[2].distance(target) < 1e-4);
+        assert_lengths_kept(&positions, &solved);
+
+        // The middle joint bends towards the pole.
+        let solved = solve_two_bone(positions, Vec3::Y, Some(Vec3::Z));
+        assert!(solved[2].distance(Vec3::Y) < 1e-4);
+        assert!(solved[1].z > 0.8);
+
+        // Targets out of reach straighten the chain.
+        let solved = solve_two_bone(positions, Vec3::X * 5.0, None);
+        assert!(solved[2].distance(Vec3::X * 2.0) < 1e-4);
+    }
+
+    #[test]
+    fn iterative_solvers() {
+        let positions = [Vec3::ZERO, Vec3::Y, Vec3::Y * 2.0, Vec3::Y * 3.0];
+        let target = Vec3::new(1.5, 1.5, 0.5);
+        for solve in [solve_fabrik, solve_ccd] {
+            let mut solved = positions;
+            solve(&mut solved, target, 50, 1e-4);
+            assert!(solved[3].distance(target) < 1e-3);
+            assert_eq!(solved[0], Vec3::ZERO);
+            assert_lengths_kept(&positions, &solved);
+        }
+    }
+
+    #[test]
+    fn look_at_limit() {
+        let rotation = look_at_rotation(Quat::IDENTITY, Dir3::NEG_Z, Dir3::X, PI / 4.0);
+        let forward = rotation * Vec3::NEG_Z;
+        assert!((forward.angle_between(Vec3::NEG_Z) - PI / 4.0).abs() < 1e-4);
+        assert!(forward.x > 0.0);
+    }
+
+    #[test]
+    fn two_bone_constraint() {
+        let mut world = World::new();
+        let target = world.spawn(Transform::from_xyz(1.0, 2.0, 0.0)).id();
+        let root = world.spawn(Transform::from_xyz(0.0, 1.0, 0.0)).id();
+        let mid = world
+            .spawn((Transform::from_xyz(0.0, 1.0, 0.0), ChildOf(root)))
+            .id();
+        let end = world
+            .spawn((
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                ChildOf(mid),
+                TwoBoneIk::new(target).with_pole(Vec3::new(0.0, 2.0, 5.0)),
+            ))
+            .id();
+
+        world.run_system_once(solve_ik_constraints).unwrap();
+
+        let mut transforms = world.query::<&Transform>();
+        let [root, mid, end] =
+            [root, mid, end].map(|entity| *transforms.get(&world, entity).unwrap());
+        let end_world = root * mid * end;
+        assert!(end_world.translation.distance(Vec3::new(1.0, 2.0, 0.0)) < 1e-4);
+        assert!((root * mid).translation.z > 0.0);
+    }
+}
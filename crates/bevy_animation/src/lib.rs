@@ -13,9 +13,14 @@ pub mod animatable;
 pub mod animation_curves;
 pub mod gltf_curves;
 pub mod graph;
+pub mod ik;
 #[cfg(feature = "bevy_mesh")]
 mod morph;
+pub mod retarget;
+pub mod root_motion;
+pub mod state_machine;
 pub mod transition;
+pub mod tween;
 
 mod animation_event;
 mod util;
@@ -42,7 +47,7 @@ use bevy_asset::{Asset, AssetApp, AssetEventSystems, Assets};
 use bevy_ecs::{prelude::*, world::EntityMutExcept};
 use bevy_math::FloatOrd;
 use bevy_platform::{collections::HashMap, hash::NoOpHash};
-use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
+use bevy_reflect::{prelude::ReflectDefault, ParsedPath, Reflect, TypePath};
 use bevy_time::Time;
 use bevy_transform::TransformSystems;
 use bevy_utils::{PreHashMap, PreHashMapExt, TypeIdMap};
@@ -57,14 +62,19 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, animation_curves::*, graph::*, transition::*, AnimationClip,
-        AnimationPlayer, AnimationPlugin, VariableCurve,
+        animatable::*, animation_curves::*, graph::*, ik::*, retarget::*, root_motion::*,
+        state_machine::*, transition::*, tween::*, AnimationClip, AnimationPlayer, AnimationPlugin,
+        VariableCurve,
     };
 }
 
 use crate::{
     animation_curves::AnimationCurve,
     graph::{AnimationGraph, AnimationGraphAssetLoader, AnimationNodeIndex},
+    retarget::{RetargetMap, RetargetMapAssetLoader},
+    state_machine::{
+        advance_state_machines, AnimationStateMachine, AnimationStateMachineAssetLoader,
+    },
     transition::{advance_transitions, expire_completed_transitions},
 };
 use alloc::sync::Arc;
@@ -722,6 +732,8 @@ pub struct AnimationEvaluationState {
 struct AnimationCurveEvaluators {
     component_property_curve_evaluators:
         PreHashMap<(TypeId, usize), Box<dyn AnimationCurveEvaluator>>,
+    reflected_field_curve_evaluators:
+        PreHashMap<(TypeId, Arc<ParsedPath>), Box<dyn AnimationCurveEvaluator>>,
     type_id_curve_evaluators: TypeIdMap<Box<dyn AnimationCurveEvaluator>>,
 }
 
@@ -732,6 +744,9 @@ impl AnimationCurveEvaluators {
             EvaluatorId::ComponentField(component_property) => self
                 .component_property_curve_evaluators
                 .get_mut(component_property),
+            EvaluatorId::ReflectedField(field) => {
+                self.reflected_field_curve_evaluators.get_mut(field)
+            }
             EvaluatorId::Type(type_id) => self.type_id_curve_evaluators.get_mut(&type_id),
         }
         .map(|e| &mut **e)
@@ -747,6 +762,9 @@ impl AnimationCurveEvaluators {
             EvaluatorId::ComponentField(component_property) => &mut **self
                 .component_property_curve_evaluators
                 .get_or_insert_with(component_property, func),
+            EvaluatorId::ReflectedField(field) => &mut **self
+                .reflected_field_curve_evaluators
+                .get_or_insert_with(field, func),
             EvaluatorId::Type(type_id) => match self.type_id_curve_evaluators.entry(type_id) {
                 bevy_platform::collections::hash_map::Entry::Occupied(occupied_entry) => {
                     &mut **occupied_entry.into_mut()
@@ -762,6 +780,7 @@ impl AnimationCurveEvaluators {
 #[derive(Default)]
 struct CurrentEvaluators {
     component_properties: PreHashMap<(TypeId, usize), ()>,
+    reflected_fields: PreHashMap<(TypeId, Arc<ParsedPath>), ()>,
     type_ids: TypeIdMap<()>,
 }
 
@@ -770,6 +789,11 @@ impl CurrentEvaluators {
         self.component_properties
             .keys()
             .map(EvaluatorId::ComponentField)
+            .chain(
+                self.reflected_fields
+                    .keys()
+                    .map(EvaluatorId::ReflectedField),
+            )
             .chain(self.type_ids.keys().copied().map(EvaluatorId::Type))
     }
 
@@ -781,6 +805,10 @@ impl CurrentEvaluators {
             (visit)(EvaluatorId::ComponentField(&key))?;
         }
 
+        for (key, _) in self.reflected_fields.drain() {
+            (visit)(EvaluatorId::ReflectedField(&key))?;
+        }
+
         for (key, _) in self.type_ids.drain() {
             (visit)(EvaluatorId::Type(key))?;
         }
@@ -794,6 +822,9 @@ impl CurrentEvaluators {
             EvaluatorId::ComponentField(component_property) => {
                 self.component_properties.insert(*component_property, ());
             }
+            EvaluatorId::ReflectedField(field) => {
+                self.reflected_fields.insert(field.clone(), ());
+            }
             EvaluatorId::Type(type_id) => {
                 self.type_ids.insert(type_id, ());
             }
@@ -1096,7 +1127,8 @@ pub fn animate_targets(
                         }
 
                         if let Err(err) = evaluation_state.push_blend_register_all(
-                            animation_graph_node.weight,
+                            animation_graph_node.weight
+                                * animation_graph_node.target_weight(target_id),
                             animation_graph_node_index,
                         ) {
                             warn!("Animation blending failed: {:?}", err);
@@ -1117,7 +1149,8 @@ pub fn animate_targets(
                         }
 
                         if let Err(err) = evaluation_state.push_blend_register_all(
-                            animation_graph_node.weight,
+                            animation_graph_node.weight
+                                * animation_graph_node.target_weight(target_id),
                             animation_graph_node_index,
                         ) {
                             warn!("Animation blending failed: {:?}", err);
@@ -1140,6 +1173,10 @@ pub fn animate_targets(
                                 & threaded_animation_graph.computed_masks
                                     [animation_graph_node_index.index()])
                                 != 0
+                            || threaded_animation_graph.computed_target_weights
+                                [animation_graph_node_index.index()]
+                            .get(&target_id)
+                                == Some(&0.0)
                         {
                             continue;
                         }
@@ -1175,7 +1212,9 @@ pub fn animate_targets(
                             continue;
                         };
 
-                        let weight = active_animation.weight * animation_graph_node.weight;
+                        let weight = active_animation.weight
+                            * animation_graph_node.weight
+                            * animation_graph_node.target_weight(target_id);
                         let seek_time = active_animation.seek_time;
 
                         for curve in curves {
@@ -1225,16 +1264,24 @@ impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<AnimationClip>()
             .init_asset::<AnimationGraph>()
+            .init_asset::<AnimationStateMachine>()
+            .init_asset::<RetargetMap>()
             .init_asset_loader::<AnimationGraphAssetLoader>()
+            .init_asset_loader::<AnimationStateMachineAssetLoader>()
+            .init_asset_loader::<RetargetMapAssetLoader>()
             .register_asset_reflect::<AnimationClip>()
             .register_asset_reflect::<AnimationGraph>()
+            .register_asset_reflect::<AnimationStateMachine>()
+            .register_asset_reflect::<RetargetMap>()
             .init_resource::<ThreadedAnimationGraphs>()
             .add_systems(
                 PostUpdate,
                 (
                     graph::thread_animation_graphs.before(AssetEventSystems),
+                    advance_state_machines,
                     advance_transitions,
                     advance_animations,
+                    retarget::record_rest_poses,
                     // TODO: `animate_targets` can animate anything, so
                     // ambiguity testing currently considers it ambiguous with
                     // every other system in `PostUpdate`. We may want to move
@@ -1247,6 +1294,10 @@ impl Plugin for AnimationPlugin {
                         .ambiguous_with_all(),
                     #[cfg(not(feature = "bevy_mesh"))]
                     animate_targets.ambiguous_with_all(),
+                    root_motion::extract_root_motion,
+                    retarget::retarget_animations,
+                    tween::advance_tweens,
+                    ik::solve_ik_constraints,
                     trigger_untargeted_animation_events,
                     expire_completed_transitions,
                 )
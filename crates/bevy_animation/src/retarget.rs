@@ -0,0 +1,587 @@
+//! Animation retargeting, which plays the animations of a source skeleton on a
+//! target skeleton with different proportions.
+//!
+//! A [`RetargetMap`] asset maps the bone names of the source skeleton to the
+//! bone names of the target skeleton. The [`Retarget`] component, placed on the
+//! root of the target skeleton, copies the animated pose of the source
+//! skeleton every frame after animations are applied.
+//!
+//! Rotations are transferred as deltas from the [`RestPose`] in the space of the
+//! skeleton root, which compensates for bones whose local axes differ between
+//! the two skeletons. The rest poses should still be similar, for example both
+//! T-poses. Only the translation of the root bone is transferred, scaled by the
+//! ratio of the heights of the root bones, so the other bones keep the lengths
+//! of the target skeleton.
+
+use core::fmt::Write;
+use std::io;
+
+use bevy_asset::{io::Reader, Asset, AssetEvent, AssetLoader, Assets, Handle, LoadContext};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    hierarchy::ChildOf,
+    message::MessageReader,
+    name::Name,
+    query::{Added, Changed, Or, Without},
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res},
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
+use bevy_transform::components::{GlobalTransform, Transform};
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{graph::AnimationTargetHierarchy, AnimatedBy};
+
+/// A mapping from the bones of a source skeleton to the bones of a target
+/// skeleton, used by [`Retarget`].
+///
+/// Bones are identified by their [`Name`](bevy_ecs::name::Name). Source bones
+/// that aren't in the map, or whose target bone doesn't exist, aren't
+/// retargeted.
+///
+/// This is serializable, and can be loaded from RON with the
+/// [`RetargetMapAssetLoader`]:
+///
+/// ```ron
+/// (
+///     bones: {
+///         "mixamorig:Hips": "Hips",
+///         "mixamorig:Spine": "Spine",
+///     },
+///     root: Some("mixamorig:Hips"),
+/// )
+/// ```
+#[derive(Asset, Clone, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, Default)]
+pub struct RetargetMap {
+    /// The names of the target bones, keyed by the names of the source bones.
+    pub bones: HashMap<String, String>,
+    /// The name of the source bone whose translation is retargeted, typically
+    /// the hips.
+    ///
+    /// The translations of all other bones are left untouched.
+    #[serde(default)]
+    pub root: Option<String>,
+    /// The factor applied to the translation of the root bone.
+    ///
+    /// If `None`, this is the height of the target root bone divided by the
+    /// height of the source root bone in their rest poses, where heights are
+    /// measured along the Y axis of the skeleton roots.
+    #[serde(default)]
+    pub translation_scale: Option<f32>,
+}
+
+/// A component that plays the animated pose of a source skeleton on the
+/// skeleton it's placed on, according to a [`RetargetMap`].
+///
+/// This is placed on the root entity of the target skeleton, and `source` is
+/// the root entity of the source skeleton, which is typically animated by an
+/// [`AnimationPlayer`](crate::AnimationPlayer) and hidden.
+///
+/// The bones are resolved and their rest poses are recorded once the map is
+/// loaded and at least one of its bones exists in both skeletons. The rest
+/// poses are taken from the [`RestPose`]s of the bones, or from their current
+/// transforms for bones that don't have one yet. Target bones are given a
+/// [`RestPose`] when they're first resolved, so that resolving them again
+/// doesn't pick up the retargeted pose. The bones are resolved again after
+/// [`Retarget::reset`], or when the map is modified.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct Retarget {
+    /// The root entity of the source skeleton.
+    pub source: Entity,
+    /// The mapping from source bones to target bones.
+    pub map: Handle<RetargetMap>,
+    #[reflect(ignore)]
+    bones: Vec<RetargetedBone>,
+    translation_scale: f32,
+    /// Whether resolving the bones failed, in which case it's only attempted
+    /// again once the map or the hierarchy changes.
+    #[reflect(ignore)]
+    unresolved: bool,
+}
+
+/// The pose of an animated entity before any animation was applied to it.
+///
+/// This is recorded by [`record_rest_poses`] when an entity becomes
+/// [`AnimatedBy`] a player, and by [`retarget_animations`] for the target bones
+/// of a [`Retarget`]. It's used as the rest pose of the bones of both
+/// skeletons, and can be inserted manually to override the recorded pose.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct RestPose(pub Transform);
+
+/// A bone resolved in both skeletons, with its rest poses relative to the
+/// skeleton roots.
+#[derive(Clone, Debug)]
+struct RetargetedBone {
+    source: Entity,
+    target: Entity,
+    source_rest: GlobalTransform,
+    target_rest: GlobalTransform,
+    root: bool,
+    depth: usize,
+}
+
+/// An [`AssetLoader`] that can load [`RetargetMap`]s as assets.
+///
+/// The canonical extension for [`RetargetMap`]s is `.retarget.ron`. Plain
+/// `.retarget` is supported as well.
+#[derive(Default, TypePath)]
+pub struct RetargetMapAssetLoader;
+
+/// Errors that can occur when deserializing retarget maps from RON.
+#[derive(Error, Debug)]
+pub enum RetargetMapLoadError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An error occurred in RON deserialization.
+    #[error(transparent)]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error(transparent)]
+    SpannedRon(#[from] SpannedError),
+}
+
+impl RetargetMap {
+    /// Maps the source bone named `source` to the target bone named `target`.
+    pub fn with_bone(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+        self.bones.insert(source.into(), target.into());
+        self
+    }
+
+    /// Sets the name of the source bone whose translation is retargeted.
+    pub fn with_root(mut self, root: impl Into<String>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// Serializes the retarget map to RON.
+    pub fn save<W>(&self, writer: &mut W) -> Result<(), ron::Error>
+    where
+        W: Write,
+    {
+        let mut ron_serializer = ron::ser::Serializer::new(writer, None)?;
+        self.serialize(&mut ron_serializer)
+    }
+}
+
+impl Retarget {
+    /// Creates a component retargeting the skeleton rooted at `source` with the
+    /// given map.
+    pub fn new(source: Entity, map: Handle<RetargetMap>) -> Self {
+        Self {
+            source,
+            map,
+            bones: Vec::new(),
+            translation_scale: 1.0,
+            unresolved: false,
+        }
+    }
+
+    /// Forgets the resolved bones, so that they are resolved and their rest
+    /// poses are recorded again.
+    pub fn reset(&mut self) {
+        self.bones.clear();
+        self.unresolved = false;
+    }
+
+    /// Finds the bones of `map` in both skeletons and records their rest
+    /// poses.
+    fn resolve(
+        &mut self,
+        target_root: Entity,
+        map: &RetargetMap,
+        hierarchy: &AnimationTargetHierarchy,
+        transforms: &Query<&mut Transform>,
+        rest_poses: &Query<&RestPose>,
+        parents: &Query<&ChildOf>,
+    ) {
+        let rest = |entity| match rest_poses.get(entity) {
+            Ok(rest_pose) => Some(rest_pose.0),
+            Err(_) => transforms.get(entity).ok().copied(),
+        };
+        self.bones.clear();
+        for (source_name, target_name) in &map.bones {
+            let (Some(source), Some(target)) = (
+                hierarchy.find_by_name(self.source, source_name),
+                hierarchy.find_by_name(target_root, target_name),
+            ) else {
+                continue;
+            };
+            let (Some(source_rest), Some(target_rest)) = (
+                root_relative_transform(source, self.source, &rest, parents),
+                root_relative_transform(target, target_root, &rest, parents),
+            ) else {
+                continue;
+            };
+            self.bones.push(RetargetedBone {
+                source,
+                target,
+                source_rest,
+                target_rest,
+                root: map.root.as_ref() == Some(source_name),
+                depth: parents.iter_ancestors(target).count(),
+            });
+        }
+
+        // Parents are retargeted before their children, whose rotations are
+        // relative to them.
+        self.bones.sort_by_key(|bone| bone.depth);
+        self.unresolved = self.bones.is_empty();
+
+        self.translation_scale = map.translation_scale.unwrap_or_else(|| {
+            self.bones
+                .iter()
+                .find(|bone| bone.root)
+                .map(|bone| bone.target_rest.translation().y / bone.source_rest.translation().y)
+                .filter(|scale| scale.is_finite())
+                .unwrap_or(1.0)
+        });
+    }
+}
+
+/// Composes the `local` transforms of `entity` and its ancestors below `root`.
+///
+/// Returns `None` if `entity` isn't a descendant of `root`.
+fn root_relative_transform(
+    entity: Entity,
+    root: Entity,
+    local: &impl Fn(Entity) -> Option<Transform>,
+    parents: &Query<&ChildOf>,
+) -> Option<GlobalTransform> {
+    let mut result = GlobalTransform::from(local(entity)?);
+    let mut current = entity;
+    loop {
+        let parent = parents.get(current).ok()?.parent();
+        if parent == root {
+            return Some(result);
+        }
+        result = GlobalTransform::from(local(parent)?) * result;
+        current = parent;
+    }
+}
+
+/// Like [`root_relative_transform`], but for the parent of `entity`.
+fn parent_root_relative_transform(
+    entity: Entity,
+    root: Entity,
+    local: &impl Fn(Entity) -> Option<Transform>,
+    parents: &Query<&ChildOf>,
+) -> Option<GlobalTransform> {
+    let parent = parents.get(entity).ok()?.parent();
+    if parent == root {
+        return Some(GlobalTransform::IDENTITY);
+    }
+    root_relative_transform(parent, root, local, parents)
+}
+
+impl AssetLoader for RetargetMapAssetLoader {
+    type Asset = RetargetMap;
+
+    type Settings = ();
+
+    type Error = RetargetMapLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        RetargetMap::deserialize(&mut deserializer)
+            .map_err(|err| deserializer.span_error(err).into())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["retarget", "retarget.ron"]
+    }
+}
+
+/// A system that records the [`RestPose`] of entities that became animated,
+/// before animations are applied to them.
+pub fn record_rest_poses(
+    mut commands: Commands,
+    bones: Query<(Entity, &Transform), (Added<AnimatedBy>, Without<RestPose>)>,
+) {
+    for (entity, transform) in &bones {
+        commands.entity(entity).insert(RestPose(*transform));
+    }
+}
+
+/// A system that copies the pose of the source skeletons of [`Retarget`]
+/// components to their target skeletons.
+pub fn retarget_animations(
+    mut commands: Commands,
+    maps: Res<Assets<RetargetMap>>,
+    mut map_events: MessageReader<AssetEvent<RetargetMap>>,
+    hierarchy: AnimationTargetHierarchy,
+    mut retargets: Query<(Entity, &mut Retarget)>,
+    mut transforms: Query<&mut Transform>,
+    rest_poses: Query<&RestPose>,
+    parents: Query<&ChildOf>,
+    changed_hierarchy: Query<(), Or<(Changed<ChildOf>, Changed<Name>)>>,
+) {
+    let modified_maps: Vec<_> = map_events
+        .read()
+        .filter_map(|event| match *event {
+            AssetEvent::Modified { id } => Some(id),
+            _ => None,
+        })
+        .collect();
+
+    let mut hierarchy_changed = None;
+    for (target_root, mut retarget) in &mut retargets {
+        if modified_maps.contains(&retarget.map.id()) {
+            retarget.reset();
+        }
+        if retarget.bones.is_empty() {
+            let Some(map) = maps.get(&retarget.map) else {
+                continue;
+            };
+            // Looking up bones walks both hierarchies, so failures are only
+            // retried once something they depend on changes.
+            if retarget.unresolved
+                && !maps.is_changed()
+                && !*hierarchy_changed.get_or_insert_with(|| !changed_hierarchy.is_empty())
+            {
+                continue;
+            }
+            retarget.resolve(
+                target_root,
+                map,
+                &hierarchy,
+                &transforms,
+                &rest_poses,
+                &parents,
+            );
+            // The target bones are about to be overwritten, so their current
+            // transforms can't be used as rest poses when resolving again.
+            for bone in &retarget.bones {
+                if rest_poses.contains(bone.target) {
+                    continue;
+                }
+                if let Ok(transform) = transforms.get(bone.target) {
+                    commands.entity(bone.target).insert(RestPose(*transform));
+                }
+            }
+        }
+
+        for bone in &retarget.bones {
+            let current = |entity| transforms.get(entity).ok().copied();
+            let (Some(source), Some(parent)) = (
+                root_relative_transform(bone.source, retarget.source, &current, &parents),
+                parent_root_relative_transform(bone.target, target_root, &current, &parents),
+            ) else {
+                continue;
+            };
+            let Ok(mut transform) = transforms.get_mut(bone.target) else {
+                continue;
+            };
+
+            // Apply the rotation of the source bone from its rest pose to the
+            // rest pose of the target bone, then make it relative to the
+            // parent of the target bone.
+            let delta = source.rotation() * bone.source_rest.rotation().inverse();
+            transform.rotation =
+                (parent.rotation().inverse() * delta * bone.target_rest.rotation()).normalize();
+
+            if bone.root {
+                let translation = bone.target_rest.translation()
+                    + (source.translation() - bone.source_rest.translation())
+                        * retarget.translation_scale;
+                transform.translation = parent.affine().inverse().transform_point3(translation);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{message::Messages, name::Name, system::RunSystemOnce, world::World};
+    use bevy_math::{Quat, Vec3};
+
+    use super::*;
+
+    fn retarget_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Assets<RetargetMap>>();
+        world.init_resource::<Messages<AssetEvent<RetargetMap>>>();
+        world
+    }
+
+    #[test]
+    fn retarget_compensates_rest_poses_and_proportions() {
+        let mut world = retarget_world();
+        let map = world.resource_mut::<Assets<RetargetMap>>().add(
+            RetargetMap::default()
+                .with_bone("Hips", "hips")
+                .with_bone("Spine", "spine")
+                .with_root("Hips"),
+        );
+
+        let source = world.spawn(Transform::default()).id();
+        let source_hips = world
+            .spawn((
+                Name::new("Hips"),
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                ChildOf(source),
+            ))
+            .id();
+        let source_spine = world
+            .spawn((
+                Name::new("Spine"),
+                Transform::from_xyz(0.0, 0.5, 0.0),
+                ChildOf(source_hips),
+            ))
+            .id();
+
+        // The target is twice as tall, and its hips are rotated at rest.
+        let hips_rest = Quat::from_rotation_z(0.5);
+        let target = world.spawn(Retarget::new(source, map)).id();
+        let target_hips = world
+            .spawn((
+                Name::new("hips"),
+                Transform::from_xyz(0.0, 2.0, 0.0).with_rotation(hips_rest),
+                ChildOf(target),
+            ))
+            .id();
+        let target_spine = world
+            .spawn((
+                Name::new("spine"),
+                Transform::from_xyz(0.0, 1.0, 0.0).with_rotation(hips_rest.inverse()),
+                ChildOf(target_hips),
+            ))
+            .id();
+        world.run_system_once(retarget_animations).unwrap();
+
+        // Animate the source.
+        let spine_rotation = Quat::from_rotation_x(0.3);
+        world.get_mut::<Transform>(source_hips).unwrap().translation = Vec3::new(0.5, 1.0, 0.0);
+        world.get_mut::<Transform>(source_spine).unwrap().rotation = spine_rotation;
+        world.run_system_once(retarget_animations).unwrap();
+
+        let hips = *world.get::<Transform>(target_hips).unwrap();
+        let spine = *world.get::<Transform>(target_spine).unwrap();
+        assert!(hips.translation.abs_diff_eq(Vec3::new(1.0, 2.0, 0.0), 1e-5));
+        assert!(hips.rotation.abs_diff_eq(hips_rest, 1e-5));
+        assert!((hips.rotation * spine.rotation).abs_diff_eq(spine_rotation, 1e-5));
+        assert_eq!(spine.translation, Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn retarget_uses_rest_poses_of_animated_source() {
+        let mut world = retarget_world();
+
+        let source = world.spawn(Transform::default()).id();
+        let source_spine = world
+            .spawn((
+                Name::new("Spine"),
+                Transform::from_xyz(0.0, 0.5, 0.0),
+                AnimatedBy(source),
+                ChildOf(source),
+            ))
+            .id();
+        world.run_system_once(record_rest_poses).unwrap();
+
+        // The source is animated before the map is available.
+        let spine_rotation = Quat::from_rotation_x(0.3);
+        world.get_mut::<Transform>(source_spine).unwrap().rotation = spine_rotation;
+
+        let map = world
+            .resource_mut::<Assets<RetargetMap>>()
+            .add(RetargetMap::default().with_bone("Spine", "spine"));
+        let target = world.spawn(Retarget::new(source, map)).id();
+        let target_spine = world
+            .spawn((
+                Name::new("spine"),
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                ChildOf(target),
+            ))
+            .id();
+        world.run_system_once(retarget_animations).unwrap();
+
+        let spine = *world.get::<Transform>(target_spine).unwrap();
+        assert!(spine.rotation.abs_diff_eq(spine_rotation, 1e-5));
+    }
+
+    #[test]
+    fn retarget_keeps_rest_poses_when_resolving_again() {
+        let mut world = retarget_world();
+        let map = world
+            .resource_mut::<Assets<RetargetMap>>()
+            .add(RetargetMap::default().with_bone("Spine", "spine"));
+
+        let source = world.spawn(Transform::default()).id();
+        let source_spine = world
+            .spawn((
+                Name::new("Spine"),
+                Transform::from_xyz(0.0, 0.5, 0.0),
+                AnimatedBy(source),
+                ChildOf(source),
+            ))
+            .id();
+        world.run_system_once(record_rest_poses).unwrap();
+
+        let spine_rest = Quat::from_rotation_z(0.5);
+        let target = world.spawn(Retarget::new(source, map.clone())).id();
+        let target_spine = world
+            .spawn((
+                Name::new("spine"),
+                Transform::from_xyz(0.0, 1.0, 0.0).with_rotation(spine_rest),
+                ChildOf(target),
+            ))
+            .id();
+        let target_chest = world
+            .spawn((
+                Name::new("chest"),
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                ChildOf(target_spine),
+            ))
+            .id();
+
+        let spine_rotation = Quat::from_rotation_x(0.3);
+        world.get_mut::<Transform>(source_spine).unwrap().rotation = spine_rotation;
+        world.run_system_once(retarget_animations).unwrap();
+        let expected = spine_rotation * spine_rest;
+        let spine = *world.get::<Transform>(target_spine).unwrap();
+        assert!(spine.rotation.abs_diff_eq(expected, 1e-5));
+        assert_eq!(
+            world.get::<RestPose>(target_spine).unwrap().0.rotation,
+            spine_rest
+        );
+
+        // Resolving again uses the recorded rest pose rather than the
+        // retargeted transform.
+        world.get_mut::<Retarget>(target).unwrap().reset();
+        world.run_system_once(retarget_animations).unwrap();
+        let spine = *world.get::<Transform>(target_spine).unwrap();
+        assert!(spine.rotation.abs_diff_eq(expected, 1e-5));
+
+        // Modifying the map resolves the bones again.
+        world
+            .resource_mut::<Assets<RetargetMap>>()
+            .get_mut(&map)
+            .unwrap()
+            .bones
+            .insert("Spine".into(), "chest".into());
+        world
+            .resource_mut::<Messages<AssetEvent<RetargetMap>>>()
+            .write(AssetEvent::Modified { id: map.id() });
+        world.run_system_once(retarget_animations).unwrap();
+        let spine = *world.get::<Transform>(target_spine).unwrap();
+        let chest = *world.get::<Transform>(target_chest).unwrap();
+        assert!((spine.rotation * chest.rotation).abs_diff_eq(expected, 1e-5));
+    }
+}
@@ -0,0 +1,477 @@
+//! Root motion, which moves a character by the motion of the root bone of its
+//! animations.
+
+use core::any::TypeId;
+
+use bevy_asset::Assets;
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::{BVec3, Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::components::Transform;
+use smallvec::SmallVec;
+use tracing::warn;
+
+use crate::{
+    animated_field,
+    animation_curves::{
+        AnimatableProperty, AnimatedField, BasicAnimationCurveEvaluator, EvaluatorId,
+    },
+    graph::{
+        AnimationGraph, AnimationGraphHandle, AnimationNodeIndex, AnimationNodeType,
+        ThreadedAnimationGraphs,
+    },
+    ActiveAnimation, AnimatedBy, AnimationClip, AnimationEvaluationError, AnimationPlayer,
+    AnimationTargetId,
+};
+
+/// Extracts the motion of a root bone from the animations of the
+/// [`AnimationPlayer`] on the same entity.
+///
+/// The extracted translation and rotation are removed from the animated pose
+/// of the root bone, which stays where it was at the start of the animations,
+/// and are stored as deltas for gameplay code to apply to the character. The
+/// deltas are blended like the pose, across blend and additive nodes and
+/// transitions, and take looping animations into account.
+///
+/// Deltas are relative to the character at the start of the frame: the
+/// parent of the root bone, turned by the rotation extracted so far. They are
+/// updated in [`PostUpdate`](bevy_app::PostUpdate) every frame and can be
+/// applied with [`RootMotion::apply_to`].
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct RootMotion {
+    /// The animation target, such as the hips, whose motion is extracted.
+    pub target: AnimationTargetId,
+    /// The axes along which the translation of the target is extracted.
+    ///
+    /// By default, the translation is extracted along X and Z, so that
+    /// vertical motion stays in the pose.
+    pub translation_axes: BVec3,
+    /// Whether the rotation of the target around the Y axis is extracted.
+    pub rotation: bool,
+    /// The translation of the target during the last frame, in the local
+    /// space of the character at the start of the frame.
+    pub delta_translation: Vec3,
+    /// The rotation of the target during the last frame.
+    pub delta_rotation: Quat,
+}
+
+impl RootMotion {
+    /// Creates a component extracting the horizontal translation and the
+    /// rotation around the Y axis of the given target.
+    pub fn new(target: AnimationTargetId) -> Self {
+        Self {
+            target,
+            translation_axes: BVec3::new(true, false, true),
+            rotation: true,
+            delta_translation: Vec3::ZERO,
+            delta_rotation: Quat::IDENTITY,
+        }
+    }
+
+    /// Sets the axes along which the translation of the target is extracted.
+    pub fn with_translation_axes(mut self, translation_axes: BVec3) -> Self {
+        self.translation_axes = translation_axes;
+        self
+    }
+
+    /// Sets whether the rotation of the target around the Y axis is extracted.
+    pub fn with_rotation(mut self, rotation: bool) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Moves a transform by the deltas of the last frame, translating it in
+    /// its local space before rotating it.
+    pub fn apply_to(&self, transform: &mut Transform) {
+        transform.translation += transform.rotation * self.delta_translation;
+        transform.rotation = (transform.rotation * self.delta_rotation).normalize();
+    }
+}
+
+/// Returns the ranges of the clip that an animation played during the last
+/// tick, as pairs of start and end times.
+///
+/// A looping animation plays up to the end of the clip and from its start in
+/// the same tick, which results in two ranges.
+fn played_ranges(animation: &ActiveAnimation, duration: f32) -> SmallVec<[(f32, f32); 2]> {
+    let Some(last_seek_time) = animation.last_seek_time else {
+        return SmallVec::new();
+    };
+    if animation.paused || (animation.is_finished() && !animation.just_completed) {
+        return SmallVec::new();
+    }
+    let seek_time = animation.seek_time;
+    let looping = animation.just_completed && !animation.is_finished();
+    match (looping, animation.is_playback_reversed()) {
+        (false, _) => SmallVec::from_slice(&[(last_seek_time, seek_time)]),
+        (true, false) => SmallVec::from_slice(&[(last_seek_time, duration), (0.0, seek_time)]),
+        (true, true) => SmallVec::from_slice(&[(last_seek_time, 0.0), (duration, seek_time)]),
+    }
+}
+
+/// Returns the rotation around the Y axis of a rotation.
+fn yaw(rotation: Quat) -> Quat {
+    let twist = Quat::from_xyzw(0.0, rotation.y, 0.0, rotation.w);
+    if twist.length_squared() > f32::EPSILON {
+        twist.normalize()
+    } else {
+        Quat::IDENTITY
+    }
+}
+
+/// Returns the component field identifying a property, if it's a field.
+fn component_field(evaluator_id: EvaluatorId) -> Option<(TypeId, usize)> {
+    match evaluator_id {
+        EvaluatorId::ComponentField(field) => Some(**field),
+        EvaluatorId::ReflectedField(_) | EvaluatorId::Type(_) => None,
+    }
+}
+
+/// The motion of the root target in a single clip.
+struct ClipRootMotion {
+    delta_translation: Vec3,
+    delta_rotation: Quat,
+    /// The translation from the start of the clip to the current time.
+    offset_translation: Vec3,
+    /// The rotation from the start of the clip to the current time.
+    offset_rotation: Quat,
+}
+
+impl ClipRootMotion {
+    fn new(
+        clip: &AnimationClip,
+        target: AnimationTargetId,
+        animation: &ActiveAnimation,
+        fields: [Option<(TypeId, usize)>; 2],
+    ) -> Self {
+        let curves = clip.curves_for_target(target);
+        let find_curve = |field| {
+            curves?
+                .iter()
+                .find(|curve| component_field(curve.0.evaluator_id()) == field)
+        };
+        let (translation_curve, rotation_curve) = (find_curve(fields[0]), find_curve(fields[1]));
+        let translation = |t| {
+            let mut translation = Vec3::ZERO;
+            if let Some(curve) = translation_curve {
+                curve.0.sample_into(t, &mut translation);
+            }
+            translation
+        };
+        let rotation = |t| {
+            let mut rotation = Quat::IDENTITY;
+            if let Some(curve) = rotation_curve {
+                curve.0.sample_into(t, &mut rotation);
+            }
+            rotation
+        };
+
+        let mut motion = Self {
+            delta_translation: Vec3::ZERO,
+            delta_rotation: Quat::IDENTITY,
+            offset_translation: translation(animation.seek_time) - translation(0.0),
+            offset_rotation: rotation(animation.seek_time) * rotation(0.0).inverse(),
+        };
+        for (start, end) in played_ranges(animation, clip.duration) {
+            // The character has already been turned by the rotation extracted
+            // up to `start`, so the translation is expressed relative to it.
+            let turned = yaw(rotation(start) * rotation(0.0).inverse());
+            let local_translation = turned.inverse() * (translation(end) - translation(start));
+            motion.delta_translation += yaw(motion.delta_rotation) * local_translation;
+            motion.delta_rotation =
+                rotation(end) * rotation(start).inverse() * motion.delta_rotation;
+        }
+        motion
+    }
+}
+
+/// Blends the motions of clips through an animation graph.
+#[derive(Default)]
+struct RootMotionEvaluator {
+    delta_translation: BasicAnimationCurveEvaluator<Vec3>,
+    delta_rotation: BasicAnimationCurveEvaluator<Quat>,
+    offset_translation: BasicAnimationCurveEvaluator<Vec3>,
+    offset_rotation: BasicAnimationCurveEvaluator<Quat>,
+}
+
+impl RootMotionEvaluator {
+    fn push(&mut self, motion: ClipRootMotion, weight: f32, node: AnimationNodeIndex) {
+        self.delta_translation
+            .push(motion.delta_translation, weight, node);
+        self.delta_rotation
+            .push(motion.delta_rotation, weight, node);
+        self.offset_translation
+            .push(motion.offset_translation, weight, node);
+        self.offset_rotation
+            .push(motion.offset_rotation, weight, node);
+    }
+
+    fn combine(
+        &mut self,
+        node: AnimationNodeIndex,
+        additive: bool,
+    ) -> Result<(), AnimationEvaluationError> {
+        self.delta_translation.combine(node, additive)?;
+        self.delta_rotation.combine(node, additive)?;
+        self.offset_translation.combine(node, additive)?;
+        self.offset_rotation.combine(node, additive)
+    }
+
+    fn push_blend_register(
+        &mut self,
+        weight: f32,
+        node: AnimationNodeIndex,
+    ) -> Result<(), AnimationEvaluationError> {
+        self.delta_translation.push_blend_register(weight, node)?;
+        self.delta_rotation.push_blend_register(weight, node)?;
+        self.offset_translation.push_blend_register(weight, node)?;
+        self.offset_rotation.push_blend_register(weight, node)
+    }
+
+    /// Returns the blended motion.
+    fn pop(&mut self) -> Option<ClipRootMotion> {
+        Some(ClipRootMotion {
+            delta_translation: self.delta_translation.pop()?,
+            delta_rotation: self.delta_rotation.pop()?,
+            offset_translation: self.offset_translation.pop()?,
+            offset_rotation: self.offset_rotation.pop()?,
+        })
+    }
+}
+
+/// Evaluates the motion of a target in the animations of a player, like
+/// [`animate_targets`](crate::animate_targets) evaluates the pose.
+fn evaluate_root_motion(
+    player: &AnimationPlayer,
+    graph: &AnimationGraph,
+    threaded_graphs: &ThreadedAnimationGraphs,
+    graph_handle: &AnimationGraphHandle,
+    clips: &Assets<AnimationClip>,
+    target: AnimationTargetId,
+    fields: [Option<(TypeId, usize)>; 2],
+) -> Option<ClipRootMotion> {
+    let threaded_graph = threaded_graphs.0.get(&graph_handle.id())?;
+    let target_mask = graph.mask_groups.get(&target).copied().unwrap_or_default();
+    let mut evaluator = RootMotionEvaluator::default();
+
+    for &node_index in &threaded_graph.threaded_graph {
+        let Some(node) = graph.get(node_index) else {
+            continue;
+        };
+        let result = match node.node_type {
+            AnimationNodeType::Blend | AnimationNodeType::Add => {
+                let additive = matches!(node.node_type, AnimationNodeType::Add);
+                threaded_graph.sorted_edge_ranges[node_index.index()]
+                    .clone()
+                    .try_for_each(|edge_index| {
+                        evaluator
+                            .combine(threaded_graph.sorted_edges[edge_index as usize], additive)
+                    })
+                    .and_then(|()| {
+                        evaluator.push_blend_register(
+                            node.weight * node.target_weight(target),
+                            node_index,
+                        )
+                    })
+            }
+            AnimationNodeType::Clip(ref clip) => {
+                let Some(animation) = player.active_animations.get(&node_index) else {
+                    continue;
+                };
+                if animation.weight == 0.0
+                    || (target_mask & threaded_graph.computed_masks[node_index.index()]) != 0
+                    || threaded_graph.computed_target_weights[node_index.index()].get(&target)
+                        == Some(&0.0)
+                {
+                    continue;
+                }
+                let Some(clip) = clips.get(clip) else {
+                    continue;
+                };
+                let motion = ClipRootMotion::new(clip, target, animation, fields);
+                evaluator.push(
+                    motion,
+                    animation.weight * node.weight * node.target_weight(target),
+                    node_index,
+                );
+                Ok(())
+            }
+        };
+        if let Err(err) = result {
+            warn!("Root motion blending failed: {:?}", err);
+        }
+    }
+
+    evaluator.pop()
+}
+
+/// A system that extracts the motion of the targets of [`RootMotion`]
+/// components from the animated pose.
+pub fn extract_root_motion(
+    clips: Res<Assets<AnimationClip>>,
+    graphs: Res<Assets<AnimationGraph>>,
+    threaded_graphs: Res<ThreadedAnimationGraphs>,
+    mut players: Query<(&AnimationPlayer, &AnimationGraphHandle, &mut RootMotion)>,
+    mut targets: Query<(&AnimationTargetId, &AnimatedBy, &mut Transform)>,
+) {
+    if players.is_empty() {
+        return;
+    }
+    for (_, _, mut root_motion) in &mut players {
+        root_motion.delta_translation = Vec3::ZERO;
+        root_motion.delta_rotation = Quat::IDENTITY;
+    }
+
+    let translation_field: AnimatedField<Transform, Vec3, _> =
+        animated_field!(Transform::translation);
+    let rotation_field: AnimatedField<Transform, Quat, _> = animated_field!(Transform::rotation);
+    let fields = [
+        component_field(translation_field.evaluator_id()),
+        component_field(rotation_field.evaluator_id()),
+    ];
+
+    for (&target, &AnimatedBy(player), mut transform) in &mut targets {
+        let Ok((player, graph_handle, mut root_motion)) = players.get_mut(player) else {
+            continue;
+        };
+        if root_motion.target != target {
+            continue;
+        }
+        // Rotation that isn't extracted stays in the pose, and doesn't turn
+        // the character.
+        let fields = [fields[0], fields[1].filter(|_| root_motion.rotation)];
+        let Some(graph) = graphs.get(graph_handle) else {
+            continue;
+        };
+        let Some(motion) = evaluate_root_motion(
+            player,
+            graph,
+            &threaded_graphs,
+            graph_handle,
+            &clips,
+            target,
+            fields,
+        ) else {
+            continue;
+        };
+
+        let axes = root_motion.translation_axes;
+        root_motion.delta_translation = Vec3::select(axes, motion.delta_translation, Vec3::ZERO);
+        transform.translation -= Vec3::select(axes, motion.offset_translation, Vec3::ZERO);
+        if root_motion.rotation {
+            root_motion.delta_rotation = yaw(motion.delta_rotation);
+            transform.rotation = yaw(motion.offset_rotation).inverse() * transform.rotation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{animation_curves::AnimatableCurve, RepeatAnimation};
+    use bevy_ecs::name::Name;
+    use bevy_math::curve::UnevenSampleAutoCurve;
+    use core::f32::consts::FRAC_PI_2;
+
+    fn walk_clip(target: AnimationTargetId) -> AnimationClip {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                UnevenSampleAutoCurve::new([(0.0, Vec3::ZERO), (1.0, Vec3::new(0.0, 0.0, 2.0))])
+                    .unwrap(),
+            ),
+        );
+        clip
+    }
+
+    #[test]
+    fn looping_clip_motion() {
+        let target = AnimationTargetId::from_name(&Name::new("Hips"));
+        let clip = walk_clip(target);
+        assert_eq!(clip.duration(), 1.0);
+        let translation_field = animated_field!(Transform::translation);
+        let fields = [component_field(translation_field.evaluator_id()), None];
+
+        let mut animation = ActiveAnimation::default();
+        animation.set_repeat(RepeatAnimation::Forever);
+        animation.update(0.75, clip.duration());
+        animation.update(0.5, clip.duration());
+        assert_eq!(
+            played_ranges(&animation, clip.duration()).as_slice(),
+            &[(0.75, 1.0), (0.0, 0.25)]
+        );
+
+        // The motion continues across the end of the clip, while the pose
+        // offset restarts from the start.
+        let motion = ClipRootMotion::new(&clip, target, &animation, fields);
+        assert!(motion
+            .delta_translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, 1.0), 1e-5));
+        assert!(motion
+            .offset_translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, 0.5), 1e-5));
+    }
+
+    #[test]
+    fn turning_clip_motion() {
+        // Turns a quarter to the left, then walks forward in the new direction.
+        let target = AnimationTargetId::from_name(&Name::new("Hips"));
+        let turned = Quat::from_rotation_y(FRAC_PI_2);
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                UnevenSampleAutoCurve::new([
+                    (0.0, Vec3::ZERO),
+                    (0.5, Vec3::ZERO),
+                    (1.0, turned * Vec3::Z),
+                ])
+                .unwrap(),
+            ),
+        );
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::rotation),
+                UnevenSampleAutoCurve::new([(0.0, Quat::IDENTITY), (0.5, turned), (1.0, turned)])
+                    .unwrap(),
+            ),
+        );
+        let translation_field = animated_field!(Transform::translation);
+        let rotation_field = animated_field!(Transform::rotation);
+        let fields = [
+            component_field(translation_field.evaluator_id()),
+            component_field(rotation_field.evaluator_id()),
+        ];
+
+        let mut animation = ActiveAnimation::default();
+        let mut root_motion = RootMotion::new(target);
+        let mut transform = Transform::default();
+        for _ in 0..4 {
+            animation.update(0.25, clip.duration());
+            let motion = ClipRootMotion::new(&clip, target, &animation, fields);
+            root_motion.delta_translation = motion.delta_translation;
+            root_motion.delta_rotation = yaw(motion.delta_rotation);
+            root_motion.apply_to(&mut transform);
+        }
+
+        // The character ends up where the clip does, without turning the
+        // translation twice.
+        assert!(transform.rotation.abs_diff_eq(turned, 1e-5));
+        assert!(transform.translation.abs_diff_eq(turned * Vec3::Z, 1e-5));
+    }
+
+    #[test]
+    fn yaw_extraction() {
+        let rotation = Quat::from_rotation_y(0.5) * Quat::from_rotation_x(0.3);
+        assert!(yaw(rotation).abs_diff_eq(Quat::from_rotation_y(0.5), 1e-5));
+    }
+}
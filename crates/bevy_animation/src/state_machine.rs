@@ -0,0 +1,1086 @@
+//! Animation state machines, which play the nodes of an [`AnimationGraph`]
+//! according to states and transition rules.
+
+use core::{fmt::Write, iter, time::Duration};
+use std::io;
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, AssetPath, Assets, Handle, LoadContext};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res},
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::{
+    graph::{AnimationGraph, AnimationGraphHandle, AnimationNodeIndex, AnimationNodeType},
+    transition::AnimationTransitions,
+    AnimationClip, AnimationPlayer,
+};
+
+/// A state machine that decides which node of an [`AnimationGraph`] plays.
+///
+/// A state machine is made of *states*, each of which plays a node of the
+/// graph, and of *transitions* between them. Transitions are gated on
+/// *parameters*, which gameplay code sets on the
+/// [`AnimationStateMachinePlayer`] driving the state machine. When a
+/// transition is taken, the animation of the new state is crossfaded in with
+/// [`AnimationTransitions`].
+///
+/// States can be grouped into *sub-state machines*. Entering a sub-state
+/// machine enters its entry state, and transitions from a sub-state machine
+/// can be taken from any state inside of it. Transitions without a source
+/// state, added with [`AnimationStateMachine::add_any_state_transition`], can
+/// be taken from any state.
+///
+/// For example, a character with a locomotion sub-state machine could be set
+/// up like this:
+///
+/// ```
+/// # use core::time::Duration;
+/// # use bevy_animation::{graph::AnimationGraph, state_machine::*};
+/// # let (graph, nodes) = AnimationGraph::from_clips([Default::default(), Default::default(), Default::default()]);
+/// # let (idle_node, run_node, death_node) = (nodes[0], nodes[1], nodes[2]);
+/// let mut machine = AnimationStateMachine::new(Default::default());
+/// machine.add_parameter("speed", AnimationParameter::Float(0.0));
+/// machine.add_parameter("die", AnimationParameter::Trigger(false));
+///
+/// let locomotion = machine.add_state_machine("locomotion", None);
+/// let idle = machine.add_state("idle", idle_node, Some(locomotion));
+/// let run = machine.add_state("run", run_node, Some(locomotion));
+/// let dead = machine.add_state("dead", death_node, None);
+///
+/// machine
+///     .add_transition(idle, run)
+///     .with_condition(AnimationCondition::Float {
+///         parameter: "speed".into(),
+///         comparison: AnimationComparison::Greater,
+///         value: 0.5,
+///     })
+///     .with_duration(Duration::from_millis(200));
+/// machine
+///     .add_transition(locomotion, dead)
+///     .with_condition(AnimationCondition::Trigger {
+///         parameter: "die".into(),
+///     });
+/// ```
+///
+/// State machines can be serialized to and deserialized from RON files,
+/// canonically with the `.animstates.ron` extension.
+#[derive(Asset, Reflect, Clone, Debug)]
+#[reflect(Debug, Clone)]
+pub struct AnimationStateMachine {
+    /// The animation graph containing the nodes played by the states.
+    pub graph: Handle<AnimationGraph>,
+    /// The parameters of the state machine, with their initial values.
+    pub parameters: HashMap<String, AnimationParameter>,
+    /// The states of the state machine, including sub-state machines.
+    pub states: Vec<AnimationState>,
+    /// The state entered when the state machine starts.
+    ///
+    /// This is the first state added to the root of the state machine by
+    /// default.
+    pub entry: Option<AnimationStateIndex>,
+    /// The transitions between states.
+    ///
+    /// When several transitions can be taken, transitions that can be taken
+    /// from any state come first, then transitions from sub-state machines,
+    /// from the outermost to the innermost, and finally transitions from the
+    /// current state. Transitions from the same source are tried in order.
+    pub transitions: Vec<AnimationStateTransition>,
+}
+
+/// The index of a state in an [`AnimationStateMachine`].
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Serialize, Deserialize,
+)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+#[serde(transparent)]
+pub struct AnimationStateIndex(pub u32);
+
+/// A state of an [`AnimationStateMachine`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug)]
+pub struct AnimationState {
+    /// The name of the state.
+    pub name: String,
+    /// The sub-state machine containing this state, if any.
+    pub parent: Option<AnimationStateIndex>,
+    /// What the state plays.
+    pub motion: AnimationStateMotion,
+}
+
+/// What an [`AnimationState`] plays.
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug)]
+pub enum AnimationStateMotion {
+    /// Plays a node of the [`AnimationGraph`].
+    Node {
+        /// The node to play.
+        node: AnimationNodeIndex,
+        /// Whether the animation repeats while the state is active.
+        repeat: bool,
+    },
+    /// A sub-state machine, grouping the states whose parent it is.
+    StateMachine {
+        /// The state entered when entering the sub-state machine.
+        ///
+        /// This is the first state added to the sub-state machine by default.
+        entry: Option<AnimationStateIndex>,
+    },
+}
+
+/// A transition between two states of an [`AnimationStateMachine`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug)]
+pub struct AnimationStateTransition {
+    /// The state the transition can be taken from, or `None` if it can be
+    /// taken from any state.
+    ///
+    /// If this is a sub-state machine, the transition can be taken from any
+    /// state inside of it.
+    pub from: Option<AnimationStateIndex>,
+    /// The state the transition goes to.
+    ///
+    /// If this is a sub-state machine, its entry state is entered.
+    pub to: AnimationStateIndex,
+    /// The conditions that must all be met to take the transition.
+    pub conditions: Vec<AnimationCondition>,
+    /// The normalized time that the animation of the current state must reach
+    /// before the transition can be taken.
+    ///
+    /// The normalized time is the number of times the clip played by the state
+    /// has completed, plus the fraction of the current repetition that has
+    /// played. For example, `1.0` waits until the clip has played once. Exit
+    /// times can only be used for states playing clip nodes.
+    pub exit_time: Option<f32>,
+    /// How long the animation of the new state takes to fade in.
+    pub duration: Duration,
+}
+
+/// A condition on a parameter of an [`AnimationStateMachine`], gating an
+/// [`AnimationStateTransition`].
+///
+/// Conditions on parameters that are missing or of another type are never
+/// met.
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AnimationCondition {
+    /// Met when a [`AnimationParameter::Bool`] has the given value.
+    Bool {
+        /// The name of the parameter.
+        parameter: String,
+        /// The expected value.
+        value: bool,
+    },
+    /// Met when an [`AnimationParameter::Int`] compares to the given value.
+    Int {
+        /// The name of the parameter.
+        parameter: String,
+        /// How the parameter is compared to the value.
+        comparison: AnimationComparison,
+        /// The value the parameter is compared to.
+        value: i32,
+    },
+    /// Met when an [`AnimationParameter::Float`] compares to the given value.
+    Float {
+        /// The name of the parameter.
+        parameter: String,
+        /// How the parameter is compared to the value.
+        comparison: AnimationComparison,
+        /// The value the parameter is compared to.
+        value: f32,
+    },
+    /// Met when an [`AnimationParameter::Trigger`] is set.
+    ///
+    /// Taking the transition resets the trigger.
+    Trigger {
+        /// The name of the parameter.
+        parameter: String,
+    },
+}
+
+/// How an [`AnimationCondition`] compares a parameter to a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AnimationComparison {
+    /// The parameter is equal to the value.
+    Equal,
+    /// The parameter isn't equal to the value.
+    NotEqual,
+    /// The parameter is less than the value.
+    Less,
+    /// The parameter is greater than the value.
+    Greater,
+}
+
+/// The value of a parameter of an [`AnimationStateMachine`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AnimationParameter {
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Int(i32),
+    /// A floating-point number.
+    Float(f32),
+    /// A boolean that is reset when a transition conditioned on it is taken.
+    Trigger(bool),
+}
+
+/// Plays an [`AnimationStateMachine`] on the [`AnimationPlayer`] of the same
+/// entity, and stores the values of its parameters.
+///
+/// The state machine plays animations through the [`AnimationTransitions`] of
+/// the entity, and sets its [`AnimationGraphHandle`] to the graph of the state
+/// machine. Animations shouldn't be played on the [`AnimationPlayer`] directly
+/// while the state machine is running.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default, Clone)]
+#[require(AnimationPlayer, AnimationTransitions)]
+pub struct AnimationStateMachinePlayer {
+    machine: Handle<AnimationStateMachine>,
+    current_state: Option<AnimationStateIndex>,
+    parameters: HashMap<String, AnimationParameter>,
+}
+
+/// An [`AssetLoader`] that can load [`AnimationStateMachine`]s as assets.
+///
+/// The canonical extension for [`AnimationStateMachine`]s is
+/// `.animstates.ron`. Plain `.animstates` is supported as well.
+#[derive(Default, TypePath)]
+pub struct AnimationStateMachineAssetLoader;
+
+/// Errors that can occur when serializing animation state machines to RON.
+#[derive(Error, Debug)]
+pub enum AnimationStateMachineSaveError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization.
+    #[error(transparent)]
+    Ron(#[from] ron::Error),
+    /// The handle to the animation graph doesn't correspond to an asset path.
+    #[error("AnimationStateMachine contains a handle to an AnimationGraph that does not correspond to an asset path")]
+    NonPathGraphHandle,
+}
+
+/// Errors that can occur when deserializing animation state machines from RON.
+#[derive(Error, Debug)]
+pub enum AnimationStateMachineLoadError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An error occurred in RON deserialization.
+    #[error(transparent)]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error(transparent)]
+    SpannedRon(#[from] SpannedError),
+}
+
+/// A version of [`AnimationStateMachine`] suitable for serializing as an
+/// asset.
+///
+/// The animation graph is referenced by its asset path.
+#[derive(Serialize, Deserialize)]
+pub struct SerializedAnimationStateMachine {
+    /// Corresponds to the `graph` field on [`AnimationStateMachine`].
+    pub graph: AssetPath<'static>,
+    /// Corresponds to the `parameters` field on [`AnimationStateMachine`].
+    pub parameters: HashMap<String, AnimationParameter>,
+    /// Corresponds to the `states` field on [`AnimationStateMachine`].
+    pub states: Vec<AnimationState>,
+    /// Corresponds to the `entry` field on [`AnimationStateMachine`].
+    pub entry: Option<AnimationStateIndex>,
+    /// Corresponds to the `transitions` field on [`AnimationStateMachine`].
+    pub transitions: Vec<AnimationStateTransition>,
+}
+
+impl AnimationStateMachine {
+    /// Creates a new state machine without states, playing nodes of the given
+    /// graph.
+    pub fn new(graph: Handle<AnimationGraph>) -> Self {
+        Self {
+            graph,
+            parameters: HashMap::default(),
+            states: Vec::new(),
+            entry: None,
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Adds a parameter to the state machine, with its initial value.
+    pub fn add_parameter(&mut self, name: impl Into<String>, value: AnimationParameter) {
+        self.parameters.insert(name.into(), value);
+    }
+
+    /// Adds a state playing the given node of the graph to the state machine,
+    /// or to the sub-state machine `parent`, and returns its index.
+    ///
+    /// The animation of the state repeats by default.
+    pub fn add_state(
+        &mut self,
+        name: impl Into<String>,
+        node: AnimationNodeIndex,
+        parent: Option<AnimationStateIndex>,
+    ) -> AnimationStateIndex {
+        self.push_state(AnimationState {
+            name: name.into(),
+            parent,
+            motion: AnimationStateMotion::Node { node, repeat: true },
+        })
+    }
+
+    /// Adds a sub-state machine to the state machine, or to the sub-state
+    /// machine `parent`, and returns its index.
+    pub fn add_state_machine(
+        &mut self,
+        name: impl Into<String>,
+        parent: Option<AnimationStateIndex>,
+    ) -> AnimationStateIndex {
+        self.push_state(AnimationState {
+            name: name.into(),
+            parent,
+            motion: AnimationStateMotion::StateMachine { entry: None },
+        })
+    }
+
+    fn push_state(&mut self, state: AnimationState) -> AnimationStateIndex {
+        let index = AnimationStateIndex(self.states.len() as u32);
+        match state.parent {
+            None => {
+                self.entry.get_or_insert(index);
+            }
+            Some(parent) => {
+                if let Some(AnimationStateMotion::StateMachine { entry }) =
+                    self.get_mut(parent).map(|parent| &mut parent.motion)
+                {
+                    entry.get_or_insert(index);
+                }
+            }
+        }
+        self.states.push(state);
+        index
+    }
+
+    /// Sets the entry state of the state machine, or of the sub-state machine
+    /// `parent`.
+    pub fn set_entry(&mut self, parent: Option<AnimationStateIndex>, state: AnimationStateIndex) {
+        match parent {
+            None => self.entry = Some(state),
+            Some(parent) => {
+                if let Some(AnimationStateMotion::StateMachine { entry }) =
+                    self.get_mut(parent).map(|parent| &mut parent.motion)
+                {
+                    *entry = Some(state);
+                }
+            }
+        }
+    }
+
+    /// Adds a transition between two states, which is taken immediately and
+    /// unconditionally until configured otherwise.
+    pub fn add_transition(
+        &mut self,
+        from: AnimationStateIndex,
+        to: AnimationStateIndex,
+    ) -> &mut AnimationStateTransition {
+        self.push_transition(Some(from), to)
+    }
+
+    /// Adds a transition that can be taken from any state.
+    ///
+    /// The transition isn't taken if the current state is already its
+    /// target.
+    pub fn add_any_state_transition(
+        &mut self,
+        to: AnimationStateIndex,
+    ) -> &mut AnimationStateTransition {
+        self.push_transition(None, to)
+    }
+
+    fn push_transition(
+        &mut self,
+        from: Option<AnimationStateIndex>,
+        to: AnimationStateIndex,
+    ) -> &mut AnimationStateTransition {
+        self.transitions.push(AnimationStateTransition {
+            from,
+            to,
+            conditions: Vec::new(),
+            exit_time: None,
+            duration: Duration::ZERO,
+        });
+        self.transitions.last_mut().unwrap()
+    }
+
+    /// Returns the state with the given index.
+    pub fn get(&self, state: AnimationStateIndex) -> Option<&AnimationState> {
+        self.states.get(state.0 as usize)
+    }
+
+    /// Returns the state with the given index mutably.
+    pub fn get_mut(&mut self, state: AnimationStateIndex) -> Option<&mut AnimationState> {
+        self.states.get_mut(state.0 as usize)
+    }
+
+    /// Returns the index of the first state with the given name.
+    pub fn find_state(&self, name: &str) -> Option<AnimationStateIndex> {
+        self.states
+            .iter()
+            .position(|state| state.name == name)
+            .map(|index| AnimationStateIndex(index as u32))
+    }
+
+    /// Returns the state played when entering the given state, following the
+    /// entries of sub-state machines.
+    pub fn resolve(&self, mut state: AnimationStateIndex) -> Option<AnimationStateIndex> {
+        // Bound the iterations in case entries form a cycle.
+        for _ in 0..=self.states.len() {
+            match self.get(state)?.motion {
+                AnimationStateMotion::Node { .. } => return Some(state),
+                AnimationStateMotion::StateMachine { entry } => state = entry?,
+            }
+        }
+        None
+    }
+
+    /// Iterates over the sub-state machines containing the given state, from
+    /// the innermost to the outermost.
+    pub fn ancestors(
+        &self,
+        state: AnimationStateIndex,
+    ) -> impl Iterator<Item = AnimationStateIndex> + '_ {
+        iter::successors(self.get(state).and_then(|state| state.parent), |&parent| {
+            self.get(parent).and_then(|parent| parent.parent)
+        })
+        .take(self.states.len())
+    }
+
+    /// Iterates over the transitions that can be taken from the given state,
+    /// in priority order.
+    pub fn transitions_from(
+        &self,
+        state: AnimationStateIndex,
+    ) -> impl Iterator<Item = &AnimationStateTransition> {
+        let mut sources: SmallVec<[Option<AnimationStateIndex>; 4]> = iter::once(Some(state))
+            .chain(self.ancestors(state).map(Some))
+            .collect();
+        sources.push(None);
+        sources.into_iter().rev().flat_map(move |source| {
+            self.transitions
+                .iter()
+                .filter(move |transition| transition.from == source)
+        })
+    }
+
+    /// Serializes the state machine to RON.
+    pub fn save<W>(&self, writer: &mut W) -> Result<(), AnimationStateMachineSaveError>
+    where
+        W: Write,
+    {
+        let mut ron_serializer = ron::ser::Serializer::new(writer, None)?;
+        let serialized_machine: SerializedAnimationStateMachine = self.clone().try_into()?;
+        Ok(serialized_machine.serialize(&mut ron_serializer)?)
+    }
+}
+
+impl AnimationStateTransition {
+    /// Adds a condition that must be met to take the transition.
+    pub fn with_condition(&mut self, condition: AnimationCondition) -> &mut Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Sets the normalized time that the animation of the current state must
+    /// reach before the transition can be taken.
+    pub fn with_exit_time(&mut self, exit_time: f32) -> &mut Self {
+        self.exit_time = Some(exit_time);
+        self
+    }
+
+    /// Sets how long the animation of the new state takes to fade in.
+    pub fn with_duration(&mut self, duration: Duration) -> &mut Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Returns whether the transition can be taken, given the parameters of
+    /// the state machine and the normalized time of the current state.
+    pub fn can_take(
+        &self,
+        parameters: &HashMap<String, AnimationParameter>,
+        normalized_time: Option<f32>,
+    ) -> bool {
+        let exit_time_reached = match (self.exit_time, normalized_time) {
+            (None, _) => true,
+            (Some(exit_time), Some(normalized_time)) => normalized_time >= exit_time,
+            (Some(_), None) => false,
+        };
+        exit_time_reached
+            && self
+                .conditions
+                .iter()
+                .all(|condition| condition.is_met(parameters))
+    }
+}
+
+impl AnimationCondition {
+    /// Returns whether the condition is met by the given parameters.
+    pub fn is_met(&self, parameters: &HashMap<String, AnimationParameter>) -> bool {
+        match (self, parameters.get(self.parameter())) {
+            (Self::Bool { value, .. }, Some(AnimationParameter::Bool(parameter))) => {
+                parameter == value
+            }
+            (
+                Self::Int {
+                    comparison, value, ..
+                },
+                Some(AnimationParameter::Int(parameter)),
+            ) => comparison.compare(parameter, value),
+            (
+                Self::Float {
+                    comparison, value, ..
+                },
+                Some(AnimationParameter::Float(parameter)),
+            ) => comparison.compare(parameter, value),
+            (Self::Trigger { .. }, Some(AnimationParameter::Trigger(set))) => *set,
+            _ => false,
+        }
+    }
+
+    /// Returns the name of the parameter that the condition is on.
+    pub fn parameter(&self) -> &str {
+        match self {
+            Self::Bool { parameter, .. }
+            | Self::Int { parameter, .. }
+            | Self::Float { parameter, .. }
+            | Self::Trigger { parameter } => parameter,
+        }
+    }
+}
+
+impl AnimationComparison {
+    /// Compares a parameter to a value.
+    pub fn compare<T: PartialOrd>(self, parameter: &T, value: &T) -> bool {
+        match self {
+            Self::Equal => parameter == value,
+            Self::NotEqual => parameter != value,
+            Self::Less => parameter < value,
+            Self::Greater => parameter > value,
+        }
+    }
+}
+
+impl AnimationStateMachinePlayer {
+    /// Creates a player for the given state machine, which starts in its entry
+    /// state.
+    pub fn new(machine: Handle<AnimationStateMachine>) -> Self {
+        Self {
+            machine,
+            current_state: None,
+            parameters: HashMap::default(),
+        }
+    }
+
+    /// Returns the state machine played.
+    pub fn machine(&self) -> &Handle<AnimationStateMachine> {
+        &self.machine
+    }
+
+    /// Returns the state that is currently playing, if the state machine has
+    /// started.
+    pub fn current_state(&self) -> Option<AnimationStateIndex> {
+        self.current_state
+    }
+
+    /// Returns the value of a parameter.
+    ///
+    /// Parameters that haven't been set have their initial value once the
+    /// state machine has started.
+    pub fn parameter(&self, name: &str) -> Option<AnimationParameter> {
+        self.parameters.get(name).copied()
+    }
+
+    /// Sets the value of a parameter.
+    pub fn set_parameter(
+        &mut self,
+        name: impl Into<String>,
+        value: AnimationParameter,
+    ) -> &mut Self {
+        self.parameters.insert(name.into(), value);
+        self
+    }
+
+    /// Sets the value of an [`AnimationParameter::Bool`].
+    pub fn set_bool(&mut self, name: impl Into<String>, value: bool) -> &mut Self {
+        self.set_parameter(name, AnimationParameter::Bool(value))
+    }
+
+    /// Sets the value of an [`AnimationParameter::Int`].
+    pub fn set_int(&mut self, name: impl Into<String>, value: i32) -> &mut Self {
+        self.set_parameter(name, AnimationParameter::Int(value))
+    }
+
+    /// Sets the value of an [`AnimationParameter::Float`].
+    pub fn set_float(&mut self, name: impl Into<String>, value: f32) -> &mut Self {
+        self.set_parameter(name, AnimationParameter::Float(value))
+    }
+
+    /// Sets an [`AnimationParameter::Trigger`], until a transition conditioned
+    /// on it is taken.
+    pub fn set_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.set_parameter(name, AnimationParameter::Trigger(true))
+    }
+
+    /// Resets an [`AnimationParameter::Trigger`].
+    pub fn reset_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.set_parameter(name, AnimationParameter::Trigger(false))
+    }
+
+    /// Enters the entry state if the state machine hasn't started, or takes
+    /// the first transition that can be taken from the current state.
+    ///
+    /// Returns the entered state and how long its animation takes to fade in.
+    fn advance(
+        &mut self,
+        machine: &AnimationStateMachine,
+        normalized_time: Option<f32>,
+    ) -> Option<(AnimationStateIndex, Duration)> {
+        for (name, value) in &machine.parameters {
+            if !self.parameters.contains_key(name) {
+                self.parameters.insert(name.clone(), *value);
+            }
+        }
+
+        let Some(current_state) = self
+            .current_state
+            .filter(|&state| machine.get(state).is_some())
+        else {
+            let entry = machine.resolve(machine.entry?)?;
+            self.current_state = Some(entry);
+            return Some((entry, Duration::ZERO));
+        };
+
+        let (transition, to) = machine
+            .transitions_from(current_state)
+            .filter(|transition| transition.can_take(&self.parameters, normalized_time))
+            .find_map(|transition| {
+                let to = machine.resolve(transition.to)?;
+                // Only explicit transitions from the current state restart it.
+                (to != current_state || transition.from == Some(current_state))
+                    .then_some((transition, to))
+            })?;
+
+        for condition in &transition.conditions {
+            if let AnimationCondition::Trigger { parameter } = condition {
+                self.parameters
+                    .insert(parameter.clone(), AnimationParameter::Trigger(false));
+            }
+        }
+        self.current_state = Some(to);
+        Some((to, transition.duration))
+    }
+}
+
+impl AssetLoader for AnimationStateMachineAssetLoader {
+    type Asset = AnimationStateMachine;
+
+    type Settings = ();
+
+    type Error = AnimationStateMachineLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let serialized_machine = SerializedAnimationStateMachine::deserialize(&mut deserializer)
+            .map_err(|err| deserializer.span_error(err))?;
+
+        Ok(AnimationStateMachine {
+            graph: load_context.load(serialized_machine.graph),
+            parameters: serialized_machine.parameters,
+            states: serialized_machine.states,
+            entry: serialized_machine.entry,
+            transitions: serialized_machine.transitions,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["animstates", "animstates.ron"]
+    }
+}
+
+impl TryFrom<AnimationStateMachine> for SerializedAnimationStateMachine {
+    type Error = AnimationStateMachineSaveError;
+
+    fn try_from(machine: AnimationStateMachine) -> Result<Self, Self::Error> {
+        let Some(graph) = machine.graph.path() else {
+            return Err(AnimationStateMachineSaveError::NonPathGraphHandle);
+        };
+        Ok(Self {
+            graph: graph.clone(),
+            parameters: machine.parameters,
+            states: machine.states,
+            entry: machine.entry,
+            transitions: machine.transitions,
+        })
+    }
+}
+
+/// A system that advances [`AnimationStateMachinePlayer`]s, and plays the
+/// animations of the states they enter.
+pub fn advance_state_machines(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut AnimationStateMachinePlayer,
+        &mut AnimationTransitions,
+        &mut AnimationPlayer,
+        Option<&AnimationGraphHandle>,
+    )>,
+    machines: Res<Assets<AnimationStateMachine>>,
+    graphs: Res<Assets<AnimationGraph>>,
+    clips: Res<Assets<AnimationClip>>,
+) {
+    for (entity, mut machine_player, mut transitions, mut player, graph_handle) in &mut query {
+        let Some(machine) = machines.get(&machine_player.machine) else {
+            continue;
+        };
+        if graph_handle.map(|handle| handle.id()) != Some(machine.graph.id()) {
+            commands
+                .entity(entity)
+                .insert(AnimationGraphHandle(machine.graph.clone()));
+        }
+
+        // The normalized time of the current state, if it plays a clip.
+        let normalized_time = machine_player.current_state.and_then(|state| {
+            let AnimationStateMotion::Node { node, .. } = machine.get(state)?.motion else {
+                return None;
+            };
+            let AnimationNodeType::Clip(ref clip) =
+                graphs.get(&machine.graph)?.get(node)?.node_type
+            else {
+                return None;
+            };
+            let duration = clips.get(clip)?.duration();
+            let animation = player.animation(node)?;
+            Some(if animation.is_finished() || duration <= 0.0 {
+                animation.completions() as f32
+            } else {
+                animation.completions() as f32 + animation.seek_time() / duration
+            })
+        });
+
+        let Some((state, duration)) = machine_player.advance(machine, normalized_time) else {
+            continue;
+        };
+        match machine.get(state).map(|state| &state.motion) {
+            Some(&AnimationStateMotion::Node { node, repeat }) => {
+                let animation = transitions.play(&mut player, node, duration);
+                if repeat {
+                    animation.repeat();
+                }
+            }
+            _ => warn!("Animation state {:?} doesn't play a node", state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bevy_app::{App, TaskPoolPlugin, Update};
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSourceBuilder, AssetSourceId,
+        },
+        AssetApp, AssetPlugin, AssetServer, LoadState,
+    };
+
+    use super::*;
+    use crate::{graph::AnimationGraphAssetLoader, RepeatAnimation};
+
+    /// A character with idle and run states in a locomotion sub-state machine,
+    /// an attack and a death.
+    fn character(
+        graph: Handle<AnimationGraph>,
+        nodes: &[AnimationNodeIndex],
+    ) -> AnimationStateMachine {
+        let mut machine = AnimationStateMachine::new(graph);
+        machine.add_parameter("speed", AnimationParameter::Float(0.0));
+        machine.add_parameter("attack", AnimationParameter::Trigger(false));
+        machine.add_parameter("dead", AnimationParameter::Bool(false));
+
+        let locomotion = machine.add_state_machine("locomotion", None);
+        let idle = machine.add_state("idle", nodes[0], Some(locomotion));
+        let run = machine.add_state("run", nodes[1], Some(locomotion));
+        let attack = machine.add_state("attack", nodes[2], None);
+        let death = machine.add_state("death", nodes[3], None);
+        machine.get_mut(attack).unwrap().motion = AnimationStateMotion::Node {
+            node: nodes[2],
+            repeat: false,
+        };
+
+        machine
+            .add_transition(idle, run)
+            .with_condition(AnimationCondition::Float {
+                parameter: "speed".into(),
+                comparison: AnimationComparison::Greater,
+                value: 0.5,
+            })
+            .with_duration(Duration::from_millis(200));
+        machine
+            .add_transition(run, idle)
+            .with_condition(AnimationCondition::Float {
+                parameter: "speed".into(),
+                comparison: AnimationComparison::Less,
+                value: 0.5,
+            });
+        machine
+            .add_transition(locomotion, attack)
+            .with_condition(AnimationCondition::Trigger {
+                parameter: "attack".into(),
+            });
+        machine
+            .add_transition(attack, locomotion)
+            .with_exit_time(1.0);
+        machine
+            .add_any_state_transition(death)
+            .with_condition(AnimationCondition::Bool {
+                parameter: "dead".into(),
+                value: true,
+            });
+        machine
+    }
+
+    #[test]
+    fn state_machine_transitions() {
+        let (_, nodes) = AnimationGraph::from_clips([
+            Handle::default(),
+            Handle::default(),
+            Handle::default(),
+            Handle::default(),
+        ]);
+        let machine = character(Handle::default(), &nodes);
+        let state = |name| machine.find_state(name).unwrap();
+        let (idle, run, attack, death) =
+            (state("idle"), state("run"), state("attack"), state("death"));
+
+        let mut player = AnimationStateMachinePlayer::default();
+        assert_eq!(player.advance(&machine, None), Some((idle, Duration::ZERO)));
+        assert_eq!(player.advance(&machine, None), None);
+
+        player.set_float("speed", 1.0);
+        assert_eq!(
+            player.advance(&machine, None),
+            Some((run, Duration::from_millis(200)))
+        );
+
+        // Transitions from a sub-state machine can be taken from its states.
+        player.set_trigger("attack");
+        assert_eq!(
+            player.advance(&machine, None),
+            Some((attack, Duration::ZERO))
+        );
+        assert_eq!(
+            player.parameter("attack"),
+            Some(AnimationParameter::Trigger(false))
+        );
+
+        // Exit times wait for the clip, and entering a sub-state machine
+        // enters its entry state.
+        assert_eq!(player.advance(&machine, Some(0.5)), None);
+        assert_eq!(
+            player.advance(&machine, Some(1.0)),
+            Some((idle, Duration::ZERO))
+        );
+
+        player.set_bool("dead", true);
+        assert_eq!(
+            player.advance(&machine, None),
+            Some((death, Duration::ZERO))
+        );
+        assert_eq!(player.advance(&machine, None), None);
+    }
+
+    #[test]
+    fn save_and_load_state_machine() {
+        let mut graph = String::new();
+        AnimationGraph::new().save(&mut graph).unwrap();
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("character.animgraph.ron"), &graph);
+
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir.clone() };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(reader.clone())),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<AnimationClip>()
+        .init_asset::<AnimationGraph>()
+        .init_asset::<AnimationStateMachine>()
+        .init_asset_loader::<AnimationGraphAssetLoader>()
+        .init_asset_loader::<AnimationStateMachineAssetLoader>();
+        let asset_server = app.world().resource::<AssetServer>().clone();
+
+        let (_, nodes) = AnimationGraph::from_clips([
+            Handle::default(),
+            Handle::default(),
+            Handle::default(),
+            Handle::default(),
+        ]);
+        let graph = asset_server.load("character.animgraph.ron");
+        let mut machine = character(graph.clone(), &nodes);
+        machine.add_state_machine("empty", None);
+        let mut ron = String::new();
+        machine.save(&mut ron).unwrap();
+        dir.insert_asset_text(Path::new("character.animstates.ron"), &ron);
+
+        let handle = asset_server.load::<AnimationStateMachine>("character.animstates.ron");
+        for _ in 0..10000 {
+            app.update();
+            match asset_server.load_state(&handle) {
+                LoadState::Loaded => break,
+                LoadState::Failed(err) => panic!("failed to load state machine: {err}"),
+                _ => {}
+            }
+        }
+        let machines = app.world().resource::<Assets<AnimationStateMachine>>();
+        let loaded = machines.get(&handle).expect("state machine did not load");
+
+        assert_eq!(loaded.graph, graph);
+        assert_eq!(loaded.parameters, machine.parameters);
+        assert_eq!(loaded.entry, machine.entry);
+        assert_eq!(
+            ron::to_string(&loaded.states).unwrap(),
+            ron::to_string(&machine.states).unwrap()
+        );
+        assert_eq!(
+            ron::to_string(&loaded.transitions).unwrap(),
+            ron::to_string(&machine.transitions).unwrap()
+        );
+    }
+
+    #[test]
+    fn advance_state_machines_plays_states() {
+        let mut app = App::new();
+        app.init_resource::<Assets<AnimationClip>>()
+            .init_resource::<Assets<AnimationGraph>>()
+            .init_resource::<Assets<AnimationStateMachine>>()
+            .add_systems(Update, advance_state_machines);
+
+        let world = app.world_mut();
+        let clips = [0.5, 2.0, 1.0, 1.0].map(|duration| {
+            let mut clip = AnimationClip::default();
+            clip.set_duration(duration);
+            world.resource_mut::<Assets<AnimationClip>>().add(clip)
+        });
+        let (graph, nodes) = AnimationGraph::from_clips(clips);
+        let graph = world.resource_mut::<Assets<AnimationGraph>>().add(graph);
+        let machine = character(graph.clone(), &nodes);
+        let machine = world
+            .resource_mut::<Assets<AnimationStateMachine>>()
+            .add(machine);
+        let entity = world.spawn(AnimationStateMachinePlayer::new(machine)).id();
+
+        // The entry state plays, and the graph of the state machine is set.
+        app.update();
+        let entity_ref = app.world().entity(entity);
+        assert_eq!(
+            entity_ref
+                .get::<AnimationGraphHandle>()
+                .map(|handle| handle.id()),
+            Some(graph.id())
+        );
+        assert_eq!(
+            entity_ref
+                .get::<AnimationTransitions>()
+                .unwrap()
+                .get_main_animation(),
+            Some(nodes[0])
+        );
+        let player = entity_ref.get::<AnimationPlayer>().unwrap();
+        assert_eq!(
+            player.animation(nodes[0]).unwrap().repeat_mode(),
+            RepeatAnimation::Forever
+        );
+
+        // Taking a transition fades the animation of the previous state out.
+        let mut entity_mut = app.world_mut().entity_mut(entity);
+        entity_mut
+            .get_mut::<AnimationStateMachinePlayer>()
+            .unwrap()
+            .set_float("speed", 1.0);
+        app.update();
+        let entity_ref = app.world().entity(entity);
+        assert_eq!(
+            entity_ref
+                .get::<AnimationTransitions>()
+                .unwrap()
+                .get_main_animation(),
+            Some(nodes[1])
+        );
+        let player = entity_ref.get::<AnimationPlayer>().unwrap();
+        assert!(player.is_playing_animation(nodes[0]));
+        assert!(player.is_playing_animation(nodes[1]));
+
+        // The attack doesn't repeat, and waits for its clip to play once.
+        app.world_mut()
+            .get_mut::<AnimationStateMachinePlayer>(entity)
+            .unwrap()
+            .set_trigger("attack");
+        app.update();
+        let player = app.world().get::<AnimationPlayer>(entity).unwrap();
+        assert_eq!(
+            player.animation(nodes[2]).unwrap().repeat_mode(),
+            RepeatAnimation::Never
+        );
+        app.world_mut()
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .animation_mut(nodes[2])
+            .unwrap()
+            .seek_to(0.5);
+        app.update();
+        assert_eq!(
+            app.world()
+                .get::<AnimationTransitions>(entity)
+                .unwrap()
+                .get_main_animation(),
+            Some(nodes[2])
+        );
+        app.world_mut()
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .animation_mut(nodes[2])
+            .unwrap()
+            .seek_to(1.0);
+        app.update();
+        assert_eq!(
+            app.world()
+                .get::<AnimationTransitions>(entity)
+                .unwrap()
+                .get_main_animation(),
+            Some(nodes[0])
+        );
+    }
+}
@@ -0,0 +1,548 @@
+//! Tweens, which are lightweight one-shot animations of entity properties.
+//!
+//! A [`Tween`] animates the properties of another entity, its target, without
+//! an [`AnimationClip`](crate::AnimationClip), graph or player. Tweens are
+//! spawned as their own entities, related to their target with [`TweenOf`],
+//! and are despawned when they complete, after triggering [`TweenCompleted`]
+//! on their target:
+//!
+//! ```
+//! # use bevy_animation::{animated_field, animation_curves::AnimatedField, tween::*};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::{curve::EaseFunction, Vec3};
+//! # use bevy_transform::components::Transform;
+//! fn pop(mut commands: Commands, entity: Entity) {
+//!     let scale = animated_field!(Transform::scale);
+//!     let tween = TweenNode::to(scale.clone(), Vec3::splat(1.2), 0.2, EaseFunction::BackOut)
+//!         .then(TweenNode::to(scale, Vec3::ONE, 0.2, EaseFunction::QuadraticIn));
+//!     commands.entity(entity).with_related::<TweenOf>(Tween::new(tween));
+//! }
+//! ```
+//!
+//! Tweens are applied after animations, so they override the properties that
+//! animations also animate.
+
+use core::fmt::{self, Debug, Formatter};
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    query::Without,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res},
+};
+use bevy_math::curve::{Curve, EaseFunction};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_time::Time;
+use tracing::warn;
+
+use crate::{
+    animatable::Animatable, animation_curves::AnimatableProperty, AnimationEntityMut,
+    AnimationEvaluationError, RepeatAnimation,
+};
+
+/// A component that plays a [`TweenNode`] on the entity it's related to with
+/// [`TweenOf`].
+#[derive(Component, Debug)]
+pub struct Tween {
+    /// The animation to play.
+    pub node: TweenNode,
+    /// The time in seconds since the tween started.
+    pub elapsed: f32,
+    /// The speed of the tween, where 1.0 is the normal speed.
+    pub speed: f32,
+    /// Whether the tween is paused.
+    pub paused: bool,
+}
+
+/// The entity animated by a [`Tween`].
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+#[reflect(Component, Clone, PartialEq, Debug)]
+#[relationship(relationship_target = Tweens)]
+pub struct TweenOf(#[entities] pub Entity);
+
+/// The [`Tween`]s animating an entity.
+///
+/// The tweens are despawned with the entity.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[relationship_target(relationship = TweenOf, linked_spawn)]
+pub struct Tweens(Vec<Entity>);
+
+/// An event triggered on the target of a [`Tween`] when the tween completes,
+/// right before the tween is despawned.
+#[derive(EntityEvent, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TweenCompleted {
+    /// The entity that was animated.
+    pub entity: Entity,
+    /// The entity of the completed tween.
+    pub tween: Entity,
+}
+
+/// A node of the animation played by a [`Tween`].
+///
+/// Nodes are combined into sequences, which play their nodes one after the
+/// other, and parallels, which play their nodes at the same time.
+pub enum TweenNode {
+    /// Animates a property.
+    Track(Box<dyn TweenTrack>),
+    /// Waits for the given number of seconds.
+    Delay(f32),
+    /// Plays nodes one after the other.
+    Sequence(Vec<TweenNode>),
+    /// Plays nodes at the same time, until all of them have completed.
+    Parallel(Vec<TweenNode>),
+    /// Plays a node multiple times.
+    Repeat {
+        /// The node to play.
+        node: Box<TweenNode>,
+        /// How many times the node is played.
+        repeat: RepeatAnimation,
+        /// Whether every other repetition plays the node backwards.
+        ping_pong: bool,
+    },
+}
+
+/// The animation of a single property by a [`TweenNode::Track`].
+///
+/// This is implemented by [`CurveTrack`] and [`EaseTrack`], and can be
+/// implemented to animate values that they can't.
+pub trait TweenTrack: Send + Sync + 'static {
+    /// The duration of the track, in seconds.
+    fn duration(&self) -> f32;
+
+    /// Applies the value of the track at time `t`, which is between 0 and
+    /// [`Self::duration`], to `entity`.
+    fn apply(
+        &mut self,
+        t: f32,
+        entity: &mut AnimationEntityMut,
+    ) -> Result<(), AnimationEvaluationError>;
+}
+
+/// A [`TweenTrack`] that samples a [`Curve`], starting at the start of its
+/// domain.
+///
+/// The domain of the curve must be bounded.
+#[derive(Clone, Debug)]
+pub struct CurveTrack<P, C> {
+    /// The animated property.
+    pub property: P,
+    /// The curve whose values are applied to the property.
+    pub curve: C,
+}
+
+/// A [`TweenTrack`] that interpolates a property between two values with an
+/// [`EaseFunction`].
+#[derive(Clone, Debug)]
+pub struct EaseTrack<P: AnimatableProperty> {
+    /// The animated property.
+    pub property: P,
+    /// The value at the start of the track.
+    ///
+    /// If `None`, this is the value of the property when the track starts.
+    pub start: Option<P::Property>,
+    /// The value at the end of the track.
+    pub end: P::Property,
+    /// The duration of the track, in seconds.
+    pub duration: f32,
+    /// The easing of the interpolation.
+    pub ease: EaseFunction,
+}
+
+impl Tween {
+    /// Creates a tween playing the given node from the start.
+    pub fn new(node: TweenNode) -> Self {
+        Self {
+            node,
+            elapsed: 0.0,
+            speed: 1.0,
+            paused: false,
+        }
+    }
+
+    /// Returns whether the tween has completed.
+    pub fn is_completed(&self) -> bool {
+        self.elapsed >= self.node.duration()
+    }
+}
+
+impl From<TweenNode> for Tween {
+    fn from(node: TweenNode) -> Self {
+        Self::new(node)
+    }
+}
+
+impl TweenNode {
+    /// Creates a node animating `property` with the values of `curve`.
+    pub fn curve<P, C>(property: P, curve: C) -> Self
+    where
+        P: AnimatableProperty,
+        C: Curve<P::Property> + Send + Sync + 'static,
+    {
+        Self::Track(Box::new(CurveTrack { property, curve }))
+    }
+
+    /// Creates a node animating `property` from its current value to `end`
+    /// over `duration` seconds.
+    pub fn to<P>(property: P, end: P::Property, duration: f32, ease: EaseFunction) -> Self
+    where
+        P: AnimatableProperty,
+        P::Property: Clone,
+    {
+        Self::Track(Box::new(EaseTrack {
+            property,
+            start: None,
+            end,
+            duration,
+            ease,
+        }))
+    }
+
+    /// Creates a node animating `property` from `start` to `end` over
+    /// `duration` seconds.
+    pub fn from_to<P>(
+        property: P,
+        start: P::Property,
+        end: P::Property,
+        duration: f32,
+        ease: EaseFunction,
+    ) -> Self
+    where
+        P: AnimatableProperty,
+        P::Property: Clone,
+    {
+        Self::Track(Box::new(EaseTrack {
+            property,
+            start: Some(start),
+            end,
+            duration,
+            ease,
+        }))
+    }
+
+    /// Creates a node waiting for `duration` seconds.
+    pub fn delay(duration: f32) -> Self {
+        Self::Delay(duration)
+    }
+
+    /// Creates a node playing `nodes` one after the other.
+    pub fn sequence(nodes: impl IntoIterator<Item = TweenNode>) -> Self {
+        Self::Sequence(nodes.into_iter().collect())
+    }
+
+    /// Creates a node playing `nodes` at the same time.
+    pub fn parallel(nodes: impl IntoIterator<Item = TweenNode>) -> Self {
+        Self::Parallel(nodes.into_iter().collect())
+    }
+
+    /// Plays `next` after this node.
+    pub fn then(self, next: TweenNode) -> Self {
+        match self {
+            Self::Sequence(mut nodes) => {
+                nodes.push(next);
+                Self::Sequence(nodes)
+            }
+            node => Self::Sequence(vec![node, next]),
+        }
+    }
+
+    /// Plays `other` at the same time as this node.
+    pub fn with(self, other: TweenNode) -> Self {
+        match self {
+            Self::Parallel(mut nodes) => {
+                nodes.push(other);
+                Self::Parallel(nodes)
+            }
+            node => Self::Parallel(vec![node, other]),
+        }
+    }
+
+    /// Plays this node multiple times.
+    pub fn repeat(self, repeat: RepeatAnimation) -> Self {
+        Self::Repeat {
+            node: Box::new(self),
+            repeat,
+            ping_pong: false,
+        }
+    }
+
+    /// Plays this node multiple times, alternating between playing it forwards
+    /// and backwards.
+    pub fn ping_pong(self, repeat: RepeatAnimation) -> Self {
+        Self::Repeat {
+            node: Box::new(self),
+            repeat,
+            ping_pong: true,
+        }
+    }
+
+    /// Returns the duration of this node in seconds, which is infinite if it
+    /// repeats forever.
+    pub fn duration(&self) -> f32 {
+        match self {
+            Self::Track(track) => track.duration(),
+            Self::Delay(duration) => *duration,
+            Self::Sequence(nodes) => nodes.iter().map(TweenNode::duration).sum(),
+            Self::Parallel(nodes) => nodes.iter().map(TweenNode::duration).fold(0.0, f32::max),
+            Self::Repeat { node, repeat, .. } => match repeat {
+                RepeatAnimation::Never => node.duration(),
+                RepeatAnimation::Count(count) => node.duration() * *count as f32,
+                RepeatAnimation::Forever => f32::INFINITY,
+            },
+        }
+    }
+
+    /// Applies the values of this node at time `t` to `entity`.
+    ///
+    /// Nodes of sequences that haven't started yet aren't applied, and nodes
+    /// that have completed apply their last values.
+    pub fn apply(
+        &mut self,
+        t: f32,
+        entity: &mut AnimationEntityMut,
+    ) -> Result<(), AnimationEvaluationError> {
+        match self {
+            Self::Track(track) => track.apply(t.clamp(0.0, track.duration()), entity),
+            Self::Delay(_) => Ok(()),
+            Self::Sequence(nodes) => {
+                let mut start = 0.0;
+                for node in nodes {
+                    if t < start {
+                        break;
+                    }
+                    node.apply(t - start, entity)?;
+                    start += node.duration();
+                }
+                Ok(())
+            }
+            Self::Parallel(nodes) => nodes.iter_mut().try_for_each(|node| node.apply(t, entity)),
+            Self::Repeat {
+                node,
+                repeat,
+                ping_pong,
+            } => {
+                let duration = node.duration();
+                if duration <= 0.0 {
+                    return node.apply(0.0, entity);
+                }
+                let count = match repeat {
+                    RepeatAnimation::Never => 1.0,
+                    RepeatAnimation::Count(count) => *count as f32,
+                    RepeatAnimation::Forever => f32::INFINITY,
+                };
+                let t = t.clamp(0.0, duration * count);
+                // Stay at the end of the last repetition once completed.
+                let repetition = (t / duration).floor().min(count - 1.0).max(0.0);
+                let mut local = t - repetition * duration;
+                if *ping_pong && repetition % 2.0 == 1.0 {
+                    local = duration - local;
+                }
+                node.apply(local, entity)
+            }
+        }
+    }
+}
+
+impl Debug for TweenNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Track(track) => f
+                .debug_struct("Track")
+                .field("duration", &track.duration())
+                .finish_non_exhaustive(),
+            Self::Delay(duration) => f.debug_tuple("Delay").field(duration).finish(),
+            Self::Sequence(nodes) => f.debug_tuple("Sequence").field(nodes).finish(),
+            Self::Parallel(nodes) => f.debug_tuple("Parallel").field(nodes).finish(),
+            Self::Repeat {
+                node,
+                repeat,
+                ping_pong,
+            } => f
+                .debug_struct("Repeat")
+                .field("node", node)
+                .field("repeat", repeat)
+                .field("ping_pong", ping_pong)
+                .finish(),
+        }
+    }
+}
+
+impl<P, C> TweenTrack for CurveTrack<P, C>
+where
+    P: AnimatableProperty,
+    C: Curve<P::Property> + Send + Sync + 'static,
+{
+    fn duration(&self) -> f32 {
+        self.curve.domain().length()
+    }
+
+    fn apply(
+        &mut self,
+        t: f32,
+        entity: &mut AnimationEntityMut,
+    ) -> Result<(), AnimationEvaluationError> {
+        *self.property.get_mut(entity)? =
+            self.curve.sample_clamped(self.curve.domain().start() + t);
+        Ok(())
+    }
+}
+
+impl<P> TweenTrack for EaseTrack<P>
+where
+    P: AnimatableProperty,
+    P::Property: Clone,
+{
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn apply(
+        &mut self,
+        t: f32,
+        entity: &mut AnimationEntityMut,
+    ) -> Result<(), AnimationEvaluationError> {
+        let value = self.property.get_mut(entity)?;
+        let start = self.start.get_or_insert_with(|| value.clone());
+        let progress = if self.duration > 0.0 {
+            self.ease.sample_clamped(t / self.duration)
+        } else {
+            1.0
+        };
+        *value = P::Property::interpolate(start, &self.end, progress);
+        Ok(())
+    }
+}
+
+/// A system that advances [`Tween`]s, applies them to their targets, and
+/// despawns them once they complete.
+pub fn advance_tweens(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tweens: Query<(Entity, &mut Tween, &TweenOf)>,
+    mut targets: Query<AnimationEntityMut, Without<Tween>>,
+) {
+    let delta_seconds = time.delta_secs();
+    for (entity, mut tween, &TweenOf(target)) in &mut tweens {
+        let Ok(mut target_entity) = targets.get_mut(target) else {
+            continue;
+        };
+        if !tween.paused {
+            tween.elapsed += delta_seconds * tween.speed;
+        }
+        let elapsed = tween.elapsed;
+        if let Err(err) = tween.node.apply(elapsed, &mut target_entity) {
+            warn!("Tween application failed: {:?}", err);
+        }
+
+        if tween.is_completed() {
+            commands.trigger(TweenCompleted {
+                entity: target,
+                tween: entity,
+            });
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use bevy_ecs::{
+        observer::On,
+        resource::Resource,
+        system::{ResMut, RunSystemOnce},
+        world::World,
+    };
+    use bevy_math::Vec3;
+    use bevy_transform::components::Transform;
+
+    use super::*;
+    use crate::{animated_field, animation_curves::AnimatedField};
+
+    fn advance(world: &mut World, seconds: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        world.run_system_once(advance_tweens).unwrap();
+        world.flush();
+    }
+
+    fn scale(world: &World, entity: Entity) -> Vec3 {
+        world.get::<Transform>(entity).unwrap().scale
+    }
+
+    #[test]
+    fn tween_sequence_completes() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<Completions>();
+        world.add_observer(
+            |_: On<TweenCompleted>, mut completions: ResMut<Completions>| {
+                completions.0 += 1;
+            },
+        );
+
+        let entity = world.spawn(Transform::default()).id();
+        let scale_field = animated_field!(Transform::scale);
+        let tween = TweenNode::to(
+            scale_field.clone(),
+            Vec3::splat(2.0),
+            1.0,
+            EaseFunction::Linear,
+        )
+        .then(TweenNode::delay(1.0))
+        .then(TweenNode::to(
+            scale_field,
+            Vec3::ONE,
+            1.0,
+            EaseFunction::Linear,
+        ));
+        assert_eq!(tween.duration(), 3.0);
+        let tween = world.spawn((Tween::new(tween), TweenOf(entity))).id();
+
+        advance(&mut world, 0.5);
+        assert!(scale(&world, entity).abs_diff_eq(Vec3::splat(1.5), 1e-5));
+        advance(&mut world, 1.0);
+        assert!(scale(&world, entity).abs_diff_eq(Vec3::splat(2.0), 1e-5));
+        advance(&mut world, 1.0);
+        assert!(scale(&world, entity).abs_diff_eq(Vec3::splat(1.5), 1e-5));
+        assert_eq!(world.resource::<Completions>().0, 0);
+
+        advance(&mut world, 1.0);
+        assert!(scale(&world, entity).abs_diff_eq(Vec3::ONE, 1e-5));
+        assert_eq!(world.resource::<Completions>().0, 1);
+        assert!(world.get_entity(tween).is_err());
+    }
+
+    #[test]
+    fn tween_ping_pong() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world.spawn(Transform::default()).id();
+        let tween = TweenNode::from_to(
+            animated_field!(Transform::scale),
+            Vec3::ZERO,
+            Vec3::ONE,
+            1.0,
+            EaseFunction::Linear,
+        )
+        .ping_pong(RepeatAnimation::Count(3));
+        assert_eq!(tween.duration(), 3.0);
+        world.spawn((Tween::new(tween), TweenOf(entity)));
+
+        advance(&mut world, 0.25);
+        assert!(scale(&world, entity).abs_diff_eq(Vec3::splat(0.25), 1e-5));
+        advance(&mut world, 1.0);
+        assert!(scale(&world, entity).abs_diff_eq(Vec3::splat(0.75), 1e-5));
+        advance(&mut world, 1.0);
+        assert!(scale(&world, entity).abs_diff_eq(Vec3::splat(0.25), 1e-5));
+        advance(&mut world, 1.0);
+        assert!(scale(&world, entity).abs_diff_eq(Vec3::ONE, 1e-5));
+    }
+
+    #[derive(Resource, Default)]
+    struct Completions(usize);
+}
@@ -0,0 +1,397 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    io::Reader, Asset, AssetLoader, AssetPath, AssetServer, Assets, Handle, LoadContext, LoadState,
+    LoadedUntypedAsset, ParseAssetPathError, RecursiveDependencyLoadState,
+};
+use bevy_reflect::TypePath;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A collection of assets of any type, loaded from a manifest file listing their paths.
+///
+/// Collections are loaded by [`AssetCollectionLoader`] from `.assets.ron` files:
+///
+/// ```ron
+/// (
+///     assets: [
+///         "textures/player.png",
+///         "models/level.gltf#Scene0",
+///         // Collections can include other collections.
+///         "levels/shared.assets.ron",
+///     ],
+/// )
+/// ```
+///
+/// Paths are relative to the root of their asset source, like paths passed to
+/// [`AssetServer::load`]. Every listed asset is a dependency of the collection, so
+/// [`AssetServer::is_loaded_with_dependencies`] on the collection's handle returns `true` once
+/// every listed asset (and everything they depend on) has loaded. Use
+/// [`AssetCollection::progress`] to track how much of the collection has loaded so far.
+#[derive(Asset, TypePath)]
+pub struct AssetCollection {
+    /// The handles of the listed assets, in the order they appear in the manifest.
+    #[dependency]
+    pub handles: Vec<Handle<LoadedUntypedAsset>>,
+    /// The paths of the listed assets. `paths[i]` is the path of `handles[i]`.
+    pub paths: Vec<AssetPath<'static>>,
+}
+
+impl AssetCollection {
+    /// Returns the number of assets listed in this collection.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if this collection lists no assets.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Iterates over the path and handle of every asset listed in this collection.
+    pub fn iter(&self) -> impl Iterator<Item = (&AssetPath<'static>, &Handle<LoadedUntypedAsset>)> {
+        self.paths.iter().zip(&self.handles)
+    }
+
+    /// Returns the handle to the [`LoadedUntypedAsset`] for `path`, if it is listed in this
+    /// collection.
+    pub fn get_untyped<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+    ) -> Option<&Handle<LoadedUntypedAsset>> {
+        let path = path.into();
+        self.iter()
+            .find_map(|(entry_path, handle)| (*entry_path == path).then_some(handle))
+    }
+
+    /// Returns the typed handle for `path`, if it is listed in this collection, has finished
+    /// loading, and is of type `A`.
+    pub fn get<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        loaded_untyped_assets: &Assets<LoadedUntypedAsset>,
+    ) -> Option<Handle<A>> {
+        let loaded = loaded_untyped_assets.get(self.get_untyped(path)?)?;
+        loaded.handle.clone().try_typed().ok()
+    }
+
+    /// Returns how many of the assets listed in this collection have finished loading, including
+    /// their dependencies.
+    pub fn progress(&self, asset_server: &AssetServer) -> LoadProgress {
+        let mut progress = LoadProgress {
+            total: self.handles.len(),
+            ..Default::default()
+        };
+        for handle in &self.handles {
+            match asset_server.get_load_states(handle) {
+                Some((LoadState::Failed(_), _, _))
+                | Some((_, _, RecursiveDependencyLoadState::Failed(_))) => progress.failed += 1,
+                Some((LoadState::Loaded, _, RecursiveDependencyLoadState::Loaded)) => {
+                    progress.loaded += 1;
+                }
+                _ => {}
+            }
+        }
+        progress
+    }
+}
+
+/// The loading progress of a group of assets, as returned by [`AssetCollection::progress`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// The number of assets that have loaded, along with all their dependencies.
+    pub loaded: usize,
+    /// The number of assets that (or one of whose dependencies) failed to load.
+    pub failed: usize,
+    /// The total number of assets.
+    pub total: usize,
+}
+
+impl LoadProgress {
+    /// Returns the fraction of assets that have loaded, between `0.0` and `1.0`.
+    ///
+    /// An empty group of assets is considered fully loaded.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.loaded as f32 / self.total as f32
+        }
+    }
+
+    /// Returns `true` if every asset has loaded.
+    pub fn is_finished(&self) -> bool {
+        self.loaded == self.total
+    }
+}
+
+/// The serialized form of an [`AssetCollection`].
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct AssetCollectionManifest {
+    /// The paths of the assets in the collection.
+    pub assets: Vec<String>,
+}
+
+/// Loads [`AssetCollection`]s from `.assets.ron` manifest files.
+///
+/// This is registered by [`AssetPlugin`](crate::AssetPlugin).
+#[derive(Default, TypePath)]
+pub struct AssetCollectionLoader;
+
+/// An error that occurs when loading an [`AssetCollection`].
+#[derive(Error, Debug)]
+pub enum AssetCollectionLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not read the manifest: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON](ron) Error
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+    /// A listed path is not a valid [`AssetPath`].
+    #[error("Invalid asset path '{path}': {error}")]
+    InvalidPath {
+        path: String,
+        error: ParseAssetPathError,
+    },
+}
+
+impl AssetLoader for AssetCollectionLoader {
+    type Asset = AssetCollection;
+    type Settings = ();
+    type Error = AssetCollectionLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<AssetCollection, AssetCollectionLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let manifest: AssetCollectionManifest = ron::de::from_bytes(&bytes)?;
+
+        let mut handles = Vec::with_capacity(manifest.assets.len());
+        let mut paths = Vec::with_capacity(manifest.assets.len());
+        for path in manifest.assets {
+            let asset_path = match AssetPath::try_parse(&path) {
+                Ok(asset_path) => asset_path.into_owned(),
+                Err(error) => return Err(AssetCollectionLoaderError::InvalidPath { path, error }),
+            };
+            handles.push(
+                load_context
+                    .loader()
+                    .with_unknown_type()
+                    .load(asset_path.clone()),
+            );
+            paths.push(asset_path);
+        }
+        Ok(AssetCollection { handles, paths })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["assets.ron"]
+    }
+}
+
+#[cfg(feature = "bevy_state")]
+pub use state::*;
+
+#[cfg(feature = "bevy_state")]
+mod state {
+    use super::{AssetCollection, LoadProgress};
+    use crate::{AssetPath, AssetServer, Assets, Handle, LoadState};
+    use alloc::vec::Vec;
+    use bevy_app::{App, Update};
+    use bevy_ecs::prelude::*;
+    use bevy_state::state::{FreelyMutableState, NextState, OnEnter, State};
+    use tracing::{error, warn};
+
+    /// Tracks the [`AssetCollection`]s loaded in the loading states of `S`, registered with
+    /// [`AssetCollectionStateAppExt::load_collection_in_state`].
+    ///
+    /// A collection is tracked from the first time its loading state is entered, and afterwards, so
+    /// its handle can be used to access the loaded assets.
+    #[derive(Resource)]
+    pub struct LoadingCollections<S: FreelyMutableState> {
+        collections: Vec<LoadingCollection<S>>,
+        /// The loading states and the states they advance to, in registration order.
+        next_states: Vec<(S, S)>,
+    }
+
+    impl<S: FreelyMutableState> Default for LoadingCollections<S> {
+        fn default() -> Self {
+            Self {
+                collections: Vec::new(),
+                next_states: Vec::new(),
+            }
+        }
+    }
+
+    impl<S: FreelyMutableState> LoadingCollections<S> {
+        /// Returns the collection loaded from `path`, if its loading state has been entered.
+        pub fn get<'a>(&self, path: impl Into<AssetPath<'a>>) -> Option<&LoadingCollection<S>> {
+            let path = path.into();
+            self.collections
+                .iter()
+                .find(|collection| collection.handle.path() == Some(&path))
+        }
+
+        /// Iterates over the collections whose loading state has been entered.
+        pub fn iter(&self) -> impl Iterator<Item = &LoadingCollection<S>> {
+            self.collections.iter()
+        }
+
+        /// Returns the combined progress of the collections loaded in `state`, as of the last
+        /// update.
+        pub fn progress(&self, state: &S) -> LoadProgress {
+            self.collections
+                .iter()
+                .filter(|collection| collection.state == *state)
+                .fold(LoadProgress::default(), |progress, collection| {
+                    LoadProgress {
+                        loaded: progress.loaded + collection.progress.loaded,
+                        failed: progress.failed + collection.progress.failed,
+                        total: progress.total + collection.progress.total,
+                    }
+                })
+        }
+    }
+
+    /// An [`AssetCollection`] loaded in a loading state, see [`LoadingCollections`].
+    pub struct LoadingCollection<S: FreelyMutableState> {
+        /// The handle to the collection.
+        pub handle: Handle<AssetCollection>,
+        /// The progress of the collection as of the last update.
+        pub progress: LoadProgress,
+        /// The state the collection is loaded in.
+        pub state: S,
+    }
+
+    /// Adds [`AssetCollection`] loading to [`App`]s using states.
+    pub trait AssetCollectionStateAppExt {
+        /// Loads the [`AssetCollection`] at `path` when entering `loading`, and transitions to
+        /// `next` once it and all the assets it lists have loaded.
+        ///
+        /// Several collections can be loaded in the same state, in which case the state advances
+        /// once all of them have loaded. They must share the same `next` state: registering a
+        /// different one logs a warning, and the first one registered is used. Progress is reported
+        /// through the [`LoadingCollections<S>`] resource. If any asset fails to load, an error is
+        /// logged and the state does not advance.
+        fn load_collection_in_state<S: FreelyMutableState>(
+            &mut self,
+            loading: S,
+            next: S,
+            path: impl Into<AssetPath<'static>>,
+        ) -> &mut Self;
+    }
+
+    impl AssetCollectionStateAppExt for App {
+        fn load_collection_in_state<S: FreelyMutableState>(
+            &mut self,
+            loading: S,
+            next: S,
+            path: impl Into<AssetPath<'static>>,
+        ) -> &mut Self {
+            if !self.world().contains_resource::<LoadingCollections<S>>() {
+                self.init_resource::<LoadingCollections<S>>().add_systems(
+                    Update,
+                    advance_when_collection_loaded::<S>.run_if(resource_exists::<State<S>>),
+                );
+            }
+            let path = path.into();
+            let mut loading_collections = self.world_mut().resource_mut::<LoadingCollections<S>>();
+            match loading_collections
+                .next_states
+                .iter()
+                .find(|(state, _)| *state == loading)
+            {
+                Some((_, registered)) if *registered != next => warn!(
+                    "The asset collection {path} is loaded in {loading:?} with the next state \
+                    {next:?}, but {registered:?} was registered first and will be used"
+                ),
+                Some(_) => {}
+                None => loading_collections
+                    .next_states
+                    .push((loading.clone(), next)),
+            }
+            self.add_systems(
+                OnEnter(loading.clone()),
+                move |mut loading_collections: ResMut<LoadingCollections<S>>,
+                      asset_server: Res<AssetServer>| {
+                    let collection = LoadingCollection {
+                        handle: asset_server.load(path.clone()),
+                        progress: LoadProgress::default(),
+                        state: loading.clone(),
+                    };
+                    match loading_collections.collections.iter_mut().find(|entry| {
+                        entry.state == collection.state && entry.handle == collection.handle
+                    }) {
+                        Some(entry) => *entry = collection,
+                        None => loading_collections.collections.push(collection),
+                    }
+                },
+            )
+        }
+    }
+
+    fn advance_when_collection_loaded<S: FreelyMutableState>(
+        state: Res<State<S>>,
+        mut loading_collections: ResMut<LoadingCollections<S>>,
+        asset_server: Res<AssetServer>,
+        collections: Res<Assets<AssetCollection>>,
+        mut next_state: ResMut<NextState<S>>,
+    ) {
+        let Some((_, next)) = loading_collections
+            .next_states
+            .iter()
+            .find(|(loading, _)| loading == state.get())
+        else {
+            return;
+        };
+        let next = next.clone();
+        let mut finished = true;
+        let mut changed = false;
+        for loading in loading_collections
+            .bypass_change_detection()
+            .collections
+            .iter_mut()
+            .filter(|loading| loading.state == *state.get())
+        {
+            let progress = match collections.get(&loading.handle) {
+                Some(collection) => collection.progress(&asset_server),
+                None => match asset_server.load_state(&loading.handle) {
+                    LoadState::Failed(err) => {
+                        if loading.progress.failed == 0 {
+                            error!("Failed to load asset collection: {err}");
+                        }
+                        LoadProgress {
+                            loaded: 0,
+                            failed: 1,
+                            total: 1,
+                        }
+                    }
+                    _ => LoadProgress::default(),
+                },
+            };
+            if progress.failed > loading.progress.failed {
+                error!(
+                    "{} asset(s) in the asset collection {:?} failed to load",
+                    progress.failed,
+                    loading.handle.path()
+                );
+            }
+            if progress != loading.progress {
+                loading.progress = progress;
+                changed = true;
+            }
+            // The collection itself isn't loaded yet when its progress is empty.
+            finished &= progress.is_finished() && collections.contains(&loading.handle);
+        }
+        if changed {
+            loading_collections.set_changed();
+        }
+        if finished {
+            next_state.set(next);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{Asset, AssetId, AssetLoadError, AssetPath, UntypedAssetId};
+use alloc::vec::Vec;
 use bevy_ecs::message::Message;
 use bevy_reflect::Reflect;
 use core::fmt::Debug;
@@ -44,6 +45,25 @@ impl<A: Asset> From<&AssetLoadFailedEvent<A>> for UntypedAssetLoadFailedEvent {
     }
 }
 
+/// A [`Message`] emitted once the [`AssetServer`](crate::AssetServer) has finished hot-reloading a
+/// batch of assets in response to changes on disk.
+///
+/// Assets are reloaded in dependency order: an asset that loads another asset's value in its
+/// [`AssetLoader`](crate::AssetLoader) (a "loader dependency") is only reloaded once that asset has
+/// been reloaded. Everything affected by the same set of changes is reported in a single message.
+#[derive(Message, Clone, Debug, Default)]
+pub struct AssetsReloaded {
+    /// The paths of the assets that were reloaded, in the order they were reloaded. This includes
+    /// both the assets that changed and the assets that depend on them in their loaders.
+    pub reloaded: Vec<AssetPath<'static>>,
+    /// The paths of the assets that failed to reload.
+    pub failed: Vec<AssetPath<'static>>,
+    /// The assets that were not reloaded themselves, but hold handles (directly, or through other
+    /// assets) to a reloaded asset. Systems that cache data derived from an asset's dependencies
+    /// should refresh it for these assets.
+    pub dependents: Vec<UntypedAssetId>,
+}
+
 /// [`Message`]s that occur for a specific loaded [`Asset`], such as "value changed" events and "dependency" events.
 #[expect(missing_docs, reason = "Documenting the id fields is unhelpful.")]
 #[derive(Message, Reflect)]
@@ -90,6 +90,18 @@ impl AssetReader for FileAssetReader {
             .map_err(|_e| AssetReaderError::NotFound(path.to_owned()))?;
         Ok(metadata.file_type().is_dir())
     }
+
+    async fn byte_len<'a>(&'a self, path: &'a Path) -> Result<u64, AssetReaderError> {
+        let full_path = self.root_path.join(path);
+        let metadata = full_path.metadata().map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AssetReaderError::NotFound(full_path.clone())
+            } else {
+                e.into()
+            }
+        })?;
+        Ok(metadata.len())
+    }
 }
 
 impl AssetWriter for FileAssetWriter {
@@ -7,7 +7,7 @@ use bevy_platform::{
     collections::HashMap,
     sync::{PoisonError, RwLock},
 };
-use core::{pin::Pin, task::Poll};
+use core::{ops::Range, pin::Pin, task::Poll};
 use futures_io::{AsyncRead, AsyncWrite};
 use futures_lite::Stream;
 use std::{
@@ -397,6 +397,32 @@ impl AssetReader for MemoryAssetReader {
     async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
         Ok(self.root.get_dir(path).is_some())
     }
+
+    async fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, AssetReaderError> {
+        let data = self
+            .root
+            .get_asset(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?;
+        let bytes = data.value();
+        let start = usize::try_from(range.start)
+            .unwrap_or(usize::MAX)
+            .min(bytes.len());
+        let end = usize::try_from(range.end)
+            .unwrap_or(usize::MAX)
+            .clamp(start, bytes.len());
+        Ok(bytes[start..end].to_vec())
+    }
+
+    async fn byte_len<'a>(&'a self, path: &'a Path) -> Result<u64, AssetReaderError> {
+        self.root
+            .get_asset(path)
+            .map(|data| data.value().len() as u64)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))
+    }
 }
 
 /// A writer that writes into [`Dir`], buffering internally until flushed/closed.
@@ -21,14 +21,17 @@ pub mod web;
 pub mod gated;
 
 mod source;
+mod stream;
 
 pub use futures_lite::AsyncWriteExt;
 pub use source::*;
+pub use stream::*;
 
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use bevy_tasks::{BoxedFuture, ConditionalSendFuture};
 use core::{
     mem::size_of,
+    ops::Range,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -235,6 +238,62 @@ pub trait AssetReader: Send + Sync + 'static {
             Ok(meta_bytes)
         }
     }
+    /// Reads the bytes in `range` of the asset at the given `path` into a [`Vec<u8>`]. If the
+    /// range extends past the end of the asset, only the bytes up to the end are returned.
+    ///
+    /// This is used by [`AssetStream`] to read large assets piece by piece. The default
+    /// implementation seeks the reader returned by [`AssetReader::read`] if it is
+    /// [seekable](Reader::seekable), and otherwise reads and discards the bytes before the
+    /// range. Implementors should override it if they can read a range more efficiently (for
+    /// example with an HTTP range request).
+    fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> impl ConditionalSendFuture<Output = Result<Vec<u8>, AssetReaderError>> {
+        use futures_lite::{AsyncReadExt, AsyncSeekExt};
+
+        async move {
+            let mut reader = self.read(path).await?;
+            let len = range.end.saturating_sub(range.start);
+            let mut bytes = Vec::new();
+            match reader.seekable() {
+                Ok(seekable) => {
+                    seekable.seek(SeekFrom::Start(range.start)).await?;
+                    seekable.take(len).read_to_end(&mut bytes).await?;
+                }
+                Err(_) => {
+                    futures_lite::io::copy(
+                        (&mut reader).take(range.start),
+                        futures_lite::io::sink(),
+                    )
+                    .await?;
+                    reader.take(len).read_to_end(&mut bytes).await?;
+                }
+            }
+            Ok(bytes)
+        }
+    }
+    /// Returns the length in bytes of the asset at the given `path`.
+    ///
+    /// The default implementation seeks to the end of the reader returned by
+    /// [`AssetReader::read`] if it is [seekable](Reader::seekable), and otherwise reads the whole
+    /// asset. Implementors should override it if they can get the length more efficiently.
+    fn byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl ConditionalSendFuture<Output = Result<u64, AssetReaderError>> {
+        use futures_lite::AsyncSeekExt;
+
+        async move {
+            let mut reader = self.read(path).await?;
+            let len = match reader.seekable() {
+                Ok(seekable) => seekable.seek(SeekFrom::End(0)).await?,
+                Err(_) => futures_lite::io::copy(reader, futures_lite::io::sink()).await?,
+            };
+            Ok(len)
+        }
+    }
 }
 
 /// Equivalent to an [`AssetReader`] but using boxed futures, necessary eg. when using a `dyn AssetReader`,
@@ -266,6 +325,16 @@ pub trait ErasedAssetReader: Send + Sync + 'static {
         &'a self,
         path: &'a Path,
     ) -> BoxedFuture<'a, Result<Vec<u8>, AssetReaderError>>;
+    /// Reads the bytes in `range` of the asset at the given `path` into a [`Vec<u8>`]. See
+    /// [`AssetReader::read_range`].
+    fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, AssetReaderError>>;
+    /// Returns the length in bytes of the asset at the given `path`. See
+    /// [`AssetReader::byte_len`].
+    fn byte_len<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<u64, AssetReaderError>>;
 }
 
 impl<T: AssetReader> ErasedAssetReader for T {
@@ -305,6 +374,16 @@ impl<T: AssetReader> ErasedAssetReader for T {
     ) -> BoxedFuture<'a, Result<Vec<u8>, AssetReaderError>> {
         Box::pin(Self::read_meta_bytes(self, path))
     }
+    fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, AssetReaderError>> {
+        Box::pin(Self::read_range(self, path, range))
+    }
+    fn byte_len<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<u64, AssetReaderError>> {
+        Box::pin(Self::byte_len(self, path))
+    }
 }
 
 pub type Writer = dyn AsyncWrite + Unpin + Send + Sync;
@@ -740,6 +819,20 @@ pub(crate) fn slice_seek(
     Ok(new_pos as _)
 }
 
+/// Returns the bytes in `range` of `slice`, clamped to its length.
+///
+/// Used when a server ignores an HTTP range request and sends the whole resource instead.
+#[cfg(any(target_arch = "wasm32", feature = "http", feature = "https"))]
+pub(crate) fn slice_range(slice: &[u8], range: Range<u64>) -> &[u8] {
+    let start = usize::try_from(range.start)
+        .unwrap_or(usize::MAX)
+        .min(slice.len());
+    let end = usize::try_from(range.end)
+        .unwrap_or(usize::MAX)
+        .clamp(start, slice.len());
+    &slice[start..end]
+}
+
 /// Copies bytes from source to dest, keeping track of where in the source it starts copying from.
 ///
 /// This is effectively the impl for [`SliceReader::read_to_end`], but this is provided here so the
@@ -792,6 +885,16 @@ impl Stream for EmptyPathStream {
 mod tests {
     use super::*;
 
+    #[cfg(any(feature = "http", feature = "https"))]
+    #[test]
+    fn slice_range_clamps_to_slice() {
+        let bytes = [0, 1, 2, 3, 4];
+        assert_eq!(slice_range(&bytes, 1..3), [1, 2]);
+        assert_eq!(slice_range(&bytes, 3..10), [3, 4]);
+        assert!(slice_range(&bytes, 7..10).is_empty());
+        assert!(slice_range(&bytes, 2..2).is_empty());
+    }
+
     #[test]
     fn get_meta_path_no_extension() {
         assert_eq!(
@@ -8,7 +8,7 @@ use crate::{
 };
 use alloc::{borrow::ToOwned, boxed::Box, sync::Arc, vec::Vec};
 use async_lock::RwLockReadGuardArc;
-use core::{pin::Pin, task::Poll};
+use core::{ops::Range, pin::Pin, task::Poll};
 use futures_io::AsyncRead;
 use std::path::Path;
 use tracing::trace;
@@ -40,6 +40,27 @@ impl ProcessorGatedReader {
     }
 }
 
+impl ProcessorGatedReader {
+    /// Waits for the asset at `path` to be processed, then returns its transaction lock.
+    async fn wait_and_lock(&self, path: &Path) -> Result<RwLockReadGuardArc<()>, AssetReaderError> {
+        let asset_path = AssetPath::from(path.to_path_buf()).with_source(self.source.clone());
+        trace!("Waiting for processing to finish before reading {asset_path}");
+        match self
+            .processing_state
+            .wait_until_processed(asset_path.clone())
+            .await
+        {
+            ProcessStatus::Processed => {}
+            ProcessStatus::Failed | ProcessStatus::NonExistent => {
+                return Err(AssetReaderError::NotFound(path.to_owned()));
+            }
+        }
+        self.processing_state
+            .get_transaction_lock(&asset_path)
+            .await
+    }
+}
+
 impl AssetReader for ProcessorGatedReader {
     async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
         let asset_path = AssetPath::from(path.to_path_buf()).with_source(self.source.clone());
@@ -111,6 +132,20 @@ impl AssetReader for ProcessorGatedReader {
         let result = self.reader.is_directory(path).await?;
         Ok(result)
     }
+
+    async fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, AssetReaderError> {
+        let _lock = self.wait_and_lock(path).await?;
+        self.reader.read_range(path, range).await
+    }
+
+    async fn byte_len<'a>(&'a self, path: &'a Path) -> Result<u64, AssetReaderError> {
+        let _lock = self.wait_and_lock(path).await?;
+        self.reader.byte_len(path).await
+    }
 }
 
 /// An [`AsyncRead`] impl that will hold its asset's transaction lock until [`TransactionLockedReader`] is dropped.
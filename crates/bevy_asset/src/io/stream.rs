@@ -0,0 +1,326 @@
+use crate::{
+    io::{
+        AssetReaderError, AssetSources, ErasedAssetReader, MissingAssetSourceError,
+        MissingProcessedAssetReaderError,
+    },
+    AssetPath,
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use bevy_platform::cell::SyncCell;
+use bevy_tasks::BoxedFuture;
+use core::{
+    ops::Range,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_io::{AsyncRead, AsyncSeek};
+use std::io::SeekFrom;
+use thiserror::Error;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::io::{Reader, ReaderNotSeekableError, SeekableReader};
+
+/// The number of bytes an [`AssetStreamReader`] reads at a time by default.
+pub const DEFAULT_STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A handle to the bytes of an asset that are read on demand, rather than all at once.
+///
+/// Loaders for large assets (such as music or big binary blobs) can open a stream with
+/// [`LoadContext::open_stream`](crate::LoadContext::open_stream) and store it in the asset they
+/// return, instead of reading the whole file into memory. The bytes are then read in ranges from
+/// the [`AssetSource`](crate::io::AssetSource) the asset was loaded from, using
+/// [`AssetReader::read_range`](crate::io::AssetReader::read_range). This works with any source:
+/// files and memory are read directly, and web sources use HTTP range requests.
+///
+/// On wasm, neither the default source nor web sources support range reads: each range is read
+/// by fetching the whole asset and discarding the rest, so streaming only saves memory, at the
+/// cost of a download per read.
+///
+/// Streams are cheap to clone, and every clone (and every reader created from one) reads
+/// independently.
+#[derive(Clone)]
+pub struct AssetStream {
+    sources: Arc<AssetSources>,
+    path: AssetPath<'static>,
+    processed: bool,
+    len: u64,
+}
+
+impl core::fmt::Debug for AssetStream {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AssetStream")
+            .field("path", &self.path)
+            .field("processed", &self.processed)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An error that occurs when opening an [`AssetStream`].
+#[derive(Error, Debug, Clone)]
+pub enum AssetStreamError {
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingProcessedAssetReader(#[from] MissingProcessedAssetReaderError),
+    #[error(transparent)]
+    AssetReaderError(#[from] AssetReaderError),
+}
+
+impl AssetStream {
+    /// Opens a stream over the asset at `path`, reading processed assets if `processed` is true.
+    pub(crate) async fn open(
+        sources: Arc<AssetSources>,
+        path: AssetPath<'static>,
+        processed: bool,
+    ) -> Result<Self, AssetStreamError> {
+        let len = asset_reader(&sources, &path, processed)?
+            .byte_len(path.path())
+            .await?;
+        Ok(Self {
+            sources,
+            path,
+            processed,
+            len,
+        })
+    }
+
+    /// The path of the asset this stream reads from.
+    pub fn path(&self) -> &AssetPath<'static> {
+        &self.path
+    }
+
+    /// The length of the asset in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the asset has no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads the bytes in `range`. If the range extends past the end of the asset, only the bytes
+    /// up to the end are returned.
+    pub async fn read_range(&self, range: Range<u64>) -> Result<Vec<u8>, AssetReaderError> {
+        let reader = asset_reader(&self.sources, &self.path, self.processed)
+            .expect("the reader was resolved when the stream was opened");
+        reader.read_range(self.path.path(), range).await
+    }
+
+    /// Creates an [`AsyncRead`] + [`AsyncSeek`] reader over this stream, which reads [`DEFAULT_STREAM_CHUNK_SIZE`] bytes at a
+    /// time.
+    pub fn reader(&self) -> AssetStreamReader {
+        AssetStreamReader {
+            stream: self.clone(),
+            chunk_size: DEFAULT_STREAM_CHUNK_SIZE as u64,
+            position: 0,
+            chunk_start: 0,
+            chunk: Vec::new(),
+            pending: None,
+        }
+    }
+
+    /// Creates a blocking [`Read`](std::io::Read) + [`Seek`](std::io::Seek) reader over this
+    /// stream, for use with APIs that don't support async reads (such as audio decoders).
+    ///
+    /// Every read that isn't buffered blocks the current thread until the bytes have been
+    /// fetched, so this should not be used on threads that run async tasks.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn blocking_reader(&self) -> BlockingAssetStreamReader {
+        BlockingAssetStreamReader(self.reader())
+    }
+}
+
+fn asset_reader<'a>(
+    sources: &'a AssetSources,
+    path: &AssetPath<'_>,
+    processed: bool,
+) -> Result<&'a dyn ErasedAssetReader, AssetStreamError> {
+    let source = sources.get(path.source())?;
+    Ok(if processed {
+        source.processed_reader()?
+    } else {
+        source.reader()
+    })
+}
+
+/// An [`AsyncRead`] + [`AsyncSeek`] reader over an [`AssetStream`] that fetches and buffers one
+/// chunk of bytes at a time. Created with [`AssetStream::reader`].
+pub struct AssetStreamReader {
+    stream: AssetStream,
+    chunk_size: u64,
+    position: u64,
+    chunk_start: u64,
+    chunk: Vec<u8>,
+    /// The chunk that is currently being fetched, and the offset it starts at.
+    pending: Option<(
+        u64,
+        SyncCell<BoxedFuture<'static, Result<Vec<u8>, AssetReaderError>>>,
+    )>,
+}
+
+impl AssetStreamReader {
+    /// Sets the number of bytes fetched at a time.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1) as u64;
+        self
+    }
+}
+
+impl AsyncRead for AssetStreamReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+        loop {
+            let chunk_end = this.chunk_start + this.chunk.len() as u64;
+            if (this.chunk_start..chunk_end).contains(&this.position) {
+                let offset = (this.position - this.chunk_start) as usize;
+                let available = &this.chunk[offset..];
+                let n = available.len().min(buf.len());
+                buf[..n].copy_from_slice(&available[..n]);
+                this.position += n as u64;
+                return Poll::Ready(Ok(n));
+            }
+            if this.position >= this.stream.len || buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let (start, future) = this.pending.get_or_insert_with(|| {
+                let stream = this.stream.clone();
+                let range = this.position..this.position + this.chunk_size;
+                let future: BoxedFuture<'static, _> =
+                    Box::pin(async move { stream.read_range(range).await });
+                (this.position, SyncCell::new(future))
+            });
+            let start = *start;
+            let result = match future.get().as_mut().poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            };
+            this.pending = None;
+            match result {
+                Ok(bytes) if bytes.is_empty() => return Poll::Ready(Ok(0)),
+                Ok(bytes) => {
+                    this.chunk_start = start;
+                    this.chunk = bytes;
+                }
+                Err(err) => return Poll::Ready(Err(std::io::Error::other(err))),
+            }
+        }
+    }
+}
+
+impl AsyncSeek for AssetStreamReader {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => (offset, 0),
+            SeekFrom::End(offset) => (self.stream.len, offset),
+            SeekFrom::Current(offset) => (self.position, offset),
+        };
+        let Some(position) = base.checked_add_signed(offset) else {
+            return Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek position is out of range",
+            )));
+        };
+        self.position = position;
+        Poll::Ready(Ok(position))
+    }
+}
+
+// On wasm, the futures returned by asset readers are not `Send`, so neither is this reader.
+#[cfg(not(target_arch = "wasm32"))]
+impl Reader for AssetStreamReader {
+    fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
+        Ok(self)
+    }
+}
+
+/// A blocking [`Read`](std::io::Read) + [`Seek`](std::io::Seek) reader over an [`AssetStream`].
+/// Created with [`AssetStream::blocking_reader`].
+#[cfg(not(target_arch = "wasm32"))]
+pub struct BlockingAssetStreamReader(AssetStreamReader);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BlockingAssetStreamReader {
+    /// Sets the number of bytes fetched at a time.
+    pub fn with_chunk_size(self, chunk_size: usize) -> Self {
+        Self(self.0.with_chunk_size(chunk_size))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::io::Read for BlockingAssetStreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        bevy_tasks::block_on(futures_lite::AsyncReadExt::read(&mut self.0, buf))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::io::Seek for BlockingAssetStreamReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        bevy_tasks::block_on(futures_lite::AsyncSeekExt::seek(&mut self.0, pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AssetStream;
+    use crate::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetSourceBuilder, AssetSourceBuilders, AssetSourceId,
+    };
+    use alloc::{boxed::Box, sync::Arc, vec::Vec};
+    use futures_lite::{AsyncReadExt, AsyncSeekExt};
+    use std::{io::SeekFrom, path::Path};
+
+    fn open_stream(bytes: Vec<u8>) -> AssetStream {
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("big.bin"), bytes);
+        let reader = MemoryAssetReader { root: dir };
+        let mut builders = AssetSourceBuilders::default();
+        builders.insert(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(reader.clone())),
+        );
+        let sources = Arc::new(builders.build_sources(false, false));
+        bevy_tasks::block_on(AssetStream::open(sources, "big.bin".into(), false)).unwrap()
+    }
+
+    #[test]
+    fn stream_reads_in_chunks_and_seeks() {
+        let bytes = (0..1000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let stream = open_stream(bytes.clone());
+        assert_eq!(stream.len(), 1000);
+        assert_eq!(
+            bevy_tasks::block_on(stream.read_range(990..2000)).unwrap(),
+            &bytes[990..]
+        );
+
+        let mut reader = stream.reader().with_chunk_size(64);
+        let mut read = Vec::new();
+        bevy_tasks::block_on(reader.read_to_end(&mut read)).unwrap();
+        assert_eq!(read, bytes);
+
+        let mut buf = [0; 10];
+        bevy_tasks::block_on(async {
+            reader.seek(SeekFrom::Start(500)).await.unwrap();
+            reader.read_exact(&mut buf).await.unwrap();
+        });
+        assert_eq!(buf, bytes[500..510]);
+
+        let mut blocking = stream.blocking_reader().with_chunk_size(7);
+        std::io::Seek::seek(&mut blocking, SeekFrom::End(-20)).unwrap();
+        let mut tail = Vec::new();
+        std::io::Read::read_to_end(&mut blocking, &mut tail).unwrap();
+        assert_eq!(tail, &bytes[980..]);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::io::{
     get_meta_path, AssetReader, AssetReaderError, EmptyPathStream, PathStream, Reader, VecReader,
 };
-use alloc::{borrow::ToOwned, boxed::Box, format};
+use alloc::{borrow::ToOwned, boxed::Box, format, vec::Vec};
+use core::ops::Range;
 use js_sys::{Uint8Array, JSON};
 use std::{
     borrow::Cow,
@@ -10,7 +11,7 @@ use std::{
 use tracing::error;
 use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
-use web_sys::Response;
+use web_sys::{Headers, RequestInit, Response};
 
 /// Represents the global object in the JavaScript context
 #[wasm_bindgen]
@@ -66,48 +67,125 @@ fn js_value_to_err(context: &str) -> impl FnOnce(JsValue) -> std::io::Error + '_
     }
 }
 
+/// Fetches `fetch_path` with the request options in `init`.
+async fn fetch(fetch_path: &str, init: &RequestInit) -> Result<Response, AssetReaderError> {
+    // The JS global scope includes a self-reference via a specializing name, which can be used to determine the type of global context available.
+    let global: Global = js_sys::global().unchecked_into();
+    let promise = if !global.window().is_undefined() {
+        let window: web_sys::Window = global.unchecked_into();
+        window.fetch_with_str_and_init(fetch_path, init)
+    } else if !global.worker().is_undefined() {
+        let worker: web_sys::WorkerGlobalScope = global.unchecked_into();
+        worker.fetch_with_str_and_init(fetch_path, init)
+    } else {
+        let error = std::io::Error::other("Unsupported JavaScript global context");
+        return Err(AssetReaderError::Io(error.into()));
+    };
+    let resp_value = JsFuture::from(promise)
+        .await
+        .map_err(js_value_to_err("fetch path"))?;
+    let resp = resp_value
+        .dyn_into::<Response>()
+        .map_err(js_value_to_err("convert fetch to Response"))?;
+    Ok(resp)
+}
+
+/// Reads the body of `resp`.
+async fn response_bytes(resp: &Response) -> Vec<u8> {
+    let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
+    Uint8Array::new(&data).to_vec()
+}
+
+/// Returns the error for a response with an unexpected status.
+fn status_to_err(status: u16, fetch_path: &str) -> AssetReaderError {
+    match status {
+        // Some web servers, including itch.io's CDN, return 403 when a requested file isn't present.
+        // TODO: remove handling of 403 as not found when it's easier to configure
+        // see https://github.com/bevyengine/bevy/pull/19268#pullrequestreview-2882410105
+        403 | 404 => AssetReaderError::NotFound(fetch_path.into()),
+        status => AssetReaderError::HttpError(status),
+    }
+}
+
 impl HttpWasmAssetReader {
+    /// Returns the URL to fetch for `path`, after applying the request mapper.
+    fn fetch_path<'a>(&self, path: &'a Path) -> Cow<'a, str> {
+        let path = path.to_str().unwrap();
+        self.request_mapper
+            .as_ref()
+            .map_or_else(|| Cow::Borrowed(path), |mapper| mapper(path))
+    }
+
     // Also used by [`WebAssetReader`](crate::web::WebAssetReader)
     pub(crate) async fn fetch_bytes(
         &self,
         path: PathBuf,
     ) -> Result<impl Reader + use<>, AssetReaderError> {
-        let path = path.to_str().unwrap();
-        let fetch_path = self
-            .request_mapper
-            .as_ref()
-            .map_or_else(|| Cow::Borrowed(path), |mapper| mapper(path));
-
-        // The JS global scope includes a self-reference via a specializing name, which can be used to determine the type of global context available.
-        let global: Global = js_sys::global().unchecked_into();
-        let promise = if !global.window().is_undefined() {
-            let window: web_sys::Window = global.unchecked_into();
-            window.fetch_with_str(&fetch_path)
-        } else if !global.worker().is_undefined() {
-            let worker: web_sys::WorkerGlobalScope = global.unchecked_into();
-            worker.fetch_with_str(&fetch_path)
-        } else {
-            let error = std::io::Error::other("Unsupported JavaScript global context");
-            return Err(AssetReaderError::Io(error.into()));
-        };
-        let resp_value = JsFuture::from(promise)
-            .await
-            .map_err(js_value_to_err("fetch path"))?;
-        let resp = resp_value
-            .dyn_into::<Response>()
-            .map_err(js_value_to_err("convert fetch to Response"))?;
+        let fetch_path = self.fetch_path(&path);
+        let resp = fetch(&fetch_path, &RequestInit::new()).await?;
         match resp.status() {
+            200 => Ok(VecReader::new(response_bytes(&resp).await)),
+            status => Err(status_to_err(status, &fetch_path)),
+        }
+    }
+
+    /// Fetches the bytes in `range` of the resource at `path` with an HTTP range request.
+    // Also used by [`WebAssetReader`](crate::web::WebAssetReader)
+    pub(crate) async fn fetch_range(
+        &self,
+        path: PathBuf,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, AssetReaderError> {
+        if range.start >= range.end {
+            return Ok(Vec::new());
+        }
+        let fetch_path = self.fetch_path(&path);
+        let headers = Headers::new().map_err(js_value_to_err("create headers"))?;
+        headers
+            .set("Range", &format!("bytes={}-{}", range.start, range.end - 1))
+            .map_err(js_value_to_err("set the Range header"))?;
+        let init = RequestInit::new();
+        init.set_headers(&headers);
+        let resp = fetch(&fetch_path, &init).await?;
+        match resp.status() {
+            206 => Ok(response_bytes(&resp).await),
+            // The server doesn't support range requests and sent the whole resource instead.
             200 => {
-                let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
-                let bytes = Uint8Array::new(&data).to_vec();
-                let reader = VecReader::new(bytes);
-                Ok(reader)
+                let bytes = response_bytes(&resp).await;
+                Ok(crate::io::slice_range(&bytes, range).to_vec())
+            }
+            // The range starts past the end of the resource.
+            416 => Ok(Vec::new()),
+            status => Err(status_to_err(status, &fetch_path)),
+        }
+    }
+
+    /// Fetches the length of the resource at `path` with an HTTP `HEAD` request.
+    // Also used by [`WebAssetReader`](crate::web::WebAssetReader)
+    pub(crate) async fn fetch_len(&self, path: PathBuf) -> Result<u64, AssetReaderError> {
+        let fetch_path = self.fetch_path(&path);
+        let init = RequestInit::new();
+        init.set_method("HEAD");
+        let resp = fetch(&fetch_path, &init).await?;
+        if resp.status() != 200 {
+            return Err(status_to_err(resp.status(), &fetch_path));
+        }
+        let content_length = resp
+            .headers()
+            .get("content-length")
+            .ok()
+            .flatten()
+            .and_then(|value| value.parse().ok());
+        match content_length {
+            Some(len) => Ok(len),
+            // The server didn't report the length, so download the whole resource to find it.
+            None => {
+                let resp = fetch(&fetch_path, &RequestInit::new()).await?;
+                match resp.status() {
+                    200 => Ok(response_bytes(&resp).await.len() as u64),
+                    status => Err(status_to_err(status, &fetch_path)),
+                }
             }
-            // Some web servers, including itch.io's CDN, return 403 when a requested file isn't present.
-            // TODO: remove handling of 403 as not found when it's easier to configure
-            // see https://github.com/bevyengine/bevy/pull/19268#pullrequestreview-2882410105
-            403 | 404 => Err(AssetReaderError::NotFound((*fetch_path).into())),
-            status => Err(AssetReaderError::HttpError(status)),
         }
     }
 }
@@ -123,6 +201,18 @@ impl AssetReader for HttpWasmAssetReader {
         self.fetch_bytes(meta_path).await
     }
 
+    async fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, AssetReaderError> {
+        self.fetch_range(self.root_path.join(path), range).await
+    }
+
+    async fn byte_len<'a>(&'a self, path: &'a Path) -> Result<u64, AssetReaderError> {
+        self.fetch_len(self.root_path.join(path)).await
+    }
+
     async fn read_directory<'a>(
         &'a self,
         _path: &'a Path,
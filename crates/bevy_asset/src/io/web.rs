@@ -1,8 +1,10 @@
 use crate::io::{AssetReader, AssetReaderError, AssetSourceBuilder, PathStream, Reader};
 use crate::{AssetApp, AssetPlugin};
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 use bevy_app::{App, Plugin};
 use bevy_tasks::ConditionalSendFuture;
+use core::ops::Range;
 use std::path::{Path, PathBuf};
 use tracing::warn;
 
@@ -86,6 +88,9 @@ impl Plugin for WebAssetPlugin {
 }
 
 /// Asset reader that treats paths as urls to load assets from.
+///
+/// [`AssetReader::read_range`] makes HTTP range requests, and [`AssetReader::byte_len`] makes
+/// `HEAD` requests.
 pub enum WebAssetReader {
     /// Unencrypted connections.
     Http,
@@ -119,24 +124,23 @@ async fn get<'a>(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
         .map(|r| Box::new(r) as Box<dyn Reader>)
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
-    use crate::io::VecReader;
-    use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
-    use bevy_platform::sync::LazyLock;
-    use blocking::unblock;
-    use std::io::{self, BufReader, Read};
+#[cfg(target_arch = "wasm32")]
+async fn get_range(path: PathBuf, range: Range<u64>) -> Result<Vec<u8>, AssetReaderError> {
+    use crate::io::wasm::HttpWasmAssetReader;
 
-    let str_path = path.to_str().ok_or_else(|| {
-        AssetReaderError::Io(
-            io::Error::other(std::format!("non-utf8 path: {}", path.display())).into(),
-        )
-    })?;
+    HttpWasmAssetReader::new("").fetch_range(path, range).await
+}
 
-    #[cfg(all(not(target_arch = "wasm32"), feature = "web_asset_cache"))]
-    if let Some(data) = web_asset_cache::try_load_from_cache(str_path).await? {
-        return Ok(Box::new(VecReader::new(data)));
-    }
+#[cfg(target_arch = "wasm32")]
+async fn get_len(path: PathBuf) -> Result<u64, AssetReaderError> {
+    use crate::io::wasm::HttpWasmAssetReader;
+
+    HttpWasmAssetReader::new("").fetch_len(path).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn agent() -> &'static ureq::Agent {
+    use bevy_platform::sync::LazyLock;
     use ureq::tls::{RootCerts, TlsConfig};
     use ureq::Agent;
 
@@ -150,11 +154,53 @@ async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
             .build()
             .new_agent()
     });
+    &AGENT
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path_to_str(path: &Path) -> Result<&str, AssetReaderError> {
+    path.to_str().ok_or_else(|| {
+        AssetReaderError::Io(
+            std::io::Error::other(std::format!("non-utf8 path: {}", path.display())).into(),
+        )
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn to_reader_error(path: PathBuf, err: ureq::Error) -> AssetReaderError {
+    match err {
+        // ureq considers all >=400 status codes as errors
+        ureq::Error::StatusCode(404) => AssetReaderError::NotFound(path),
+        ureq::Error::StatusCode(code) => AssetReaderError::HttpError(code),
+        err => AssetReaderError::Io(
+            std::io::Error::other(std::format!(
+                "unexpected error while loading asset {}: {}",
+                path.display(),
+                err
+            ))
+            .into(),
+        ),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
+    use crate::io::VecReader;
+    use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
+    use blocking::unblock;
+    use std::io::{BufReader, Read};
+
+    let str_path = path_to_str(&path)?;
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "web_asset_cache"))]
+    if let Some(data) = web_asset_cache::try_load_from_cache(str_path).await? {
+        return Ok(Box::new(VecReader::new(data)));
+    }
 
     let uri = str_path.to_owned();
     // Use [`unblock`] to run the http request on a separately spawned thread as to not block bevy's
     // async executor.
-    let response = unblock(|| AGENT.get(uri).call()).await;
+    let response = unblock(|| agent().get(uri).call()).await;
 
     match response {
         Ok(mut response) => {
@@ -168,22 +214,85 @@ async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
 
             Ok(Box::new(VecReader::new(buffer)))
         }
-        // ureq considers all >=400 status codes as errors
-        Err(ureq::Error::StatusCode(code)) => {
-            if code == 404 {
-                Err(AssetReaderError::NotFound(path))
-            } else {
-                Err(AssetReaderError::HttpError(code))
-            }
+        Err(err) => Err(to_reader_error(path, err)),
+    }
+}
+
+/// Fetches the bytes in `range` of the resource at `path` with an HTTP range request.
+#[cfg(not(target_arch = "wasm32"))]
+async fn get_range(path: PathBuf, range: Range<u64>) -> Result<Vec<u8>, AssetReaderError> {
+    use alloc::borrow::ToOwned;
+    use blocking::unblock;
+    use std::io::Read;
+
+    if range.start >= range.end {
+        return Ok(Vec::new());
+    }
+    let str_path = path_to_str(&path)?;
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "web_asset_cache"))]
+    if let Some(bytes) = web_asset_cache::try_read_range_from_cache(str_path, range.clone()).await?
+    {
+        return Ok(bytes);
+    }
+
+    let uri = str_path.to_owned();
+    let header = std::format!("bytes={}-{}", range.start, range.end - 1);
+    let result = unblock(move || {
+        let mut response = agent().get(uri).header("Range", header).call()?;
+        let partial = response.status().as_u16() == 206;
+        let mut bytes = Vec::new();
+        response
+            .body_mut()
+            .with_config()
+            .reader()
+            .read_to_end(&mut bytes)?;
+        Ok::<_, ureq::Error>((partial, bytes))
+    })
+    .await;
+
+    match result {
+        Ok((true, bytes)) => Ok(bytes),
+        // The server doesn't support range requests and sent the whole resource instead.
+        Ok((false, bytes)) => Ok(crate::io::slice_range(&bytes, range).to_vec()),
+        // The range starts past the end of the resource.
+        Err(ureq::Error::StatusCode(416)) => Ok(Vec::new()),
+        Err(err) => Err(to_reader_error(path, err)),
+    }
+}
+
+/// Fetches the length of the resource at `path` with an HTTP `HEAD` request.
+#[cfg(not(target_arch = "wasm32"))]
+async fn get_len(path: PathBuf) -> Result<u64, AssetReaderError> {
+    use alloc::borrow::ToOwned;
+    use blocking::unblock;
+
+    let str_path = path_to_str(&path)?;
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "web_asset_cache"))]
+    if let Some(len) = web_asset_cache::try_len_from_cache(str_path).await? {
+        return Ok(len);
+    }
+
+    let uri = str_path.to_owned();
+    let response = unblock(move || agent().head(uri).call()).await;
+    let content_length = match response {
+        Ok(response) => response
+            .headers()
+            .get("content-length")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok()),
+        Err(err) => return Err(to_reader_error(path, err)),
+    };
+    match content_length {
+        Some(len) => Ok(len),
+        // The server didn't report the length, so download the whole resource to find it.
+        None => {
+            let mut reader = get(path).await?;
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(bytes.len() as u64)
         }
-        Err(err) => Err(AssetReaderError::Io(
-            io::Error::other(std::format!(
-                "unexpected error while loading asset {}: {}",
-                path.display(),
-                err
-            ))
-            .into(),
-        )),
     }
 }
 
@@ -210,6 +319,18 @@ impl AssetReader for WebAssetReader {
     ) -> Result<Box<PathStream>, AssetReaderError> {
         Err(AssetReaderError::NotFound(self.make_uri(path)))
     }
+
+    async fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, AssetReaderError> {
+        get_range(self.make_uri(path), range).await
+    }
+
+    async fn byte_len<'a>(&'a self, path: &'a Path) -> Result<u64, AssetReaderError> {
+        get_len(self.make_uri(path)).await
+    }
 }
 
 /// A naive implementation of a cache for assets downloaded from the web that never invalidates.
@@ -220,13 +341,12 @@ mod web_asset_cache {
     use alloc::string::String;
     use alloc::vec::Vec;
     use core::hash::{Hash, Hasher};
-    use futures_lite::AsyncWriteExt;
+    use core::ops::Range;
+    use futures_lite::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
     use std::collections::hash_map::DefaultHasher;
-    use std::io;
+    use std::io::{self, SeekFrom};
     use std::path::PathBuf;
 
-    use crate::io::Reader;
-
     const CACHE_DIR: &str = ".web-asset-cache";
 
     fn url_to_hash(url: &str) -> String {
@@ -249,6 +369,37 @@ mod web_asset_cache {
         }
     }
 
+    pub async fn try_read_range_from_cache(
+        url: &str,
+        range: Range<u64>,
+    ) -> Result<Option<Vec<u8>>, io::Error> {
+        let filename = url_to_hash(url);
+        let cache_path = PathBuf::from(CACHE_DIR).join(&filename);
+
+        if cache_path.exists() {
+            let mut file = async_fs::File::open(&cache_path).await?;
+            file.seek(SeekFrom::Start(range.start)).await?;
+            let mut buffer = Vec::new();
+            file.take(range.end.saturating_sub(range.start))
+                .read_to_end(&mut buffer)
+                .await?;
+            Ok(Some(buffer))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn try_len_from_cache(url: &str) -> Result<Option<u64>, io::Error> {
+        let filename = url_to_hash(url);
+        let cache_path = PathBuf::from(CACHE_DIR).join(&filename);
+
+        if cache_path.exists() {
+            Ok(Some(async_fs::metadata(&cache_path).await?.len()))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub async fn save_to_cache(url: &str, data: &[u8]) -> Result<(), io::Error> {
         let filename = url_to_hash(url);
         let cache_path = PathBuf::from(CACHE_DIR).join(&filename);
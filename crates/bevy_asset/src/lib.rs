@@ -174,6 +174,7 @@ pub mod prelude {
 
 mod asset_changed;
 mod assets;
+mod collection;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -189,6 +190,7 @@ mod server;
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
 use bevy_diagnostic::{Diagnostic, DiagnosticsStore, RegisterDiagnostic};
+pub use collection::*;
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -417,8 +419,11 @@ impl Plugin for AssetPlugin {
         app.insert_resource(embedded)
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
+            .init_asset::<AssetCollection>()
             .init_asset::<()>()
+            .register_asset_loader(AssetCollectionLoader)
             .add_message::<UntypedAssetLoadFailedEvent>()
+            .add_message::<AssetsReloaded>()
             .configure_sets(
                 PreUpdate,
                 AssetTrackingSystems.after(handle_internal_asset_events),
@@ -715,8 +720,9 @@ mod tests {
             AssetWatcher, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, InvalidGenerationError, LoadState, LoadedAsset,
+        Asset, AssetApp, AssetCollection, AssetEvent, AssetId, AssetLoadError,
+        AssetLoadFailedEvent, AssetPath, AssetPlugin, AssetServer, Assets, AssetsReloaded,
+        InvalidGenerationError, LoadProgress, LoadState, LoadedAsset, LoadedUntypedAsset,
         UnapprovedPathMode, UntypedHandle, WriteDefaultMetaError,
     };
     use alloc::{
@@ -1789,6 +1795,221 @@ mod tests {
         assert_eq!(get_started_load_count(app.world()), 4);
     }
 
+    #[test]
+    fn load_asset_collection() {
+        let dir = Dir::default();
+
+        let collection_path = "level.assets.ron";
+        let collection_ron = r#"
+(
+    assets: [
+        "a.cool.ron",
+        "nested.assets.ron",
+    ],
+)"#;
+        let nested_path = "nested.assets.ron";
+        let nested_ron = r#"
+(
+    assets: [
+        "b.cool.ron",
+    ],
+)"#;
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(collection_path), collection_ron);
+        dir.insert_asset_text(Path::new(nested_path), nested_ron);
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let (mut app, gate_opener) = create_app_with_gate(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<AssetCollection> = asset_server.load(collection_path);
+
+        gate_opener.open(collection_path);
+        gate_opener.open(nested_path);
+        run_app_until(&mut app, |world| {
+            let collections = world.resource::<Assets<AssetCollection>>();
+            let collection = collections.get(&handle)?;
+            assert_eq!(
+                collection.progress(world.resource::<AssetServer>()),
+                LoadProgress {
+                    loaded: 0,
+                    failed: 0,
+                    total: 2,
+                }
+            );
+            Some(())
+        });
+
+        // `b` completes the nested collection, but `a` is still waiting on its dependency `c`.
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+        run_app_until(&mut app, |world| {
+            let collection = world.resource::<Assets<AssetCollection>>().get(&handle)?;
+            let progress = collection.progress(world.resource::<AssetServer>());
+            (progress.loaded == 1).then_some(())
+        });
+        assert!(!asset_server.is_loaded_with_dependencies(&handle));
+
+        gate_opener.open(c_path);
+        run_app_until(&mut app, |world| {
+            world
+                .resource::<AssetServer>()
+                .is_loaded_with_dependencies(&handle)
+                .then_some(())
+        });
+
+        let collection = app
+            .world()
+            .resource::<Assets<AssetCollection>>()
+            .get(&handle)
+            .unwrap();
+        let progress = collection.progress(&asset_server);
+        assert!(progress.is_finished());
+        assert_eq!(progress.fraction(), 1.0);
+
+        let a_handle = collection
+            .get::<CoolText>(a_path, app.world().resource::<Assets<LoadedUntypedAsset>>())
+            .unwrap();
+        assert_eq!(
+            app.world()
+                .resource::<Assets<CoolText>>()
+                .get(&a_handle)
+                .unwrap()
+                .text,
+            "a"
+        );
+        assert!(collection
+            .get::<SubText>(a_path, app.world().resource::<Assets<LoadedUntypedAsset>>())
+            .is_none());
+    }
+
+    #[cfg(feature = "bevy_state")]
+    #[test]
+    fn load_asset_collections_in_states() {
+        use crate::{AssetCollectionStateAppExt, LoadingCollections};
+        use bevy_state::{
+            app::{AppExtStates, StatesPlugin},
+            state::{NextState, State, States},
+        };
+
+        #[derive(States, Default, Clone, PartialEq, Eq, Hash, Debug)]
+        enum GameState {
+            #[default]
+            LoadingMenu,
+            Menu,
+            LoadingLevel,
+            Level,
+        }
+
+        let dir = Dir::default();
+        for (path, ron) in [
+            ("menu.assets.ron", r#"(assets: ["a.cool.ron"])"#),
+            ("shared.assets.ron", r#"(assets: ["b.cool.ron"])"#),
+            ("level.assets.ron", r#"(assets: ["c.cool.ron"])"#),
+        ] {
+            dir.insert_asset_text(Path::new(path), ron);
+        }
+        for text in ["a", "b", "c"] {
+            dir.insert_asset_text(
+                Path::new(&format!("{text}.cool.ron")),
+                &format!(
+                    "(text: \"{text}\", dependencies: [], embedded_dependencies: [], sub_texts: [])"
+                ),
+            );
+        }
+
+        let (mut app, gate_opener) = create_app_with_gate(dir);
+        app.add_plugins(StatesPlugin)
+            .init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .init_state::<GameState>()
+            .load_collection_in_state(GameState::LoadingMenu, GameState::Menu, "menu.assets.ron")
+            .load_collection_in_state(GameState::LoadingMenu, GameState::Menu, "shared.assets.ron")
+            .load_collection_in_state(
+                GameState::LoadingLevel,
+                GameState::Level,
+                "level.assets.ron",
+            );
+        let state = |world: &World| world.resource::<State<GameState>>().get().clone();
+
+        // The state waits for every collection loaded in it.
+        gate_opener.open("menu.assets.ron");
+        gate_opener.open("shared.assets.ron");
+        gate_opener.open("a.cool.ron");
+        run_app_until(&mut app, |world| {
+            let progress = world
+                .resource::<LoadingCollections<GameState>>()
+                .progress(&GameState::LoadingMenu);
+            (progress
+                == LoadProgress {
+                    loaded: 1,
+                    failed: 0,
+                    total: 2,
+                })
+            .then_some(())
+        });
+        app.update();
+        assert_eq!(state(app.world()), GameState::LoadingMenu);
+
+        gate_opener.open("b.cool.ron");
+        run_app_until(&mut app, |world| {
+            (state(world) == GameState::Menu).then_some(())
+        });
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::LoadingLevel);
+        gate_opener.open("level.assets.ron");
+        gate_opener.open("c.cool.ron");
+        run_app_until(&mut app, |world| {
+            (state(world) == GameState::Level).then_some(())
+        });
+
+        // Collections of previous loading states are still tracked.
+        let loading_collections = app.world().resource::<LoadingCollections<GameState>>();
+        assert_eq!(loading_collections.iter().count(), 3);
+        let menu = loading_collections.get("menu.assets.ron").unwrap();
+        assert_eq!(menu.state, GameState::LoadingMenu);
+        assert!(menu.progress.is_finished());
+        assert!(app
+            .world()
+            .resource::<AssetServer>()
+            .is_loaded_with_dependencies(&menu.handle));
+        assert!(loading_collections
+            .progress(&GameState::LoadingLevel)
+            .is_finished());
+    }
+
     /// Tests that `AssetLoadFailedEvent<A>` events are emitted and can be used to retry failed assets.
     #[test]
     fn load_error_events() {
@@ -2416,6 +2637,62 @@ mod tests {
         });
     }
 
+    #[test]
+    fn reload_reports_dependents() {
+        let (mut app, dir, source_events) = create_app_with_source_event_sender();
+        let asset_server = app.world().resource::<AssetServer>().clone();
+
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            r#"(
+    text: "a",
+    dependencies: ["b.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+
+        let handle: Handle<CoolText> = asset_server.load("a.cool.ron");
+        run_app_until(&mut app, |world| {
+            let asset_server = world.resource::<AssetServer>();
+            asset_server
+                .is_loaded_with_dependencies(&handle)
+                .then_some(())
+        });
+
+        source_events
+            .send_blocking(AssetSourceEvent::ModifiedAsset(PathBuf::from("b.cool.ron")))
+            .unwrap();
+
+        run_app_until(&mut app, |world| {
+            let messages = world
+                .resource_mut::<Messages<AssetsReloaded>>()
+                .drain()
+                .collect::<Vec<_>>();
+            let [message] = &messages[..] else {
+                assert!(messages.is_empty());
+                return None;
+            };
+            assert_eq!(message.reloaded, [AssetPath::from("b.cool.ron")]);
+            assert!(message.failed.is_empty());
+            assert_eq!(message.dependents, [handle.id().untyped()]);
+            Some(())
+        });
+    }
+
     #[test]
     fn added_asset_reloads_previously_missing_asset() {
         let (mut app, dir, source_events) = create_app_with_source_event_sender();
@@ -1,5 +1,8 @@
 use crate::{
-    io::{AssetReaderError, MissingAssetSourceError, MissingProcessedAssetReaderError, Reader},
+    io::{
+        AssetReaderError, AssetStream, AssetStreamError, MissingAssetSourceError,
+        MissingProcessedAssetReaderError, Reader,
+    },
     loader_builders::{Deferred, NestedLoader, StaticTyped},
     meta::{AssetHash, AssetMeta, AssetMetaDyn, ProcessedInfo, ProcessedInfoMinimal, Settings},
     path::AssetPath,
@@ -525,6 +528,24 @@ impl<'a> LoadContext<'a> {
         Ok(bytes)
     }
 
+    /// Opens an [`AssetStream`] over the asset at the given path, which reads its bytes on demand
+    /// rather than all at once. To stream the asset currently being loaded, pass
+    /// [`LoadContext::path`].
+    ///
+    /// Unlike [`LoadContext::read_asset_bytes`], the streamed asset is not recorded as a
+    /// dependency of the asset being loaded, since its bytes are read after loading finishes.
+    pub async fn open_stream<'b>(
+        &self,
+        path: impl Into<AssetPath<'b>>,
+    ) -> Result<AssetStream, AssetStreamError> {
+        AssetStream::open(
+            self.asset_server.data.sources.clone(),
+            path.into().into_owned(),
+            self.asset_server.mode() == AssetServerMode::Processed,
+        )
+        .await
+    }
+
     /// Returns a handle to an asset of type `A` with the label `label`. This [`LoadContext`] must produce an asset of the
     /// given type and the given label or the dependencies of this asset will never be considered "fully loaded". However you
     /// can call this method before _or_ after adding the labeled asset.
@@ -0,0 +1,278 @@
+use crate::{meta::AssetHash, AssetPath};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_ecs::error::BevyError;
+use bevy_tasks::BoxedFuture;
+use core::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use {alloc::boxed::Box, std::path::PathBuf};
+use thiserror::Error;
+
+/// A content-addressed key into a [`ProcessedAssetCache`].
+///
+/// Keys are derived from everything that can affect the output of a processor: the hash of the
+/// source asset bytes and its meta (which contains the processor settings), the type path and
+/// [`version`](crate::processor::Process::version) of the processor, and (for
+/// [`ProcessedCacheKey::output`]) the full hashes of every process dependency. Two machines that
+/// compute the same key are guaranteed to produce the same processed asset, so the cached bytes
+/// can be shared between them.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ProcessedCacheKey(pub AssetHash);
+
+impl ProcessedCacheKey {
+    /// Computes the key of the "manifest" entry for an asset, which records the paths of the
+    /// process dependencies used the last time the asset was processed with these inputs.
+    ///
+    /// NOTE: changing the hashing logic here invalidates every existing cache entry.
+    pub fn manifest(source_hash: AssetHash, processor: &str, processor_version: u32) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"bevy_asset::processed_cache::manifest");
+        hasher.update(&source_hash);
+        hasher.update(processor.as_bytes());
+        hasher.update(&processor_version.to_le_bytes());
+        Self(*hasher.finalize().as_bytes())
+    }
+
+    /// Computes the key of the "output" entry for an asset from its manifest key and the
+    /// `(path, full_hash)` of each of its process dependencies, in the order they were recorded.
+    ///
+    /// NOTE: changing the hashing logic here invalidates every existing cache entry.
+    pub fn output<'a>(
+        manifest: ProcessedCacheKey,
+        dependencies: impl IntoIterator<Item = (&'a AssetPath<'static>, AssetHash)>,
+    ) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"bevy_asset::processed_cache::output");
+        hasher.update(&manifest.0);
+        for (path, full_hash) in dependencies {
+            hasher.update(path.to_string().as_bytes());
+            // Separate the path from the hash so paths that are prefixes of each other can't
+            // collide.
+            hasher.update(&[0]);
+            hasher.update(&full_hash);
+        }
+        Self(*hasher.finalize().as_bytes())
+    }
+
+    /// Returns the lowercase hexadecimal representation of this key. This is suitable for use as
+    /// a file name or URL segment.
+    pub fn to_hex(&self) -> String {
+        use core::fmt::Write;
+        let mut hex = String::with_capacity(self.0.len() * 2);
+        for byte in self.0 {
+            write!(hex, "{byte:02x}").unwrap();
+        }
+        hex
+    }
+}
+
+impl fmt::Debug for ProcessedCacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ProcessedCacheKey({})", self.to_hex())
+    }
+}
+
+impl fmt::Display for ProcessedCacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// A store of processed assets, keyed by [`ProcessedCacheKey`], that the [`AssetProcessor`] can
+/// consult before running a processor.
+///
+/// Since keys are content-addressed, a cache can safely be shared between several processors (for
+/// example, every developer on a team and CI), even if they run on different machines. Entries are
+/// never invalidated: a change to any input simply produces a different key.
+///
+/// Cache failures are never fatal. If [`ProcessedAssetCache::get`] or
+/// [`ProcessedAssetCache::put`] returns an error, the [`AssetProcessor`] logs a warning and
+/// processes the asset as if the cache did not exist.
+///
+/// [`AssetProcessor`]: crate::processor::AssetProcessor
+pub trait ProcessedAssetCache: Send + Sync + 'static {
+    /// Returns the bytes stored for `key`, or [`None`] if there is no such entry.
+    fn get<'a>(
+        &'a self,
+        key: &'a ProcessedCacheKey,
+    ) -> BoxedFuture<'a, Result<Option<Vec<u8>>, BevyError>>;
+
+    /// Stores `bytes` for `key`, replacing any previous entry.
+    ///
+    /// Implementations should make the write atomic, so that concurrent readers never observe a
+    /// partially written entry.
+    fn put<'a>(
+        &'a self,
+        key: &'a ProcessedCacheKey,
+        bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), BevyError>>;
+}
+
+/// Encodes the processed meta and asset bytes into a single cache entry.
+pub(crate) fn encode_cached_asset(meta_bytes: &[u8], asset_bytes: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(8 + meta_bytes.len() + asset_bytes.len());
+    bytes.extend_from_slice(&(meta_bytes.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(meta_bytes);
+    bytes.extend_from_slice(asset_bytes);
+    bytes
+}
+
+/// Decodes a cache entry produced by [`encode_cached_asset`] into its meta and asset bytes.
+pub(crate) fn decode_cached_asset(bytes: &[u8]) -> Result<(&[u8], &[u8]), InvalidCacheEntryError> {
+    let (len, rest) = bytes
+        .split_first_chunk::<8>()
+        .ok_or(InvalidCacheEntryError)?;
+    let len = usize::try_from(u64::from_le_bytes(*len)).map_err(|_| InvalidCacheEntryError)?;
+    if len > rest.len() {
+        return Err(InvalidCacheEntryError);
+    }
+    Ok(rest.split_at(len))
+}
+
+/// An error that occurs when a [`ProcessedAssetCache`] entry is malformed.
+#[derive(Error, Debug)]
+#[error("Encountered a malformed processed asset cache entry")]
+pub struct InvalidCacheEntryError;
+
+/// A [`ProcessedAssetCache`] that stores each entry as a file named after its key in a directory.
+///
+/// The directory can be local, or a shared network location. Entries are written to a temporary
+/// file first and then renamed into place, so several processors can use the same directory
+/// at once.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileProcessedAssetCache {
+    /// The directory that cache entries are stored in.
+    pub root: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileProcessedAssetCache {
+    /// Creates a new [`FileProcessedAssetCache`] that stores its entries in `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn entry_path(&self, key: &ProcessedCacheKey) -> PathBuf {
+        let hex = key.to_hex();
+        // Shard entries by the first byte of the key to avoid huge directories.
+        self.root.join(&hex[..2]).join(hex)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ProcessedAssetCache for FileProcessedAssetCache {
+    fn get<'a>(
+        &'a self,
+        key: &'a ProcessedCacheKey,
+    ) -> BoxedFuture<'a, Result<Option<Vec<u8>>, BevyError>> {
+        Box::pin(async move {
+            match async_fs::read(self.entry_path(key)).await {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(err) if err.kind() == futures_io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err.into()),
+            }
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a ProcessedCacheKey,
+        bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), BevyError>> {
+        Box::pin(async move {
+            let path = self.entry_path(key);
+            if let Some(parent) = path.parent() {
+                async_fs::create_dir_all(parent).await?;
+            }
+            let temp_path = path.with_extension(std::format!("{}.tmp", uuid::Uuid::new_v4()));
+            async_fs::write(&temp_path, bytes).await?;
+            if let Err(err) = async_fs::rename(&temp_path, &path).await {
+                let _ = async_fs::remove_file(&temp_path).await;
+                return Err(err.into());
+            }
+            Ok(())
+        })
+    }
+}
+
+/// A [`ProcessedAssetCache`] backed by a plain HTTP server.
+///
+/// Entries are fetched with `GET {base_url}/{key}` and stored with `PUT {base_url}/{key}`. A `404`
+/// response is treated as a cache miss. Any server that supports these two verbs (for example a
+/// `WebDAV` share, or an object store bucket behind a proxy) can be used.
+///
+/// If `read_only` is set, entries are never uploaded. This is useful for team members who should
+/// benefit from the cache populated by CI without writing to it.
+#[cfg(all(not(target_arch = "wasm32"), any(feature = "http", feature = "https")))]
+pub struct HttpProcessedAssetCache {
+    /// The URL that keys are appended to, without a trailing slash.
+    pub base_url: String,
+    /// If true, [`ProcessedAssetCache::put`] does nothing.
+    pub read_only: bool,
+}
+
+#[cfg(all(not(target_arch = "wasm32"), any(feature = "http", feature = "https")))]
+impl HttpProcessedAssetCache {
+    /// Creates a new writable [`HttpProcessedAssetCache`] rooted at `base_url`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            read_only: false,
+        }
+    }
+
+    fn entry_url(&self, key: &ProcessedCacheKey) -> String {
+        std::format!("{}/{}", self.base_url.trim_end_matches('/'), key.to_hex())
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), any(feature = "http", feature = "https")))]
+impl ProcessedAssetCache for HttpProcessedAssetCache {
+    fn get<'a>(
+        &'a self,
+        key: &'a ProcessedCacheKey,
+    ) -> BoxedFuture<'a, Result<Option<Vec<u8>>, BevyError>> {
+        use std::io::Read;
+
+        let url = self.entry_url(key);
+        Box::pin(async move {
+            // Use [`unblock`](blocking::unblock) to run the http request on a separately spawned
+            // thread as to not block bevy's async executor.
+            blocking::unblock(move || match ureq::get(&url).call() {
+                Ok(mut response) => {
+                    let mut bytes = Vec::new();
+                    response
+                        .body_mut()
+                        .with_config()
+                        .reader()
+                        .read_to_end(&mut bytes)?;
+                    Ok(Some(bytes))
+                }
+                Err(ureq::Error::StatusCode(404)) => Ok(None),
+                Err(err) => Err(err.into()),
+            })
+            .await
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a ProcessedCacheKey,
+        bytes: Vec<u8>,
+    ) -> BoxedFuture<'a, Result<(), BevyError>> {
+        let url = self.entry_url(key);
+        let read_only = self.read_only;
+        Box::pin(async move {
+            if read_only {
+                return Ok(());
+            }
+            blocking::unblock(move || {
+                ureq::put(&url).send(&bytes[..])?;
+                Ok(())
+            })
+            .await
+        })
+    }
+}
@@ -36,18 +36,36 @@
 //! - [`Process`]: a flexible low-level API for processing assets in arbitrary ways.
 //!
 //! In most cases, [`LoadTransformAndSave`] should be sufficient.
+//!
+//! # Sharing processed assets
+//!
+//! Processing can be expensive, so the results can be shared between machines using a
+//! [`ProcessedAssetCache`]. Cache entries are keyed by a [`ProcessedCacheKey`] derived from the
+//! source asset, its settings, the processor and its [`Process::version`], and the hashes of any
+//! process dependencies. Use [`AssetProcessorData::set_processed_asset_cache`] to enable it, for
+//! example with a [`FileProcessedAssetCache`] pointing at a shared directory.
+//!
+//! # Validating assets
+//!
+//! [`AssetProcessor::validate`] loads every processed asset and resolves its dependencies, producing
+//! a [`ValidationReport`] that lists every broken reference at once. Add the
+//! [`AssetValidationPlugin`] to a headless app to run this in CI before packaging.
 
+mod cache;
 mod log;
 mod process;
+mod validation;
 
 use async_lock::RwLockReadGuardArc;
+pub use cache::*;
 pub use log::*;
 pub use process::*;
+pub use validation::*;
 
 use crate::{
     io::{
         AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent, AssetSourceId,
-        AssetSources, AssetWriterError, ErasedAssetReader, MissingAssetSourceError,
+        AssetSources, AssetWriterError, ErasedAssetReader, MissingAssetSourceError, Writer,
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
@@ -56,7 +74,14 @@ use crate::{
     AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, DeserializeMetaError,
     MissingAssetLoaderForExtensionError, UnapprovedPathMode, WriteDefaultMetaError,
 };
-use alloc::{borrow::ToOwned, boxed::Box, string::String, sync::Arc, vec, vec::Vec};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 use bevy_ecs::prelude::*;
 use bevy_platform::{
     collections::{hash_map::Entry, HashMap, HashSet},
@@ -74,10 +99,7 @@ use thiserror::Error;
 use tracing::{debug, error, trace, warn};
 
 #[cfg(feature = "trace")]
-use {
-    alloc::string::ToString,
-    tracing::{info_span, instrument::Instrument},
-};
+use tracing::{info_span, instrument::Instrument};
 
 /// A "background" asset processor that reads asset values from a source [`AssetSource`] (which corresponds to an [`AssetReader`](crate::io::AssetReader) / [`AssetWriter`](crate::io::AssetWriter) pair),
 /// processes them in some way, and writes them to a destination [`AssetSource`].
@@ -113,6 +135,8 @@ pub struct AssetProcessorData {
     log: async_lock::RwLock<Option<Box<dyn ProcessorTransactionLog>>>,
     /// The processors that will be used to process assets.
     processors: RwLock<Processors>,
+    /// The cache that processed assets are shared through, if any.
+    processed_asset_cache: RwLock<Option<Arc<dyn ProcessedAssetCache>>>,
     sources: Arc<AssetSources>,
 }
 
@@ -489,7 +513,8 @@ impl AssetProcessor {
                 let _ = new_task_sender.send((source.id(), path)).await;
             }
             AssetSourceEvent::RemovedAsset(path) => {
-                self.handle_removed_asset(source, path).await;
+                self.handle_removed_asset(source, path, new_task_sender)
+                    .await;
             }
             AssetSourceEvent::RemovedMeta(path) => {
                 self.handle_removed_meta(source, path, new_task_sender)
@@ -503,7 +528,8 @@ impl AssetProcessor {
             // touch this folder (ex: the folder might be re-created with new assets). Clean up the old state first.
             // Currently this event handler is not parallel, but it could be (and likely should be) in the future.
             AssetSourceEvent::RemovedFolder(path) => {
-                self.handle_removed_folder(source, &path).await;
+                self.handle_removed_folder(source, &path, new_task_sender)
+                    .await;
             }
             AssetSourceEvent::RenamedAsset { old, new } => {
                 // If there was a rename event, but the path hasn't changed, this asset might need reprocessing.
@@ -536,7 +562,8 @@ impl AssetProcessor {
                 } else {
                     // PERF: this reprocesses everything in the moved folder. this is not necessary in most cases, but
                     // requires some nuance when it comes to path handling.
-                    self.handle_removed_folder(source, &old).await;
+                    self.handle_removed_folder(source, &old, new_task_sender)
+                        .await;
                     self.handle_added_folder(source, new, new_task_sender).await;
                 }
             }
@@ -545,12 +572,14 @@ impl AssetProcessor {
                 match processed_reader.is_directory(&path).await {
                     Ok(is_directory) => {
                         if is_directory {
-                            self.handle_removed_folder(source, &path).await;
+                            self.handle_removed_folder(source, &path, new_task_sender)
+                                .await;
                         } else if is_meta {
                             self.handle_removed_meta(source, path, new_task_sender)
                                 .await;
                         } else {
-                            self.handle_removed_asset(source, path).await;
+                            self.handle_removed_asset(source, path, new_task_sender)
+                                .await;
                         }
                     }
                     Err(err) => {
@@ -613,7 +642,12 @@ impl AssetProcessor {
     }
 
     /// Removes all processed assets stored at the given path (respecting transactionality), then removes the folder itself.
-    async fn handle_removed_folder(&self, source: &AssetSource, path: &Path) {
+    async fn handle_removed_folder(
+        &self,
+        source: &AssetSource,
+        path: &Path,
+        new_task_sender: &async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
+    ) {
         debug!(
             "Removing folder {} because source was removed",
             path.display()
@@ -622,7 +656,8 @@ impl AssetProcessor {
         match processed_reader.read_directory(path).await {
             Ok(mut path_stream) => {
                 while let Some(child_path) = path_stream.next().await {
-                    self.handle_removed_asset(source, child_path).await;
+                    self.handle_removed_asset(source, child_path, new_task_sender)
+                        .await;
                 }
             }
             Err(err) => match err {
@@ -662,13 +697,18 @@ impl AssetProcessor {
 
     /// Removes the processed version of an asset and associated in-memory metadata. This will block until all existing reads/writes to the
     /// asset have finished, thanks to the `file_transaction_lock`.
-    async fn handle_removed_asset(&self, source: &AssetSource, path: PathBuf) {
+    async fn handle_removed_asset(
+        &self,
+        source: &AssetSource,
+        path: PathBuf,
+        new_task_sender: &async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
+    ) {
         let asset_path = AssetPath::from(path).with_source(source.id());
         debug!("Removing processed {asset_path} because source was removed");
         let lock = {
             // Scope the infos lock so we don't hold up other processing for too long.
             let mut infos = self.data.processing_state.asset_infos.write().await;
-            infos.remove(&asset_path).await
+            infos.remove(&asset_path, new_task_sender).await
         };
         let Some(lock) = lock else {
             return;
@@ -1130,6 +1170,29 @@ impl AssetProcessor {
             }
         }
 
+        let processed_asset_cache = self
+            .data
+            .processed_asset_cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        // The manifest key is only known (and caching is only worthwhile) for processed assets.
+        let cache_and_key =
+            processed_asset_cache
+                .zip(processor.as_ref())
+                .map(|(cache, processor)| {
+                    let key = ProcessedCacheKey::manifest(
+                        new_hash,
+                        processor.type_path(),
+                        processor.version(),
+                    );
+                    (cache, key)
+                });
+        let cached = match &cache_and_key {
+            Some((cache, key)) => self.get_cached_asset(&**cache, asset_path, key).await,
+            None => None,
+        };
+
         // Note: this lock must remain alive until all processed asset and meta writes have finished (or failed)
         // See ProcessedAssetInfo::file_transaction_lock docs for more info
         let _transaction_lock = {
@@ -1148,7 +1211,28 @@ impl AssetProcessor {
         // Directly writing to the asset destination in the processor necessitates this behavior
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
-        if let Some(processor) = processor {
+        if let Some(cached) = cached {
+            debug!("Restored {} from the processed asset cache", asset_path);
+            let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
+            writer.write_all(&cached.asset_bytes).await.map_err(|e| {
+                ProcessError::AssetWriterError {
+                    path: asset_path.clone(),
+                    err: AssetWriterError::Io(e),
+                }
+            })?;
+            writer
+                .flush()
+                .await
+                .map_err(|e| ProcessError::AssetWriterError {
+                    path: asset_path.clone(),
+                    err: AssetWriterError::Io(e),
+                })?;
+            processed_writer
+                .write_meta_bytes(path, &cached.meta_bytes)
+                .await
+                .map_err(writer_err)?;
+            new_processed_info = cached.processed_info;
+        } else if let Some(processor) = processor {
             // Unwrap is ok since we have a processor, so the `AssetAction` must have been
             // `AssetAction::Process` (which includes its settings).
             let settings = source_meta.process_settings().unwrap();
@@ -1165,6 +1249,15 @@ impl AssetProcessor {
             let reader_for_process = reader.read(path).await.map_err(reader_err)?;
 
             let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
+            // If the result will be stored in the cache, process into memory so we don't need to
+            // read the processed asset back.
+            let mut cache_buffer = cache_and_key
+                .is_some()
+                .then(|| futures_lite::io::Cursor::new(Vec::new()));
+            let processor_writer: &mut Writer = match &mut cache_buffer {
+                Some(buffer) => buffer,
+                None => &mut *writer,
+            };
             let mut processed_meta = {
                 let mut context = ProcessContext::new(
                     self,
@@ -1172,7 +1265,7 @@ impl AssetProcessor {
                     reader_for_process,
                     &mut new_processed_info,
                 );
-                let process = processor.process(&mut context, settings, &mut *writer);
+                let process = processor.process(&mut context, settings, processor_writer);
                 #[cfg(feature = "trace")]
                 let process = {
                     let span = info_span!(
@@ -1185,6 +1278,14 @@ impl AssetProcessor {
                 process.await?
             };
 
+            if let Some(buffer) = &cache_buffer {
+                writer.write_all(buffer.get_ref()).await.map_err(|e| {
+                    ProcessError::AssetWriterError {
+                        path: asset_path.clone(),
+                        err: AssetWriterError::Io(e),
+                    }
+                })?;
+            }
             writer
                 .flush()
                 .await
@@ -1208,6 +1309,18 @@ impl AssetProcessor {
                 .write_meta_bytes(path, &meta_bytes)
                 .await
                 .map_err(writer_err)?;
+
+            if let (Some((cache, key)), Some(buffer)) = (&cache_and_key, cache_buffer) {
+                self.put_cached_asset(
+                    &**cache,
+                    asset_path,
+                    key,
+                    &new_processed_info,
+                    &meta_bytes,
+                    &buffer.into_inner(),
+                )
+                .await;
+            }
         } else {
             // See the reasoning for processing why it's ok to do a second read here.
             let mut reader_for_copy = reader.read(path).await.map_err(reader_err)?;
@@ -1230,6 +1343,122 @@ impl AssetProcessor {
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Looks up the processed version of `asset_path` in `cache`.
+    ///
+    /// This waits for every process dependency recorded in the manifest entry to finish processing,
+    /// so that the output entry can be looked up using their current hashes. Any cache error is
+    /// logged and treated as a miss.
+    async fn get_cached_asset(
+        &self,
+        cache: &dyn ProcessedAssetCache,
+        asset_path: &AssetPath<'static>,
+        manifest_key: &ProcessedCacheKey,
+    ) -> Option<CachedProcessedAsset> {
+        let manifest = match cache.get(manifest_key).await {
+            Ok(Some(manifest)) => manifest,
+            Ok(None) => return None,
+            Err(err) => {
+                warn!("Failed to read processed asset cache for {asset_path}: {err}");
+                return None;
+            }
+        };
+        let dependencies: Vec<AssetPath<'static>> = match ron::de::from_bytes(&manifest) {
+            Ok(dependencies) => dependencies,
+            Err(err) => {
+                warn!("Ignoring invalid processed asset cache manifest for {asset_path}: {err}");
+                return None;
+            }
+        };
+
+        let mut dependency_hashes = Vec::with_capacity(dependencies.len());
+        for dependency in &dependencies {
+            if self.data.wait_until_processed(dependency.clone()).await != ProcessStatus::Processed
+            {
+                return None;
+            }
+            let infos = self.data.processing_state.asset_infos.read().await;
+            let full_hash = infos
+                .get(dependency)
+                .and_then(|info| info.processed_info.as_ref())
+                .map(|info| info.full_hash)?;
+            dependency_hashes.push(full_hash);
+        }
+        let output_key = ProcessedCacheKey::output(
+            *manifest_key,
+            dependencies.iter().zip(dependency_hashes.iter().copied()),
+        );
+
+        let entry = match cache.get(&output_key).await {
+            Ok(Some(entry)) => entry,
+            Ok(None) => return None,
+            Err(err) => {
+                warn!("Failed to read processed asset cache for {asset_path}: {err}");
+                return None;
+            }
+        };
+        let parsed = decode_cached_asset(&entry)
+            .map_err(BevyError::from)
+            .and_then(|(meta_bytes, asset_bytes)| {
+                let minimal: ProcessedInfoMinimal = ron::de::from_bytes(meta_bytes)?;
+                let processed_info = minimal.processed_info.ok_or(InvalidCacheEntryError)?;
+                Ok(CachedProcessedAsset {
+                    processed_info,
+                    meta_bytes: meta_bytes.to_vec(),
+                    asset_bytes: asset_bytes.to_vec(),
+                })
+            });
+        match parsed {
+            Ok(cached) => Some(cached),
+            Err(err) => {
+                warn!("Ignoring invalid processed asset cache entry for {asset_path}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Stores the freshly processed `asset_path` in `cache`. Any cache error is logged and
+    /// otherwise ignored.
+    async fn put_cached_asset(
+        &self,
+        cache: &dyn ProcessedAssetCache,
+        asset_path: &AssetPath<'static>,
+        manifest_key: &ProcessedCacheKey,
+        processed_info: &ProcessedInfo,
+        meta_bytes: &[u8],
+        asset_bytes: &[u8],
+    ) {
+        let dependencies = processed_info
+            .process_dependencies
+            .iter()
+            .map(|dependency| &dependency.path)
+            .collect::<Vec<_>>();
+        let manifest = match ron::ser::to_string(&dependencies) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                warn!("Failed to serialize processed asset cache manifest for {asset_path}: {err}");
+                return;
+            }
+        };
+        let output_key = ProcessedCacheKey::output(
+            *manifest_key,
+            processed_info
+                .process_dependencies
+                .iter()
+                .map(|dependency| (&dependency.path, dependency.full_hash)),
+        );
+        // Write the output first, so that a reader that sees the manifest can also find the output.
+        let result = match cache
+            .put(&output_key, encode_cached_asset(meta_bytes, asset_bytes))
+            .await
+        {
+            Ok(()) => cache.put(manifest_key, manifest.into_bytes()).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            warn!("Failed to write {asset_path} to the processed asset cache: {err}");
+        }
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
         let log_factory = self
             .data
@@ -1334,9 +1563,22 @@ impl AssetProcessorData {
             log_factory: Mutex::new(Some(Box::new(FileTransactionLogFactory::default()))),
             log: Default::default(),
             processors: Default::default(),
+            processed_asset_cache: Default::default(),
         }
     }
 
+    /// Sets the [`ProcessedAssetCache`] that the processor consults before processing an asset,
+    /// and stores newly processed assets in.
+    ///
+    /// This should be called before asset processing begins (in the `Startup` schedule). By
+    /// default, no cache is used.
+    pub fn set_processed_asset_cache(&self, cache: Box<dyn ProcessedAssetCache>) {
+        *self
+            .processed_asset_cache
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Arc::from(cache));
+    }
+
     /// Sets the transaction log factory for the processor.
     ///
     /// If this is called after asset processing has begun (in the `Startup` schedule), it will
@@ -1486,6 +1728,13 @@ impl ProcessingState {
     }
 }
 
+/// A processed asset retrieved from a [`ProcessedAssetCache`].
+struct CachedProcessedAsset {
+    processed_info: ProcessedInfo,
+    meta_bytes: Vec<u8>,
+    asset_bytes: Vec<u8>,
+}
+
 /// The (successful) result of processing an asset
 #[derive(Debug, Clone)]
 pub enum ProcessResult {
@@ -1509,6 +1758,8 @@ pub(crate) struct ProcessorAssetInfo {
     /// Paths of assets that depend on this asset when they are being processed.
     dependents: HashSet<AssetPath<'static>>,
     status: Option<ProcessStatus>,
+    /// The reason processing failed, if the last attempt to process this asset failed.
+    failure: Option<AssetDiagnostic>,
     /// A lock that controls read/write access to processed asset files. The lock is shared for both the asset bytes and the meta bytes.
     /// _This lock must be locked whenever a read or write to processed assets occurs_
     /// There are scenarios where processed assets (and their metadata) are being read and written in multiple places at once:
@@ -1534,6 +1785,7 @@ impl Default for ProcessorAssetInfo {
             dependents: Default::default(),
             file_transaction_lock: Default::default(),
             status: None,
+            failure: None,
             status_sender,
             status_receiver,
         }
@@ -1564,6 +1816,11 @@ pub struct ProcessorAssetInfos {
     /// Therefore this _must_ always be consistent with the `infos` data. If a new asset is added to `infos`, it should
     /// check this maps for dependencies and add them. If an asset is removed, it should update the dependents here.
     non_existent_dependents: HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
+    /// Dependents whose reprocessing has been deferred until some of their dependencies have
+    /// finished processing, mapped to the number of dependencies they are still waiting on.
+    deferred_dependents: HashMap<AssetPath<'static>, usize>,
+    /// The deferred dependents waiting on each asset. See `deferred_dependents`.
+    waiting_dependents: HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
 }
 
 impl ProcessorAssetInfos {
@@ -1586,6 +1843,120 @@ impl ProcessorAssetInfos {
         self.infos.get_mut(asset_path)
     }
 
+    /// Returns the direct dependents of `asset_path` that should be reprocessed now that it has
+    /// been reprocessed.
+    ///
+    /// Dependents that also depend on another (transitive) dependent of `asset_path` are deferred
+    /// until those dependencies have finished processing, whatever the outcome (see
+    /// [`Self::release_waiting_dependents`]). This reprocesses the dependency graph in topological
+    /// order, processing each asset only once per change.
+    fn dependents_to_reprocess(
+        &mut self,
+        asset_path: &AssetPath<'static>,
+    ) -> Vec<AssetPath<'static>> {
+        let Some(info) = self.infos.get(asset_path) else {
+            return Vec::new();
+        };
+
+        let mut affected = <HashSet<_>>::default();
+        let mut queue = info.dependents.iter().collect::<Vec<_>>();
+        while let Some(path) = queue.pop() {
+            if path == asset_path || !affected.insert(path) {
+                continue;
+            }
+            if let Some(info) = self.infos.get(path) {
+                queue.extend(info.dependents.iter());
+            }
+        }
+
+        let mut to_reprocess = Vec::new();
+        let mut deferred = Vec::new();
+        for dependent in &info.dependents {
+            // Already waiting on a previous change, it will be queued once that is done.
+            if self.deferred_dependents.contains_key(dependent) {
+                continue;
+            }
+            let blockers = self
+                .infos
+                .get(dependent)
+                .and_then(|info| info.processed_info.as_ref())
+                .map(|processed_info| {
+                    processed_info
+                        .process_dependencies
+                        .iter()
+                        .filter(|dependency| {
+                            dependency.path != *asset_path && affected.contains(&dependency.path)
+                        })
+                        .map(|dependency| dependency.path.clone())
+                        .collect::<HashSet<_>>()
+                })
+                .unwrap_or_default();
+            if blockers.is_empty() {
+                to_reprocess.push(dependent.clone());
+            } else {
+                deferred.push((dependent.clone(), blockers));
+            }
+        }
+
+        for (dependent, blockers) in deferred {
+            self.deferred_dependents
+                .insert(dependent.clone(), blockers.len());
+            for blocker in blockers {
+                self.waiting_dependents
+                    .entry(blocker)
+                    .or_default()
+                    .insert(dependent.clone());
+            }
+        }
+        to_reprocess
+    }
+
+    /// Queues the deferred dependents that were only waiting on `asset_path` to finish processing.
+    ///
+    /// If `asset_path` was not reprocessed (it was skipped, ignored, failed or removed), it won't
+    /// queue its own dependents, so anything deferred on those is released as well. Otherwise a
+    /// failure anywhere in a dependency chain would leave the dependents at the end of it stale.
+    async fn release_waiting_dependents(
+        &mut self,
+        asset_path: &AssetPath<'static>,
+        reprocessed: bool,
+        reprocess_sender: &async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
+    ) {
+        let mut finished = vec![(asset_path.clone(), reprocessed)];
+        let mut visited = <HashSet<_>>::default();
+        while let Some((path, reprocessed)) = finished.pop() {
+            if self.deferred_dependents.is_empty() {
+                return;
+            }
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+            for dependent in self.waiting_dependents.remove(&path).unwrap_or_default() {
+                let Some(count) = self.deferred_dependents.get_mut(&dependent) else {
+                    continue;
+                };
+                *count -= 1;
+                if *count == 0 {
+                    self.deferred_dependents.remove(&dependent);
+                    let _ = reprocess_sender
+                        .send((
+                            dependent.source().clone_owned(),
+                            dependent.path().to_owned(),
+                        ))
+                        .await;
+                }
+            }
+            if !reprocessed && let Some(info) = self.infos.get(&path) {
+                finished.extend(
+                    info.dependents
+                        .iter()
+                        .filter(|dependent| !self.deferred_dependents.contains_key(*dependent))
+                        .map(|dependent| (dependent.clone(), false)),
+                );
+            }
+        }
+    }
+
     fn add_dependent(&mut self, asset_path: &AssetPath<'static>, dependent: AssetPath<'static>) {
         if let Some(info) = self.get_mut(asset_path) {
             info.dependents.insert(dependent);
@@ -1605,6 +1976,7 @@ impl ProcessorAssetInfos {
         result: Result<ProcessResult, ProcessError>,
         reprocess_sender: async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
     ) {
+        let reprocessed = matches!(result, Ok(ProcessResult::Processed(_)));
         match result {
             Ok(ProcessResult::Processed(processed_info)) => {
                 debug!("Finished processing \"{}\"", asset_path);
@@ -1621,11 +1993,11 @@ impl ProcessorAssetInfos {
                 for process_dependency_info in &processed_info.process_dependencies {
                     self.add_dependent(&process_dependency_info.path, asset_path.to_owned());
                 }
-                let info = self.get_or_insert(asset_path);
+                let info = self.get_or_insert(asset_path.clone());
                 info.processed_info = Some(processed_info);
+                info.failure = None;
                 info.update_status(ProcessStatus::Processed).await;
-                let dependents = info.dependents.iter().cloned().collect::<Vec<_>>();
-                for path in dependents {
+                for path in self.dependents_to_reprocess(&asset_path) {
                     let _ = reprocess_sender
                         .send((path.source().clone_owned(), path.path().to_owned()))
                         .await;
@@ -1634,6 +2006,7 @@ impl ProcessorAssetInfos {
             Ok(ProcessResult::SkippedNotChanged) => {
                 debug!("Skipping processing (unchanged) \"{}\"", asset_path);
                 let info = self.get_mut(&asset_path).expect("info should exist");
+                info.failure = None;
                 // NOTE: skipping an asset on a given pass doesn't mean it won't change in the future as a result
                 // of a dependency being re-processed. This means apps might receive an "old" (but valid) asset first.
                 // This is in the interest of fast startup times that don't block for all assets being checked + reprocessed
@@ -1660,6 +2033,7 @@ impl ProcessorAssetInfos {
             }
             Err(err) => {
                 error!("Failed to process asset {asset_path}: {err}");
+                let failure = AssetDiagnostic::processing_failed(&asset_path, &err);
                 // if this failed because a dependency could not be loaded, make sure it is reprocessed if that dependency is reprocessed
                 if let ProcessError::AssetLoadError(AssetLoadError::AssetLoaderError(dependency)) =
                     err
@@ -1674,9 +2048,12 @@ impl ProcessorAssetInfos {
                 }
 
                 let info = self.get_mut(&asset_path).expect("info should exist");
+                info.failure = Some(failure);
                 info.update_status(ProcessStatus::Failed).await;
             }
         }
+        self.release_waiting_dependents(&asset_path, reprocessed, &reprocess_sender)
+            .await;
     }
 
     /// Remove the info for the given path. This should only happen if an asset's source is
@@ -1685,7 +2062,11 @@ impl ProcessorAssetInfos {
     async fn remove(
         &mut self,
         asset_path: &AssetPath<'static>,
+        reprocess_sender: &async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
     ) -> Option<Arc<async_lock::RwLock<()>>> {
+        self.release_waiting_dependents(asset_path, false, reprocess_sender)
+            .await;
+        self.deferred_dependents.remove(asset_path);
         let info = self.infos.remove(asset_path)?;
         if let Some(processed_info) = info.processed_info {
             self.clear_dependencies(asset_path, processed_info);
@@ -1716,6 +2097,9 @@ impl ProcessorAssetInfos {
         new: &AssetPath<'static>,
         new_task_sender: &async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
     ) -> Option<(Arc<async_lock::RwLock<()>>, Arc<async_lock::RwLock<()>>)> {
+        self.release_waiting_dependents(old, false, new_task_sender)
+            .await;
+        self.deferred_dependents.remove(old);
         let mut info = self.infos.remove(old)?;
         if !info.dependents.is_empty() {
             // TODO: We can't currently ensure "moved" folders with relative paths aren't broken because AssetPath
@@ -1752,6 +2136,10 @@ impl ProcessorAssetInfos {
         let new_info = self.get_or_insert(new.clone());
         new_info.processed_info = info.processed_info;
         new_info.status = info.status;
+        new_info.failure = info.failure.map(|failure| AssetDiagnostic {
+            path: new.to_string(),
+            ..failure
+        });
         // Ensure things waiting on the new path are informed of the status of this asset
         if let Some(status) = new_info.status {
             new_info.status_sender.broadcast(status).await.unwrap();
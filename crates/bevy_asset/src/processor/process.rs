@@ -42,6 +42,13 @@ pub trait Process: TypePath + Send + Sync + Sized + 'static {
     ) -> impl ConditionalSendFuture<
         Output = Result<<Self::OutputLoader as AssetLoader>::Settings, ProcessError>,
     >;
+
+    /// The version of this processor's output format. This is part of the key used to look up
+    /// results in a [`ProcessedAssetCache`](crate::processor::ProcessedAssetCache), so it should be
+    /// bumped whenever a change to the processor would produce different bytes for the same input.
+    fn version(&self) -> u32 {
+        0
+    }
 }
 
 /// A flexible [`Process`] implementation that loads the source [`Asset`] using the `L` [`AssetLoader`], then transforms
@@ -224,6 +231,8 @@ pub trait ErasedProcessor: Send + Sync {
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError>;
     /// Returns the type-path of the original [`Process`].
     fn type_path(&self) -> &'static str;
+    /// Type-erased variant of [`Process::version`].
+    fn version(&self) -> u32;
     /// Returns the default type-erased [`AssetMeta`] for the underlying [`Process`] impl.
     fn default_meta(&self) -> Box<dyn AssetMetaDyn>;
 }
@@ -256,6 +265,10 @@ impl<P: Process> ErasedProcessor for P {
         P::type_path()
     }
 
+    fn version(&self) -> u32 {
+        <P as Process>::version(self)
+    }
+
     fn default_meta(&self) -> Box<dyn AssetMetaDyn> {
         Box::new(AssetMeta::<(), P>::new(AssetAction::Process {
             processor: P::type_path().to_string(),
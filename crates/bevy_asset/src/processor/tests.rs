@@ -29,15 +29,17 @@ use crate::{
         AssetSourceId, AssetWatcher, PathStream, Reader,
     },
     processor::{
-        AssetProcessor, GetProcessorError, LoadTransformAndSave, LogEntry, Process, ProcessContext,
-        ProcessError, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
+        AssetDiagnosticKind, AssetProcessor, GetProcessorError, LoadTransformAndSave, LogEntry,
+        Process, ProcessContext, ProcessError, ProcessStatus, ProcessedAssetCache,
+        ProcessedCacheKey, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
+        SourceLocation,
     },
     saver::AssetSaver,
     tests::{
         read_asset_as_string, read_meta_as_string, run_app_until, CoolText, CoolTextLoader,
         CoolTextRon, SubText,
     },
-    transformer::{AssetTransformer, TransformedAsset},
+    transformer::{AssetTransformer, IdentityAssetTransformer, TransformedAsset},
     Asset, AssetApp, AssetLoader, AssetMode, AssetPath, AssetPlugin, LoadContext,
     WriteDefaultMetaError,
 };
@@ -1467,6 +1469,171 @@ fn nested_loads_of_processed_asset_reprocesses_on_reload() {
     assert_eq!(get_process_count(), 7);
 }
 
+#[test]
+fn dependents_are_reprocessed_when_intermediate_dependency_fails() {
+    let AppWithProcessor {
+        mut app,
+        source_gate,
+        default_source_dirs:
+            ProcessingDirs {
+                source: source_dir,
+                processed: processed_dir,
+                source_event_sender: source_events,
+            },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    #[derive(Serialize, Deserialize)]
+    enum PartSerialized {
+        Leaf(String),
+        /// Loads the path, falling back to "missing" if it fails to load.
+        Path(String),
+        /// Loads the path, failing if its value contains "invalid".
+        Checked(String),
+    }
+
+    #[derive(Asset, TypePath)]
+    struct Joined {
+        value: String,
+    }
+
+    #[derive(TypePath)]
+    struct JoinedLoader;
+
+    impl AssetLoader for JoinedLoader {
+        type Asset = Joined;
+        type Settings = ();
+        type Error = std::io::Error;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &Self::Settings,
+            load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut bytes = vec![];
+            reader.read_to_end(&mut bytes).await?;
+
+            let parts: Vec<PartSerialized> = ron::de::from_bytes(&bytes).unwrap();
+            let mut value = String::new();
+            for part in parts {
+                match part {
+                    PartSerialized::Leaf(leaf) => value.push_str(&leaf),
+                    PartSerialized::Path(path) => {
+                        match load_context.loader().immediate().load::<Joined>(path).await {
+                            Ok(loaded) => value.push_str(&loaded.get().value),
+                            Err(_) => value.push_str("missing"),
+                        }
+                    }
+                    PartSerialized::Checked(path) => {
+                        let loaded = load_context
+                            .loader()
+                            .immediate()
+                            .load::<Joined>(path)
+                            .await
+                            .map_err(std::io::Error::other)?;
+                        if loaded.get().value.contains("invalid") {
+                            return Err(std::io::Error::other("invalid dependency"));
+                        }
+                        value.push_str(&loaded.get().value);
+                    }
+                }
+            }
+            Ok(Joined { value })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["join"]
+        }
+    }
+
+    fn serialize_parts(parts: &[PartSerialized]) -> String {
+        ron::ser::to_string(parts).unwrap()
+    }
+
+    #[derive(TypePath)]
+    struct JoinedSaver;
+
+    impl AssetSaver for JoinedSaver {
+        type Asset = Joined;
+        type Error = std::io::Error;
+        type Settings = ();
+        type OutputLoader = JoinedLoader;
+
+        async fn save(
+            &self,
+            writer: &mut crate::io::Writer,
+            asset: crate::saver::SavedAsset<'_, Self::Asset>,
+            _settings: &Self::Settings,
+        ) -> Result<<Self::OutputLoader as AssetLoader>::Settings, Self::Error> {
+            let serialized = serialize_parts(&[PartSerialized::Leaf(asset.get().value.clone())]);
+            writer.write_all(serialized.as_bytes()).await
+        }
+    }
+
+    type JoinedProcessor =
+        LoadTransformAndSave<JoinedLoader, IdentityAssetTransformer<Joined>, JoinedSaver>;
+    app.init_asset::<Joined>()
+        .register_asset_loader(JoinedLoader)
+        .register_asset_processor::<JoinedProcessor>(JoinedSaver.into())
+        .set_default_asset_processor::<JoinedProcessor>("join");
+
+    let guard = source_gate.write_blocking();
+
+    // `top` depends on both `bottom` and `middle`, which itself depends on `bottom`.
+    source_dir.insert_asset_text(
+        Path::new("bottom.join"),
+        &serialize_parts(&[PartSerialized::Leaf("valid".into())]),
+    );
+    source_dir.insert_asset_text(
+        Path::new("middle.join"),
+        &serialize_parts(&[PartSerialized::Checked("bottom.join".into())]),
+    );
+    source_dir.insert_asset_text(
+        Path::new("top.join"),
+        &serialize_parts(&[
+            PartSerialized::Path("bottom.join".into()),
+            PartSerialized::Leaf("+".into()),
+            PartSerialized::Path("middle.join".into()),
+        ]),
+    );
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert_eq!(
+        read_asset_as_string(&processed_dir, Path::new("middle.join")),
+        serialize_parts(&[PartSerialized::Leaf("valid".into())])
+    );
+    assert_eq!(
+        read_asset_as_string(&processed_dir, Path::new("top.join")),
+        serialize_parts(&[PartSerialized::Leaf("valid+valid".into())])
+    );
+
+    // Changing `bottom` makes `middle` fail to process. `top` must still be reprocessed since it
+    // depends on `bottom` directly.
+    let guard = source_gate.write_blocking();
+
+    source_dir.insert_asset_text(
+        Path::new("bottom.join"),
+        &serialize_parts(&[PartSerialized::Leaf("invalid".into())]),
+    );
+    source_events
+        .send_blocking(AssetSourceEvent::ModifiedAsset("bottom.join".into()))
+        .unwrap();
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    let processor = app.world().resource::<AssetProcessor>().clone();
+    assert_eq!(
+        bevy_tasks::block_on(processor.data().wait_until_processed("middle.join".into())),
+        ProcessStatus::Failed
+    );
+    assert_eq!(
+        read_asset_as_string(&processed_dir, Path::new("top.join")),
+        serialize_parts(&[PartSerialized::Leaf("invalid+missing".into())])
+    );
+}
+
 #[test]
 fn clears_invalid_data_from_processed_dir() {
     let AppWithProcessor {
@@ -1806,3 +1973,240 @@ fn write_default_meta_does_not_overwrite() {
         META_TEXT
     );
 }
+
+#[test]
+fn processed_asset_cache_is_shared_between_processors() {
+    /// A [`ProcessedAssetCache`] that stores entries in memory, shared between clones.
+    #[derive(Clone, Default)]
+    struct MemoryCache(Arc<Mutex<HashMap<ProcessedCacheKey, Vec<u8>>>>);
+
+    impl ProcessedAssetCache for MemoryCache {
+        fn get<'a>(
+            &'a self,
+            key: &'a ProcessedCacheKey,
+        ) -> BoxedFuture<'a, Result<Option<Vec<u8>>, BevyError>> {
+            let entry = self
+                .0
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(key)
+                .cloned();
+            Box::pin(async move { Ok(entry) })
+        }
+
+        fn put<'a>(
+            &'a self,
+            key: &'a ProcessedCacheKey,
+            bytes: Vec<u8>,
+        ) -> BoxedFuture<'a, Result<(), BevyError>> {
+            self.0
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(*key, bytes);
+            Box::pin(async move { Ok(()) })
+        }
+    }
+
+    #[derive(TypePath, Clone)]
+    struct CountedMergeEmbedded(Arc<Mutex<u32>>);
+
+    impl MutateAsset<CoolText> for CountedMergeEmbedded {
+        fn mutate(&self, asset: &mut CoolText) {
+            *self.0.lock().unwrap_or_else(PoisonError::into_inner) += 1;
+            asset.text.push_str(" processed");
+            if !asset.embedded.is_empty() {
+                asset.text.push(' ');
+                asset.text.push_str(&asset.embedded);
+            }
+        }
+    }
+
+    type CoolTextProcessor = LoadTransformAndSave<
+        CoolTextLoader,
+        RootAssetTransformer<CountedMergeEmbedded, CoolText>,
+        CoolTextSaver,
+    >;
+
+    let dep_path = Path::new("dep.cool.ron");
+    let root_path = Path::new("root.cool.ron");
+    let root_source = ron::ser::to_string_pretty(
+        &CoolTextRon {
+            text: "root".into(),
+            dependencies: vec![],
+            embedded_dependencies: vec![dep_path.to_string_lossy().into_owned()],
+            sub_texts: vec![],
+        },
+        PrettyConfig::new().new_line("\n"),
+    )
+    .unwrap();
+
+    let cache = MemoryCache::default();
+    let counter = Arc::new(Mutex::new(0));
+
+    // Runs a processor with a fresh processed directory, returning the processed root asset.
+    let process = |dep_text: &str| {
+        let AppWithProcessor {
+            mut app,
+            source_gate,
+            default_source_dirs:
+                ProcessingDirs {
+                    source: source_dir,
+                    processed: processed_dir,
+                    ..
+                },
+            ..
+        } = create_app_with_asset_processor(&[]);
+
+        app.world()
+            .resource::<AssetProcessor>()
+            .data()
+            .set_processed_asset_cache(Box::new(cache.clone()));
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .register_asset_processor(CoolTextProcessor::new(
+                RootAssetTransformer::new(CountedMergeEmbedded(counter.clone())),
+                CoolTextSaver,
+            ))
+            .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+
+        let guard = source_gate.write_blocking();
+        source_dir.insert_asset_text(dep_path, &serialize_as_cool_text(dep_text));
+        source_dir.insert_asset_text(root_path, &root_source);
+        run_app_until_finished_processing(&mut app, guard);
+
+        (
+            read_asset_as_string(&processed_dir, root_path),
+            read_meta_as_string(&processed_dir, root_path),
+        )
+    };
+    let take_count =
+        || core::mem::take(&mut *counter.lock().unwrap_or_else(PoisonError::into_inner));
+
+    let (first_asset, first_meta) = process("dep");
+    assert_eq!(
+        first_asset,
+        serialize_as_cool_text("root processed dep processed")
+    );
+    assert_eq!(take_count(), 2);
+
+    // A second processor with an empty processed directory restores everything from the cache.
+    let (second_asset, second_meta) = process("dep");
+    assert_eq!(second_asset, first_asset);
+    assert_eq!(second_meta, first_meta);
+    assert_eq!(take_count(), 0);
+
+    // Changing the dependency changes the key of the root asset too, so both are reprocessed.
+    let (third_asset, _) = process("changed");
+    assert_eq!(
+        third_asset,
+        serialize_as_cool_text("root processed changed processed")
+    );
+    assert_eq!(take_count(), 2);
+}
+
+#[test]
+fn validation_reports_broken_references() {
+    let AppWithProcessor {
+        mut app,
+        source_gate,
+        default_source_dirs: ProcessingDirs {
+            source: source_dir, ..
+        },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    app.init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+
+    let guard = source_gate.write_blocking();
+
+    source_dir.insert_asset_text(
+        Path::new("a.cool.ron"),
+        r#"(
+    text: "a",
+    dependencies: ["missing.cool.ron", "b.cool.ron#sub", "b.cool.ron#nope"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+    );
+    source_dir.insert_asset_text(
+        Path::new("b.cool.ron"),
+        r#"(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: ["sub"],
+)"#,
+    );
+    source_dir.insert_asset_text(
+        Path::new("c.cool.ron"),
+        r#"(
+    text: 5,
+)"#,
+    );
+    source_dir.insert_asset_text(
+        Path::new("d.cool.ron"),
+        r#"(
+    text: "d",
+    dependencies: ["c.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+    );
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    let processor = app.world().resource::<AssetProcessor>().clone();
+    let report = bevy_tasks::block_on(processor.validate());
+
+    assert_eq!(report.validated_assets, 4);
+    let diagnostics = report
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic.path.as_str(),
+                diagnostic.kind.clone(),
+                diagnostic.location,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics,
+        [
+            (
+                "a.cool.ron",
+                AssetDiagnosticKind::MissingDependency {
+                    dependency: "missing.cool.ron".to_string()
+                },
+                None
+            ),
+            (
+                "a.cool.ron",
+                AssetDiagnosticKind::MissingLabeledAsset {
+                    dependency: "b.cool.ron#nope".to_string()
+                },
+                None
+            ),
+            (
+                "c.cool.ron",
+                AssetDiagnosticKind::LoadFailed,
+                Some(SourceLocation {
+                    line: 2,
+                    column: 10
+                })
+            ),
+            (
+                "d.cool.ron",
+                AssetDiagnosticKind::FailedDependency {
+                    dependency: "c.cool.ron".to_string()
+                },
+                None
+            ),
+        ]
+    );
+    assert!(!report.is_ok());
+    assert!(report.to_ron().is_ok());
+}
@@ -0,0 +1,441 @@
+use crate::{
+    io::AssetReaderError,
+    loader::ErasedLoadedAsset,
+    processor::{AssetProcessor, ProcessError, ProcessStatus},
+    AssetLoadError, AssetLoaderError, AssetPath, UntypedAssetId,
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_app::{App, AppExit, Plugin, Startup, Update};
+use bevy_ecs::prelude::*;
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_tasks::{IoTaskPool, Task};
+use core::{error::Error, fmt};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+use tracing::{error, info};
+
+/// The result of [`AssetProcessor::validate`]: every problem found while loading all processed
+/// assets and resolving their dependencies.
+///
+/// The report can be serialized (for example with [`ValidationReport::to_ron`]) to be consumed by
+/// other tools, such as CI jobs.
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+    /// The number of assets that were loaded and checked.
+    pub validated_assets: usize,
+    /// The problems that were found, sorted by asset path.
+    pub diagnostics: Vec<AssetDiagnostic>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Serializes this report to a pretty-printed RON string.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+}
+
+/// A single problem found by [`AssetProcessor::validate`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AssetDiagnostic {
+    /// The path of the asset the problem was found in.
+    pub path: String,
+    /// What kind of problem this is.
+    pub kind: AssetDiagnosticKind,
+    /// A human-readable description of the problem.
+    pub message: String,
+    /// The location in the asset's file that caused the problem, if the loader reported one.
+    pub location: Option<SourceLocation>,
+}
+
+impl fmt::Display for AssetDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        if let Some(location) = &self.location {
+            write!(f, ":{location}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// The kind of an [`AssetDiagnostic`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AssetDiagnosticKind {
+    /// The asset could not be processed.
+    ProcessingFailed,
+    /// The processed asset could not be loaded.
+    LoadFailed,
+    /// The asset depends on an asset that does not exist.
+    MissingDependency {
+        /// The path of the missing dependency.
+        dependency: String,
+    },
+    /// The asset depends on an asset that exists, but could not be processed or loaded.
+    FailedDependency {
+        /// The path of the failed dependency.
+        dependency: String,
+    },
+    /// The asset depends on a labeled asset that its file does not contain.
+    MissingLabeledAsset {
+        /// The path of the dependency, including the missing label.
+        dependency: String,
+    },
+}
+
+/// A line and column in an asset file. Both are 1-based.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SourceLocation {
+    /// The line number.
+    pub line: usize,
+    /// The column number.
+    pub column: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl SourceLocation {
+    /// Searches `error` and its [`source`](Error::source) chain for a location. This recognizes
+    /// [`LocatedError`] and [`ron::error::SpannedError`].
+    pub fn find(error: &(dyn Error + 'static)) -> Option<Self> {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(error) = error.downcast_ref::<LocatedError>() {
+                return Some(error.location);
+            }
+            if let Some(error) = error.downcast_ref::<ron::error::SpannedError>() {
+                return Some(SourceLocation {
+                    line: error.span.start.line,
+                    column: error.span.start.col,
+                });
+            }
+            // Loader errors don't expose their inner error as a source (and are often wrapped
+            // transparently), so step into them manually.
+            let loader_error = match error.downcast_ref::<AssetLoadError>() {
+                Some(AssetLoadError::AssetLoaderError(error)) => Some(error),
+                _ => error.downcast_ref::<AssetLoaderError>(),
+            };
+            current = match loader_error {
+                Some(error) => Some(error.error().as_error()),
+                None => error.source(),
+            };
+        }
+        None
+    }
+}
+
+/// An error annotated with the location in the asset file that caused it.
+///
+/// [`AssetLoader`](crate::AssetLoader)s for formats other than RON can wrap their errors in this
+/// (or include it as the source of their errors) so that the location is reported in
+/// [`AssetDiagnostic`]s.
+#[derive(Error, Debug)]
+#[error("{error} (at {location})")]
+pub struct LocatedError {
+    /// Where the error occurred.
+    pub location: SourceLocation,
+    /// The error itself.
+    #[source]
+    pub error: Box<dyn Error + Send + Sync + 'static>,
+}
+
+impl AssetDiagnostic {
+    /// Creates an [`AssetDiagnosticKind::ProcessingFailed`] diagnostic from `error`.
+    pub(crate) fn processing_failed(path: &AssetPath<'_>, error: &ProcessError) -> Self {
+        let location = match error {
+            ProcessError::AssetLoadError(error) => SourceLocation::find(error),
+            ProcessError::AssetSaveError(error) | ProcessError::AssetTransformError(error) => {
+                SourceLocation::find(&**error)
+            }
+            _ => None,
+        };
+        Self {
+            path: path.to_string(),
+            kind: AssetDiagnosticKind::ProcessingFailed,
+            message: error.to_string(),
+            location,
+        }
+    }
+
+    fn load_failed(path: &AssetPath<'_>, error: &AssetLoadError) -> Self {
+        Self {
+            path: path.to_string(),
+            kind: AssetDiagnosticKind::LoadFailed,
+            message: error.to_string(),
+            location: SourceLocation::find(error),
+        }
+    }
+}
+
+/// The outcome of loading the file of a dependency during validation.
+enum LoadedFile {
+    /// The file loaded successfully, and contains these labeled assets.
+    Loaded(HashSet<String>),
+    /// The file does not exist.
+    Missing,
+    /// The file exists, but failed to process or load.
+    Failed,
+}
+
+impl AssetProcessor {
+    /// Waits for processing to finish, then loads every processed asset, resolves all of its
+    /// dependencies (including labeled assets), and reports every problem found.
+    ///
+    /// Unlike loading assets in an app, this reports every broken reference at once, along with
+    /// the location in the file that caused it when the loader provides one. This is useful to
+    /// check assets before packaging them, for example with [`AssetValidationPlugin`] in CI.
+    pub async fn validate(&self) -> ValidationReport {
+        self.data.wait_until_finished().await;
+
+        let mut report = ValidationReport::default();
+        let mut files = HashMap::<AssetPath<'static>, LoadedFile>::default();
+        let mut processed_paths = Vec::new();
+        {
+            let infos = self.data.processing_state.asset_infos.read().await;
+            for (path, info) in &infos.infos {
+                match info.status {
+                    Some(ProcessStatus::Failed) => {
+                        if let Some(failure) = &info.failure {
+                            report.diagnostics.push(failure.clone());
+                        }
+                        files.insert(path.clone(), LoadedFile::Failed);
+                    }
+                    Some(ProcessStatus::Processed) => processed_paths.push(path.clone()),
+                    _ => {}
+                }
+            }
+        }
+
+        // Load every processed asset first, so that dependencies on processed assets are resolved
+        // using these results.
+        let mut dependencies = Vec::with_capacity(processed_paths.len());
+        for path in processed_paths {
+            report.validated_assets += 1;
+            let mut asset_dependencies = HashSet::new();
+            let file = match self.load_for_validation(&path).await {
+                Ok(loaded_asset) => {
+                    self.collect_dependency_paths(&loaded_asset, &mut asset_dependencies);
+                    LoadedFile::Loaded(labels(&loaded_asset))
+                }
+                Err(error) => {
+                    report
+                        .diagnostics
+                        .push(AssetDiagnostic::load_failed(&path, &error));
+                    LoadedFile::Failed
+                }
+            };
+            files.insert(path.clone(), file);
+            dependencies.push((path, asset_dependencies));
+        }
+
+        for (path, asset_dependencies) in dependencies {
+            for dependency in asset_dependencies {
+                let file_path = dependency.without_label().into_owned();
+                if !files.contains_key(&file_path) {
+                    // The dependency isn't a processed asset (for example, it is in an unprocessed
+                    // source, or it doesn't exist), so try to load it directly.
+                    let file = match self.load_for_validation(&file_path).await {
+                        Ok(loaded_asset) => LoadedFile::Loaded(labels(&loaded_asset)),
+                        Err(AssetLoadError::AssetReaderError(AssetReaderError::NotFound(_))) => {
+                            LoadedFile::Missing
+                        }
+                        Err(error) => {
+                            report
+                                .diagnostics
+                                .push(AssetDiagnostic::load_failed(&file_path, &error));
+                            LoadedFile::Failed
+                        }
+                    };
+                    files.insert(file_path.clone(), file);
+                }
+
+                let (kind, message) = match &files[&file_path] {
+                    LoadedFile::Loaded(labels) => match dependency.label() {
+                        Some(label) if !labels.contains(label) => (
+                            AssetDiagnosticKind::MissingLabeledAsset {
+                                dependency: dependency.to_string(),
+                            },
+                            std::format!(
+                                "'{file_path}' does not contain the labeled asset '{label}'"
+                            ),
+                        ),
+                        _ => continue,
+                    },
+                    LoadedFile::Missing => (
+                        AssetDiagnosticKind::MissingDependency {
+                            dependency: dependency.to_string(),
+                        },
+                        std::format!("The dependency '{dependency}' does not exist"),
+                    ),
+                    LoadedFile::Failed => (
+                        AssetDiagnosticKind::FailedDependency {
+                            dependency: dependency.to_string(),
+                        },
+                        std::format!("The dependency '{dependency}' failed to load"),
+                    ),
+                };
+                report.diagnostics.push(AssetDiagnostic {
+                    path: path.to_string(),
+                    kind,
+                    message,
+                    location: None,
+                });
+            }
+        }
+
+        report
+            .diagnostics
+            .sort_by(|a, b| (&a.path, &a.kind, a.location).cmp(&(&b.path, &b.kind, b.location)));
+        report
+    }
+
+    /// Loads the (processed) asset at `path`, without loading its dependencies.
+    async fn load_for_validation(
+        &self,
+        path: &AssetPath<'static>,
+    ) -> Result<ErasedLoadedAsset, AssetLoadError> {
+        let (meta, loader, mut reader) = self.server.get_meta_loader_and_reader(path, None).await?;
+        let Some(settings) = meta.loader_settings() else {
+            return Err(AssetLoadError::CannotLoadProcessedAsset { path: path.clone() });
+        };
+        self.server
+            .load_with_settings_loader_and_reader(
+                path,
+                settings,
+                &*loader,
+                &mut reader,
+                false,
+                false,
+            )
+            .await
+    }
+
+    /// Adds the paths of all dependencies of `loaded_asset` and its labeled assets to `paths`.
+    fn collect_dependency_paths(
+        &self,
+        loaded_asset: &ErasedLoadedAsset,
+        paths: &mut HashSet<AssetPath<'static>>,
+    ) {
+        let labeled_assets = loaded_asset
+            .labeled_assets
+            .values()
+            .map(|labeled_asset| &labeled_asset.asset);
+        for asset in core::iter::once(loaded_asset).chain(labeled_assets) {
+            for index in &asset.dependencies {
+                // Dependencies without a path are assets created by the loader itself.
+                if let Some(path) = self.server.get_path(UntypedAssetId::from(*index)) {
+                    paths.insert(path.into_owned());
+                }
+            }
+        }
+    }
+}
+
+fn labels(loaded_asset: &ErasedLoadedAsset) -> HashSet<String> {
+    loaded_asset
+        .iter_labels()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Validates all assets once the [`AssetProcessor`] finishes its initial processing pass, then
+/// exits the app.
+///
+/// This is meant for running headlessly (for example with
+/// [`MinimalPlugins`](https://docs.rs/bevy/latest/bevy/struct.MinimalPlugins.html)) in CI, with
+/// [`AssetMode::Processed`](crate::AssetMode::Processed) and the `asset_processor` feature
+/// enabled. Every problem is logged, the [`ValidationReport`] is inserted as a resource and
+/// optionally written to a file, and the app exits with [`AppExit::error`] if any problems were
+/// found.
+#[derive(Default)]
+pub struct AssetValidationPlugin {
+    /// If set, the [`ValidationReport`] is written to this file in RON format.
+    pub report_path: Option<PathBuf>,
+}
+
+#[derive(Resource)]
+struct ValidationTask {
+    task: Task<ValidationReport>,
+    report_path: Option<PathBuf>,
+}
+
+impl Plugin for AssetValidationPlugin {
+    fn build(&self, app: &mut App) {
+        let report_path = self.report_path.clone();
+        app.add_systems(
+            Startup,
+            move |mut commands: Commands,
+                  processor: Option<Res<AssetProcessor>>,
+                  mut exit: MessageWriter<AppExit>| {
+                let Some(processor) = processor else {
+                    error!("Asset validation requires the asset processor to be enabled");
+                    exit.write(AppExit::error());
+                    return;
+                };
+                let processor = processor.clone();
+                commands.insert_resource(ValidationTask {
+                    task: IoTaskPool::get().spawn(async move { processor.validate().await }),
+                    report_path: report_path.clone(),
+                });
+            },
+        )
+        .add_systems(Update, finish_validation);
+    }
+}
+
+fn finish_validation(
+    mut commands: Commands,
+    task: Option<ResMut<ValidationTask>>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    let Some(mut task) = task else {
+        return;
+    };
+    let Some(report) = bevy_tasks::futures::check_ready(&mut task.task) else {
+        return;
+    };
+    commands.remove_resource::<ValidationTask>();
+
+    for diagnostic in &report.diagnostics {
+        error!("{diagnostic}");
+    }
+    info!(
+        "Validated {} assets: found {} problems",
+        report.validated_assets,
+        report.diagnostics.len()
+    );
+
+    let mut success = report.is_ok();
+    if let Some(report_path) = &task.report_path {
+        let result = report
+            .to_ron()
+            .map_err(|err| err.to_string())
+            .and_then(|ron| std::fs::write(report_path, ron).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            error!(
+                "Failed to write the asset validation report to {}: {err}",
+                report_path.display()
+            );
+            success = false;
+        }
+    }
+
+    commands.insert_resource(report);
+    exit.write(if success {
+        AppExit::Success
+    } else {
+        AppExit::error()
+    });
+}
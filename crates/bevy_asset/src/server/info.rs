@@ -7,6 +7,8 @@ use crate::{
 use alloc::{
     borrow::ToOwned,
     boxed::Box,
+    collections::VecDeque,
+    string::ToString,
     sync::{Arc, Weak},
     vec::Vec,
 };
@@ -40,6 +42,13 @@ pub(crate) struct AssetInfo {
     ///
     /// [`LoadedAsset`]: crate::loader::LoadedAsset
     loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+    /// The assets this asset holds handles to (its "runtime dependencies"). This is set using the
+    /// value from [`LoadedAsset`].
+    /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
+    /// save memory.
+    ///
+    /// [`LoadedAsset`]: crate::loader::LoadedAsset
+    dependencies: HashSet<ErasedAssetIndex>,
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
@@ -60,6 +69,7 @@ impl AssetInfo {
             loading_rec_dependencies: HashSet::default(),
             failed_rec_dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
+            dependencies: HashSet::default(),
             dependents_waiting_on_load: HashSet::default(),
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
@@ -85,6 +95,9 @@ pub(crate) struct AssetInfos {
     /// Tracks assets that depend on the "key" asset path inside their asset loaders ("loader dependencies")
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) loader_dependents: HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
+    /// Tracks the assets whose runtime [`AssetInfo::dependencies`] contain the "key" asset.
+    /// This should only be set when watching for changes to avoid unnecessary work.
+    pub(crate) runtime_dependents: HashMap<ErasedAssetIndex, HashSet<ErasedAssetIndex>>,
     /// Tracks living labeled assets for a given source asset.
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) living_labeled_assets: HashMap<AssetPath<'static>, HashSet<Box<str>>>,
@@ -370,6 +383,37 @@ impl AssetInfos {
             .any(|info| info.weak_handle.strong_count() > 0)
     }
 
+    /// Returns every asset that holds a handle to one of the assets at `paths` (including their
+    /// labeled assets), either directly or through other assets, in breadth-first order.
+    ///
+    /// This only returns results if [`AssetInfos::watching_for_changes`] is set to `true`.
+    pub(crate) fn get_runtime_dependents<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a AssetPath<'static>>,
+    ) -> Vec<ErasedAssetIndex> {
+        let mut visited = <HashSet<ErasedAssetIndex>>::default();
+        let mut queue = VecDeque::new();
+        for path in paths {
+            visited.extend(self.get_path_indices(path));
+            for label in self.living_labeled_assets.get(path).into_iter().flatten() {
+                let labeled_path = path.clone().with_label(label.to_string());
+                visited.extend(self.get_path_indices(&labeled_path));
+            }
+        }
+        queue.extend(visited.iter().copied());
+
+        let mut result = Vec::new();
+        while let Some(index) = queue.pop_front() {
+            for dependent in self.runtime_dependents.get(&index).into_iter().flatten() {
+                if visited.insert(*dependent) {
+                    result.push(*dependent);
+                    queue.push_back(*dependent);
+                }
+            }
+        }
+        result
+    }
+
     /// Returns `true` if the asset at this path should be reloaded
     pub(crate) fn should_reload(&self, path: &AssetPath) -> bool {
         if self.is_path_alive(path) {
@@ -389,6 +433,7 @@ impl AssetInfos {
             &mut self.infos,
             &mut self.path_to_index,
             &mut self.loader_dependents,
+            &mut self.runtime_dependents,
             &mut self.living_labeled_assets,
             &mut self.pending_tasks,
             self.watching_for_changes,
@@ -427,6 +472,22 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_index.index, world);
+        if self.watching_for_changes
+            && let Some(info) = self.infos.get_mut(&loaded_asset_index)
+        {
+            for dependency in info.dependencies.drain() {
+                if let Some(dependents) = self.runtime_dependents.get_mut(&dependency) {
+                    dependents.remove(&loaded_asset_index);
+                }
+            }
+            for dependency in &loaded_asset.dependencies {
+                self.runtime_dependents
+                    .entry(*dependency)
+                    .or_default()
+                    .insert(loaded_asset_index);
+            }
+            info.dependencies = loaded_asset.dependencies.clone();
+        }
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
@@ -714,6 +775,7 @@ impl AssetInfos {
         infos: &mut HashMap<ErasedAssetIndex, AssetInfo>,
         path_to_id: &mut HashMap<AssetPath<'static>, TypeIdMap<AssetIndex>>,
         loader_dependents: &mut HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
+        runtime_dependents: &mut HashMap<ErasedAssetIndex, HashSet<ErasedAssetIndex>>,
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<Box<str>>>,
         pending_tasks: &mut HashMap<ErasedAssetIndex, Task<()>>,
         watching_for_changes: bool,
@@ -735,6 +797,14 @@ impl AssetInfos {
         let type_id = entry.key().type_id;
 
         let info = entry.remove();
+        if watching_for_changes {
+            runtime_dependents.remove(&index);
+            for dependency in &info.dependencies {
+                if let Some(dependents) = runtime_dependents.get_mut(dependency) {
+                    dependents.remove(&index);
+                }
+            }
+        }
         let Some(path) = &info.path else {
             return true;
         };
@@ -773,6 +843,7 @@ impl AssetInfos {
                         &mut self.infos,
                         &mut self.path_to_index,
                         &mut self.loader_dependents,
+                        &mut self.runtime_dependents,
                         &mut self.living_labeled_assets,
                         &mut self.pending_tasks,
                         self.watching_for_changes,
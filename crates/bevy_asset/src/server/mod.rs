@@ -15,9 +15,9 @@ use crate::{
     },
     path::AssetPath,
     Asset, AssetEvent, AssetHandleProvider, AssetId, AssetIndex, AssetLoadFailedEvent,
-    AssetMetaCheck, Assets, DeserializeMetaError, ErasedAssetIndex, ErasedLoadedAsset, Handle,
-    LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId, UntypedAssetLoadFailedEvent,
-    UntypedHandle,
+    AssetMetaCheck, Assets, AssetsReloaded, DeserializeMetaError, ErasedAssetIndex,
+    ErasedLoadedAsset, Handle, LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId,
+    UntypedAssetLoadFailedEvent, UntypedHandle,
 };
 use alloc::{borrow::ToOwned, boxed::Box, vec, vec::Vec};
 use alloc::{
@@ -29,7 +29,7 @@ use atomicow::CowArc;
 use bevy_diagnostic::{DiagnosticPath, Diagnostics};
 use bevy_ecs::prelude::*;
 use bevy_platform::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 use bevy_tasks::IoTaskPool;
@@ -69,7 +69,7 @@ pub(crate) struct AssetServerData {
     pub(crate) loaders: Arc<RwLock<AssetLoaders>>,
     asset_event_sender: Sender<InternalAssetEvent>,
     asset_event_receiver: Receiver<InternalAssetEvent>,
-    sources: Arc<AssetSources>,
+    pub(crate) sources: Arc<AssetSources>,
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
     unapproved_path_mode: UnapprovedPathMode,
@@ -885,43 +885,90 @@ impl AssetServer {
         let path = path.into().into_owned();
         IoTaskPool::get()
             .spawn(async move {
-                let mut reloaded = false;
-
-                // First, try to reload the asset for any handles to that path. This will try both
-                // root assets and subassets.
-                let requests = server
-                    .read_infos()
-                    .get_path_handles(&path)
-                    .map(|handle| server.load_internal(Some(handle), path.clone(), true, None))
-                    .collect::<Vec<_>>();
-
-                for result in requests {
-                    // Count each reload as a started load.
-                    server.write_infos().stats.started_load_tasks += 1;
-                    match result.await {
-                        Ok(_) => reloaded = true,
-                        Err(err) => error!("{}", err),
-                    }
+                server.reload_async(&path, log).await;
+            })
+            .detach();
+    }
+
+    /// Reloads the asset at `path` if it is currently loaded. Errors are logged.
+    async fn reload_async(&self, path: &AssetPath<'static>, log: bool) -> ReloadResult {
+        let mut reloaded = false;
+        let mut failed = false;
+
+        // First, try to reload the asset for any handles to that path. This will try both
+        // root assets and subassets.
+        let requests = self
+            .read_infos()
+            .get_path_handles(path)
+            .map(|handle| self.load_internal(Some(handle), path.clone(), true, None))
+            .collect::<Vec<_>>();
+
+        for result in requests {
+            // Count each reload as a started load.
+            self.write_infos().stats.started_load_tasks += 1;
+            match result.await {
+                Ok(_) => reloaded = true,
+                Err(err) => {
+                    failed = true;
+                    error!("{}", err);
                 }
+            }
+        }
 
-                // If the above section failed, and there are still living subassets (aka we should
-                // reload), then just try doing an untyped load. This helps catch cases where the
-                // root asset has been dropped, but all its subassets are still being used (in which
-                // case the above section would have tried to find the loader with the root asset's
-                // type and loaded it). Hopefully the untyped load will find the right loader and
-                // reload all the subassets (though this is not guaranteed).
-                // TODO: Make sure we use the same loader as the original load (e.g., by storing a
-                // map from asset index to loader).
-                if !reloaded && server.read_infos().should_reload(&path) {
-                    server.write_infos().stats.started_load_tasks += 1;
-                    match server.load_internal(None, path.clone(), true, None).await {
-                        Ok(_) => reloaded = true,
-                        Err(err) => error!("{}", err),
-                    }
+        // If the above section failed, and there are still living subassets (aka we should
+        // reload), then just try doing an untyped load. This helps catch cases where the
+        // root asset has been dropped, but all its subassets are still being used (in which
+        // case the above section would have tried to find the loader with the root asset's
+        // type and loaded it). Hopefully the untyped load will find the right loader and
+        // reload all the subassets (though this is not guaranteed).
+        // TODO: Make sure we use the same loader as the original load (e.g., by storing a
+        // map from asset index to loader).
+        if !reloaded && self.read_infos().should_reload(path) {
+            self.write_infos().stats.started_load_tasks += 1;
+            match self.load_internal(None, path.clone(), true, None).await {
+                Ok(_) => reloaded = true,
+                Err(err) => {
+                    failed = true;
+                    error!("{}", err);
                 }
+            }
+        }
 
-                if log && reloaded {
-                    info!("Reloaded {}", path);
+        if log && reloaded {
+            info!("Reloaded {}", path);
+        }
+
+        match (reloaded, failed) {
+            (true, _) => ReloadResult::Reloaded,
+            (false, true) => ReloadResult::Failed,
+            (false, false) => ReloadResult::NotLoaded,
+        }
+    }
+
+    /// Reloads each layer of `layers` in order, waiting for every asset in a layer to reload before
+    /// starting the next one. Once finished, the results are reported through
+    /// [`AssetsReloaded`].
+    fn reload_batch(&self, layers: Vec<Vec<AssetPath<'static>>>) {
+        let server = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let mut reloaded = Vec::new();
+                let mut failed = Vec::new();
+                for layer in layers {
+                    let results = futures_util::future::join_all(
+                        layer.iter().map(|path| server.reload_async(path, true)),
+                    )
+                    .await;
+                    for (path, result) in layer.into_iter().zip(results) {
+                        match result {
+                            ReloadResult::Reloaded => reloaded.push(path),
+                            ReloadResult::Failed => failed.push(path),
+                            ReloadResult::NotLoaded => {}
+                        }
+                    }
+                }
+                if !reloaded.is_empty() || !failed.is_empty() {
+                    server.send_asset_event(InternalAssetEvent::Reloaded { reloaded, failed });
                 }
             })
             .detach();
@@ -1755,6 +1802,7 @@ pub fn handle_internal_asset_events(world: &mut World) {
         let mut infos = server.write_infos();
         let var_name = vec![];
         let mut untyped_failures = var_name;
+        let mut reload_batches = Vec::new();
         for event in server.data.asset_event_receiver.try_iter() {
             match event {
                 InternalAssetEvent::Loaded {
@@ -1797,6 +1845,18 @@ pub fn handle_internal_asset_events(world: &mut World) {
                         .expect("Asset failed event sender should exist");
                     sender(world, index.index, path, error);
                 }
+                InternalAssetEvent::Reloaded { reloaded, failed } => {
+                    let dependents = infos
+                        .get_runtime_dependents(&reloaded)
+                        .into_iter()
+                        .map(Into::into)
+                        .collect();
+                    reload_batches.push(AssetsReloaded {
+                        reloaded,
+                        failed,
+                        dependents,
+                    });
+                }
             }
         }
 
@@ -1804,6 +1864,10 @@ pub fn handle_internal_asset_events(world: &mut World) {
             world.write_message_batch(untyped_failures);
         }
 
+        if !reload_batches.is_empty() {
+            world.write_message_batch(reload_batches);
+        }
+
         // The following code all deals with hot-reloading, which we can skip if the server isn't
         // watching for changes.
         if !infos.watching_for_changes {
@@ -1886,13 +1950,15 @@ pub fn handle_internal_asset_events(world: &mut World) {
             }
         }
 
+        let reload_layers = order_reloads(paths_to_reload, &infos);
+
         // Drop the lock on `AssetInfos` before spawning a task that may block on it in
         // single-threaded.
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
         drop(infos);
 
-        for path in paths_to_reload {
-            server.reload_internal(path, true);
+        if !reload_layers.is_empty() {
+            server.reload_batch(reload_layers);
         }
 
         #[cfg(not(any(target_arch = "wasm32", not(feature = "multi_threaded"))))]
@@ -1902,6 +1968,63 @@ pub fn handle_internal_asset_events(world: &mut World) {
     });
 }
 
+/// Splits `paths` into layers such that every asset is in a later layer than all of the assets in
+/// `paths` that it depends on in its loader. Reloading the layers in order ensures that loaders
+/// always see the latest version of their dependencies.
+///
+/// Dependency cycles can't be ordered, so any assets in a cycle are put in the final layer.
+fn order_reloads(
+    paths: HashSet<AssetPath<'static>>,
+    infos: &AssetInfos,
+) -> Vec<Vec<AssetPath<'static>>> {
+    // The number of loader dependencies of each path that are also being reloaded.
+    let mut pending_dependencies = paths
+        .iter()
+        .map(|path| (path.clone(), 0usize))
+        .collect::<HashMap<_, _>>();
+    for path in &paths {
+        for dependent in infos.loader_dependents.get(path).into_iter().flatten() {
+            if let Some(count) = pending_dependencies.get_mut(dependent) {
+                *count += 1;
+            }
+        }
+    }
+
+    let mut layers = Vec::new();
+    let mut layer = pending_dependencies
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    while !layer.is_empty() {
+        let mut next_layer = Vec::new();
+        for path in &layer {
+            pending_dependencies.remove(path);
+            for dependent in infos.loader_dependents.get(path).into_iter().flatten() {
+                if let Some(count) = pending_dependencies.get_mut(dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        next_layer.push(dependent.clone());
+                    }
+                }
+            }
+        }
+        layers.push(core::mem::replace(&mut layer, next_layer));
+    }
+
+    if !pending_dependencies.is_empty() {
+        layers.push(pending_dependencies.into_keys().collect());
+    }
+    layers
+}
+
+/// The outcome of reloading a single path.
+enum ReloadResult {
+    Reloaded,
+    Failed,
+    NotLoaded,
+}
+
 /// A system publishing asset server statistics to [`bevy_diagnostic`].
 pub fn publish_asset_server_diagnostics(
     asset_server: Res<AssetServer>,
@@ -1927,6 +2050,11 @@ pub(crate) enum InternalAssetEvent {
         path: AssetPath<'static>,
         error: AssetLoadError,
     },
+    /// A batch of hot-reloads started by [`handle_internal_asset_events`] has finished.
+    Reloaded {
+        reloaded: Vec<AssetPath<'static>>,
+        failed: Vec<AssetPath<'static>>,
+    },
 }
 
 /// The load state of an asset.
@@ -1,4 +1,6 @@
 use alloc::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy_asset::io::{AssetStream, AssetStreamError, BlockingAssetStreamReader};
 use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
 use bevy_reflect::TypePath;
 use std::io::Cursor;
@@ -26,6 +28,40 @@ impl AsRef<[u8]> for AudioSource {
     }
 }
 
+/// The file extensions supported by the enabled audio format features.
+const AUDIO_EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "mp3")]
+    "mp3",
+    #[cfg(feature = "flac")]
+    "flac",
+    #[cfg(feature = "wav")]
+    "wav",
+    #[cfg(feature = "vorbis")]
+    "oga",
+    #[cfg(feature = "vorbis")]
+    "ogg",
+    #[cfg(feature = "vorbis")]
+    "spx",
+];
+
+/// The file extensions of streamed audio files, such as `music.stream.ogg`, supported by the
+/// enabled audio format features.
+#[cfg(not(target_arch = "wasm32"))]
+const STREAMING_AUDIO_EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "mp3")]
+    "stream.mp3",
+    #[cfg(feature = "flac")]
+    "stream.flac",
+    #[cfg(feature = "wav")]
+    "stream.wav",
+    #[cfg(feature = "vorbis")]
+    "stream.oga",
+    #[cfg(feature = "vorbis")]
+    "stream.ogg",
+    #[cfg(feature = "vorbis")]
+    "stream.spx",
+];
+
 /// Loads files as [`AudioSource`] [`Assets`](bevy_asset::Assets)
 ///
 /// This asset loader supports different audio formats based on the enable Bevy features.
@@ -56,20 +92,54 @@ impl AssetLoader for AudioLoader {
     }
 
     fn extensions(&self) -> &[&str] {
-        &[
-            #[cfg(feature = "mp3")]
-            "mp3",
-            #[cfg(feature = "flac")]
-            "flac",
-            #[cfg(feature = "wav")]
-            "wav",
-            #[cfg(feature = "vorbis")]
-            "oga",
-            #[cfg(feature = "vorbis")]
-            "ogg",
-            #[cfg(feature = "vorbis")]
-            "spx",
-        ]
+        AUDIO_EXTENSIONS
+    }
+}
+
+/// A source of audio data that is streamed from its [`AssetSource`](bevy_asset::io::AssetSource)
+/// while it plays, instead of being read into memory up front.
+///
+/// This is useful for long tracks such as background music. Audio files are loaded as this type
+/// when their extension is prefixed with `stream`, e.g. `asset_server.load("music.stream.ogg")`,
+/// so that the file's `.meta` file and its processed version refer to [`StreamingAudioLoader`]
+/// rather than [`AudioLoader`]. It supports the same file formats as [`AudioSource`].
+///
+/// The decoder reads the stream on the audio thread, which blocks whenever it reaches bytes that
+/// haven't been fetched yet. Sources that are slow to read from (such as web sources) may cause
+/// audible stutters.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Asset, Debug, Clone, TypePath)]
+pub struct StreamingAudioSource {
+    /// The stream the audio data is read from.
+    pub stream: AssetStream,
+}
+
+/// Loads files as [`StreamingAudioSource`] [`Assets`](bevy_asset::Assets), supporting the same
+/// file formats as [`AudioLoader`] with `stream`-prefixed extensions, such as `.stream.ogg`.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default, TypePath)]
+pub struct StreamingAudioLoader;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AssetLoader for StreamingAudioLoader {
+    type Asset = StreamingAudioSource;
+    type Settings = ();
+    type Error = AssetStreamError;
+
+    async fn load(
+        &self,
+        _reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<StreamingAudioSource, Self::Error> {
+        let stream = load_context
+            .open_stream(load_context.path().clone())
+            .await?;
+        Ok(StreamingAudioSource { stream })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        STREAMING_AUDIO_EXTENSIONS
     }
 }
 
@@ -103,6 +173,16 @@ impl Decodable for AudioSource {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl Decodable for StreamingAudioSource {
+    type DecoderItem = <rodio::Decoder<BlockingAssetStreamReader> as Iterator>::Item;
+    type Decoder = rodio::Decoder<BlockingAssetStreamReader>;
+
+    fn decoder(&self) -> Self::Decoder {
+        rodio::Decoder::new(self.stream.blocking_reader()).unwrap()
+    }
+}
+
 /// A trait that allows adding a custom audio source to the object.
 /// This is implemented for [`App`][bevy_app::App] to allow registering custom [`Decodable`] types.
 pub trait AddAudioSource {
@@ -117,3 +197,90 @@ pub trait AddAudioSource {
         T: Decodable + Asset,
         f32: rodio::cpal::FromSample<T::DecoderItem>;
 }
+
+#[cfg(all(test, feature = "vorbis", not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSourceBuilder, AssetSourceId,
+        },
+        AssetApp, AssetPlugin, AssetServer, Assets, Handle, LoadState,
+    };
+    use std::path::Path;
+
+    fn test_app(dir: Dir) -> App {
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(reader.clone())),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<AudioSource>()
+        .init_asset::<StreamingAudioSource>()
+        .init_asset_loader::<AudioLoader>()
+        .init_asset_loader::<StreamingAudioLoader>();
+        app
+    }
+
+    fn wait_until_loaded<A: Asset>(app: &mut App, handle: &Handle<A>) {
+        for _ in 0..10000 {
+            app.update();
+            match app.world().resource::<AssetServer>().load_state(handle) {
+                LoadState::Loaded => return,
+                LoadState::Failed(err) => panic!("failed to load asset: {err}"),
+                _ => {}
+            }
+        }
+        panic!("asset did not load");
+    }
+
+    #[test]
+    fn select_loader_by_extension() {
+        let bytes = (0..200u8).collect::<Vec<_>>();
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("sound.ogg"), bytes.clone());
+        dir.insert_asset(Path::new("music.stream.ogg"), bytes.clone());
+        let mut app = test_app(dir);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+
+        // The default meta of each file names the loader for its extension.
+        let loader_name = |path: &'static str| {
+            bevy_tasks::block_on(asset_server.get_path_asset_loader(path))
+                .unwrap()
+                .type_path()
+        };
+        assert_eq!(
+            loader_name("sound.ogg"),
+            <AudioLoader as TypePath>::type_path()
+        );
+        assert_eq!(
+            loader_name("music.stream.ogg"),
+            <StreamingAudioLoader as TypePath>::type_path()
+        );
+
+        let sound: Handle<AudioSource> = asset_server.load("sound.ogg");
+        let music: Handle<StreamingAudioSource> = asset_server.load("music.stream.ogg");
+        wait_until_loaded(&mut app, &sound);
+        wait_until_loaded(&mut app, &music);
+
+        let sound = &app
+            .world()
+            .resource::<Assets<AudioSource>>()
+            .get(&sound)
+            .unwrap();
+        assert_eq!(&*sound.bytes, &bytes[..]);
+        let music = app
+            .world()
+            .resource::<Assets<StreamingAudioSource>>()
+            .get(&music)
+            .unwrap();
+        assert_eq!(music.stream.len(), 200);
+        let mut streamed = Vec::new();
+        std::io::Read::read_to_end(&mut music.stream.blocking_reader(), &mut streamed).unwrap();
+        assert_eq!(streamed, bytes);
+    }
+}
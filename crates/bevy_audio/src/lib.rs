@@ -96,6 +96,11 @@ impl Plugin for AudioPlugin {
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         {
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                app.add_audio_source::<StreamingAudioSource>();
+                app.init_asset_loader::<StreamingAudioLoader>();
+            }
             app.add_audio_source::<AudioSource>();
             app.init_asset_loader::<AudioLoader>();
         }
@@ -35,6 +35,12 @@ impl BevyError {
         self.inner.error.downcast_ref::<E>()
     }
 
+    /// Returns a reference to the internal error. This can be used to walk its
+    /// [`source`](Error::source) chain.
+    pub fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.inner.error
+    }
+
     fn format_backtrace(&self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         #[cfg(feature = "backtrace")]
         {
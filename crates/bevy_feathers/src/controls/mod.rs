@@ -6,9 +6,13 @@ mod checkbox;
 mod color_plane;
 mod color_slider;
 mod color_swatch;
+mod number_input;
 mod radio;
+mod select;
 mod slider;
+mod tabs;
 mod toggle_switch;
+mod tree_view;
 mod virtual_keyboard;
 
 pub use button::{button, ButtonPlugin, ButtonProps, ButtonVariant};
@@ -18,9 +22,13 @@ pub use color_slider::{
     color_slider, ColorChannel, ColorSlider, ColorSliderPlugin, ColorSliderProps, SliderBaseColor,
 };
 pub use color_swatch::{color_swatch, ColorSwatch, ColorSwatchFg, ColorSwatchValue};
+pub use number_input::{number_input, NumberInputPlugin, NumberInputProps};
 pub use radio::{radio, RadioPlugin};
+pub use select::{select, SelectOptions, SelectPlugin, SelectProps};
 pub use slider::{slider, SliderPlugin, SliderProps};
+pub use tabs::{tab, tab_bar, TabsPlugin};
 pub use toggle_switch::{toggle_switch, ToggleSwitchPlugin};
+pub use tree_view::{tree_item, tree_view, TreeViewPlugin};
 pub use virtual_keyboard::{virtual_keyboard, VirtualKeyPressed};
 
 use crate::{
@@ -40,9 +48,13 @@ impl Plugin for ControlsPlugin {
             ColorPlanePlugin,
             ColorSliderPlugin,
             ColorSwatchPlugin,
+            NumberInputPlugin,
             RadioPlugin,
+            SelectPlugin,
             SliderPlugin,
+            TabsPlugin,
             ToggleSwitchPlugin,
+            TreeViewPlugin,
         ));
     }
 }
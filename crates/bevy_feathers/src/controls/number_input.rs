@@ -0,0 +1,247 @@
+use bevy_app::{Plugin, PreUpdate};
+use bevy_ecs::{
+    bundle::Bundle,
+    children,
+    component::Component,
+    entity::Entity,
+    hierarchy::Children,
+    lifecycle::RemovedComponents,
+    query::{Added, Changed, Has, Or, With},
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query},
+};
+use bevy_input_focus::tab_navigation::TabIndex;
+use bevy_picking::{hover::Hovered, PickingSystems};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::{widget::Text, AlignItems, InteractionDisabled, JustifyContent, Node, UiRect, Val};
+use bevy_ui_widgets::{NumberInput, SliderPrecision, SliderRange, SliderStep, SliderValue};
+
+use crate::{
+    constants::{fonts, size},
+    cursor::EntityCursor,
+    font_styles::InheritableFont,
+    handle_or_path::HandleOrPath,
+    rounded_corners::RoundedCorners,
+    theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText},
+    tokens,
+};
+
+/// Number input template properties, passed to [`number_input`] function.
+pub struct NumberInputProps {
+    /// Current value
+    pub value: f32,
+    /// Minimum value
+    pub min: f32,
+    /// Maximum value
+    pub max: f32,
+    /// Amount by which the arrow keys change the value
+    pub step: f32,
+    /// Number of decimal places the value is rounded to while dragging
+    pub precision: i32,
+}
+
+impl Default for NumberInputProps {
+    fn default() -> Self {
+        Self {
+            value: 0.0,
+            min: f32::MIN,
+            max: f32::MAX,
+            step: 1.0,
+            precision: 2,
+        }
+    }
+}
+
+#[derive(Component, Default, Clone)]
+#[require(NumberInput)]
+#[derive(Reflect)]
+#[reflect(Component, Clone, Default)]
+struct NumberInputStyle;
+
+/// Marker for the text
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct NumberInputValueText;
+
+/// Spawn a new number input widget, whose value is edited by dragging horizontally.
+///
+/// # Arguments
+///
+/// * `props` - construction properties for the number input.
+/// * `overrides` - a bundle of components that are merged in with the normal number input components.
+///
+/// # Emitted events
+///
+/// * [`bevy_ui_widgets::ValueChange<f32>`] when the value is changed.
+///
+///  These events can be disabled by adding an [`bevy_ui::InteractionDisabled`] component to the entity
+pub fn number_input<B: Bundle>(props: NumberInputProps, overrides: B) -> impl Bundle {
+    (
+        Node {
+            height: size::ROW_HEIGHT,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(0.)),
+            flex_grow: 1.0,
+            border_radius: RoundedCorners::All.to_border_radius(4.0),
+            ..Default::default()
+        },
+        NumberInputStyle,
+        SliderValue(props.value),
+        SliderRange::new(props.min, props.max),
+        SliderStep(props.step),
+        SliderPrecision(props.precision),
+        Hovered::default(),
+        EntityCursor::System(bevy_window::SystemCursorIcon::EwResize),
+        TabIndex(0),
+        ThemeBackgroundColor(tokens::NUMBER_INPUT_BG),
+        ThemeFontColor(tokens::NUMBER_INPUT_TEXT),
+        InheritableFont {
+            font: HandleOrPath::Path(fonts::MONO.to_owned()),
+            font_size: 12.0,
+        },
+        overrides,
+        children![(Text::new(""), ThemedText, NumberInputValueText)],
+    )
+}
+
+fn update_number_input_styles(
+    q_inputs: Query<
+        (
+            Entity,
+            Has<InteractionDisabled>,
+            &Hovered,
+            &ThemeBackgroundColor,
+            &ThemeFontColor,
+        ),
+        (
+            With<NumberInputStyle>,
+            Or<(Changed<Hovered>, Added<InteractionDisabled>)>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for (input_ent, disabled, hovered, bg_color, font_color) in q_inputs.iter() {
+        set_number_input_styles(
+            input_ent,
+            disabled,
+            hovered.0,
+            bg_color,
+            font_color,
+            &mut commands,
+        );
+    }
+}
+
+fn update_number_input_styles_remove(
+    q_inputs: Query<
+        (
+            Entity,
+            Has<InteractionDisabled>,
+            &Hovered,
+            &ThemeBackgroundColor,
+            &ThemeFontColor,
+        ),
+        With<NumberInputStyle>,
+    >,
+    mut removed_disabled: RemovedComponents<InteractionDisabled>,
+    mut commands: Commands,
+) {
+    removed_disabled.read().for_each(|ent| {
+        if let Ok((input_ent, disabled, hovered, bg_color, font_color)) = q_inputs.get(ent) {
+            set_number_input_styles(
+                input_ent,
+                disabled,
+                hovered.0,
+                bg_color,
+                font_color,
+                &mut commands,
+            );
+        }
+    });
+}
+
+fn set_number_input_styles(
+    input_ent: Entity,
+    disabled: bool,
+    hovered: bool,
+    bg_color: &ThemeBackgroundColor,
+    font_color: &ThemeFontColor,
+    commands: &mut Commands,
+) {
+    let bg_token = match (disabled, hovered) {
+        (true, _) => tokens::NUMBER_INPUT_BG_DISABLED,
+        (false, true) => tokens::NUMBER_INPUT_BG_HOVER,
+        (false, false) => tokens::NUMBER_INPUT_BG,
+    };
+
+    let font_color_token = match disabled {
+        true => tokens::NUMBER_INPUT_TEXT_DISABLED,
+        false => tokens::NUMBER_INPUT_TEXT,
+    };
+
+    let cursor_shape = match disabled {
+        true => bevy_window::SystemCursorIcon::NotAllowed,
+        false => bevy_window::SystemCursorIcon::EwResize,
+    };
+
+    // Change background color
+    if bg_color.0 != bg_token {
+        commands
+            .entity(input_ent)
+            .insert(ThemeBackgroundColor(bg_token));
+    }
+
+    // Change font color
+    if font_color.0 != font_color_token {
+        commands
+            .entity(input_ent)
+            .insert(ThemeFontColor(font_color_token));
+    }
+
+    // Change cursor shape
+    commands
+        .entity(input_ent)
+        .insert(EntityCursor::System(cursor_shape));
+}
+
+fn update_number_input_text(
+    q_inputs: Query<
+        (Entity, &SliderValue, &SliderPrecision),
+        (
+            With<NumberInputStyle>,
+            Or<(Changed<SliderValue>, Changed<Children>)>,
+        ),
+    >,
+    q_children: Query<&Children>,
+    mut q_text: Query<&mut Text, With<NumberInputValueText>>,
+) {
+    for (input_ent, value, precision) in q_inputs.iter() {
+        q_children.iter_descendants(input_ent).for_each(|child| {
+            if let Ok(mut text) = q_text.get_mut(child) {
+                text.0 = match precision.0 >= 0 {
+                    true => format!("{:.precision$}", value.0, precision = precision.0 as usize),
+                    false => format!("{}", value.0),
+                };
+            }
+        });
+    }
+}
+
+/// Plugin which registers the systems for updating the number input styles.
+pub struct NumberInputPlugin;
+
+impl Plugin for NumberInputPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(
+            PreUpdate,
+            (
+                update_number_input_styles,
+                update_number_input_styles_remove,
+                update_number_input_text,
+            )
+                .in_set(PickingSystems::Last),
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use bevy_app::{Plugin, PreUpdate};
+use bevy_camera::visibility::Visibility;
+use bevy_ecs::{
+    bundle::Bundle,
+    children,
+    component::Component,
+    entity::Entity,
+    hierarchy::Children,
+    observer::On,
+    query::{Changed, Has, Or, With},
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    spawn::{SpawnIter, SpawnRelated},
+    system::{Commands, Query, ResMut},
+};
+use bevy_input_focus::{tab_navigation::TabIndex, InputFocus};
+use bevy_math::Rot2;
+use bevy_picking::{hover::Hovered, PickingSystems};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::{
+    widget::Text, AlignItems, Display, FlexDirection, GlobalZIndex, JustifyContent, Node,
+    OverrideClip, PositionType, UiRect, UiTransform, Val,
+};
+use bevy_ui_widgets::{
+    popover::{Popover, PopoverAlign, PopoverPlacement, PopoverSide},
+    MenuAction, MenuEvent, MenuPopup, Select, SelectOption, SelectValue,
+};
+
+use crate::{
+    constants::{fonts, size},
+    controls::ButtonVariant,
+    cursor::EntityCursor,
+    font_styles::InheritableFont,
+    handle_or_path::HandleOrPath,
+    rounded_corners::RoundedCorners,
+    theme::{ThemeBackgroundColor, ThemeBorderColor, ThemeFontColor, ThemedText},
+    tokens,
+};
+
+/// Parameters for the select template, passed to [`select`] function.
+#[derive(Default)]
+pub struct SelectProps {
+    /// The labels of the options.
+    pub options: Vec<String>,
+    /// The index of the selected option.
+    pub value: usize,
+}
+
+/// The labels of the options of a feathers [`select`]. Changing this component updates the
+/// options shown in the popup and the [`Select::option_count`].
+#[derive(Component, Default, Clone, Debug, Reflect)]
+#[reflect(Component, Clone, Default)]
+pub struct SelectOptions(pub Vec<String>);
+
+/// Marker for the text showing the selected option
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct SelectValueText;
+
+/// Marker for the options in the popup
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct SelectOptionStyle {
+    selected: bool,
+}
+
+/// Template function to spawn a select box.
+///
+/// # Arguments
+/// * `props` - construction properties for the select box.
+/// * `overrides` - a bundle of components that are merged in with the normal select components.
+///
+/// # Emitted events
+/// * [`bevy_ui_widgets::ValueChange<usize>`] with the index of the option, when an option is
+///   chosen from the popup, or using the arrow keys while the select has focus.
+///
+///  These events can be disabled by adding an [`bevy_ui::InteractionDisabled`] component to the entity
+pub fn select<B: Bundle>(props: SelectProps, overrides: B) -> impl Bundle {
+    let label = props.options.get(props.value).cloned().unwrap_or_default();
+    (
+        Node {
+            height: size::ROW_HEIGHT,
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(0.)),
+            column_gap: Val::Px(8.0),
+            flex_grow: 1.0,
+            border_radius: RoundedCorners::All.to_border_radius(4.0),
+            ..Default::default()
+        },
+        Select {
+            option_count: props.options.len(),
+        },
+        SelectValue(props.value),
+        SelectOptions(props.options),
+        ButtonVariant::Normal,
+        Hovered::default(),
+        EntityCursor::System(bevy_window::SystemCursorIcon::Pointer),
+        TabIndex(0),
+        ThemeBackgroundColor(tokens::BUTTON_BG),
+        ThemeFontColor(tokens::BUTTON_TEXT),
+        InheritableFont {
+            font: HandleOrPath::Path(fonts::REGULAR.to_owned()),
+            font_size: 14.0,
+        },
+        overrides,
+        children![
+            (Text::new(label), ThemedText, SelectValueText),
+            (
+                // Chevron: rotated node with L-shaped border.
+                Node {
+                    width: Val::Px(6.0),
+                    height: Val::Px(6.0),
+                    margin: UiRect::bottom(Val::Px(3.0)),
+                    border: UiRect {
+                        bottom: Val::Px(2.0),
+                        right: Val::Px(2.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                UiTransform::from_rotation(Rot2::FRAC_PI_4),
+                ThemeBorderColor(tokens::BUTTON_TEXT),
+            )
+        ],
+    )
+}
+
+fn select_on_menu_event(
+    mut menu_event: On<MenuEvent>,
+    q_select: Query<(&SelectOptions, &SelectValue, Option<&Children>), With<Select>>,
+    q_popup: Query<(), With<MenuPopup>>,
+    mut focus: ResMut<InputFocus>,
+    mut commands: Commands,
+) {
+    let select = menu_event.source;
+    let Ok((options, value, children)) = q_select.get(select) else {
+        return;
+    };
+    menu_event.propagate(false);
+    let popup = children.and_then(|children| children.iter().copied().find(|c| q_popup.contains(*c)));
+    match (menu_event.action, popup) {
+        (MenuAction::Open | MenuAction::Toggle, None) => {
+            spawn_select_popup(select, options, value, &mut commands);
+        }
+        (MenuAction::Toggle | MenuAction::Close | MenuAction::CloseAll, Some(popup)) => {
+            commands.entity(popup).despawn();
+        }
+        (MenuAction::FocusRoot, _) => {
+            focus.0 = Some(select);
+        }
+        _ => {}
+    }
+}
+
+fn spawn_select_popup(
+    select: Entity,
+    options: &SelectOptions,
+    value: &SelectValue,
+    commands: &mut Commands,
+) {
+    let selected = value.0;
+    let items = options
+        .0
+        .clone()
+        .into_iter()
+        .enumerate()
+        .map(move |(index, label)| select_option(index, label, index == selected));
+    commands.entity(select).with_child((
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            min_width: Val::Percent(100.),
+            border: UiRect::all(Val::Px(1.0)),
+            padding: UiRect::all(Val::Px(2.0)),
+            position_type: PositionType::Absolute,
+            border_radius: RoundedCorners::All.to_border_radius(4.0),
+            ..Default::default()
+        },
+        MenuPopup::default(),
+        Visibility::Hidden, // Will be visible after positioning
+        ThemeBackgroundColor(tokens::MENU_BG),
+        ThemeBorderColor(tokens::MENU_BORDER),
+        ThemeFontColor(tokens::MENU_ITEM_TEXT),
+        GlobalZIndex(100),
+        Popover {
+            positions: vec![
+                PopoverPlacement {
+                    side: PopoverSide::Bottom,
+                    align: PopoverAlign::Start,
+                    gap: 2.0,
+                },
+                PopoverPlacement {
+                    side: PopoverSide::Top,
+                    align: PopoverAlign::Start,
+                    gap: 2.0,
+                },
+            ],
+            window_margin: 10.0,
+        },
+        OverrideClip,
+        Children::spawn(SpawnIter(items)),
+    ));
+}
+
+fn select_option(index: usize, label: String, selected: bool) -> impl Bundle {
+    (
+        Node {
+            height: size::ROW_HEIGHT,
+            align_items: AlignItems::Center,
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(0.)),
+            border_radius: RoundedCorners::All.to_border_radius(3.0),
+            ..Default::default()
+        },
+        SelectOption(index),
+        SelectOptionStyle { selected },
+        Hovered::default(),
+        EntityCursor::System(bevy_window::SystemCursorIcon::Pointer),
+        TabIndex(0),
+        ThemeBackgroundColor(match selected {
+            true => tokens::MENU_ITEM_BG_SELECTED,
+            false => tokens::MENU_ITEM_BG,
+        }),
+        children![(Text::new(label), ThemedText)],
+    )
+}
+
+fn update_select_text(
+    mut q_select: Query<
+        (Entity, &SelectOptions, &SelectValue, &mut Select),
+        Or<(Changed<SelectOptions>, Changed<SelectValue>)>,
+    >,
+    q_children: Query<&Children>,
+    mut q_text: Query<&mut Text, With<SelectValueText>>,
+) {
+    for (select_ent, options, value, mut select) in q_select.iter_mut() {
+        if select.option_count != options.0.len() {
+            select.option_count = options.0.len();
+        }
+        let label = options.0.get(value.0).cloned().unwrap_or_default();
+        q_children.iter_descendants(select_ent).for_each(|child| {
+            if let Ok(mut text) = q_text.get_mut(child)
+                && text.0 != label
+            {
+                text.0.clone_from(&label);
+            }
+        });
+    }
+}
+
+fn update_select_option_styles(
+    q_options: Query<
+        (
+            Entity,
+            &SelectOptionStyle,
+            Has<bevy_ui::InteractionDisabled>,
+            &Hovered,
+            &ThemeBackgroundColor,
+        ),
+        Changed<Hovered>,
+    >,
+    mut commands: Commands,
+) {
+    for (option_ent, style, disabled, hovered, bg_color) in q_options.iter() {
+        let bg_token = match (style.selected, disabled, hovered.0) {
+            (true, _, _) => tokens::MENU_ITEM_BG_SELECTED,
+            (false, false, true) => tokens::MENU_ITEM_BG_HOVER,
+            _ => tokens::MENU_ITEM_BG,
+        };
+        if bg_color.0 != bg_token {
+            commands
+                .entity(option_ent)
+                .insert(ThemeBackgroundColor(bg_token));
+        }
+    }
+}
+
+/// Plugin which registers the observers and systems for the select box.
+pub struct SelectPlugin;
+
+impl Plugin for SelectPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_observer(select_on_menu_event).add_systems(
+            PreUpdate,
+            (update_select_text, update_select_option_styles).in_set(PickingSystems::Last),
+        );
+    }
+}
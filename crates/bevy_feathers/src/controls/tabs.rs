@@ -0,0 +1,223 @@
+use bevy_app::{Plugin, PreUpdate};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    lifecycle::RemovedComponents,
+    query::{Added, Changed, Has, Or, With},
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    spawn::{SpawnRelated, SpawnableList},
+    system::{Commands, Query},
+};
+use bevy_input_focus::tab_navigation::TabIndex;
+use bevy_picking::{hover::Hovered, PickingSystems};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::{
+    AlignItems, Checked, FlexDirection, InteractionDisabled, JustifyContent, Node, UiRect, Val,
+};
+use bevy_ui_widgets::{Tab, TabBar};
+
+use crate::{
+    constants::{fonts, size},
+    cursor::EntityCursor,
+    font_styles::InheritableFont,
+    handle_or_path::HandleOrPath,
+    rounded_corners::RoundedCorners,
+    theme::{ThemeBackgroundColor, ThemeFontColor},
+    tokens,
+};
+
+/// Marker for the tabs
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct TabStyle;
+
+/// Template function to spawn a tab bar.
+///
+/// # Arguments
+/// * `overrides` - a bundle of components that are merged in with the normal tab bar components.
+/// * `tabs` - a [`SpawnableList`] of tabs, usually created with [`tab`].
+///
+/// The content of each tab can be placed in an entity with a [`bevy_ui_widgets::TabPanel`]
+/// component, which is only displayed while the tab is active.
+///
+/// # Emitted events
+/// * [`bevy_ui_widgets::ValueChange<Entity>`] with the id of the tab, when a tab is clicked or
+///   selected with the arrow keys.
+pub fn tab_bar<C: SpawnableList<ChildOf> + Send + Sync + 'static, B: Bundle>(
+    overrides: B,
+    tabs: C,
+) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::End,
+            column_gap: Val::Px(2.0),
+            padding: UiRect::horizontal(Val::Px(4.0)),
+            ..Default::default()
+        },
+        TabBar,
+        TabIndex(0),
+        ThemeBackgroundColor(tokens::TAB_BAR_BG),
+        overrides,
+        Children::spawn(tabs),
+    )
+}
+
+/// Template function to spawn a tab within a [`tab_bar`]. The active tab should be marked with
+/// [`Checked`].
+///
+/// # Arguments
+/// * `overrides` - a bundle of components that are merged in with the normal tab components.
+/// * `label` - the label of the tab.
+pub fn tab<C: SpawnableList<ChildOf> + Send + Sync + 'static, B: Bundle>(
+    overrides: B,
+    label: C,
+) -> impl Bundle {
+    (
+        Node {
+            height: size::ROW_HEIGHT,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            padding: UiRect::axes(Val::Px(12.0), Val::Px(0.)),
+            border_radius: RoundedCorners::Top.to_border_radius(4.0),
+            ..Default::default()
+        },
+        Tab,
+        TabStyle,
+        Hovered::default(),
+        EntityCursor::System(bevy_window::SystemCursorIcon::Pointer),
+        ThemeBackgroundColor(tokens::TAB_BG),
+        ThemeFontColor(tokens::TAB_TEXT),
+        InheritableFont {
+            font: HandleOrPath::Path(fonts::REGULAR.to_owned()),
+            font_size: 14.0,
+        },
+        overrides,
+        Children::spawn(label),
+    )
+}
+
+fn update_tab_styles(
+    q_tabs: Query<
+        (
+            Entity,
+            Has<InteractionDisabled>,
+            Has<Checked>,
+            &Hovered,
+            &ThemeBackgroundColor,
+            &ThemeFontColor,
+        ),
+        (
+            With<TabStyle>,
+            Or<(Changed<Hovered>, Added<Checked>, Added<InteractionDisabled>)>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for (tab_ent, disabled, checked, hovered, bg_color, font_color) in q_tabs.iter() {
+        set_tab_styles(
+            tab_ent,
+            disabled,
+            checked,
+            hovered.0,
+            bg_color,
+            font_color,
+            &mut commands,
+        );
+    }
+}
+
+fn update_tab_styles_remove(
+    q_tabs: Query<
+        (
+            Entity,
+            Has<InteractionDisabled>,
+            Has<Checked>,
+            &Hovered,
+            &ThemeBackgroundColor,
+            &ThemeFontColor,
+        ),
+        With<TabStyle>,
+    >,
+    mut removed_disabled: RemovedComponents<InteractionDisabled>,
+    mut removed_checked: RemovedComponents<Checked>,
+    mut commands: Commands,
+) {
+    removed_disabled
+        .read()
+        .chain(removed_checked.read())
+        .for_each(|ent| {
+            if let Ok((tab_ent, disabled, checked, hovered, bg_color, font_color)) = q_tabs.get(ent)
+            {
+                set_tab_styles(
+                    tab_ent,
+                    disabled,
+                    checked,
+                    hovered.0,
+                    bg_color,
+                    font_color,
+                    &mut commands,
+                );
+            }
+        });
+}
+
+fn set_tab_styles(
+    tab_ent: Entity,
+    disabled: bool,
+    checked: bool,
+    hovered: bool,
+    bg_color: &ThemeBackgroundColor,
+    font_color: &ThemeFontColor,
+    commands: &mut Commands,
+) {
+    let bg_token = match (checked, disabled, hovered) {
+        (true, _, _) => tokens::TAB_BG_ACTIVE,
+        (false, false, true) => tokens::TAB_BG_HOVER,
+        _ => tokens::TAB_BG,
+    };
+
+    let font_color_token = match disabled {
+        true => tokens::TAB_TEXT_DISABLED,
+        false => tokens::TAB_TEXT,
+    };
+
+    let cursor_shape = match disabled {
+        true => bevy_window::SystemCursorIcon::NotAllowed,
+        false => bevy_window::SystemCursorIcon::Pointer,
+    };
+
+    // Change background color
+    if bg_color.0 != bg_token {
+        commands
+            .entity(tab_ent)
+            .insert(ThemeBackgroundColor(bg_token));
+    }
+
+    // Change font color
+    if font_color.0 != font_color_token {
+        commands
+            .entity(tab_ent)
+            .insert(ThemeFontColor(font_color_token));
+    }
+
+    // Change cursor shape
+    commands
+        .entity(tab_ent)
+        .insert(EntityCursor::System(cursor_shape));
+}
+
+/// Plugin which registers the systems for updating the tab styles.
+pub struct TabsPlugin;
+
+impl Plugin for TabsPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(
+            PreUpdate,
+            (update_tab_styles, update_tab_styles_remove).in_set(PickingSystems::Last),
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use core::f32::consts::FRAC_PI_4;
+
+use bevy_app::{Plugin, PreUpdate};
+use bevy_camera::visibility::Visibility;
+use bevy_ecs::{
+    bundle::Bundle,
+    children,
+    component::Component,
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    query::{Has, With, Without},
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    spawn::{Spawn, SpawnRelated, SpawnableList},
+    system::{Commands, Query},
+};
+use bevy_input_focus::tab_navigation::TabIndex;
+use bevy_math::Rot2;
+use bevy_picking::{hover::Hovered, PickingSystems};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::{
+    AlignItems, Checked, Display, FlexDirection, JustifyContent, Node, UiRect, UiTransform, Val,
+};
+use bevy_ui_widgets::{Expanded, TreeItem, TreeItemToggle, TreeView};
+
+use crate::{
+    constants::{fonts, size},
+    cursor::EntityCursor,
+    font_styles::InheritableFont,
+    handle_or_path::HandleOrPath,
+    rounded_corners::RoundedCorners,
+    theme::{ThemeBackgroundColor, ThemeBorderColor, ThemeFontColor},
+    tokens,
+};
+
+/// Marker for the tree items
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct TreeItemStyle;
+
+/// Marker for the row containing the label of a tree item
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct TreeItemRow;
+
+/// Marker for the disclosure triangle of a tree item
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct TreeItemChevron;
+
+/// Marker for the container of the child items of a tree item
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct TreeItemChildren;
+
+/// Template function to spawn a tree view.
+///
+/// # Arguments
+/// * `overrides` - a bundle of components that are merged in with the normal tree components.
+/// * `items` - a [`SpawnableList`] of the top-level items, usually created with [`tree_item`].
+///
+/// # Emitted events
+/// * [`bevy_ui_widgets::ValueChange<Entity>`] with the id of the item to select, when an item is
+///   clicked or selected with the arrow keys.
+pub fn tree_view<C: SpawnableList<ChildOf> + Send + Sync + 'static, B: Bundle>(
+    overrides: B,
+    items: C,
+) -> impl Bundle {
+    (
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        TreeView,
+        TabIndex(0),
+        ThemeFontColor(tokens::TREE_ITEM_TEXT),
+        InheritableFont {
+            font: HandleOrPath::Path(fonts::REGULAR.to_owned()),
+            font_size: 14.0,
+        },
+        overrides,
+        Children::spawn(items),
+    )
+}
+
+/// Template function to spawn an item of a [`tree_view`]. The selected item should be marked
+/// with [`Checked`], and items whose children are shown with [`Expanded`]. The children of
+/// collapsed items are hidden automatically.
+///
+/// # Arguments
+/// * `overrides` - a bundle of components that are merged in with the normal tree item components.
+/// * `label` - the label of the item.
+/// * `items` - a [`SpawnableList`] of the child items.
+///
+/// # Emitted events
+/// * [`bevy_ui_widgets::ValueChange<bool>`] with the new expanded state, when the disclosure
+///   triangle is clicked, or when using the left and right arrow keys.
+/// * [`bevy_ui_widgets::Activate`] when the ENTER key is pressed while the item is selected.
+pub fn tree_item<
+    L: SpawnableList<ChildOf> + Send + Sync + 'static,
+    C: SpawnableList<ChildOf> + Send + Sync + 'static,
+    B: Bundle,
+>(
+    overrides: B,
+    label: L,
+    items: C,
+) -> impl Bundle {
+    (
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        TreeItem,
+        TreeItemStyle,
+        overrides,
+        Children::spawn((
+            Spawn((
+                Node {
+                    height: size::ROW_HEIGHT,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(4.0),
+                    padding: UiRect::right(Val::Px(8.0)),
+                    border_radius: RoundedCorners::All.to_border_radius(3.0),
+                    ..Default::default()
+                },
+                TreeItemRow,
+                Hovered::default(),
+                EntityCursor::System(bevy_window::SystemCursorIcon::Pointer),
+                ThemeBackgroundColor(tokens::TREE_ITEM_BG),
+                Children::spawn((
+                    Spawn((
+                        Node {
+                            width: Val::Px(16.0),
+                            height: Val::Px(16.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        TreeItemToggle,
+                        children![(
+                            // Chevron: rotated node with L-shaped border.
+                            Node {
+                                width: Val::Px(6.0),
+                                height: Val::Px(6.0),
+                                border: UiRect {
+                                    bottom: Val::Px(2.0),
+                                    right: Val::Px(2.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            UiTransform::from_rotation(Rot2::radians(-FRAC_PI_4)),
+                            TreeItemChevron,
+                            ThemeBorderColor(tokens::TREE_ITEM_TOGGLE),
+                        )],
+                    )),
+                    label,
+                )),
+            )),
+            Spawn((
+                Node {
+                    display: Display::None,
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::left(Val::Px(16.0)),
+                    ..Default::default()
+                },
+                TreeItemChildren,
+                Children::spawn(items),
+            )),
+        )),
+    )
+}
+
+fn update_tree_item_styles(
+    q_items: Query<(Has<Checked>, Has<Expanded>, &Children), With<TreeItemStyle>>,
+    q_rows: Query<(Entity, &Hovered, &ThemeBackgroundColor), With<TreeItemRow>>,
+    mut q_containers: Query<(&mut Node, Option<&Children>), With<TreeItemChildren>>,
+    mut q_chevrons: Query<(&mut UiTransform, &mut Visibility), With<TreeItemChevron>>,
+    q_children: Query<&Children, Without<TreeItemStyle>>,
+    mut commands: Commands,
+) {
+    for (checked, expanded, children) in q_items.iter() {
+        let mut has_children = false;
+        for &child in children {
+            if let Ok((mut node, items)) = q_containers.get_mut(child) {
+                has_children = items.is_some_and(|items| !items.is_empty());
+                let display = match expanded {
+                    true => Display::Flex,
+                    false => Display::None,
+                };
+                if node.display != display {
+                    node.display = display;
+                }
+            }
+        }
+
+        for &child in children {
+            let Ok((row_ent, hovered, bg_color)) = q_rows.get(child) else {
+                continue;
+            };
+            let bg_token = match (checked, hovered.0) {
+                (true, _) => tokens::TREE_ITEM_BG_SELECTED,
+                (false, true) => tokens::TREE_ITEM_BG_HOVER,
+                (false, false) => tokens::TREE_ITEM_BG,
+            };
+            if bg_color.0 != bg_token {
+                commands
+                    .entity(row_ent)
+                    .insert(ThemeBackgroundColor(bg_token));
+            }
+
+            for descendant in q_children.iter_descendants(row_ent) {
+                if let Ok((mut transform, mut visibility)) = q_chevrons.get_mut(descendant) {
+                    let rotation = match expanded {
+                        true => Rot2::FRAC_PI_4,
+                        false => Rot2::radians(-FRAC_PI_4),
+                    };
+                    if transform.rotation != rotation {
+                        transform.rotation = rotation;
+                    }
+                    let chevron_visibility = match has_children {
+                        true => Visibility::Inherited,
+                        false => Visibility::Hidden,
+                    };
+                    if *visibility != chevron_visibility {
+                        *visibility = chevron_visibility;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Plugin which registers the systems for updating the tree view styles.
+pub struct TreeViewPlugin;
+
+impl Plugin for TreeViewPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(
+            PreUpdate,
+            update_tree_item_styles.in_set(PickingSystems::Last),
+        );
+    }
+}
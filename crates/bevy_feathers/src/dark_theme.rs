@@ -96,6 +96,41 @@ pub fn create_dark_theme() -> ThemeProps {
                 palette::LIGHT_GRAY_2.with_alpha(0.3),
             ),
             (tokens::COLOR_PLANE_BG, palette::GRAY_1),
+            // Menu
+            (tokens::MENU_BG, palette::GRAY_1),
+            (tokens::MENU_BORDER, palette::GRAY_3),
+            (tokens::MENU_ITEM_BG, palette::GRAY_1),
+            (tokens::MENU_ITEM_BG_HOVER, palette::GRAY_3),
+            (tokens::MENU_ITEM_BG_SELECTED, palette::ACCENT),
+            (tokens::MENU_ITEM_TEXT, palette::WHITE),
+            // Tabs
+            (tokens::TAB_BAR_BG, palette::GRAY_1),
+            (tokens::TAB_BG, palette::GRAY_1),
+            (tokens::TAB_BG_HOVER, palette::GRAY_2),
+            (tokens::TAB_BG_ACTIVE, palette::GRAY_3),
+            (tokens::TAB_TEXT, palette::LIGHT_GRAY_1),
+            (
+                tokens::TAB_TEXT_DISABLED,
+                palette::LIGHT_GRAY_1.with_alpha(0.5),
+            ),
+            // Tree view
+            (tokens::TREE_ITEM_BG, palette::GRAY_0.with_alpha(0.0)),
+            (tokens::TREE_ITEM_BG_HOVER, palette::GRAY_2),
+            (tokens::TREE_ITEM_BG_SELECTED, palette::ACCENT),
+            (tokens::TREE_ITEM_TEXT, palette::LIGHT_GRAY_1),
+            (tokens::TREE_ITEM_TOGGLE, palette::LIGHT_GRAY_2),
+            // Number input
+            (tokens::NUMBER_INPUT_BG, palette::GRAY_1),
+            (tokens::NUMBER_INPUT_BG_HOVER, palette::GRAY_2),
+            (
+                tokens::NUMBER_INPUT_BG_DISABLED,
+                palette::GRAY_1.with_alpha(0.5),
+            ),
+            (tokens::NUMBER_INPUT_TEXT, palette::WHITE),
+            (
+                tokens::NUMBER_INPUT_TEXT_DISABLED,
+                palette::WHITE.with_alpha(0.5),
+            ),
         ]),
     }
 }
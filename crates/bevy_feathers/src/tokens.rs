@@ -142,3 +142,64 @@ pub const SWITCH_SLIDE_DISABLED: ThemeToken =
 
 /// Color plane frame background
 pub const COLOR_PLANE_BG: ThemeToken = ThemeToken::new_static("feathers.colorplane.bg");
+
+// Menus and select boxes
+
+/// Background for popup menus, such as the options of a select box
+pub const MENU_BG: ThemeToken = ThemeToken::new_static("feathers.menu.bg");
+/// Border for popup menus
+pub const MENU_BORDER: ThemeToken = ThemeToken::new_static("feathers.menu.border");
+/// Background for menu items
+pub const MENU_ITEM_BG: ThemeToken = ThemeToken::new_static("feathers.menu.item.bg");
+/// Background for menu items (hovered)
+pub const MENU_ITEM_BG_HOVER: ThemeToken = ThemeToken::new_static("feathers.menu.item.bg.hover");
+/// Background for menu items (selected)
+pub const MENU_ITEM_BG_SELECTED: ThemeToken =
+    ThemeToken::new_static("feathers.menu.item.bg.selected");
+/// Text for menu items
+pub const MENU_ITEM_TEXT: ThemeToken = ThemeToken::new_static("feathers.menu.item.text");
+
+// Tabs
+
+/// Background for the tab bar
+pub const TAB_BAR_BG: ThemeToken = ThemeToken::new_static("feathers.tabbar.bg");
+/// Background for tabs
+pub const TAB_BG: ThemeToken = ThemeToken::new_static("feathers.tab.bg");
+/// Background for tabs (hovered)
+pub const TAB_BG_HOVER: ThemeToken = ThemeToken::new_static("feathers.tab.bg.hover");
+/// Background for tabs (active)
+pub const TAB_BG_ACTIVE: ThemeToken = ThemeToken::new_static("feathers.tab.bg.active");
+/// Text for tabs
+pub const TAB_TEXT: ThemeToken = ThemeToken::new_static("feathers.tab.text");
+/// Text for tabs (disabled)
+pub const TAB_TEXT_DISABLED: ThemeToken = ThemeToken::new_static("feathers.tab.text.disabled");
+
+// Tree view
+
+/// Background for tree item rows
+pub const TREE_ITEM_BG: ThemeToken = ThemeToken::new_static("feathers.tree.item.bg");
+/// Background for tree item rows (hovered)
+pub const TREE_ITEM_BG_HOVER: ThemeToken = ThemeToken::new_static("feathers.tree.item.bg.hover");
+/// Background for tree item rows (selected)
+pub const TREE_ITEM_BG_SELECTED: ThemeToken =
+    ThemeToken::new_static("feathers.tree.item.bg.selected");
+/// Text for tree items
+pub const TREE_ITEM_TEXT: ThemeToken = ThemeToken::new_static("feathers.tree.item.text");
+/// Disclosure triangle for tree items
+pub const TREE_ITEM_TOGGLE: ThemeToken = ThemeToken::new_static("feathers.tree.item.toggle");
+
+// Number input
+
+/// Background for number inputs
+pub const NUMBER_INPUT_BG: ThemeToken = ThemeToken::new_static("feathers.numberinput.bg");
+/// Background for number inputs (hovered)
+pub const NUMBER_INPUT_BG_HOVER: ThemeToken =
+    ThemeToken::new_static("feathers.numberinput.bg.hover");
+/// Background for number inputs (disabled)
+pub const NUMBER_INPUT_BG_DISABLED: ThemeToken =
+    ThemeToken::new_static("feathers.numberinput.bg.disabled");
+/// Text for number inputs
+pub const NUMBER_INPUT_TEXT: ThemeToken = ThemeToken::new_static("feathers.numberinput.text");
+/// Text for number inputs (disabled)
+pub const NUMBER_INPUT_TEXT_DISABLED: ThemeToken =
+    ThemeToken::new_static("feathers.numberinput.text.disabled");
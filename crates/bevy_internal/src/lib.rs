@@ -56,6 +56,8 @@ pub use bevy_input as input;
 pub use bevy_input_focus as input_focus;
 #[cfg(feature = "bevy_light")]
 pub use bevy_light as light;
+#[cfg(feature = "bevy_localization")]
+pub use bevy_localization as localization;
 #[cfg(feature = "bevy_log")]
 pub use bevy_log as log;
 #[cfg(feature = "bevy_material")]
@@ -79,6 +79,10 @@ pub use crate::sprite_render::prelude::*;
 #[cfg(feature = "bevy_text")]
 pub use crate::text::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_localization")]
+pub use crate::localization::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_ui")]
 pub use crate::ui::prelude::*;
@@ -0,0 +1,99 @@
+use alloc::sync::Arc;
+use std::path::Path;
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
+use bevy_reflect::TypePath;
+use fluent_bundle::FluentResource;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use unic_langid::LanguageIdentifier;
+
+/// A Fluent (`.ftl`) message file, containing the translations of messages for one locale.
+///
+/// Fluent files are registered with [`Localization::add`](crate::Localization::add) to make their
+/// messages available for formatting. When the file is modified and hot reloading is enabled,
+/// the translations update automatically.
+#[derive(Asset, TypePath, Debug)]
+pub struct FluentAsset {
+    /// The locale of the messages in this file.
+    pub locale: LanguageIdentifier,
+    /// The parsed messages.
+    pub resource: Arc<FluentResource>,
+}
+
+/// Settings for the [`FluentLoader`].
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct FluentLoaderSettings {
+    /// The locale of the messages in the file, such as `"en-US"`.
+    ///
+    /// If `None`, the locale is taken from the name of the directory containing the file, such as
+    /// `en-US` for `locales/en-US/menu.ftl`.
+    pub locale: Option<String>,
+}
+
+/// An [`AssetLoader`] for [`FluentAsset`]s.
+#[derive(Default, TypePath)]
+pub struct FluentLoader;
+
+/// Possible errors that can be produced by [`FluentLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum FluentLoaderError {
+    /// An [IO](std::io) Error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The file is not valid UTF-8.
+    #[error(transparent)]
+    Utf8(#[from] alloc::string::FromUtf8Error),
+    /// The file contains Fluent syntax errors.
+    #[error("invalid Fluent syntax: {0:?}")]
+    Syntax(Vec<fluent_bundle::FluentError>),
+    /// The locale could not be determined from the settings or the path of the file.
+    #[error("could not determine the locale of `{0}`")]
+    UnknownLocale(String),
+}
+
+impl AssetLoader for FluentLoader {
+    type Asset = FluentAsset;
+    type Settings = FluentLoaderSettings;
+    type Error = FluentLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &FluentLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<FluentAsset, Self::Error> {
+        let path = load_context.path().path();
+        let locale = match &settings.locale {
+            Some(locale) => locale.parse().ok(),
+            None => locale_from_path(path),
+        }
+        .ok_or_else(|| FluentLoaderError::UnknownLocale(path.display().to_string()))?;
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let source = String::from_utf8(bytes)?;
+        let resource = FluentResource::try_new(source).map_err(|(_, errors)| {
+            FluentLoaderError::Syntax(
+                errors
+                    .into_iter()
+                    .map(fluent_bundle::FluentError::ParserError)
+                    .collect(),
+            )
+        })?;
+
+        Ok(FluentAsset {
+            locale,
+            resource: Arc::new(resource),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+fn locale_from_path(path: &Path) -> Option<LanguageIdentifier> {
+    path.parent()?.file_name()?.to_str()?.parse().ok()
+}
@@ -0,0 +1,187 @@
+use alloc::borrow::Cow;
+use core::convert::Infallible;
+
+use fluent_bundle::{types::FluentNumber, FluentArgs, FluentValue};
+use intl_memoizer::{concurrent::IntlLangMemoizer, Memoizable};
+use unic_langid::{subtags::Region, LanguageIdentifier};
+
+/// The symbols used to format numbers in a locale.
+struct NumberSymbols {
+    decimal: char,
+    group: char,
+    /// The size of the group of digits closest to the decimal separator, and of the others.
+    grouping: (usize, usize),
+    /// The minimum number of digits in the leftmost group for the integer digits to be grouped.
+    min_grouping: usize,
+}
+
+impl Memoizable for NumberSymbols {
+    type Args = ();
+    type Error = Infallible;
+
+    fn construct(locale: LanguageIdentifier, _: ()) -> Result<Self, Infallible> {
+        let region = locale.region.as_ref().map(Region::as_str);
+        let (decimal, group) = match (locale.language.as_str(), region) {
+            ("de" | "it", Some("CH" | "LI")) => ('.', '’'),
+            ("es", Some("MX" | "US" | "419")) => ('.', ','),
+            ("pt", Some("PT"))
+            | (
+                "bg" | "cs" | "et" | "fi" | "fr" | "hu" | "lt" | "lv" | "nb" | "no" | "pl" | "ru"
+                | "sk" | "sv" | "uk",
+                _,
+            ) => (',', '\u{a0}'),
+            (
+                "da" | "de" | "el" | "es" | "hr" | "id" | "it" | "nl" | "pt" | "ro" | "sl" | "sr"
+                | "tr" | "vi",
+                _,
+            ) => (',', '.'),
+            _ => ('.', ','),
+        };
+        let grouping = match (locale.language.as_str(), region) {
+            ("bn" | "hi" | "mr" | "ta" | "te", _) | ("en", Some("IN")) => (3, 2),
+            _ => (3, 3),
+        };
+        let min_grouping = match (locale.language.as_str(), region) {
+            ("es", Some("MX" | "US" | "419")) => 1,
+            ("es" | "pl", _) | ("pt", Some("PT")) => 2,
+            _ => 1,
+        };
+        Ok(Self {
+            decimal,
+            group,
+            grouping,
+            min_grouping,
+        })
+    }
+}
+
+impl NumberSymbols {
+    fn format(&self, number: &FluentNumber) -> String {
+        let options = &number.options;
+        if !number.value.is_finite() {
+            return number.as_string().into_owned();
+        }
+
+        let min_fraction_digits = options.minimum_fraction_digits.unwrap_or(0);
+        let digits = match options.maximum_fraction_digits {
+            Some(max_fraction_digits) => {
+                let max_fraction_digits = max_fraction_digits.max(min_fraction_digits);
+                format!("{:.*}", max_fraction_digits, number.value.abs())
+            }
+            None => number.value.abs().to_string(),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        let mut fraction = fraction.trim_end_matches('0').to_owned();
+        let padding = min_fraction_digits.saturating_sub(fraction.len());
+        fraction.extend(core::iter::repeat_n('0', padding));
+        let padding = options
+            .minimum_integer_digits
+            .unwrap_or(1)
+            .saturating_sub(integer.len());
+        let integer = "0".repeat(padding) + integer;
+        // Rounding can turn a negative number into zero.
+        let negative = number.value < 0.0
+            && integer
+                .chars()
+                .chain(fraction.chars())
+                .any(|digit| digit != '0');
+
+        let mut formatted = String::with_capacity(integer.len() * 2 + fraction.len() + 2);
+        if negative {
+            formatted.push('-');
+        }
+        let (primary, secondary) = self.grouping;
+        if options.use_grouping && integer.len() >= primary + self.min_grouping {
+            let (head, tail) = integer.split_at(integer.len() - primary);
+            let first = head.len() % secondary;
+            if first > 0 {
+                formatted.push_str(&head[..first]);
+            }
+            for (i, group) in head.as_bytes()[first..].chunks(secondary).enumerate() {
+                if i > 0 || first > 0 {
+                    formatted.push(self.group);
+                }
+                formatted.extend(group.iter().map(|&digit| char::from(digit)));
+            }
+            formatted.push(self.group);
+            formatted.push_str(tail);
+        } else {
+            formatted.push_str(&integer);
+        }
+        if !fraction.is_empty() {
+            formatted.push(self.decimal);
+            formatted.push_str(&fraction);
+        }
+        formatted
+    }
+}
+
+/// Formats numbers with the decimal and group separators of the locale of the bundle, see
+/// [`Localization`](crate::Localization).
+pub(crate) fn format_number(value: &FluentValue, intls: &IntlLangMemoizer) -> Option<String> {
+    let FluentValue::Number(number) = value else {
+        return None;
+    };
+    intls
+        .with_try_get::<NumberSymbols, _, _>((), |symbols| symbols.format(number))
+        .ok()
+}
+
+/// The `DATETIME` function, see [`Localization`](crate::Localization).
+pub(crate) fn datetime<'a>(
+    positional: &[FluentValue<'a>],
+    named: &FluentArgs,
+    utc_offset: i32,
+) -> FluentValue<'a> {
+    let Some(FluentValue::Number(seconds)) = positional.first() else {
+        return FluentValue::Error;
+    };
+    let pattern = match named.get("pattern") {
+        Some(FluentValue::String(pattern)) => pattern.as_ref(),
+        _ => "%Y-%m-%d",
+    };
+
+    let seconds = seconds.value.floor() as i64 + i64::from(utc_offset);
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time_of_day = seconds.rem_euclid(86_400);
+    let (hour, minute, second) = (time_of_day / 3600, time_of_day / 60 % 60, time_of_day % 60);
+
+    let mut formatted = String::with_capacity(pattern.len() + 8);
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            formatted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => formatted.push_str(&year.to_string()),
+            Some('m') => formatted.push_str(&format!("{month:02}")),
+            Some('d') => formatted.push_str(&format!("{day:02}")),
+            Some('H') => formatted.push_str(&format!("{hour:02}")),
+            Some('M') => formatted.push_str(&format!("{minute:02}")),
+            Some('S') => formatted.push_str(&format!("{second:02}")),
+            Some('%') | None => formatted.push('%'),
+            Some(other) => {
+                formatted.push('%');
+                formatted.push(other);
+            }
+        }
+    }
+    FluentValue::String(Cow::Owned(formatted))
+}
+
+/// Converts a number of days since 1970-01-01 to a (year, month, day) date in the proleptic
+/// Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
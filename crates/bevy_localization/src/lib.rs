@@ -0,0 +1,121 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevy.org/assets/icon.png",
+    html_favicon_url = "https://bevy.org/assets/icon.png"
+)]
+
+//! Localization of text using [Fluent](https://projectfluent.org/) message files.
+//!
+//! Translations are written in `.ftl` files, one or more per locale, which are loaded as
+//! [`FluentAsset`]s and registered with the [`Localization`] resource. Text entities with a
+//! [`Localized`] component display the message with the given id, translated into the current
+//! [`Locale`]:
+//!
+//! ```ftl
+//! # assets/locales/en-US/main.ftl
+//! greeting = Hello, { $name }!
+//! apples = { $count ->
+//!     [one] One apple
+//!    *[other] { $count } apples
+//! }
+//! ```
+//!
+//! ```
+//! # use bevy_asset::AssetServer;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_localization::{Localization, Localized};
+//! # use bevy_text::TextSpan;
+//! fn setup(
+//!     mut commands: Commands,
+//!     asset_server: Res<AssetServer>,
+//!     mut localization: ResMut<Localization>,
+//! ) {
+//!     localization.add(asset_server.load("locales/en-US/main.ftl"));
+//!     localization.add(asset_server.load("locales/fr/main.ftl"));
+//!
+//!     commands.spawn((
+//!         TextSpan::default(),
+//!         Localized::new("greeting").with_arg("name", "Ferris"),
+//!     ));
+//! }
+//! ```
+//!
+//! Changing the [`Locale`] resource, the arguments of a [`Localized`] component, or the `.ftl`
+//! files (with hot reloading enabled) updates the text.
+//!
+//! `Text` and `Text2d` entities are localized when the `bevy_ui` and `bevy_sprite` features are
+//! enabled respectively. Other text components implementing [`TextSpanAccess`] can be localized
+//! by adding the [`update_localized_text`] system for them.
+//!
+//! [`TextSpanAccess`]: bevy_text::TextSpanAccess
+
+extern crate alloc;
+
+mod asset;
+mod format;
+mod locale;
+mod localization;
+mod localized;
+
+pub use asset::*;
+pub use locale::*;
+pub use localization::*;
+pub use localized::*;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+pub use unic_langid::{langid, LanguageIdentifier};
+
+/// The localization prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{LanguageIdentifier, Locale, Localization, LocalizationPlugin, Localized};
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
+use bevy_ecs::prelude::*;
+
+/// System set in [`PostUpdate`] where [`Localized`] text is updated, before the text is laid out.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub struct LocalizationSystems;
+
+/// Adds support for localizing text with Fluent message files.
+#[derive(Default)]
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<FluentAsset>()
+            .init_asset_loader::<FluentLoader>()
+            .init_resource::<Locale>()
+            .init_resource::<Localization>()
+            .configure_sets(
+                PostUpdate,
+                LocalizationSystems.before(bevy_text::Text2dUpdateSystems),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_bundles,
+                    (
+                        update_localized_text::<bevy_text::TextSpan>,
+                        #[cfg(feature = "bevy_ui")]
+                        update_localized_text::<bevy_ui::widget::Text>,
+                        #[cfg(feature = "bevy_sprite")]
+                        update_localized_text::<bevy_sprite::Text2d>,
+                    ),
+                )
+                    .chain()
+                    .in_set(LocalizationSystems),
+            );
+
+        #[cfg(feature = "bevy_ui")]
+        app.configure_sets(
+            PostUpdate,
+            LocalizationSystems.before(bevy_ui::UiSystems::Prepare),
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use bevy_ecs::resource::Resource;
+use unic_langid::LanguageIdentifier;
+
+/// The locale that text is localized into.
+///
+/// Messages that aren't translated for the [`requested`](Self::requested) locale are looked up in
+/// the locales of the [`fallback_chain`](Self::fallback_chain), which starts with less specific
+/// versions of the requested locale (`pt` for `pt-BR`), followed by the
+/// [`fallbacks`](Self::fallbacks).
+///
+/// Changing this resource re-renders all [`Localized`](crate::Localized) text.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Locale {
+    /// The locale requested by the user.
+    pub requested: LanguageIdentifier,
+    /// The locales to use, in order, for messages that aren't available in the requested locale.
+    /// Usually this is the language the game is written in.
+    pub fallbacks: Vec<LanguageIdentifier>,
+}
+
+impl Locale {
+    /// Creates a new [`Locale`] without fallbacks.
+    pub fn new(requested: LanguageIdentifier) -> Self {
+        Self {
+            requested,
+            fallbacks: Vec::new(),
+        }
+    }
+
+    /// Returns this [`Locale`] with the given fallback locales.
+    pub fn with_fallbacks(
+        mut self,
+        fallbacks: impl IntoIterator<Item = LanguageIdentifier>,
+    ) -> Self {
+        self.fallbacks = fallbacks.into_iter().collect();
+        self
+    }
+
+    /// Returns the locales in which messages are looked up, in order.
+    ///
+    /// Each locale is followed by its less specific versions, without variants, region and
+    /// script, and duplicates are removed. For example, `sr-Latn-RS` with the fallback `en-US`
+    /// results in `sr-Latn-RS`, `sr-Latn`, `sr`, `en-US`, `en`.
+    pub fn fallback_chain(&self) -> Vec<LanguageIdentifier> {
+        let mut chain: Vec<LanguageIdentifier> = Vec::new();
+        for locale in core::iter::once(&self.requested).chain(&self.fallbacks) {
+            let mut locale = locale.clone();
+            let mut push = |locale: &LanguageIdentifier| {
+                if !chain.contains(locale) {
+                    chain.push(locale.clone());
+                }
+            };
+            push(&locale);
+            locale.clear_variants();
+            push(&locale);
+            locale.region = None;
+            push(&locale);
+            locale.script = None;
+            push(&locale);
+        }
+        chain
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::new(unic_langid::langid!("en-US"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unic_langid::langid;
+
+    #[test]
+    fn fallback_chain() {
+        let locale =
+            Locale::new(langid!("sr-Latn-RS")).with_fallbacks([langid!("en-US"), langid!("en")]);
+        assert_eq!(
+            locale.fallback_chain(),
+            vec![
+                langid!("sr-Latn-RS"),
+                langid!("sr-Latn"),
+                langid!("sr"),
+                langid!("en-US"),
+                langid!("en"),
+            ]
+        );
+    }
+}
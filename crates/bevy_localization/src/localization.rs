@@ -0,0 +1,382 @@
+use alloc::sync::Arc;
+
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::{
+    message::MessageReader,
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_log::warn;
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use thiserror::Error;
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    format::{datetime, format_number},
+    FluentAsset, Locale,
+};
+
+/// A function that can be called from Fluent messages, see [`Localization::add_function`].
+pub type LocalizationFunction =
+    Arc<dyn for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync>;
+
+/// Errors that can occur when formatting a message with [`Localization::format`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum LocalizationError {
+    /// None of the locales in the [fallback chain](Locale::fallback_chain) have a translation for
+    /// the message.
+    #[error("message `{0}` not found")]
+    MessageNotFound(String),
+    /// The message exists, but has no value. This happens for messages which only have
+    /// attributes, which are formatted with ids of the form `message.attribute`.
+    #[error("message `{0}` has no value")]
+    NoValue(String),
+}
+
+/// The translated messages used to localize text, read from the registered [`FluentAsset`]s.
+///
+/// Fluent files are registered with [`Localization::add`], typically at startup:
+///
+/// ```
+/// # use bevy_asset::AssetServer;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_localization::Localization;
+/// fn setup(asset_server: Res<AssetServer>, mut localization: ResMut<Localization>) {
+///     for locale in ["en-US", "de", "ja"] {
+///         localization.add(asset_server.load(format!("locales/{locale}/main.ftl")));
+///     }
+/// }
+/// ```
+///
+/// All messages of the same locale are combined into one Fluent bundle; when a message is defined
+/// in multiple files, the file registered last takes precedence. Bundles are rebuilt when the
+/// registered files are loaded or change, for example through hot reloading.
+///
+/// The bundles support Fluent's plural rules, as well as the `NUMBER` and `DATETIME` functions.
+///
+/// Numbers are formatted with the decimal and group separators of the locale of the bundle, such
+/// as `1,234.5` in English and `1.234,5` in German. The separators are those of the most common
+/// conventions of each language, with a few regional variants, and `.` and `,` for languages that
+/// aren't known. `NUMBER` supports the `useGrouping`, `minimumIntegerDigits`,
+/// `minimumFractionDigits` and `maximumFractionDigits` options. Numbers that aren't quantities,
+/// like years, should be formatted without grouping:
+///
+/// ```ftl
+/// score = Score: { NUMBER($score, maximumFractionDigits: 1) }
+/// copyright = © { NUMBER($year, useGrouping: "false") }
+/// ```
+///
+/// `DATETIME` formats a number of seconds since the Unix epoch, using the `pattern` option, which
+/// defaults to `"%Y-%m-%d"`. Times are in UTC, shifted by the offset set with
+/// [`Localization::set_utc_offset`]. The pattern supports `%Y`, `%m`, `%d`, `%H`, `%M`, `%S` and
+/// `%%`, so translators can choose the date format of their locale:
+///
+/// ```ftl
+/// saved-at = Saved on { DATETIME($time, pattern: "%d.%m.%Y %H:%M") }
+/// ```
+///
+/// Unicode directional isolation marks around placeables are disabled, since most game fonts
+/// don't contain glyphs for them.
+#[derive(Resource, Default)]
+pub struct Localization {
+    files: Vec<Handle<FluentAsset>>,
+    functions: Vec<(String, LocalizationFunction)>,
+    utc_offset: i32,
+    bundles: Vec<FluentBundle<Arc<FluentResource>>>,
+    needs_rebuild: bool,
+}
+
+impl Localization {
+    /// Registers a Fluent file, making its messages available once it's loaded.
+    pub fn add(&mut self, file: Handle<FluentAsset>) {
+        self.files.push(file);
+        self.needs_rebuild = true;
+    }
+
+    /// Unregisters a Fluent file.
+    pub fn remove(&mut self, file: impl Into<AssetId<FluentAsset>>) {
+        let id = file.into();
+        self.files.retain(|handle| handle.id() != id);
+        self.needs_rebuild = true;
+    }
+
+    /// Returns the registered Fluent files.
+    pub fn files(&self) -> &[Handle<FluentAsset>] {
+        &self.files
+    }
+
+    /// Returns the locales for which messages have been loaded.
+    pub fn locales(&self) -> impl Iterator<Item = &LanguageIdentifier> {
+        self.bundles
+            .iter()
+            .filter_map(|bundle| bundle.locales.first())
+    }
+
+    /// Makes a function with the given name available to all messages, such as
+    /// `{ SHOUT($name) }`. Functions added this way take precedence over the built-in `NUMBER`
+    /// and `DATETIME` functions.
+    pub fn add_function<F>(&mut self, name: impl Into<String>, function: F)
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync + 'static,
+    {
+        self.functions.push((name.into(), Arc::new(function)));
+        self.needs_rebuild = true;
+    }
+
+    /// Sets the offset from UTC, in seconds, of the times formatted by `DATETIME`, such as the
+    /// offset of the time zone of the player. Defaults to `0`.
+    pub fn set_utc_offset(&mut self, seconds: i32) {
+        self.utc_offset = seconds;
+        self.needs_rebuild = true;
+    }
+
+    /// Returns the offset from UTC, in seconds, of the times formatted by `DATETIME`.
+    pub fn utc_offset(&self) -> i32 {
+        self.utc_offset
+    }
+
+    /// Returns `true` if messages have been loaded for at least one locale of the
+    /// [fallback chain](Locale::fallback_chain).
+    pub fn is_ready(&self, locale: &Locale) -> bool {
+        locale
+            .fallback_chain()
+            .iter()
+            .any(|locale| self.bundle(locale).is_some())
+    }
+
+    /// Formats the message with the given id in the first locale of the
+    /// [fallback chain](Locale::fallback_chain) that has a translation for it.
+    ///
+    /// Attributes of messages are formatted with ids of the form `message.attribute`.
+    pub fn format(
+        &self,
+        locale: &Locale,
+        id: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<String, LocalizationError> {
+        let (message_id, attribute) = match id.split_once('.') {
+            Some((message_id, attribute)) => (message_id, Some(attribute)),
+            None => (id, None),
+        };
+
+        for locale in locale.fallback_chain() {
+            let Some(bundle) = self.bundle(&locale) else {
+                continue;
+            };
+            let Some(message) = bundle.get_message(message_id) else {
+                continue;
+            };
+            let pattern = match attribute {
+                Some(attribute) => message.get_attribute(attribute).map(|attr| attr.value()),
+                None => message.value(),
+            };
+            let Some(pattern) = pattern else {
+                return Err(LocalizationError::NoValue(id.to_owned()));
+            };
+
+            let mut errors = Vec::new();
+            let value = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                warn!("Errors formatting message `{id}` for locale `{locale}`: {errors:?}");
+            }
+            return Ok(value.into_owned());
+        }
+
+        Err(LocalizationError::MessageNotFound(id.to_owned()))
+    }
+
+    fn bundle(&self, locale: &LanguageIdentifier) -> Option<&FluentBundle<Arc<FluentResource>>> {
+        self.bundles
+            .iter()
+            .find(|bundle| bundle.locales.first() == Some(locale))
+    }
+
+    fn rebuild(&mut self, assets: &Assets<FluentAsset>) {
+        self.needs_rebuild = false;
+        self.bundles.clear();
+
+        for file in &self.files {
+            let Some(asset) = assets.get(file) else {
+                continue;
+            };
+
+            let index = match self
+                .bundles
+                .iter()
+                .position(|bundle| bundle.locales.first() == Some(&asset.locale))
+            {
+                Some(index) => index,
+                None => {
+                    self.bundles.push(new_bundle(
+                        asset.locale.clone(),
+                        &self.functions,
+                        self.utc_offset,
+                    ));
+                    self.bundles.len() - 1
+                }
+            };
+            self.bundles[index].add_resource_overriding(asset.resource.clone());
+        }
+    }
+}
+
+fn new_bundle(
+    locale: LanguageIdentifier,
+    functions: &[(String, LocalizationFunction)],
+    utc_offset: i32,
+) -> FluentBundle<Arc<FluentResource>> {
+    let mut bundle = FluentBundle::new_concurrent(vec![locale]);
+    bundle.set_use_isolating(false);
+    bundle.set_formatter(Some(format_number));
+
+    // Later functions with the same name replace earlier ones.
+    for (name, function) in functions.iter().rev() {
+        let function = function.clone();
+        let _ = bundle.add_function(name, move |positional, named| function(positional, named));
+    }
+    // These fail if the app added a function with the same name, which is intended.
+    let _ = bundle.add_function("NUMBER", fluent_bundle::builtins::NUMBER);
+    let _ = bundle.add_function("DATETIME", move |positional, named| {
+        datetime(positional, named, utc_offset)
+    });
+    bundle
+}
+
+/// Rebuilds the bundles of the [`Localization`] when its files are loaded, modified or removed.
+pub(crate) fn update_bundles(
+    mut events: MessageReader<AssetEvent<FluentAsset>>,
+    assets: Res<Assets<FluentAsset>>,
+    mut localization: ResMut<Localization>,
+) {
+    let files_changed = events.read().any(|event| match event {
+        AssetEvent::Added { id } | AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
+            localization.files.iter().any(|file| file.id() == *id)
+        }
+        AssetEvent::Unused { .. } | AssetEvent::LoadedWithDependencies { .. } => false,
+    });
+    if files_changed || localization.needs_rebuild {
+        localization.rebuild(&assets);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unic_langid::langid;
+
+    fn localization(files: &[(LanguageIdentifier, &str)]) -> Localization {
+        let mut assets = Assets::<FluentAsset>::default();
+        let mut localization = Localization::default();
+        for (locale, source) in files {
+            localization.add(assets.add(FluentAsset {
+                locale: locale.clone(),
+                resource: Arc::new(FluentResource::try_new(source.to_string()).unwrap()),
+            }));
+        }
+        localization.rebuild(&assets);
+        localization
+    }
+
+    #[test]
+    fn format_with_fallback_and_plurals() {
+        let localization = localization(&[
+            (
+                langid!("en"),
+                "apples = { $count ->\n    [one] one apple\n   *[other] { $count } apples\n}\nquit = Quit",
+            ),
+            (langid!("de"), "apples = { $count } Äpfel"),
+        ]);
+        let locale = Locale::new(langid!("de-AT")).with_fallbacks([langid!("en")]);
+
+        let mut args = FluentArgs::new();
+        args.set("count", 3);
+        assert_eq!(
+            localization.format(&locale, "apples", Some(&args)),
+            Ok("3 Äpfel".to_owned())
+        );
+        assert_eq!(
+            localization.format(&locale, "quit", None),
+            Ok("Quit".to_owned())
+        );
+
+        args.set("count", 1);
+        assert_eq!(
+            localization.format(&Locale::new(langid!("en-US")), "apples", Some(&args)),
+            Ok("one apple".to_owned())
+        );
+        assert_eq!(
+            localization.format(&locale, "missing", None),
+            Err(LocalizationError::MessageNotFound("missing".to_owned()))
+        );
+    }
+
+    #[test]
+    fn format_dates() {
+        let source = "saved = { DATETIME($time) }\nsaved-at = { DATETIME($time, pattern: \"%d.%m.%Y %H:%M\") }";
+        let localization = localization(&[(langid!("de"), source)]);
+        let locale = Locale::new(langid!("de"));
+        let mut args = FluentArgs::new();
+        args.set("time", 1_709_210_096);
+        assert_eq!(
+            localization.format(&locale, "saved", Some(&args)),
+            Ok("2024-02-29".to_owned())
+        );
+        assert_eq!(
+            localization.format(&locale, "saved-at", Some(&args)),
+            Ok("29.02.2024 12:34".to_owned())
+        );
+
+        // Times are shifted by the offset of the time zone.
+        let mut assets = Assets::<FluentAsset>::default();
+        let mut localization = Localization::default();
+        localization.set_utc_offset(12 * 3600);
+        assert_eq!(localization.utc_offset(), 12 * 3600);
+        localization.add(assets.add(FluentAsset {
+            locale: langid!("de"),
+            resource: Arc::new(FluentResource::try_new(source.to_owned()).unwrap()),
+        }));
+        localization.rebuild(&assets);
+        assert_eq!(
+            localization.format(&locale, "saved-at", Some(&args)),
+            Ok("01.03.2024 00:34".to_owned())
+        );
+    }
+
+    #[test]
+    fn format_numbers() {
+        let source = "count = { $count }\n\
+            score = { NUMBER($score, minimumFractionDigits: 1, maximumFractionDigits: 2) }\n\
+            year = { NUMBER($year, useGrouping: \"false\") }\n\
+            level = { NUMBER($level, minimumIntegerDigits: 3) }";
+        let localization = localization(&[
+            (langid!("en"), source),
+            (langid!("de"), source),
+            (langid!("fr"), source),
+            (langid!("es"), source),
+            (langid!("hi"), source),
+        ]);
+        let format = |locale, id, arg: f64| {
+            let mut args = FluentArgs::new();
+            args.set(id, arg);
+            localization
+                .format(&Locale::new(locale), id, Some(&args))
+                .unwrap()
+        };
+
+        assert_eq!(format(langid!("en"), "count", 1_234_567.0), "1,234,567");
+        assert_eq!(format(langid!("de"), "count", 1_234_567.0), "1.234.567");
+        assert_eq!(
+            format(langid!("fr"), "count", 1_234_567.0),
+            "1\u{a0}234\u{a0}567"
+        );
+        assert_eq!(format(langid!("hi"), "count", 1_234_567.0), "12,34,567");
+        assert_eq!(format(langid!("es"), "count", 1234.0), "1234");
+        assert_eq!(format(langid!("es"), "count", 12_345.0), "12.345");
+        assert_eq!(format(langid!("en"), "count", -0.5), "-0.5");
+        assert_eq!(format(langid!("en"), "score", 1234.0), "1,234.0");
+        assert_eq!(format(langid!("de"), "score", 2.345_6), "2,35");
+        assert_eq!(format(langid!("de"), "score", -0.001), "0,0");
+        assert_eq!(format(langid!("en"), "year", 2024.0), "2024");
+        assert_eq!(format(langid!("en"), "level", 7.0), "007");
+    }
+}
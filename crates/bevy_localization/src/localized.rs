@@ -0,0 +1,264 @@
+use bevy_ecs::{
+    change_detection::{DetectChanges, Ref},
+    component::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_log::warn;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_text::TextSpanAccess;
+use fluent_bundle::{FluentArgs, FluentValue};
+
+use crate::{Locale, Localization};
+
+/// Sets the text of a `Text`, `Text2d` or [`TextSpan`](bevy_text::TextSpan) entity to a localized
+/// message.
+///
+/// The text is updated when this component, the [`Locale`] or the [`Localization`] changes, for
+/// example because the translations were hot reloaded. If the message isn't found in any locale,
+/// the text is set to the message id.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_localization::Localized;
+/// # use bevy_text::TextSpan;
+/// # let mut world = World::new();
+/// world.spawn((
+///     TextSpan::default(),
+///     Localized::new("apples").with_arg("count", 3),
+/// ));
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct Localized {
+    /// The id of the message, or `message.attribute` for an attribute of a message.
+    pub id: String,
+    /// The arguments of the message.
+    pub args: Vec<(String, LocalizedArg)>,
+}
+
+impl Localized {
+    /// Creates a new [`Localized`] for the message with the given id, without arguments.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Returns this [`Localized`] with the given argument set.
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<LocalizedArg>) -> Self {
+        self.set_arg(name, value);
+        self
+    }
+
+    /// Sets the value of an argument, replacing its previous value.
+    pub fn set_arg(&mut self, name: impl Into<String>, value: impl Into<LocalizedArg>) {
+        let name = name.into();
+        let value = value.into();
+        match self.args.iter_mut().find(|(arg, _)| *arg == name) {
+            Some((_, arg)) => *arg = value,
+            None => self.args.push((name, value)),
+        }
+    }
+
+    /// Returns the arguments as [`FluentArgs`].
+    pub fn fluent_args(&self) -> FluentArgs<'_> {
+        let mut args = FluentArgs::with_capacity(self.args.len());
+        for (name, value) in &self.args {
+            args.set(name.as_str(), FluentValue::from(value));
+        }
+        args
+    }
+}
+
+/// The value of an argument of a [`Localized`] message.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq)]
+pub enum LocalizedArg {
+    /// A string.
+    String(String),
+    /// A number, which selects the plural category in select expressions, and can be formatted
+    /// with `NUMBER` or `DATETIME`.
+    Number(f64),
+}
+
+impl<'a> From<&'a LocalizedArg> for FluentValue<'a> {
+    fn from(value: &'a LocalizedArg) -> Self {
+        match value {
+            LocalizedArg::String(string) => FluentValue::from(string.as_str()),
+            LocalizedArg::Number(number) => FluentValue::from(*number),
+        }
+    }
+}
+
+impl From<String> for LocalizedArg {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for LocalizedArg {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for LocalizedArg {
+                fn from(value: $ty) -> Self {
+                    Self::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+/// Updates the text of entities with a [`Localized`] component and a text component `T`.
+pub fn update_localized_text<T: TextSpanAccess>(
+    locale: Res<Locale>,
+    localization: Res<Localization>,
+    mut query: Query<(Ref<Localized>, &mut T)>,
+) {
+    let update_all = locale.is_changed() || localization.is_changed();
+    if !localization.is_ready(&locale) {
+        return;
+    }
+
+    for (localized, mut text) in &mut query {
+        if !update_all && !localized.is_changed() {
+            continue;
+        }
+
+        let value =
+            match localization.format(&locale, &localized.id, Some(&localized.fluent_args())) {
+                Ok(value) => value,
+                Err(error) => {
+                    warn!("Failed to localize text: {error}");
+                    localized.id.clone()
+                }
+            };
+        if text.read_span() != value {
+            *text.write_span() = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_asset::{AssetPlugin, Assets, Handle};
+    use bevy_ecs::entity::Entity;
+    use bevy_text::TextSpan;
+    use fluent_bundle::FluentResource;
+    use unic_langid::{langid, LanguageIdentifier};
+
+    use super::*;
+    use crate::{FluentAsset, LocalizationPlugin};
+
+    fn fluent_asset(locale: LanguageIdentifier, source: &str) -> FluentAsset {
+        FluentAsset {
+            locale,
+            resource: Arc::new(FluentResource::try_new(source.to_owned()).unwrap()),
+        }
+    }
+
+    fn add_file(app: &mut App, asset: FluentAsset) -> Handle<FluentAsset> {
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<FluentAsset>>()
+            .add(asset);
+        app.world_mut()
+            .resource_mut::<Localization>()
+            .add(handle.clone());
+        handle
+    }
+
+    fn span(app: &App, entity: Entity) -> &str {
+        &app.world().get::<TextSpan>(entity).unwrap().0
+    }
+
+    #[test]
+    fn localized_text_updates() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            LocalizationPlugin,
+        ))
+        .insert_resource(Locale::new(langid!("fr")));
+        let en = add_file(
+            &mut app,
+            fluent_asset(
+                langid!("en"),
+                "apples = { $count ->\n    [one] One apple\n   *[other] { $count } apples\n}",
+            ),
+        );
+        let entity = app
+            .world_mut()
+            .spawn((
+                TextSpan::default(),
+                Localized::new("apples").with_arg("count", 1),
+            ))
+            .id();
+        #[cfg(feature = "bevy_ui")]
+        let ui_entity = app
+            .world_mut()
+            .spawn((
+                bevy_ui::widget::Text::default(),
+                Localized::new("apples").with_arg("count", 1),
+            ))
+            .id();
+
+        // Text isn't updated until messages are loaded for the locale.
+        app.update();
+        app.update();
+        assert_eq!(span(&app, entity), "");
+
+        // Changing the locale.
+        app.insert_resource(Locale::new(langid!("en")));
+        app.update();
+        assert_eq!(span(&app, entity), "One apple");
+        #[cfg(feature = "bevy_ui")]
+        assert_eq!(
+            app.world()
+                .get::<bevy_ui::widget::Text>(ui_entity)
+                .unwrap()
+                .0,
+            "One apple"
+        );
+
+        // Changing the arguments.
+        app.world_mut()
+            .get_mut::<Localized>(entity)
+            .unwrap()
+            .set_arg("count", 3);
+        app.update();
+        assert_eq!(span(&app, entity), "3 apples");
+
+        // Modifying the file, like hot reloading does.
+        *app.world_mut()
+            .resource_mut::<Assets<FluentAsset>>()
+            .get_mut(&en)
+            .unwrap() = fluent_asset(langid!("en"), "apples = { $count } red apples");
+        app.update();
+        app.update();
+        assert_eq!(span(&app, entity), "3 red apples");
+
+        // Loading the messages of the requested locale.
+        app.insert_resource(Locale::new(langid!("fr")));
+        add_file(
+            &mut app,
+            fluent_asset(langid!("fr"), "apples = { $count } pommes"),
+        );
+        app.update();
+        app.update();
+        assert_eq!(span(&app, entity), "3 pommes");
+    }
+}
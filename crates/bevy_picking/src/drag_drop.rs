@@ -0,0 +1,888 @@
+//! Typed drag and drop, across UI and world entities, and from the files of the OS.
+//!
+//! The drag events of [`events`](crate::events) only tell which entity is dragged. This module adds
+//! a protocol on top of them for drags that carry data:
+//! + A [`DragPayload`] on a dragged entity, or on one of its ancestors, is the data of the drag.
+//! + A [`DropTarget`] declares which types of payloads an entity accepts.
+//! + A [`DragPreview`] entity is spawned for each drag, and follows the pointer. Observe the
+//!   addition of [`DragPreview`] to give it a visual, like a `Node` or a `Sprite`.
+//! + [`Pointer<PayloadEnter>`] and [`Pointer<PayloadLeave>`] are sent to the drop targets
+//!   under the pointer, and [`Pointer<PayloadDrop>`] to the target the payload is dropped on, if
+//!   it accepts the payload.
+//! + [`Pointer<PayloadDragEnd>`] is sent to the source of the payload, with the [`DropOutcome`] of
+//!   the drag.
+//!
+//! Pressing [`KeyCode::Escape`] cancels every drag with a payload. Files dragged into a window
+//! from the OS are dragged by the mouse pointer, with a [`DraggedFiles`] payload from the window.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_picking::prelude::*;
+//! #[derive(Clone)]
+//! struct Item(&'static str);
+//!
+//! # let mut world = World::default();
+//! world.spawn(DragPayload::new(Item("sword")));
+//! world
+//!     .spawn(DropTarget::accepting::<Item>().accept::<DraggedFiles>())
+//!     .observe(|drop: On<Pointer<PayloadDrop>>| {
+//!         if let Some(Item(name)) = drop.payload.get::<Item>() {
+//!             println!("Dropped a {name}");
+//!         } else if let Some(DraggedFiles(paths)) = drop.payload.get::<DraggedFiles>() {
+//!             println!("Dropped {paths:?}");
+//!         }
+//!     });
+//! ```
+
+use alloc::sync::Arc;
+use core::{
+    any::{Any, TypeId},
+    fmt,
+};
+use std::path::PathBuf;
+
+use bevy_camera::NormalizedRenderTarget;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_math::{primitives::InfinitePlane3d, Vec2, Vec3};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::prelude::*;
+use bevy_transform::components::Transform;
+use bevy_window::{FileDragAndDrop, WindowRef};
+
+use crate::{
+    backend::{
+        ray::{RayId, RayMap},
+        HitData,
+    },
+    events::{DragEnd, DragStart, Pointer},
+    hover::HoverMap,
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput, PointerLocation},
+    Pickable,
+};
+
+/// The data carried by a drag, of any type.
+///
+/// Dragging an entity drags the payload of the entity, or of its nearest ancestor with a payload.
+#[derive(Component, Clone, Reflect)]
+#[reflect(opaque)]
+#[reflect(Component, Clone, Debug, PartialEq)]
+pub struct DragPayload {
+    value: Arc<dyn Any + Send + Sync>,
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+impl DragPayload {
+    /// Creates a payload carrying `value`.
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self {
+            value: Arc::new(value),
+            type_id: TypeId::of::<T>(),
+            type_name: core::any::type_name::<T>(),
+        }
+    }
+
+    /// Returns the value of the payload, if it is a `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+
+    /// Returns `true` if the value of the payload is a `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Returns the [`TypeId`] of the value of the payload.
+    pub fn value_type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the name of the type of the value of the payload, for debugging.
+    pub fn value_type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DragPayload").field(&self.type_name).finish()
+    }
+}
+
+/// Payloads are equal if they share the same value.
+impl PartialEq for DragPayload {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+/// The [`DragPayload`] of files dragged into a window from the OS.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq, Debug)]
+pub struct DraggedFiles(pub Vec<PathBuf>);
+
+/// Marks an entity that [`DragPayload`]s can be dropped on.
+///
+/// Payloads can also be dropped on the descendants of a drop target, unless they're drop targets
+/// themselves.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct DropTarget {
+    /// The types of the payloads accepted by the target, or `None` to accept any payload.
+    accepted: Option<Vec<TypeId>>,
+}
+
+impl DropTarget {
+    /// Creates a target accepting any payload.
+    pub fn any() -> Self {
+        Self { accepted: None }
+    }
+
+    /// Creates a target accepting the payloads of type `T`.
+    pub fn accepting<T: Any>() -> Self {
+        Self {
+            accepted: Some(vec![TypeId::of::<T>()]),
+        }
+    }
+
+    /// Also accepts the payloads of type `T`.
+    pub fn accept<T: Any>(mut self) -> Self {
+        if let Some(accepted) = &mut self.accepted {
+            accepted.push(TypeId::of::<T>());
+        }
+        self
+    }
+
+    /// Returns `true` if the target accepts `payload`.
+    pub fn accepts(&self, payload: &DragPayload) -> bool {
+        self.accepted
+            .as_ref()
+            .is_none_or(|accepted| accepted.contains(&payload.value_type_id()))
+    }
+}
+
+/// An entity following the pointer during a drag with a [`DragPayload`].
+///
+/// The preview is spawned when the drag starts, and despawned when it ends. It is never picked, so
+/// it doesn't hide the drop targets under it. If it has a [`Transform`], its translation follows
+/// the world position of the pointer. Over empty space, the preview stays at the same depth: it
+/// follows the ray of the pointer on the plane facing the ray through its latest world position.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Clone)]
+#[require(Pickable = Pickable::IGNORE)]
+pub struct DragPreview {
+    /// The pointer dragging the payload.
+    pub pointer_id: PointerId,
+    /// The entity with the dragged [`DragPayload`].
+    pub source: Entity,
+    /// The position of the pointer on its render target, in logical pixels.
+    pub position: Vec2,
+    /// The world position of the nearest entity under the pointer, if the picking backend reports
+    /// one, or the position of the pointer at the same depth as before over empty space. This is
+    /// the latest known world position if there is neither.
+    pub world_position: Option<Vec3>,
+    /// The drop target under the pointer.
+    pub target: Option<Entity>,
+    /// Whether the drop target under the pointer accepts the payload.
+    pub accepted: bool,
+}
+
+/// How a drag with a [`DragPayload`] ended.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+#[reflect(Clone, PartialEq, Hash, Debug)]
+pub enum DropOutcome {
+    /// The payload was dropped on a target that accepts it.
+    Dropped,
+    /// The payload was dropped on a target that doesn't accept it, or outside of any target.
+    Rejected,
+    /// The drag was canceled, with [`KeyCode::Escape`] or by canceling the pointer.
+    Canceled,
+}
+
+/// Fires when a pointer dragging a payload enters the [target entity](EntityEvent::event_target).
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct PayloadEnter {
+    /// The entity with the dragged payload.
+    pub source: Entity,
+    /// The dragged payload.
+    pub payload: DragPayload,
+    /// Whether the target accepts the payload.
+    pub accepted: bool,
+    /// Information about the picking intersection.
+    pub hit: HitData,
+}
+
+/// Fires when a pointer dragging a payload leaves the [target entity](EntityEvent::event_target),
+/// or when the drag is canceled or rejected over it.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct PayloadLeave {
+    /// The entity with the dragged payload.
+    pub source: Entity,
+    /// The dragged payload.
+    pub payload: DragPayload,
+}
+
+/// Fires when a payload is dropped on the [target entity](EntityEvent::event_target), which
+/// accepts it.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct PayloadDrop {
+    /// The entity with the dropped payload.
+    pub source: Entity,
+    /// The dropped payload.
+    pub payload: DragPayload,
+    /// Information about the picking intersection.
+    pub hit: HitData,
+}
+
+/// Fires on the source of a payload when its drag ends.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct PayloadDragEnd {
+    /// The drop target under the pointer when the drag ended.
+    pub target: Option<Entity>,
+    /// How the drag ended.
+    pub outcome: DropOutcome,
+}
+
+/// The drags with a [`DragPayload`] of each pointer.
+#[derive(Debug, Default, Deref, DerefMut, Resource)]
+pub struct PayloadDrags(pub HashMap<PointerId, PayloadDrag>);
+
+/// A drag with a [`DragPayload`].
+#[derive(Clone, Debug)]
+pub struct PayloadDrag {
+    /// The entity with the dragged payload.
+    pub source: Entity,
+    /// The dragged payload.
+    pub payload: DragPayload,
+    /// The button dragging the payload.
+    pub button: PointerButton,
+    /// The [`DragPreview`] entity of the drag.
+    pub preview: Entity,
+    /// The latest location of the pointer.
+    pub location: Location,
+    /// The camera and the latest world position of the pointer, if any.
+    pub world_position: Option<(Entity, Vec3)>,
+    /// The drop target under the pointer, the picking intersection with it, and whether it accepts
+    /// the payload.
+    pub target: Option<(Entity, HitData, bool)>,
+}
+
+/// A helper system param for accessing the payload event writers.
+#[derive(SystemParam)]
+pub struct PayloadMessageWriters<'w> {
+    enter_events: MessageWriter<'w, Pointer<PayloadEnter>>,
+    leave_events: MessageWriter<'w, Pointer<PayloadLeave>>,
+    drop_events: MessageWriter<'w, Pointer<PayloadDrop>>,
+    drag_end_events: MessageWriter<'w, Pointer<PayloadDragEnd>>,
+}
+
+/// Starts, updates and ends the drags with a [`DragPayload`], and dispatches their events.
+///
+/// This runs after [`pointer_events`](crate::events::pointer_events), which sends the
+/// [`DragStart`] and [`DragEnd`] events starting and ending the drags.
+pub fn update_payload_drags(
+    mut drag_starts: MessageReader<Pointer<DragStart>>,
+    mut drag_ends: MessageReader<Pointer<DragEnd>>,
+    mut input_events: MessageReader<PointerInput>,
+    mut file_drag_and_drops: MessageReader<FileDragAndDrop>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    hover_map: Res<HoverMap>,
+    ray_map: Res<RayMap>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    payloads: Query<&DragPayload>,
+    drop_targets: Query<&DropTarget>,
+    parents: Query<&ChildOf>,
+    mut previews: Query<(&mut DragPreview, Option<&mut Transform>)>,
+    mut drags: ResMut<PayloadDrags>,
+    mut commands: Commands,
+    mut message_writers: PayloadMessageWriters,
+) {
+    let mut ended = Vec::new();
+
+    // Cancel drags.
+    if keys.is_some_and(|keys| keys.just_pressed(KeyCode::Escape)) {
+        ended.extend(
+            drags
+                .keys()
+                .map(|pointer_id| (*pointer_id, DropOutcome::Canceled)),
+        );
+    }
+    for input in input_events.read() {
+        if matches!(input.action, PointerAction::Cancel) {
+            ended.push((input.pointer_id, DropOutcome::Canceled));
+        }
+    }
+
+    // Start drags.
+    let mut start = |pointer_id,
+                     source,
+                     payload,
+                     button,
+                     location: Location,
+                     world_position: Option<(Entity, Vec3)>| {
+        let preview = commands
+            .spawn(DragPreview {
+                pointer_id,
+                source,
+                position: location.position,
+                world_position: world_position.map(|(_, position)| position),
+                target: None,
+                accepted: false,
+            })
+            .id();
+        PayloadDrag {
+            source,
+            payload,
+            button,
+            preview,
+            location,
+            world_position,
+            target: None,
+        }
+    };
+    for drag_start in drag_starts.read() {
+        if drags.contains_key(&drag_start.pointer_id) {
+            continue;
+        }
+        let Some((source, payload)) = core::iter::once(drag_start.entity)
+            .chain(parents.iter_ancestors(drag_start.entity))
+            .find_map(|entity| Some((entity, payloads.get(entity).ok()?.clone())))
+        else {
+            continue;
+        };
+        let drag = start(
+            drag_start.pointer_id,
+            source,
+            payload,
+            drag_start.button,
+            drag_start.pointer_location.clone(),
+            drag_start
+                .hit
+                .position
+                .map(|position| (drag_start.hit.camera, position)),
+        );
+        drags.insert(drag_start.pointer_id, drag);
+    }
+
+    // Files dragged from the OS are dragged by the mouse, and may only be dropped without having
+    // been hovered first, depending on the platform.
+    let mut dropped_files = false;
+    for file_drag_and_drop in file_drag_and_drops.read() {
+        let (window, path) = match file_drag_and_drop {
+            FileDragAndDrop::HoveredFile { window, path_buf }
+            | FileDragAndDrop::DroppedFile { window, path_buf } => (*window, path_buf),
+            FileDragAndDrop::HoveredFileCanceled { window } => {
+                if drags
+                    .get(&PointerId::Mouse)
+                    .is_some_and(|drag| drag.source == *window)
+                {
+                    ended.push((PointerId::Mouse, DropOutcome::Canceled));
+                }
+                continue;
+            }
+        };
+        dropped_files |= matches!(file_drag_and_drop, FileDragAndDrop::DroppedFile { .. });
+
+        match drags.get_mut(&PointerId::Mouse) {
+            Some(drag) if drag.source == window => {
+                let Some(DraggedFiles(paths)) = drag.payload.get::<DraggedFiles>() else {
+                    continue;
+                };
+                if !paths.contains(path) {
+                    let mut paths = paths.clone();
+                    paths.push(path.clone());
+                    drag.payload = DragPayload::new(DraggedFiles(paths));
+                }
+            }
+            Some(_) => {}
+            None => {
+                let location = pointers
+                    .iter()
+                    .find(|(pointer_id, _)| pointer_id.is_mouse())
+                    .and_then(|(_, location)| location.location.clone())
+                    .or_else(|| {
+                        Some(Location {
+                            target: NormalizedRenderTarget::Window(
+                                WindowRef::Entity(window).normalize(None)?,
+                            ),
+                            position: Vec2::ZERO,
+                        })
+                    });
+                let Some(location) = location else {
+                    continue;
+                };
+                let payload = DragPayload::new(DraggedFiles(vec![path.clone()]));
+                let drag = start(
+                    PointerId::Mouse,
+                    window,
+                    payload,
+                    PointerButton::Primary,
+                    location,
+                    None,
+                );
+                drags.insert(PointerId::Mouse, drag);
+            }
+        }
+    }
+    if dropped_files
+        && drags
+            .get(&PointerId::Mouse)
+            .is_some_and(|drag| drag.payload.is::<DraggedFiles>())
+    {
+        ended.push((PointerId::Mouse, DropOutcome::Dropped));
+    }
+
+    // Find the drop targets under the pointers.
+    for (pointer_id, drag) in drags.iter_mut() {
+        if let Some((_, location)) = pointers.iter().find(|(id, _)| *id == pointer_id)
+            && let Some(location) = &location.location
+        {
+            drag.location = location.clone();
+        }
+
+        // Windows are hit behind every other entity, see `update_window_hits`.
+        let mut hits = hover_map
+            .get(pointer_id)
+            .into_iter()
+            .flatten()
+            .filter(|(entity, _)| **entity != drag.preview)
+            .collect::<Vec<_>>();
+        hits.sort_by(|(a, a_hit), (b, b_hit)| {
+            (a_hit.camera == **a)
+                .cmp(&(b_hit.camera == **b))
+                .then(a_hit.depth.total_cmp(&b_hit.depth))
+        });
+        // Over empty space, the pointer stays at the depth of its latest world position.
+        let world_position = match hits.first() {
+            Some((_, hit)) => hit.position.map(|position| (hit.camera, position)),
+            None => drag.world_position.and_then(|(camera, position)| {
+                let ray = ray_map.map.get(&RayId::new(camera, *pointer_id))?;
+                let plane = InfinitePlane3d {
+                    normal: ray.direction,
+                };
+                Some((camera, ray.plane_intersection_point(position, plane)?))
+            }),
+        };
+        drag.world_position = world_position.or(drag.world_position);
+        let world_position = drag.world_position.map(|(_, position)| position);
+        let target = hits.into_iter().find_map(|(entity, hit)| {
+            let target = core::iter::once(*entity)
+                .chain(parents.iter_ancestors(*entity))
+                .find(|entity| *entity != drag.source && drop_targets.contains(*entity))?;
+            let accepted = drop_targets.get(target).ok()?.accepts(&drag.payload);
+            Some((target, hit.clone(), accepted))
+        });
+
+        let previous_target = drag.target.as_ref().map(|(entity, ..)| *entity);
+        let new_target = target.as_ref().map(|(entity, ..)| *entity);
+        if previous_target != new_target {
+            if let Some(previous_target) = previous_target {
+                let leave = PayloadLeave {
+                    source: drag.source,
+                    payload: drag.payload.clone(),
+                };
+                let event =
+                    Pointer::new(*pointer_id, drag.location.clone(), leave, previous_target);
+                commands.trigger(event.clone());
+                message_writers.leave_events.write(event);
+            }
+            if let Some((entity, hit, accepted)) = &target {
+                let enter = PayloadEnter {
+                    source: drag.source,
+                    payload: drag.payload.clone(),
+                    accepted: *accepted,
+                    hit: hit.clone(),
+                };
+                let event = Pointer::new(*pointer_id, drag.location.clone(), enter, *entity);
+                commands.trigger(event.clone());
+                message_writers.enter_events.write(event);
+            }
+        }
+        drag.target = target;
+
+        let preview = DragPreview {
+            pointer_id: *pointer_id,
+            source: drag.source,
+            position: drag.location.position,
+            world_position,
+            target: new_target,
+            accepted: drag.target.as_ref().is_some_and(|(.., accepted)| *accepted),
+        };
+        match previews.get_mut(drag.preview) {
+            Ok((mut current, transform)) => {
+                current.set_if_neq(preview);
+                if let Some((mut transform, world_position)) = transform.zip(world_position) {
+                    transform.translation = world_position;
+                }
+            }
+            // The preview of a drag started this frame isn't spawned yet.
+            Err(_) => {
+                commands.entity(drag.preview).insert(preview);
+            }
+        }
+    }
+
+    // End drags.
+    for drag_end in drag_ends.read() {
+        if drags
+            .get(&drag_end.pointer_id)
+            .is_some_and(|drag| drag.button == drag_end.button)
+        {
+            ended.push((drag_end.pointer_id, DropOutcome::Dropped));
+        }
+    }
+    for (pointer_id, outcome) in ended {
+        let Some(drag) = drags.remove(&pointer_id) else {
+            continue;
+        };
+        if let Ok(mut preview) = commands.get_entity(drag.preview) {
+            preview.despawn();
+        }
+
+        let outcome = match (&drag.target, outcome) {
+            (Some((entity, hit, true)), DropOutcome::Dropped) => {
+                let drop = PayloadDrop {
+                    source: drag.source,
+                    payload: drag.payload.clone(),
+                    hit: hit.clone(),
+                };
+                let event = Pointer::new(pointer_id, drag.location.clone(), drop, *entity);
+                commands.trigger(event.clone());
+                message_writers.drop_events.write(event);
+                DropOutcome::Dropped
+            }
+            (target, outcome) => {
+                if let Some((entity, ..)) = target {
+                    let leave = PayloadLeave {
+                        source: drag.source,
+                        payload: drag.payload.clone(),
+                    };
+                    let event = Pointer::new(pointer_id, drag.location.clone(), leave, *entity);
+                    commands.trigger(event.clone());
+                    message_writers.leave_events.write(event);
+                }
+                match outcome {
+                    DropOutcome::Canceled => DropOutcome::Canceled,
+                    _ => DropOutcome::Rejected,
+                }
+            }
+        };
+        let drag_end = PayloadDragEnd {
+            target: drag.target.map(|(entity, ..)| entity),
+            outcome,
+        };
+        let event = Pointer::new(pointer_id, drag.location, drag_end, drag.source);
+        commands.trigger(event.clone());
+        message_writers.drag_end_events.write(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::message::Messages;
+    use bevy_math::{Dir3, Ray3d};
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Item;
+
+    fn setup() -> World {
+        let mut world = World::default();
+        world.init_resource::<HoverMap>();
+        world.init_resource::<RayMap>();
+        world.init_resource::<PayloadDrags>();
+        world.init_resource::<Messages<Pointer<DragStart>>>();
+        world.init_resource::<Messages<Pointer<DragEnd>>>();
+        world.init_resource::<Messages<PointerInput>>();
+        world.init_resource::<Messages<FileDragAndDrop>>();
+        world.init_resource::<Messages<Pointer<PayloadEnter>>>();
+        world.init_resource::<Messages<Pointer<PayloadLeave>>>();
+        world.init_resource::<Messages<Pointer<PayloadDrop>>>();
+        world.init_resource::<Messages<Pointer<PayloadDragEnd>>>();
+        world
+    }
+
+    fn location() -> Location {
+        Location {
+            target: NormalizedRenderTarget::None {
+                width: 800,
+                height: 600,
+            },
+            position: Vec2::new(10.0, 20.0),
+        }
+    }
+
+    fn hover(world: &mut World, pointer_id: PointerId, entity: Entity) {
+        let camera = world.spawn_empty().id();
+        world.resource_mut::<HoverMap>().insert(
+            pointer_id,
+            [(entity, HitData::new(camera, 0.0, None, None))].into(),
+        );
+    }
+
+    fn update(world: &mut World) {
+        world.run_system_cached(update_payload_drags).unwrap();
+        world.flush();
+    }
+
+    #[test]
+    fn drop_accepted_payload() {
+        let mut world = setup();
+        let source = world.spawn(DragPayload::new(Item)).id();
+        let handle = world.spawn(ChildOf(source)).id();
+        let target = world.spawn(DropTarget::accepting::<Item>()).id();
+        let target_child = world.spawn(ChildOf(target)).id();
+
+        let hit = HitData::new(source, 0.0, None, None);
+        let drag_start = DragStart {
+            button: PointerButton::Primary,
+            hit,
+        };
+        world.write_message(Pointer::new(
+            PointerId::Mouse,
+            location(),
+            drag_start,
+            handle,
+        ));
+        hover(&mut world, PointerId::Mouse, target_child);
+        update(&mut world);
+
+        let drag = &world.resource::<PayloadDrags>()[&PointerId::Mouse];
+        assert_eq!(drag.source, source);
+        assert_eq!(
+            drag.target.as_ref().map(|(entity, ..)| *entity),
+            Some(target)
+        );
+        let preview = drag.preview;
+        assert!(world.get::<DragPreview>(preview).unwrap().accepted);
+        let enters = world.resource::<Messages<Pointer<PayloadEnter>>>();
+        let enter = enters.iter_current_update_messages().next().unwrap();
+        assert_eq!(enter.entity, target);
+        assert!(enter.accepted);
+
+        let drag_end = DragEnd {
+            button: PointerButton::Primary,
+            distance: Vec2::ZERO,
+        };
+        world.write_message(Pointer::new(PointerId::Mouse, location(), drag_end, handle));
+        update(&mut world);
+
+        assert!(world.resource::<PayloadDrags>().is_empty());
+        assert!(world.get_entity(preview).is_err());
+        let drops = world.resource::<Messages<Pointer<PayloadDrop>>>();
+        let drop = drops.iter_current_update_messages().next().unwrap();
+        assert_eq!(drop.entity, target);
+        assert!(drop.payload.is::<Item>());
+        let drag_ends = world.resource::<Messages<Pointer<PayloadDragEnd>>>();
+        let drag_end = drag_ends.iter_current_update_messages().next().unwrap();
+        assert_eq!(drag_end.entity, source);
+        assert_eq!(drag_end.outcome, DropOutcome::Dropped);
+    }
+
+    #[test]
+    fn change_target() {
+        let mut world = setup();
+        let source = world.spawn(DragPayload::new(Item)).id();
+        let first = world.spawn(DropTarget::any()).id();
+        let second = world.spawn(DropTarget::accepting::<DraggedFiles>()).id();
+
+        let drag_start = DragStart {
+            button: PointerButton::Primary,
+            hit: HitData::new(source, 0.0, None, None),
+        };
+        world.write_message(Pointer::new(
+            PointerId::Mouse,
+            location(),
+            drag_start,
+            source,
+        ));
+        hover(&mut world, PointerId::Mouse, first);
+        update(&mut world);
+        world
+            .resource_mut::<Messages<Pointer<PayloadEnter>>>()
+            .clear();
+
+        hover(&mut world, PointerId::Mouse, second);
+        update(&mut world);
+
+        let leaves = world.resource::<Messages<Pointer<PayloadLeave>>>();
+        let leave = leaves.iter_current_update_messages().next().unwrap();
+        assert_eq!(leave.entity, first);
+        assert_eq!(leave.source, source);
+        assert_eq!(leaves.len(), 1);
+        let enters = world.resource::<Messages<Pointer<PayloadEnter>>>();
+        let enter = enters.iter_current_update_messages().next().unwrap();
+        assert_eq!(enter.entity, second);
+        assert!(!enter.accepted);
+        let drag = &world.resource::<PayloadDrags>()[&PointerId::Mouse];
+        let preview = world.get::<DragPreview>(drag.preview).unwrap();
+        assert_eq!(preview.target, Some(second));
+        assert!(!preview.accepted);
+    }
+
+    #[test]
+    fn follow_pointer_over_empty_space() {
+        let mut world = setup();
+        let source = world.spawn(DragPayload::new(Item)).id();
+        let camera = world.spawn_empty().id();
+
+        let drag_start = DragStart {
+            button: PointerButton::Primary,
+            hit: HitData::new(camera, 0.0, Some(Vec3::new(1.0, 2.0, 3.0)), None),
+        };
+        world.write_message(Pointer::new(
+            PointerId::Mouse,
+            location(),
+            drag_start,
+            source,
+        ));
+        update(&mut world);
+        let preview = world.resource::<PayloadDrags>()[&PointerId::Mouse].preview;
+        assert_eq!(
+            world.get::<DragPreview>(preview).unwrap().world_position,
+            Some(Vec3::new(1.0, 2.0, 3.0))
+        );
+        world.entity_mut(preview).insert(Transform::default());
+
+        world.resource_mut::<RayMap>().map.insert(
+            RayId::new(camera, PointerId::Mouse),
+            Ray3d::new(Vec3::new(5.0, 0.0, 10.0), Dir3::NEG_Z),
+        );
+        update(&mut world);
+
+        let position = Vec3::new(5.0, 0.0, 3.0);
+        assert_eq!(
+            world.get::<DragPreview>(preview).unwrap().world_position,
+            Some(position)
+        );
+        assert_eq!(
+            world.get::<Transform>(preview).unwrap().translation,
+            position
+        );
+    }
+
+    #[test]
+    fn reject_and_cancel() {
+        let mut world = setup();
+        let source = world.spawn(DragPayload::new(Item)).id();
+        let target = world.spawn(DropTarget::accepting::<DraggedFiles>()).id();
+        hover(&mut world, PointerId::Mouse, target);
+
+        let start = |world: &mut World| {
+            let drag_start = DragStart {
+                button: PointerButton::Primary,
+                hit: HitData::new(source, 0.0, None, None),
+            };
+            world.write_message(Pointer::new(
+                PointerId::Mouse,
+                location(),
+                drag_start,
+                source,
+            ));
+            update(world);
+            world
+                .resource_mut::<Messages<Pointer<PayloadDragEnd>>>()
+                .clear();
+        };
+
+        start(&mut world);
+        let drag_end = DragEnd {
+            button: PointerButton::Primary,
+            distance: Vec2::ZERO,
+        };
+        world.write_message(Pointer::new(PointerId::Mouse, location(), drag_end, source));
+        update(&mut world);
+        let drag_ends = world.resource::<Messages<Pointer<PayloadDragEnd>>>();
+        let drag_end = drag_ends.iter_current_update_messages().next().unwrap();
+        assert_eq!(drag_end.outcome, DropOutcome::Rejected);
+        assert_eq!(world.resource::<Messages<Pointer<PayloadDrop>>>().len(), 0);
+
+        start(&mut world);
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(KeyCode::Escape);
+        world.insert_resource(keys);
+        update(&mut world);
+        let drag_ends = world.resource::<Messages<Pointer<PayloadDragEnd>>>();
+        let drag_end = drag_ends.iter_current_update_messages().next().unwrap();
+        assert_eq!(drag_end.outcome, DropOutcome::Canceled);
+        assert!(world.resource::<PayloadDrags>().is_empty());
+    }
+
+    #[test]
+    fn drop_files() {
+        let mut world = setup();
+        let window = world.spawn_empty().id();
+        let target = world.spawn(DropTarget::accepting::<DraggedFiles>()).id();
+        hover(&mut world, PointerId::Mouse, target);
+
+        for path in ["a.png", "b.png"] {
+            world.write_message(FileDragAndDrop::DroppedFile {
+                window,
+                path_buf: path.into(),
+            });
+        }
+        update(&mut world);
+
+        let drops = world.resource::<Messages<Pointer<PayloadDrop>>>();
+        let drop = drops.iter_current_update_messages().next().unwrap();
+        assert_eq!(drop.entity, target);
+        assert_eq!(drop.source, window);
+        let DraggedFiles(paths) = drop.payload.get::<DraggedFiles>().unwrap();
+        assert_eq!(paths, &[PathBuf::from("a.png"), PathBuf::from("b.png")]);
+    }
+
+    #[test]
+    fn hover_then_drop_files() {
+        let mut world = setup();
+        let window = world.spawn_empty().id();
+        let target = world.spawn(DropTarget::accepting::<DraggedFiles>()).id();
+        hover(&mut world, PointerId::Mouse, target);
+
+        for path in ["a.png", "b.png"] {
+            world.write_message(FileDragAndDrop::HoveredFile {
+                window,
+                path_buf: path.into(),
+            });
+            update(&mut world);
+        }
+        let drag = &world.resource::<PayloadDrags>()[&PointerId::Mouse];
+        assert_eq!(drag.source, window);
+        let DraggedFiles(paths) = drag.payload.get::<DraggedFiles>().unwrap();
+        assert_eq!(paths, &[PathBuf::from("a.png"), PathBuf::from("b.png")]);
+        let enters = world.resource::<Messages<Pointer<PayloadEnter>>>();
+        assert_eq!(enters.len(), 1);
+        assert!(enters
+            .iter_current_update_messages()
+            .all(|enter| enter.accepted));
+
+        for path in ["b.png", "a.png", "c.png"] {
+            world.write_message(FileDragAndDrop::DroppedFile {
+                window,
+                path_buf: path.into(),
+            });
+        }
+        update(&mut world);
+
+        assert!(world.resource::<PayloadDrags>().is_empty());
+        let drops = world.resource::<Messages<Pointer<PayloadDrop>>>();
+        assert_eq!(drops.len(), 1);
+        let drop = drops.iter_current_update_messages().next().unwrap();
+        assert_eq!(drop.entity, target);
+        let DraggedFiles(paths) = drop.payload.get::<DraggedFiles>().unwrap();
+        assert_eq!(
+            paths,
+            &[
+                PathBuf::from("a.png"),
+                PathBuf::from("b.png"),
+                PathBuf::from("c.png")
+            ]
+        );
+    }
+}
@@ -0,0 +1,806 @@
+//! Recognizes gestures, like taps, swipes and pinches, from the [`PointerInput`] stream.
+//!
+//! Gestures are recognized the same way for every platform and every kind of pointer: mouse,
+//! touch, pen or custom pointers. Multi-finger gestures use the touch pointers that are pressed at
+//! the same time.
+//!
+//! Like the other pointer events, gestures are delivered as [`Pointer`] events to the entities
+//! that were hovered when the gesture started, and bubble up the hierarchy:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_picking::prelude::*;
+//! # let mut world = World::default();
+//! world.spawn_empty()
+//!     .observe(|double_tap: On<Pointer<DoubleTap>>| {
+//!         println!("Zooming in");
+//!     })
+//!     .observe(|pinch: On<Pointer<Pinch>>| {
+//!         println!("Zooming by {}", pinch.delta);
+//!     });
+//! ```
+//!
+//! The thresholds of the recognizers, and how they resolve conflicts between gestures, are
+//! configured by the [`GestureSettings`] resource:
+//! + A pointer that moves further than [`GestureSettings::tap_max_distance`] can't tap or
+//!   long-press anymore, but can swipe when it's released.
+//! + A long press suppresses the tap of its release.
+//! + When a second touch is pressed, the single-pointer gestures of both touches are canceled, and
+//!   they start a two-finger gesture instead.
+//! + [`GestureSettings::exclusive_taps`] delays taps until they can't become a double tap.
+//! + [`GestureSettings::simultaneous_two_finger_gestures`] allows [`Pinch`], [`Rotate`] and
+//!   [`Pan`] to be recognized together, rather than only the first of them.
+
+use core::{f32::consts::PI, time::Duration};
+
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{Dir2, Vec2};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::prelude::*;
+use bevy_time::{Real, Time};
+
+use crate::{
+    backend::HitData,
+    events::Pointer,
+    hover::HoverMap,
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput},
+};
+
+/// Configures the thresholds of gesture recognition, and how conflicts between gestures are
+/// resolved.
+///
+/// Distances are in logical pixels.
+#[derive(Clone, Debug, Resource, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct GestureSettings {
+    /// The furthest a pointer can move between its press and release to tap.
+    pub tap_max_distance: f32,
+    /// The longest a pointer can be pressed to tap.
+    pub tap_max_duration: Duration,
+    /// The longest time between two taps for them to form a double tap.
+    pub double_tap_max_interval: Duration,
+    /// The furthest apart two taps can be to form a double tap.
+    pub double_tap_max_distance: f32,
+    /// How long a pointer must be pressed without moving to long-press.
+    pub long_press_duration: Duration,
+    /// The shortest distance a pointer must move between its press and release to swipe.
+    pub swipe_min_distance: f32,
+    /// The lowest average speed, in logical pixels per second, of a swipe.
+    pub swipe_min_velocity: f32,
+    /// The relative change of the distance between two touches needed to pinch.
+    pub pinch_threshold: f32,
+    /// The angle, in radians, two touches must rotate by to rotate.
+    pub rotate_threshold: f32,
+    /// The distance the center of two touches must move by to pan.
+    pub pan_threshold: f32,
+    /// Whether a tap is only delivered once it can't become a double tap anymore.
+    ///
+    /// If `false`, both taps of a double tap are delivered as [`Tap`] events as well, and the
+    /// first one isn't delayed.
+    pub exclusive_taps: bool,
+    /// Whether [`Pinch`], [`Rotate`] and [`Pan`] can be recognized for the same two touches.
+    ///
+    /// If `false`, only the first of them that exceeds its threshold is recognized until one of
+    /// the touches is released.
+    pub simultaneous_two_finger_gestures: bool,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_max_distance: 10.0,
+            tap_max_duration: Duration::from_millis(300),
+            double_tap_max_interval: Duration::from_millis(300),
+            double_tap_max_distance: 30.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_min_velocity: 300.0,
+            pinch_threshold: 0.05,
+            rotate_threshold: 0.1,
+            pan_threshold: 10.0,
+            exclusive_taps: false,
+            simultaneous_two_finger_gestures: true,
+        }
+    }
+}
+
+/// Fires when a pointer is pressed and quickly released without moving.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Tap {
+    /// Information about the picking intersection when the pointer was pressed.
+    pub hit: HitData,
+}
+
+/// Fires on the second of two taps close to each other in space and time.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct DoubleTap {
+    /// Information about the picking intersection when the pointer was pressed.
+    pub hit: HitData,
+}
+
+/// Fires once when a pointer stays pressed without moving for
+/// [`GestureSettings::long_press_duration`].
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct LongPress {
+    /// Information about the picking intersection when the pointer was pressed.
+    pub hit: HitData,
+    /// How long the pointer has been pressed.
+    pub duration: Duration,
+}
+
+/// Fires when a pointer is released after moving quickly in a direction.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Swipe {
+    /// The main direction of the swipe.
+    pub direction: SwipeDirection,
+    /// The distance between the press and the release of the pointer.
+    pub distance: Vec2,
+    /// The average velocity of the pointer, in logical pixels per second.
+    pub velocity: Vec2,
+}
+
+/// The main direction of a [`Swipe`], in the space of the render target.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+#[reflect(Clone, PartialEq, Hash)]
+pub enum SwipeDirection {
+    /// Towards the left edge.
+    Left,
+    /// Towards the right edge.
+    Right,
+    /// Towards the top edge.
+    Up,
+    /// Towards the bottom edge.
+    Down,
+}
+
+/// Fires when the distance between two touches changes.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Pinch {
+    /// The factor by which the distance changed since the last [`Pinch`] event.
+    pub delta: f32,
+    /// The factor by which the distance changed since the touches were pressed.
+    pub scale: f32,
+}
+
+/// Fires when two touches rotate around each other.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Rotate {
+    /// The angle, in radians, rotated since the last [`Rotate`] event.
+    ///
+    /// Positive angles are clockwise on screen.
+    pub delta: f32,
+    /// The angle, in radians, rotated since the touches were pressed.
+    pub angle: f32,
+}
+
+/// Fires when the center of two touches moves.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Pan {
+    /// The movement of the center since the last [`Pan`] event.
+    pub delta: Vec2,
+    /// The movement of the center since the touches were pressed.
+    pub distance: Vec2,
+}
+
+impl SwipeDirection {
+    /// Returns the direction closest to `direction`, in the space of the render target where Y
+    /// points down.
+    pub fn from_vec2(direction: Dir2) -> Self {
+        if direction.x.abs() >= direction.y.abs() {
+            if direction.x < 0.0 {
+                Self::Left
+            } else {
+                Self::Right
+            }
+        } else if direction.y < 0.0 {
+            Self::Up
+        } else {
+            Self::Down
+        }
+    }
+}
+
+/// The state of the gesture recognizers.
+#[derive(Default)]
+pub struct GestureState {
+    pressed: HashMap<PointerId, PressedPointer>,
+    two_fingers: Option<TwoFingers>,
+    last_taps: HashMap<Entity, LastTap>,
+}
+
+/// A pointer pressed with its primary button.
+struct PressedPointer {
+    targets: Vec<(Entity, HitData)>,
+    start: Location,
+    start_time: Duration,
+    latest: Location,
+    moved: bool,
+    long_pressed: bool,
+    canceled: bool,
+}
+
+/// The last tap on an entity, which can become a double tap.
+struct LastTap {
+    pointer_id: PointerId,
+    location: Location,
+    time: Duration,
+    hit: HitData,
+    delivered: bool,
+}
+
+/// Two touches pressed at the same time.
+struct TwoFingers {
+    pointers: [PointerId; 2],
+    targets: Vec<(Entity, HitData)>,
+    initial: TwoFingerPose,
+    last_pinch: f32,
+    last_rotate: f32,
+    last_pan: Vec2,
+    recognized: [bool; 3],
+}
+
+#[derive(Clone, Copy)]
+struct TwoFingerPose {
+    distance: f32,
+    angle: f32,
+    center: Vec2,
+}
+
+impl TwoFingerPose {
+    fn new(a: Vec2, b: Vec2) -> Self {
+        let offset = b - a;
+        Self {
+            distance: offset.length(),
+            angle: offset.to_angle(),
+            center: (a + b) / 2.0,
+        }
+    }
+}
+
+/// Wraps an angle to `[-π, π)`.
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+/// A helper system param for accessing the gesture event writers.
+#[derive(SystemParam)]
+pub struct GestureMessageWriters<'w> {
+    tap_events: MessageWriter<'w, Pointer<Tap>>,
+    double_tap_events: MessageWriter<'w, Pointer<DoubleTap>>,
+    long_press_events: MessageWriter<'w, Pointer<LongPress>>,
+    swipe_events: MessageWriter<'w, Pointer<Swipe>>,
+    pinch_events: MessageWriter<'w, Pointer<Pinch>>,
+    rotate_events: MessageWriter<'w, Pointer<Rotate>>,
+    pan_events: MessageWriter<'w, Pointer<Pan>>,
+}
+
+/// Recognizes gestures from the [`PointerInput`] stream, and dispatches them as [`Pointer`]
+/// events to the entities hovered when the gestures started.
+///
+/// This runs after [`pointer_events`](crate::events::pointer_events), so within a frame the
+/// gesture events of a pointer follow its other events.
+pub fn recognize_gestures(
+    mut input_events: MessageReader<PointerInput>,
+    hover_map: Res<HoverMap>,
+    settings: Res<GestureSettings>,
+    time: Res<Time<Real>>,
+    mut state: Local<GestureState>,
+    mut commands: Commands,
+    mut message_writers: GestureMessageWriters,
+) {
+    let now = time.elapsed();
+    let state = &mut *state;
+
+    macro_rules! send {
+        ($writer:ident, $pointer_id:expr, $location:expr, $event:expr, $entity:expr) => {{
+            let event = Pointer::new($pointer_id, $location, $event, $entity);
+            commands.trigger(event.clone());
+            message_writers.$writer.write(event);
+        }};
+    }
+
+    for PointerInput {
+        pointer_id,
+        location,
+        action,
+    } in input_events.read().cloned()
+    {
+        match action {
+            PointerAction::Press(PointerButton::Primary) => {
+                let targets = hover_map
+                    .get(&pointer_id)
+                    .iter()
+                    .flat_map(|h| h.iter().map(|(entity, hit)| (*entity, hit.clone())))
+                    .collect::<Vec<_>>();
+                state.pressed.insert(
+                    pointer_id,
+                    PressedPointer {
+                        targets: targets.clone(),
+                        start: location.clone(),
+                        start_time: now,
+                        latest: location.clone(),
+                        moved: false,
+                        long_pressed: false,
+                        canceled: false,
+                    },
+                );
+
+                // A second touch starts a two-finger gesture, and cancels the gestures of both.
+                if pointer_id.is_touch() && state.two_fingers.is_none() {
+                    let other = state
+                        .pressed
+                        .iter()
+                        .find(|(id, pressed)| {
+                            id.is_touch() && **id != pointer_id && !pressed.canceled
+                        })
+                        .map(|(id, _)| *id);
+                    if let Some(other) = other {
+                        let first = &state.pressed[&other];
+                        let initial = TwoFingerPose::new(first.latest.position, location.position);
+                        let targets = if first.targets.is_empty() {
+                            targets
+                        } else {
+                            first.targets.clone()
+                        };
+                        state.two_fingers = Some(TwoFingers {
+                            pointers: [other, pointer_id],
+                            targets,
+                            initial,
+                            last_pinch: initial.distance,
+                            last_rotate: initial.angle,
+                            last_pan: initial.center,
+                            recognized: [false; 3],
+                        });
+                        for id in [other, pointer_id] {
+                            if let Some(pressed) = state.pressed.get_mut(&id) {
+                                pressed.canceled = true;
+                            }
+                        }
+                    }
+                }
+            }
+            PointerAction::Move { .. } => {
+                let Some(pressed) = state.pressed.get_mut(&pointer_id) else {
+                    continue;
+                };
+                pressed.latest = location.clone();
+                if pressed.latest.position.distance(pressed.start.position)
+                    > settings.tap_max_distance
+                {
+                    pressed.moved = true;
+                }
+
+                let Some(two_fingers) = state
+                    .two_fingers
+                    .as_mut()
+                    .filter(|two_fingers| two_fingers.pointers.contains(&pointer_id))
+                else {
+                    continue;
+                };
+                let [Some(a), Some(b)] = two_fingers.pointers.map(|id| state.pressed.get(&id))
+                else {
+                    continue;
+                };
+                let pose = TwoFingerPose::new(a.latest.position, b.latest.position);
+                let gesture_location = Location {
+                    target: location.target.clone(),
+                    position: pose.center,
+                };
+                let first_pointer = two_fingers.pointers[0];
+
+                // Find which gestures exceed their thresholds.
+                let scale = pose.distance / two_fingers.initial.distance.max(f32::EPSILON);
+                let angle = wrap_angle(pose.angle - two_fingers.initial.angle);
+                let distance = pose.center - two_fingers.initial.center;
+                let exceeded = [
+                    (scale - 1.0).abs() >= settings.pinch_threshold,
+                    angle.abs() >= settings.rotate_threshold,
+                    distance.length() >= settings.pan_threshold,
+                ];
+                let any_recognized = two_fingers.recognized.iter().any(|&r| r);
+                for (recognized, exceeded) in two_fingers.recognized.iter_mut().zip(exceeded) {
+                    if exceeded && (settings.simultaneous_two_finger_gestures || !any_recognized) {
+                        *recognized = true;
+                    }
+                }
+                if !settings.simultaneous_two_finger_gestures {
+                    // Only keep the first gesture recognized this frame.
+                    let mut found = false;
+                    for recognized in &mut two_fingers.recognized {
+                        *recognized &= !found;
+                        found |= *recognized;
+                    }
+                }
+
+                for (entity, _) in &two_fingers.targets {
+                    if two_fingers.recognized[0] && pose.distance != two_fingers.last_pinch {
+                        let pinch = Pinch {
+                            delta: pose.distance / two_fingers.last_pinch.max(f32::EPSILON),
+                            scale,
+                        };
+                        send!(
+                            pinch_events,
+                            first_pointer,
+                            gesture_location.clone(),
+                            pinch,
+                            *entity
+                        );
+                    }
+                    if two_fingers.recognized[1] && pose.angle != two_fingers.last_rotate {
+                        let rotate = Rotate {
+                            delta: wrap_angle(pose.angle - two_fingers.last_rotate),
+                            angle,
+                        };
+                        send!(
+                            rotate_events,
+                            first_pointer,
+                            gesture_location.clone(),
+                            rotate,
+                            *entity
+                        );
+                    }
+                    if two_fingers.recognized[2] && pose.center != two_fingers.last_pan {
+                        let pan = Pan {
+                            delta: pose.center - two_fingers.last_pan,
+                            distance,
+                        };
+                        send!(
+                            pan_events,
+                            first_pointer,
+                            gesture_location.clone(),
+                            pan,
+                            *entity
+                        );
+                    }
+                }
+                if two_fingers.recognized[0] {
+                    two_fingers.last_pinch = pose.distance;
+                }
+                if two_fingers.recognized[1] {
+                    two_fingers.last_rotate = pose.angle;
+                }
+                if two_fingers.recognized[2] {
+                    two_fingers.last_pan = pose.center;
+                }
+            }
+            PointerAction::Release(PointerButton::Primary) => {
+                let Some(pressed) = state.pressed.remove(&pointer_id) else {
+                    continue;
+                };
+                if state
+                    .two_fingers
+                    .as_ref()
+                    .is_some_and(|two_fingers| two_fingers.pointers.contains(&pointer_id))
+                {
+                    state.two_fingers = None;
+                }
+                if pressed.canceled || pressed.long_pressed {
+                    continue;
+                }
+
+                let duration = now.saturating_sub(pressed.start_time);
+                let distance = location.position - pressed.start.position;
+                if !pressed.moved && duration <= settings.tap_max_duration {
+                    for (entity, hit) in pressed.targets {
+                        let is_double_tap = state.last_taps.get(&entity).is_some_and(|last| {
+                            now.saturating_sub(last.time) <= settings.double_tap_max_interval
+                                && last.location.position.distance(location.position)
+                                    <= settings.double_tap_max_distance
+                        });
+                        if is_double_tap {
+                            state.last_taps.remove(&entity);
+                            if !settings.exclusive_taps {
+                                send!(
+                                    tap_events,
+                                    pointer_id,
+                                    location.clone(),
+                                    Tap { hit: hit.clone() },
+                                    entity
+                                );
+                            }
+                            send!(
+                                double_tap_events,
+                                pointer_id,
+                                location.clone(),
+                                DoubleTap { hit },
+                                entity
+                            );
+                            continue;
+                        }
+
+                        if !settings.exclusive_taps {
+                            send!(
+                                tap_events,
+                                pointer_id,
+                                location.clone(),
+                                Tap { hit: hit.clone() },
+                                entity
+                            );
+                        }
+                        state.last_taps.insert(
+                            entity,
+                            LastTap {
+                                pointer_id,
+                                location: location.clone(),
+                                time: now,
+                                hit,
+                                delivered: !settings.exclusive_taps,
+                            },
+                        );
+                    }
+                } else if let Ok(direction) = Dir2::new(distance)
+                    && distance.length() >= settings.swipe_min_distance
+                {
+                    let velocity = distance / duration.as_secs_f32().max(f32::EPSILON);
+                    if velocity.length() >= settings.swipe_min_velocity {
+                        let swipe = Swipe {
+                            direction: SwipeDirection::from_vec2(direction),
+                            distance,
+                            velocity,
+                        };
+                        for (entity, _) in pressed.targets {
+                            send!(
+                                swipe_events,
+                                pointer_id,
+                                location.clone(),
+                                swipe.clone(),
+                                entity
+                            );
+                        }
+                    }
+                }
+            }
+            PointerAction::Cancel => {
+                state.pressed.remove(&pointer_id);
+                if state
+                    .two_fingers
+                    .as_ref()
+                    .is_some_and(|two_fingers| two_fingers.pointers.contains(&pointer_id))
+                {
+                    state.two_fingers = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Recognize long presses.
+    for (pointer_id, pressed) in &mut state.pressed {
+        let duration = now.saturating_sub(pressed.start_time);
+        if pressed.canceled
+            || pressed.moved
+            || pressed.long_pressed
+            || duration < settings.long_press_duration
+        {
+            continue;
+        }
+        pressed.long_pressed = true;
+        for (entity, hit) in &pressed.targets {
+            let long_press = LongPress {
+                hit: hit.clone(),
+                duration,
+            };
+            send!(
+                long_press_events,
+                *pointer_id,
+                pressed.latest.clone(),
+                long_press,
+                *entity
+            );
+        }
+    }
+
+    // Deliver the exclusive taps that can't become double taps anymore.
+    state.last_taps.retain(|entity, last| {
+        if now.saturating_sub(last.time) <= settings.double_tap_max_interval {
+            return true;
+        }
+        if !last.delivered {
+            let tap = Tap {
+                hit: last.hit.clone(),
+            };
+            send!(
+                tap_events,
+                last.pointer_id,
+                last.location.clone(),
+                tap,
+                *entity
+            );
+        }
+        false
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_camera::NormalizedRenderTarget;
+    use bevy_ecs::message::Messages;
+    use bevy_platform::time::Instant;
+
+    use super::*;
+
+    fn setup(entity_count: usize) -> (World, Vec<Entity>) {
+        let mut world = World::default();
+        world.init_resource::<GestureSettings>();
+        world.init_resource::<Messages<PointerInput>>();
+        world.init_resource::<Messages<Pointer<Tap>>>();
+        world.init_resource::<Messages<Pointer<DoubleTap>>>();
+        world.init_resource::<Messages<Pointer<LongPress>>>();
+        world.init_resource::<Messages<Pointer<Swipe>>>();
+        world.init_resource::<Messages<Pointer<Pinch>>>();
+        world.init_resource::<Messages<Pointer<Rotate>>>();
+        world.init_resource::<Messages<Pointer<Pan>>>();
+        world.insert_resource(Time::<Real>::new(Instant::now()));
+
+        let camera = world.spawn_empty().id();
+        let entities = (0..entity_count)
+            .map(|_| world.spawn_empty().id())
+            .collect::<Vec<_>>();
+        let mut hover_map = HoverMap::default();
+        for pointer_id in [PointerId::Mouse, PointerId::Touch(0), PointerId::Touch(1)] {
+            hover_map.insert(
+                pointer_id,
+                entities
+                    .iter()
+                    .map(|entity| (*entity, HitData::new(camera, 0.0, None, None)))
+                    .collect(),
+            );
+        }
+        world.insert_resource(hover_map);
+        (world, entities)
+    }
+
+    fn input(world: &mut World, pointer_id: PointerId, position: Vec2, action: PointerAction) {
+        let location = Location {
+            target: NormalizedRenderTarget::None {
+                width: 800,
+                height: 600,
+            },
+            position,
+        };
+        world.write_message(PointerInput::new(pointer_id, location, action));
+    }
+
+    fn advance(world: &mut World, millis: u64) {
+        world
+            .resource_mut::<Time<Real>>()
+            .update_with_duration(Duration::from_millis(millis));
+        world.run_system_cached(recognize_gestures).unwrap();
+    }
+
+    fn count<M: Message>(world: &World) -> usize {
+        world.resource::<Messages<M>>().len()
+    }
+
+    #[test]
+    fn tap_and_double_tap() {
+        let (mut world, _) = setup(1);
+        let press = PointerAction::Press(PointerButton::Primary);
+        let release = PointerAction::Release(PointerButton::Primary);
+
+        input(&mut world, PointerId::Mouse, Vec2::ZERO, press);
+        advance(&mut world, 50);
+        input(&mut world, PointerId::Mouse, Vec2::new(2.0, 0.0), release);
+        advance(&mut world, 50);
+        assert_eq!(count::<Pointer<Tap>>(&world), 1);
+        assert_eq!(count::<Pointer<DoubleTap>>(&world), 0);
+
+        input(&mut world, PointerId::Mouse, Vec2::ZERO, press);
+        advance(&mut world, 50);
+        input(&mut world, PointerId::Mouse, Vec2::ZERO, release);
+        advance(&mut world, 50);
+        assert_eq!(count::<Pointer<Tap>>(&world), 2);
+        assert_eq!(count::<Pointer<DoubleTap>>(&world), 1);
+
+        // Pressing long enough is a long press rather than a tap.
+        input(&mut world, PointerId::Mouse, Vec2::ZERO, press);
+        advance(&mut world, 50);
+        assert_eq!(count::<Pointer<LongPress>>(&world), 0);
+        advance(&mut world, 500);
+        assert_eq!(count::<Pointer<LongPress>>(&world), 1);
+        input(&mut world, PointerId::Mouse, Vec2::ZERO, release);
+        advance(&mut world, 50);
+        assert_eq!(count::<Pointer<Tap>>(&world), 2);
+    }
+
+    #[test]
+    fn exclusive_taps_wait_for_double_tap() {
+        let (mut world, _) = setup(1);
+        world.resource_mut::<GestureSettings>().exclusive_taps = true;
+        let press = PointerAction::Press(PointerButton::Primary);
+        let release = PointerAction::Release(PointerButton::Primary);
+
+        input(&mut world, PointerId::Mouse, Vec2::ZERO, press);
+        input(&mut world, PointerId::Mouse, Vec2::ZERO, release);
+        advance(&mut world, 50);
+        assert_eq!(count::<Pointer<Tap>>(&world), 0);
+        advance(&mut world, 400);
+        assert_eq!(count::<Pointer<Tap>>(&world), 1);
+    }
+
+    #[test]
+    fn swipe() {
+        let (mut world, _) = setup(1);
+        input(
+            &mut world,
+            PointerId::Touch(0),
+            Vec2::ZERO,
+            PointerAction::Press(PointerButton::Primary),
+        );
+        advance(&mut world, 100);
+        input(
+            &mut world,
+            PointerId::Touch(0),
+            Vec2::new(-100.0, 10.0),
+            PointerAction::Move {
+                delta: Vec2::new(-100.0, 10.0),
+            },
+        );
+        input(
+            &mut world,
+            PointerId::Touch(0),
+            Vec2::new(-100.0, 10.0),
+            PointerAction::Release(PointerButton::Primary),
+        );
+        advance(&mut world, 16);
+
+        let swipes = world.resource::<Messages<Pointer<Swipe>>>();
+        let swipe = swipes.iter_current_update_messages().next().unwrap();
+        assert_eq!(swipe.direction, SwipeDirection::Left);
+        assert_eq!(count::<Pointer<Tap>>(&world), 0);
+    }
+
+    #[test]
+    fn two_finger_pinch() {
+        let (mut world, _) = setup(1);
+        let press = PointerAction::Press(PointerButton::Primary);
+        input(
+            &mut world,
+            PointerId::Touch(0),
+            Vec2::new(-50.0, 0.0),
+            press,
+        );
+        input(&mut world, PointerId::Touch(1), Vec2::new(50.0, 0.0), press);
+        advance(&mut world, 16);
+        input(
+            &mut world,
+            PointerId::Touch(1),
+            Vec2::new(150.0, 0.0),
+            PointerAction::Move {
+                delta: Vec2::new(100.0, 0.0),
+            },
+        );
+        advance(&mut world, 16);
+
+        let pinches = world.resource::<Messages<Pointer<Pinch>>>();
+        let pinch = pinches.iter_current_update_messages().next().unwrap();
+        assert_eq!(pinch.scale, 2.0);
+        assert_eq!(pinch.pointer_location.position, Vec2::new(50.0, 0.0));
+        assert_eq!(count::<Pointer<Rotate>>(&world), 0);
+        assert_eq!(count::<Pointer<Pan>>(&world), 1);
+
+        // The touches of a two-finger gesture don't tap when they're released.
+        let release = PointerAction::Release(PointerButton::Primary);
+        input(
+            &mut world,
+            PointerId::Touch(0),
+            Vec2::new(-50.0, 0.0),
+            release,
+        );
+        input(
+            &mut world,
+            PointerId::Touch(1),
+            Vec2::new(150.0, 0.0),
+            release,
+        );
+        advance(&mut world, 16);
+        assert_eq!(count::<Pointer<Tap>>(&world), 0);
+    }
+}
@@ -157,12 +157,15 @@
 extern crate alloc;
 
 pub mod backend;
+pub mod drag_drop;
 pub mod events;
+pub mod gestures;
 pub mod hover;
 pub mod input;
 #[cfg(feature = "mesh_picking")]
 pub mod mesh_picking;
 pub mod pointer;
+pub mod selection;
 pub mod window;
 
 use bevy_app::{prelude::*, PluginGroupBuilder};
@@ -182,8 +185,8 @@ pub mod prelude {
     };
     #[doc(hidden)]
     pub use crate::{
-        events::*, input::PointerInputPlugin, pointer::PointerButton, DefaultPickingPlugins,
-        InteractionPlugin, Pickable, PickingPlugin,
+        drag_drop::*, events::*, gestures::*, input::PointerInputPlugin, pointer::PointerButton,
+        selection::*, DefaultPickingPlugins, InteractionPlugin, Pickable, PickingPlugin,
     };
 }
 
@@ -434,6 +437,20 @@ impl Plugin for InteractionPlugin {
             .add_message::<Pointer<Over>>()
             .add_message::<Pointer<Release>>()
             .add_message::<Pointer<Scroll>>()
+            .init_resource::<gestures::GestureSettings>()
+            .add_message::<Pointer<gestures::Tap>>()
+            .add_message::<Pointer<gestures::DoubleTap>>()
+            .add_message::<Pointer<gestures::LongPress>>()
+            .add_message::<Pointer<gestures::Swipe>>()
+            .add_message::<Pointer<gestures::Pinch>>()
+            .add_message::<Pointer<gestures::Rotate>>()
+            .add_message::<Pointer<gestures::Pan>>()
+            .init_resource::<drag_drop::PayloadDrags>()
+            .add_message::<Pointer<drag_drop::PayloadEnter>>()
+            .add_message::<Pointer<drag_drop::PayloadLeave>>()
+            .add_message::<Pointer<drag_drop::PayloadDrop>>()
+            .add_message::<Pointer<drag_drop::PayloadDragEnd>>()
+            .add_message::<bevy_window::FileDragAndDrop>()
             .add_systems(
                 PreUpdate,
                 (
@@ -441,6 +458,8 @@ impl Plugin for InteractionPlugin {
                     update_interactions,
                     (update_is_hovered, update_is_directly_hovered),
                     pointer_events,
+                    gestures::recognize_gestures,
+                    drag_drop::update_payload_drags,
                 )
                     .chain()
                     .in_set(PickingSystems::Hover),
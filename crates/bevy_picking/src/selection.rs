@@ -0,0 +1,968 @@
+//! Selects every entity inside a screen-space area, like the marquee selection of editors and
+//! strategy games.
+//!
+//! Unlike the rest of picking, which finds the entities under a single point, area selection finds
+//! all the entities whose projected bounds intersect a [`SelectionArea`]: either a [rectangle]
+//! or a [lasso] polygon. The entities are found with the [`Aabb`] and [`GlobalTransform`] of
+//! both 2d sprites and 3d meshes, respecting [`Pickable`] and [`RenderLayers`]. With the
+//! `mesh_picking` feature, [`SelectionPrecision::Geometry`] tests the triangles of meshes rather
+//! than their bounds.
+//!
+//! The [`AreaSelection`] system parameter selects the entities of an area on demand. The
+//! [`AreaSelectionPlugin`] instead turns pointer drags into areas, as configured by
+//! [`AreaSelectionSettings`], and sends an [`AreaSelected`] event when a drag is released:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_picking::prelude::*;
+//! # let mut world = World::default();
+//! world.add_observer(|selected: On<AreaSelected>| {
+//!     println!("Selected {:?}", selected.entities);
+//! });
+//! ```
+//!
+//! The areas being dragged are available in the [`AreaSelectionDrags`] resource, for example to
+//! draw the marquee.
+//!
+//! [rectangle]: SelectionArea::Rect
+//! [lasso]: SelectionArea::Lasso
+
+use alloc::borrow::Cow;
+
+use bevy_app::prelude::*;
+use bevy_camera::{
+    primitives::Aabb,
+    visibility::{InheritedVisibility, RenderLayers},
+    Camera, RenderTarget,
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{Mat4, Rect, Vec2, Vec3, Vec4, Vec4Swizzles};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_reflect::prelude::*;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::PrimaryWindow;
+
+#[cfg(feature = "mesh_picking")]
+use {
+    bevy_asset::Assets,
+    bevy_mesh::{Mesh, Mesh2d, Mesh3d, PrimitiveTopology},
+};
+
+use crate::{
+    hover::HoverMap,
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput},
+    Pickable, PickingSystems,
+};
+
+/// Adds [`AreaSelectionDrags`] and sends [`AreaSelected`] events when a pointer drags an area.
+///
+/// This isn't part of the [`DefaultPickingPlugins`](crate::DefaultPickingPlugins), as dragging
+/// an area conflicts with other uses of drags.
+#[derive(Default)]
+pub struct AreaSelectionPlugin;
+
+impl Plugin for AreaSelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AreaSelectionSettings>()
+            .init_resource::<AreaSelectionDrags>()
+            .add_message::<AreaSelected>()
+            .add_systems(
+                PreUpdate,
+                update_area_selections.in_set(PickingSystems::Last),
+            );
+    }
+}
+
+/// An area of a render target, in logical pixels like [`Location::position`].
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub enum SelectionArea {
+    /// An axis-aligned rectangle.
+    Rect(Rect),
+    /// A closed polygon, which may be concave or self-intersecting.
+    ///
+    /// Points are inside the polygon according to the even-odd rule.
+    Lasso(Vec<Vec2>),
+}
+
+/// Whether entities must be partially or entirely inside a [`SelectionArea`] to be selected.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+#[reflect(Clone, PartialEq, Hash, Default)]
+pub enum SelectionMode {
+    /// Select the entities that intersect the area.
+    #[default]
+    Intersect,
+    /// Select the entities entirely inside the area.
+    Contain,
+}
+
+/// The shape used to test whether entities are inside a [`SelectionArea`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+#[reflect(Clone, PartialEq, Hash, Default)]
+pub enum SelectionPrecision {
+    /// Test the projection of the [`Aabb`] of entities.
+    #[default]
+    Bounds,
+    /// Test the triangles of the meshes of entities, and the [`Aabb`] of other entities.
+    ///
+    /// This requires the `mesh_picking` feature, and behaves like [`SelectionPrecision::Bounds`]
+    /// otherwise.
+    Geometry,
+}
+
+/// The shape of the [`SelectionArea`] dragged by a pointer.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+#[reflect(Clone, PartialEq, Hash, Default)]
+pub enum SelectionShape {
+    /// A rectangle between where the drag started and the pointer.
+    #[default]
+    Box,
+    /// A polygon following the path of the pointer.
+    Lasso,
+}
+
+/// Configures how the [`AreaSelectionPlugin`] turns drags into [`AreaSelected`] events.
+#[derive(Clone, Debug, Resource, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct AreaSelectionSettings {
+    /// The shape of the dragged area.
+    pub shape: SelectionShape,
+    /// The button that drags an area.
+    pub button: PointerButton,
+    /// Whether entities must be partially or entirely inside the area to be selected.
+    pub mode: SelectionMode,
+    /// The shape used to test whether entities are inside the area.
+    pub precision: SelectionPrecision,
+    /// The distance, in logical pixels, a pointer must move for its drag to select an area.
+    ///
+    /// Releasing the pointer before that doesn't send an [`AreaSelected`] event, so the press can
+    /// be handled as a click instead.
+    pub min_drag_distance: f32,
+    /// Whether drags only select an area when they start on an empty spot, rather than on a
+    /// hovered entity.
+    pub require_empty_start: bool,
+}
+
+impl Default for AreaSelectionSettings {
+    fn default() -> Self {
+        Self {
+            shape: SelectionShape::Box,
+            button: PointerButton::Primary,
+            mode: SelectionMode::Intersect,
+            precision: SelectionPrecision::Bounds,
+            min_drag_distance: 5.0,
+            require_empty_start: true,
+        }
+    }
+}
+
+/// Sent when a pointer releases a drag that selected an area.
+#[derive(Message, Event, Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct AreaSelected {
+    /// The pointer that dragged the area.
+    pub pointer_id: PointerId,
+    /// The dragged area.
+    pub area: SelectionArea,
+    /// The selected entities, from every camera whose viewport contains the start of the drag.
+    pub entities: Vec<Entity>,
+}
+
+/// The areas being dragged by each pointer.
+#[derive(Debug, Default, Deref, DerefMut, Resource)]
+pub struct AreaSelectionDrags(pub HashMap<PointerId, AreaSelectionDrag>);
+
+/// An area being dragged by a pointer.
+#[derive(Clone, Debug)]
+pub struct AreaSelectionDrag {
+    /// Where the drag started.
+    pub start: Location,
+    /// The area dragged so far.
+    pub area: SelectionArea,
+    /// The cameras whose viewport contains the start of the drag.
+    pub cameras: Vec<Entity>,
+    /// Whether the pointer moved further than [`AreaSelectionSettings::min_drag_distance`].
+    pub is_selecting: bool,
+}
+
+impl SelectionArea {
+    /// Returns the vertices of the outline of the area.
+    pub fn vertices(&self) -> Cow<'_, [Vec2]> {
+        match self {
+            Self::Rect(rect) => Cow::Owned(vec![
+                rect.min,
+                Vec2::new(rect.max.x, rect.min.y),
+                rect.max,
+                Vec2::new(rect.min.x, rect.max.y),
+            ]),
+            Self::Lasso(points) => Cow::Borrowed(points),
+        }
+    }
+
+    /// Returns the smallest rectangle containing the area.
+    pub fn bounding_rect(&self) -> Rect {
+        match self {
+            Self::Rect(rect) => *rect,
+            Self::Lasso(points) => bounding_rect(points),
+        }
+    }
+
+    /// Returns `true` if `point` is inside the area.
+    pub fn contains(&self, point: Vec2) -> bool {
+        match self {
+            Self::Rect(rect) => rect.contains(point),
+            Self::Lasso(points) => polygon_contains(points, point),
+        }
+    }
+
+    /// Returns `true` if the convex `polygon` intersects the area.
+    pub fn intersects_polygon(&self, polygon: &[Vec2]) -> bool {
+        if polygon.is_empty() || !rects_overlap(self.bounding_rect(), bounding_rect(polygon)) {
+            return false;
+        }
+        let vertices = self.vertices();
+        polygon.iter().any(|point| self.contains(*point))
+            || vertices
+                .iter()
+                .any(|point| polygon_contains(polygon, *point))
+            || edges_cross(&vertices, polygon)
+    }
+
+    /// Returns `true` if the `polygon` is entirely inside the area.
+    pub fn contains_polygon(&self, polygon: &[Vec2]) -> bool {
+        if polygon.is_empty() || !polygon.iter().all(|point| self.contains(*point)) {
+            return false;
+        }
+        match self {
+            // A rectangle is convex, so it contains every edge between its points.
+            Self::Rect(_) => true,
+            Self::Lasso(points) => !edges_cross(points, polygon),
+        }
+    }
+
+    fn matches(&self, polygon: &[Vec2], mode: SelectionMode) -> bool {
+        match mode {
+            SelectionMode::Intersect => self.intersects_polygon(polygon),
+            SelectionMode::Contain => self.contains_polygon(polygon),
+        }
+    }
+}
+
+fn bounding_rect(points: &[Vec2]) -> Rect {
+    let min = points.iter().copied().fold(Vec2::MAX, Vec2::min);
+    let max = points.iter().copied().fold(Vec2::MIN, Vec2::max);
+    Rect { min, max }
+}
+
+/// Like [`Rect::intersect`], but also returns `true` for rectangles that only touch.
+fn rects_overlap(a: Rect, b: Rect) -> bool {
+    a.min.cmple(b.max).all() && b.min.cmple(a.max).all()
+}
+
+/// Returns the edges of a closed polygon.
+fn edges<T: Copy>(polygon: &[T]) -> impl Iterator<Item = (T, T)> + '_ {
+    polygon
+        .iter()
+        .copied()
+        .zip(polygon.iter().copied().cycle().skip(1))
+}
+
+/// Tests whether `point` is inside `polygon` with the even-odd rule.
+fn polygon_contains(polygon: &[Vec2], point: Vec2) -> bool {
+    if polygon.len() < 3 {
+        return false;
+    }
+    edges(polygon).fold(false, |inside, (a, b)| {
+        let crosses = (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x;
+        inside != crosses
+    })
+}
+
+/// Returns `true` if any edge of `a` crosses an edge of `b`.
+fn edges_cross(a: &[Vec2], b: &[Vec2]) -> bool {
+    edges(a).any(|(a1, a2)| {
+        edges(b).any(|(b1, b2)| {
+            let side = |from: Vec2, to: Vec2, point: Vec2| (to - from).perp_dot(point - from);
+            (side(b1, b2, a1) > 0.0) != (side(b1, b2, a2) > 0.0)
+                && (side(a1, a2, b1) > 0.0) != (side(a1, a2, b2) > 0.0)
+        })
+    })
+}
+
+/// Returns the convex hull of `points`, counterclockwise.
+fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    // Andrew's monotone chain, building the lower and then the upper hull.
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+    for reverse in [false, true] {
+        let start = hull.len();
+        for i in 0..points.len() {
+            let point = points[if reverse { points.len() - 1 - i } else { i }];
+            while hull.len() >= start + 2
+                && (hull[hull.len() - 1] - hull[hull.len() - 2])
+                    .perp_dot(point - hull[hull.len() - 2])
+                    <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point of each half is the first point of the other half.
+        hull.pop();
+    }
+    hull
+}
+
+/// Returns the six faces of `aabb`, as quads of corners.
+fn aabb_faces(aabb: &Aabb) -> impl Iterator<Item = [Vec3; 4]> {
+    let center = Vec3::from(aabb.center);
+    let half_extents = Vec3::from(aabb.half_extents);
+    (0..3).flat_map(move |axis| {
+        [-1.0, 1.0].map(|side| {
+            [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].map(|(u, v)| {
+                let mut corner = Vec3::ZERO;
+                corner[axis] = side;
+                corner[(axis + 1) % 3] = u;
+                corner[(axis + 2) % 3] = v;
+                center + half_extents * corner
+            })
+        })
+    })
+}
+
+/// Clips the convex polygon `points` to the side of a plane where `distance` is positive.
+fn clip_polygon(points: &[Vec4], distance: fn(Vec4) -> f32) -> Vec<Vec4> {
+    let mut clipped = Vec::with_capacity(points.len() + 1);
+    for (a, b) in edges(points) {
+        let (distance_a, distance_b) = (distance(a), distance(b));
+        if distance_a >= 0.0 {
+            clipped.push(a);
+        }
+        if (distance_a >= 0.0) != (distance_b >= 0.0) {
+            clipped.push(a.lerp(b, distance_a / (distance_a - distance_b)));
+        }
+    }
+    clipped
+}
+
+/// Projects convex polygons from world space to the viewport of a camera, keeping only the part
+/// between its near and far planes.
+struct ViewportProjector {
+    clip_from_world: Mat4,
+    viewport: Rect,
+}
+
+impl ViewportProjector {
+    fn new(camera: &Camera, camera_transform: &GlobalTransform) -> Option<Self> {
+        Some(Self {
+            clip_from_world: camera.clip_from_view()
+                * Mat4::from(camera_transform.affine().inverse()),
+            viewport: camera.logical_viewport_rect()?,
+        })
+    }
+
+    /// Returns the projection of the part of `polygon` in front of the camera, which is empty if
+    /// the polygon is entirely outside of the near and far planes.
+    fn project_polygon(&self, polygon: impl IntoIterator<Item = Vec3>) -> Vec<Vec2> {
+        let mut points: Vec<Vec4> = polygon
+            .into_iter()
+            .map(|point| self.clip_from_world * point.extend(1.0))
+            .collect();
+        // In clip space, the points between the near and far planes have `0 <= z <= w`.
+        points = clip_polygon(&points, |point| point.w - point.z);
+        points = clip_polygon(&points, |point| point.z);
+        points
+            .into_iter()
+            .map(|point| {
+                // Like `Camera::world_to_viewport`, with the origin at the top.
+                let ndc = point.xy() / point.w;
+                (Vec2::new(ndc.x, -ndc.y) + Vec2::ONE) / 2.0 * self.viewport.size()
+                    + self.viewport.min
+            })
+            .collect()
+    }
+}
+
+/// Selects the entities inside a [`SelectionArea`] of a camera.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::{Rect, Vec2};
+/// # use bevy_picking::prelude::*;
+/// fn select(camera: Single<Entity, With<bevy_camera::Camera>>, area_selection: AreaSelection) {
+///     let area = SelectionArea::Rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+///     for entity in area_selection.select(
+///         *camera,
+///         &area,
+///         SelectionMode::Contain,
+///         SelectionPrecision::Bounds,
+///     ) {
+///         println!("{entity} is entirely inside the area");
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct AreaSelection<'w, 's> {
+    cameras: Query<
+        'w,
+        's,
+        (
+            &'static Camera,
+            &'static GlobalTransform,
+            Option<&'static RenderLayers>,
+        ),
+    >,
+    targets: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Aabb,
+            &'static GlobalTransform,
+            Option<&'static Pickable>,
+            Option<&'static RenderLayers>,
+            Option<&'static InheritedVisibility>,
+        ),
+    >,
+    #[cfg(feature = "mesh_picking")]
+    meshes: Res<'w, Assets<Mesh>>,
+    #[cfg(feature = "mesh_picking")]
+    mesh_handles: Query<'w, 's, (Option<&'static Mesh2d>, Option<&'static Mesh3d>)>,
+}
+
+impl<'w, 's> AreaSelection<'w, 's> {
+    /// Returns the visible entities rendered by `camera` that are inside `area`.
+    ///
+    /// Entities that aren't hoverable according to their [`Pickable`], or that don't share a
+    /// [`RenderLayers`] layer with the camera, are never selected. Entities partially behind the
+    /// camera are tested with the part of their bounds (or triangles) in front of it, clipped
+    /// against the camera's near and far planes.
+    pub fn select(
+        &self,
+        camera: Entity,
+        area: &SelectionArea,
+        mode: SelectionMode,
+        precision: SelectionPrecision,
+    ) -> Vec<Entity> {
+        let Ok((camera, camera_transform, camera_layers)) = self.cameras.get(camera) else {
+            return Vec::new();
+        };
+        let camera_layers = camera_layers.cloned().unwrap_or_default();
+        let Some(projector) = ViewportProjector::new(camera, camera_transform) else {
+            return Vec::new();
+        };
+
+        self.targets
+            .iter()
+            .filter(|(_, _, _, pickable, layers, visibility)| {
+                pickable.is_none_or(|pickable| pickable.is_hoverable)
+                    && visibility.is_none_or(|visibility| visibility.get())
+                    // Entities missing render layers are on the default layer 0.
+                    && camera_layers.intersects(&layers.cloned().unwrap_or_default())
+            })
+            .filter(|(entity, aabb, transform, ..)| {
+                let bounds = convex_hull(
+                    aabb_faces(aabb)
+                        .flat_map(|face| {
+                            projector.project_polygon(
+                                face.map(|corner| transform.transform_point(corner)),
+                            )
+                        })
+                        .collect(),
+                );
+                match (precision, mode) {
+                    (SelectionPrecision::Bounds, _) => area.matches(&bounds, mode),
+                    // Entities entirely inside the area can't have triangles outside of it.
+                    (SelectionPrecision::Geometry, SelectionMode::Contain)
+                        if area.contains_polygon(&bounds) =>
+                    {
+                        true
+                    }
+                    (SelectionPrecision::Geometry, _) if !area.intersects_polygon(&bounds) => false,
+                    (SelectionPrecision::Geometry, _) => self
+                        .geometry_matches(*entity, transform, area, mode, &projector)
+                        .unwrap_or_else(|| area.matches(&bounds, mode)),
+                }
+            })
+            .map(|(entity, ..)| entity)
+            .collect()
+    }
+
+    /// Tests the triangles of the mesh of `entity`, or returns `None` if it has no mesh.
+    #[cfg(feature = "mesh_picking")]
+    fn geometry_matches(
+        &self,
+        entity: Entity,
+        transform: &GlobalTransform,
+        area: &SelectionArea,
+        mode: SelectionMode,
+        projector: &ViewportProjector,
+    ) -> Option<bool> {
+        let (mesh2d, mesh3d) = self.mesh_handles.get(entity).ok()?;
+        let handle = mesh3d.map(|m| &m.0).or(mesh2d.map(|m| &m.0))?;
+        let mesh = self.meshes.get(handle)?;
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let positions = mesh
+            .try_attribute(Mesh::ATTRIBUTE_POSITION)
+            .ok()?
+            .as_float3()?;
+        let indices: Vec<usize> = match mesh.try_indices().ok() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+
+        let mut triangles = indices.chunks_exact(3).map(|triangle| {
+            triangle
+                .iter()
+                .map(|&index| Some(transform.transform_point(Vec3::from(*positions.get(index)?))))
+                .collect::<Option<Vec<_>>>()
+                .map(|triangle| projector.project_polygon(triangle))
+        });
+        Some(match mode {
+            SelectionMode::Intersect => triangles.any(|triangle| {
+                triangle.is_some_and(|triangle| area.intersects_polygon(&triangle))
+            }),
+            // Triangles entirely behind the camera are ignored, like the parts of the others.
+            SelectionMode::Contain => triangles.all(|triangle| {
+                triangle
+                    .is_some_and(|triangle| triangle.is_empty() || area.contains_polygon(&triangle))
+            }),
+        })
+    }
+
+    #[cfg(not(feature = "mesh_picking"))]
+    fn geometry_matches(
+        &self,
+        _entity: Entity,
+        _transform: &GlobalTransform,
+        _area: &SelectionArea,
+        _mode: SelectionMode,
+        _projector: &ViewportProjector,
+    ) -> Option<bool> {
+        None
+    }
+}
+
+/// Tracks the areas dragged by pointers, and sends [`AreaSelected`] events when the drags are
+/// released.
+pub fn update_area_selections(
+    mut input_events: MessageReader<PointerInput>,
+    hover_map: Res<HoverMap>,
+    settings: Res<AreaSelectionSettings>,
+    mut drags: ResMut<AreaSelectionDrags>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    cameras: Query<(Entity, &Camera, &RenderTarget)>,
+    area_selection: AreaSelection,
+    mut commands: Commands,
+    mut area_selected_writer: MessageWriter<AreaSelected>,
+) {
+    for PointerInput {
+        pointer_id,
+        location,
+        action,
+    } in input_events.read().cloned()
+    {
+        match action {
+            PointerAction::Press(button) if button == settings.button => {
+                if settings.require_empty_start
+                    && hover_map
+                        .get(&pointer_id)
+                        .is_some_and(|hovered| !hovered.is_empty())
+                {
+                    continue;
+                }
+                let cameras = cameras
+                    .iter()
+                    .filter(|(_, camera, render_target)| {
+                        camera.is_active
+                            && location.is_in_viewport(camera, render_target, &primary_window)
+                    })
+                    .map(|(entity, ..)| entity)
+                    .collect::<Vec<_>>();
+                if cameras.is_empty() {
+                    continue;
+                }
+                let area = match settings.shape {
+                    SelectionShape::Box => SelectionArea::Rect(Rect::from_corners(
+                        location.position,
+                        location.position,
+                    )),
+                    SelectionShape::Lasso => SelectionArea::Lasso(vec![location.position]),
+                };
+                drags.insert(
+                    pointer_id,
+                    AreaSelectionDrag {
+                        start: location,
+                        area,
+                        cameras,
+                        is_selecting: false,
+                    },
+                );
+            }
+            PointerAction::Move { .. } => {
+                let Some(drag) = drags.get_mut(&pointer_id) else {
+                    continue;
+                };
+                if drag.start.target != location.target {
+                    continue;
+                }
+                match &mut drag.area {
+                    SelectionArea::Rect(rect) => {
+                        *rect = Rect::from_corners(drag.start.position, location.position);
+                    }
+                    SelectionArea::Lasso(points) => {
+                        if points.last() != Some(&location.position) {
+                            points.push(location.position);
+                        }
+                    }
+                }
+                if drag.start.position.distance(location.position) >= settings.min_drag_distance {
+                    drag.is_selecting = true;
+                }
+            }
+            PointerAction::Release(button) if button == settings.button => {
+                let Some(drag) = drags.remove(&pointer_id) else {
+                    continue;
+                };
+                if !drag.is_selecting {
+                    continue;
+                }
+                let mut selected = HashSet::new();
+                let entities = drag
+                    .cameras
+                    .iter()
+                    .flat_map(|camera| {
+                        area_selection.select(
+                            *camera,
+                            &drag.area,
+                            settings.mode,
+                            settings.precision,
+                        )
+                    })
+                    .filter(|entity| selected.insert(*entity))
+                    .collect();
+                let area_selected = AreaSelected {
+                    pointer_id,
+                    area: drag.area,
+                    entities,
+                };
+                commands.trigger(area_selected.clone());
+                area_selected_writer.write(area_selected);
+            }
+            PointerAction::Cancel => {
+                drags.remove(&pointer_id);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_camera::{
+        CameraProjection, ComputedCameraValues, NormalizedRenderTarget, PerspectiveProjection,
+        RenderTargetInfo,
+    };
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_math::UVec2;
+    use bevy_window::Window;
+
+    /// Creates an app with a 100x100 camera at the origin, looking down -Z.
+    fn test_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(AreaSelectionPlugin)
+            .init_resource::<HoverMap>()
+            .add_message::<PointerInput>();
+        #[cfg(feature = "mesh_picking")]
+        app.init_resource::<Assets<Mesh>>();
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera {
+                    computed: ComputedCameraValues {
+                        clip_from_view: PerspectiveProjection {
+                            aspect_ratio: 1.0,
+                            ..Default::default()
+                        }
+                        .get_clip_from_view(),
+                        target_info: Some(RenderTargetInfo {
+                            physical_size: UVec2::splat(100),
+                            scale_factor: 1.0,
+                        }),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                RenderTarget::None {
+                    size: UVec2::splat(100),
+                },
+                GlobalTransform::IDENTITY,
+            ))
+            .id();
+        (app, camera)
+    }
+
+    /// Spawns a unit cube at `translation`.
+    fn spawn_cube(app: &mut App, translation: Vec3, extra: impl Bundle) -> Entity {
+        app.world_mut()
+            .spawn((
+                Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5)),
+                GlobalTransform::from_translation(translation),
+                extra,
+            ))
+            .id()
+    }
+
+    fn select(app: &mut App, camera: Entity, rect: Rect, mode: SelectionMode) -> Vec<Entity> {
+        let mut entities = app
+            .world_mut()
+            .run_system_once(move |area_selection: AreaSelection| {
+                area_selection.select(
+                    camera,
+                    &SelectionArea::Rect(rect),
+                    mode,
+                    SelectionPrecision::Bounds,
+                )
+            })
+            .unwrap();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn select_with_camera() {
+        let (mut app, camera) = test_app();
+        let visible = spawn_cube(&mut app, Vec3::new(0.0, 0.0, -5.0), ());
+        spawn_cube(&mut app, Vec3::new(0.0, 0.0, -5.0), Pickable::IGNORE);
+        spawn_cube(
+            &mut app,
+            Vec3::new(0.0, 0.0, -5.0),
+            InheritedVisibility::HIDDEN,
+        );
+        spawn_cube(&mut app, Vec3::new(0.0, 0.0, -5.0), RenderLayers::layer(1));
+        spawn_cube(&mut app, Vec3::new(5.0, 0.0, -5.0), ());
+        spawn_cube(&mut app, Vec3::new(0.0, 0.0, 5.0), ());
+        // A long box that goes through the camera, so its nearest part covers the viewport.
+        let through = app
+            .world_mut()
+            .spawn((
+                Aabb::from_min_max(Vec3::new(-0.5, -0.5, -5.0), Vec3::new(0.5, 0.5, 5.0)),
+                GlobalTransform::IDENTITY,
+            ))
+            .id();
+
+        let center = Rect::new(30.0, 30.0, 70.0, 70.0);
+        let mut expected = vec![visible, through];
+        expected.sort();
+        assert_eq!(
+            select(&mut app, camera, center, SelectionMode::Intersect),
+            expected
+        );
+        assert_eq!(
+            select(&mut app, camera, center, SelectionMode::Contain),
+            vec![visible]
+        );
+
+        // Render layers of the camera.
+        app.world_mut()
+            .entity_mut(camera)
+            .insert(RenderLayers::layer(1));
+        assert_eq!(
+            select(&mut app, camera, center, SelectionMode::Intersect).len(),
+            1
+        );
+
+        // The cube is 5 units away, so it doesn't reach the edge of the viewport.
+        let corner = Rect::new(0.0, 0.0, 20.0, 20.0);
+        assert_eq!(
+            select(&mut app, camera, corner, SelectionMode::Intersect),
+            vec![]
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct Selections(Vec<Vec<Entity>>);
+
+    fn drag_app() -> (App, Entity) {
+        let (mut app, _) = test_app();
+        app.init_resource::<Selections>().add_observer(
+            |selected: On<AreaSelected>, mut selections: ResMut<Selections>| {
+                selections.0.push(selected.entities.clone());
+            },
+        );
+        let cube = spawn_cube(&mut app, Vec3::new(0.0, 0.0, -5.0), ());
+        app.update();
+        (app, cube)
+    }
+
+    fn send(app: &mut App, action: PointerAction, position: Vec2) {
+        let location = Location {
+            target: NormalizedRenderTarget::None {
+                width: 100,
+                height: 100,
+            },
+            position,
+        };
+        app.world_mut()
+            .write_message(PointerInput::new(PointerId::Mouse, location, action));
+        app.update();
+    }
+
+    fn drag(app: &mut App, from: Vec2, to: Vec2) {
+        send(app, PointerAction::Press(PointerButton::Primary), from);
+        send(app, PointerAction::Move { delta: to - from }, to);
+        send(app, PointerAction::Release(PointerButton::Primary), to);
+    }
+
+    fn take_selections(app: &mut App) -> Vec<Vec<Entity>> {
+        core::mem::take(&mut app.world_mut().resource_mut::<Selections>().0)
+    }
+
+    #[test]
+    fn drag_area() {
+        let (mut app, cube) = drag_app();
+
+        drag(&mut app, Vec2::splat(30.0), Vec2::splat(70.0));
+        assert_eq!(take_selections(&mut app), vec![vec![cube]]);
+
+        // Short drags are left to be handled as clicks.
+        drag(&mut app, Vec2::splat(30.0), Vec2::splat(32.0));
+        assert_eq!(take_selections(&mut app), Vec::<Vec<Entity>>::new());
+        app.world_mut()
+            .resource_mut::<AreaSelectionSettings>()
+            .min_drag_distance = 1.0;
+        drag(&mut app, Vec2::splat(48.0), Vec2::splat(50.0));
+        assert_eq!(take_selections(&mut app), vec![vec![cube]]);
+    }
+
+    #[test]
+    fn drag_area_from_hovered_entity() {
+        let (mut app, cube) = drag_app();
+        app.world_mut()
+            .resource_mut::<HoverMap>()
+            .entry(PointerId::Mouse)
+            .or_default()
+            .insert(
+                cube,
+                crate::backend::HitData::new(Entity::PLACEHOLDER, 0.0, None, None),
+            );
+
+        drag(&mut app, Vec2::splat(30.0), Vec2::splat(70.0));
+        assert_eq!(take_selections(&mut app), Vec::<Vec<Entity>>::new());
+
+        app.world_mut()
+            .resource_mut::<AreaSelectionSettings>()
+            .require_empty_start = false;
+        drag(&mut app, Vec2::splat(30.0), Vec2::splat(70.0));
+        assert_eq!(take_selections(&mut app), vec![vec![cube]]);
+    }
+
+    #[test]
+    fn cancel_drag_area() {
+        let (mut app, _) = drag_app();
+        send(
+            &mut app,
+            PointerAction::Press(PointerButton::Primary),
+            Vec2::splat(30.0),
+        );
+        send(
+            &mut app,
+            PointerAction::Move {
+                delta: Vec2::splat(40.0),
+            },
+            Vec2::splat(70.0),
+        );
+        assert!(app.world().resource::<AreaSelectionDrags>()[&PointerId::Mouse].is_selecting);
+
+        send(&mut app, PointerAction::Cancel, Vec2::splat(70.0));
+        assert!(app.world().resource::<AreaSelectionDrags>().is_empty());
+        send(
+            &mut app,
+            PointerAction::Release(PointerButton::Primary),
+            Vec2::splat(70.0),
+        );
+        assert_eq!(take_selections(&mut app), Vec::<Vec<Entity>>::new());
+    }
+
+    fn square(center: Vec2, half_size: f32) -> Vec<Vec2> {
+        SelectionArea::Rect(Rect::from_center_half_size(center, Vec2::splat(half_size)))
+            .vertices()
+            .into_owned()
+    }
+
+    #[test]
+    fn rect_area() {
+        let area = SelectionArea::Rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+
+        let inside = square(Vec2::new(50.0, 50.0), 10.0);
+        assert!(area.intersects_polygon(&inside));
+        assert!(area.contains_polygon(&inside));
+
+        let overlapping = square(Vec2::new(100.0, 50.0), 10.0);
+        assert!(area.intersects_polygon(&overlapping));
+        assert!(!area.contains_polygon(&overlapping));
+
+        // The area is inside the polygon.
+        let around = square(Vec2::new(50.0, 50.0), 100.0);
+        assert!(area.intersects_polygon(&around));
+        assert!(!area.contains_polygon(&around));
+
+        let outside = square(Vec2::new(150.0, 50.0), 10.0);
+        assert!(!area.intersects_polygon(&outside));
+    }
+
+    #[test]
+    fn concave_lasso_area() {
+        // A "U" shape, open at the top.
+        let area = SelectionArea::Lasso(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 0.0),
+            Vec2::new(30.0, 70.0),
+            Vec2::new(70.0, 70.0),
+            Vec2::new(70.0, 0.0),
+            Vec2::new(100.0, 0.0),
+            Vec2::new(100.0, 100.0),
+            Vec2::new(0.0, 100.0),
+        ]);
+
+        assert!(area.contains_polygon(&square(Vec2::new(15.0, 50.0), 5.0)));
+        assert!(!area.intersects_polygon(&square(Vec2::new(50.0, 30.0), 5.0)));
+
+        // Every corner is inside the area, but the polygon crosses the gap of the "U".
+        let across = square(Vec2::new(50.0, 50.0), 40.0);
+        assert!(across.iter().all(|corner| area.contains(*corner)));
+        assert!(area.intersects_polygon(&across));
+        assert!(!area.contains_polygon(&across));
+    }
+
+    #[test]
+    fn convex_hull_of_projected_corners() {
+        let hull = convex_hull(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(5.0, 5.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+            Vec2::new(0.0, 0.0),
+        ]);
+        assert_eq!(hull.len(), 4);
+        assert!(!hull.contains(&Vec2::new(5.0, 5.0)));
+
+        // Flat bounds, like the bounds of a sprite seen from the side, project to a segment.
+        let segment = convex_hull(vec![Vec2::ZERO, Vec2::X, Vec2::ZERO, Vec2::X]);
+        assert_eq!(segment, vec![Vec2::ZERO, Vec2::X]);
+        let area = SelectionArea::Rect(Rect::new(-1.0, -1.0, 0.5, 1.0));
+        assert!(area.intersects_polygon(&segment));
+    }
+}
@@ -15,6 +15,8 @@ mod picking_backend;
 mod sprite;
 #[cfg(feature = "bevy_text")]
 mod text2d;
+#[cfg(all(feature = "bevy_text", feature = "bevy_picking"))]
+mod text2d_selection;
 mod texture_slice;
 
 /// The sprite prelude.
@@ -49,6 +51,8 @@ pub use picking_backend::*;
 pub use sprite::*;
 #[cfg(feature = "bevy_text")]
 pub use text2d::*;
+#[cfg(all(feature = "bevy_text", feature = "bevy_picking"))]
+pub use text2d_selection::*;
 pub use texture_slice::*;
 
 use bevy_app::prelude::*;
@@ -95,6 +99,10 @@ impl Plugin for SpritePlugin {
 
         #[cfg(feature = "bevy_picking")]
         app.add_plugins(SpritePickingPlugin);
+
+        #[cfg(all(feature = "bevy_text", feature = "bevy_picking"))]
+        app.add_observer(text2d_selection_on_press)
+            .add_observer(text2d_selection_on_drag);
     }
 }
 
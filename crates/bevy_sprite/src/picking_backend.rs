@@ -5,6 +5,9 @@
 //! A sprite is picked only when a pointer is over an opaque pixel.
 //! Alternatively, you can configure picking to be based on sprite bounds.
 //!
+//! With the `bevy_text` feature, `Text2d` entities are picked by their bounds as well, reporting
+//! hits for the individual text spans under the pointer.
+//!
 //! ## Implementation Notes
 //!
 //! - The `position` reported in `HitData` in world space, and the `normal` is a normalized
@@ -81,6 +84,9 @@ impl Plugin for SpritePickingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SpritePickingSettings>()
             .add_systems(PreUpdate, sprite_picking.in_set(PickingSystems::Backend));
+
+        #[cfg(feature = "bevy_text")]
+        app.add_systems(PreUpdate, text2d_picking.in_set(PickingSystems::Backend));
     }
 }
 
@@ -301,3 +307,168 @@ fn sprite_picking(
         pointer_hits_writer.write(PointerHits::new(pointer, picks, order as f32));
     });
 }
+
+/// Picks [`Text2d`](crate::Text2d) entities by their bounds. When the pointer is over a span of
+/// the text, the hit is reported for the span entity rather than the text entity, so that spans
+/// such as links can respond to pointer events. Events on spans bubble up to the text entity.
+#[cfg(feature = "bevy_text")]
+fn text2d_picking(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(
+        Entity,
+        &Camera,
+        &RenderTarget,
+        &GlobalTransform,
+        &Projection,
+        Has<SpritePickingCamera>,
+        Option<&RenderLayers>,
+    )>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    settings: Res<SpritePickingSettings>,
+    text_query: Query<
+        (
+            Entity,
+            &bevy_text::TextLayoutInfo,
+            &bevy_text::ComputedTextBlock,
+            &bevy_text::TextBounds,
+            &GlobalTransform,
+            &Anchor,
+            &Pickable,
+            &ViewVisibility,
+            Option<&RenderLayers>,
+        ),
+        With<crate::Text2d>,
+    >,
+    mut pointer_hits_writer: MessageWriter<PointerHits>,
+    ray_map: Res<RayMap>,
+) {
+    let mut sorted_texts: Vec<_> = text_query
+        .iter()
+        .filter(|(_, _, _, _, transform, _, _, vis, _)| !transform.affine().is_nan() && vis.get())
+        .collect();
+    radsort::sort_by_key(&mut sorted_texts, |(_, _, _, _, transform, _, _, _, _)| {
+        -transform.translation().z
+    });
+
+    let primary_window = primary_window.single().ok();
+
+    for (ray_id, ray) in ray_map.iter() {
+        let Ok((
+            cam_entity,
+            camera,
+            render_target,
+            cam_transform,
+            Projection::Orthographic(cam_ortho),
+            cam_can_pick,
+            cam_render_layers,
+        )) = cameras.get(ray_id.camera)
+        else {
+            continue;
+        };
+
+        let marker_requirement = !settings.require_markers || cam_can_pick;
+        if !camera.is_active || !marker_requirement {
+            continue;
+        }
+
+        let Some(location) = pointers
+            .iter()
+            .find_map(|(id, loc)| (*id == ray_id.pointer).then_some(loc.location.as_ref())?)
+        else {
+            continue;
+        };
+
+        if render_target
+            .normalize(primary_window)
+            .is_none_or(|x| x != location.target)
+        {
+            continue;
+        }
+
+        if let Some(viewport) = camera.logical_viewport_rect()
+            && !viewport.contains(location.position)
+        {
+            continue;
+        }
+
+        let cursor_ray_len = cam_ortho.far - cam_ortho.near;
+        let cursor_ray_end = ray.origin + ray.direction * cursor_ray_len;
+
+        let mut picks = Vec::new();
+        for &(
+            entity,
+            layout_info,
+            computed_block,
+            bounds,
+            transform,
+            anchor,
+            pickable,
+            _,
+            text_render_layers,
+        ) in &sorted_texts
+        {
+            if !cam_render_layers
+                .unwrap_or_default()
+                .intersects(text_render_layers.unwrap_or_default())
+            {
+                continue;
+            }
+
+            // Intersect the cursor segment with the plane of the text, see `sprite_picking`.
+            let world_to_text = transform.affine().inverse();
+            let cursor_start = world_to_text.transform_point3(ray.origin);
+            let cursor_end = world_to_text.transform_point3(cursor_ray_end);
+            if cursor_start.z == cursor_end.z {
+                continue;
+            }
+            let lerp_factor = f32::inverse_lerp(cursor_start.z, cursor_end.z, 0.0);
+            if !(0.0..=1.0).contains(&lerp_factor) {
+                continue;
+            }
+            let cursor_pos = cursor_start.lerp(cursor_end, lerp_factor).xy();
+
+            // Convert to the coordinates of the text layout, whose origin is the top left corner
+            // and whose y axis points down.
+            let size = Vec2::new(
+                bounds.width.unwrap_or(layout_info.size.x),
+                bounds.height.unwrap_or(layout_info.size.y),
+            );
+            let top_left = (Anchor::TOP_LEFT.0 - anchor.as_vec()) * size;
+            let layout_pos = (cursor_pos - top_left) * Vec2::new(1., -1.);
+            if !Rect::from_corners(Vec2::ZERO, size).contains(layout_pos) {
+                continue;
+            }
+
+            let physical_pos = layout_pos * layout_info.scale_factor;
+            let span_entity = layout_info
+                .run_geometry
+                .iter()
+                .find(|run| run.bounds.contains(physical_pos))
+                .and_then(|run| computed_block.entities().get(run.span_index))
+                .map(|text_entity| text_entity.entity)
+                .unwrap_or(entity);
+
+            let hit_pos_world = transform.transform_point(cursor_pos.extend(0.0));
+            let hit_pos_cam = cam_transform
+                .affine()
+                .inverse()
+                .transform_point3(hit_pos_world);
+            let depth = -cam_ortho.near - hit_pos_cam.z;
+            picks.push((
+                span_entity,
+                HitData::new(
+                    cam_entity,
+                    depth,
+                    Some(hit_pos_world),
+                    Some(*transform.back()),
+                ),
+            ));
+
+            if pickable.should_block_lower {
+                break;
+            }
+        }
+
+        pointer_hits_writer.write(PointerHits::new(ray_id.pointer, picks, camera.order as f32));
+    }
+}
@@ -22,7 +22,7 @@ use bevy_reflect::{prelude::ReflectDefault, Reflect};
 use bevy_text::{
     ComputedTextBlock, CosmicFontSystem, Font, FontAtlasSet, FontHinting, LineBreak, LineHeight,
     SwashCache, TextBounds, TextColor, TextError, TextFont, TextLayout, TextLayoutInfo,
-    TextPipeline, TextReader, TextRoot, TextSpanAccess, TextWriter,
+    TextPipeline, TextReader, TextRenderMode, TextRoot, TextSpanAccess, TextWriter,
 };
 use bevy_transform::components::Transform;
 use core::any::TypeId;
@@ -90,7 +90,8 @@ use core::any::TypeId;
     VisibilityClass,
     Transform,
     // Disable hinting as `Text2d` text is not always pixel-aligned
-    FontHinting::Disabled
+    FontHinting::Disabled,
+    TextRenderMode
 )]
 #[component(on_add = visibility::add_visibility_class::<Sprite>)]
 pub struct Text2d(pub String);
@@ -178,6 +179,7 @@ pub fn update_text2d_layout(
         &mut TextLayoutInfo,
         &mut ComputedTextBlock,
         Ref<FontHinting>,
+        Ref<TextRenderMode>,
     )>,
     mut text_reader: Text2dReader,
     mut font_system: ResMut<CosmicFontSystem>,
@@ -200,8 +202,16 @@ pub fn update_text2d_layout(
     let mut previous_scale_factor = 0.;
     let mut previous_mask = &RenderLayers::none();
 
-    for (entity, maybe_entity_mask, block, bounds, mut text_layout_info, mut computed, hinting) in
-        &mut text_query
+    for (
+        entity,
+        maybe_entity_mask,
+        block,
+        bounds,
+        mut text_layout_info,
+        mut computed,
+        hinting,
+        render_mode,
+    ) in &mut text_query
     {
         let entity_mask = maybe_entity_mask.unwrap_or_default();
 
@@ -225,6 +235,7 @@ pub fn update_text2d_layout(
         let text_changed = scale_factor != text_layout_info.scale_factor
             || block.is_changed()
             || hinting.is_changed()
+            || render_mode.is_changed()
             || computed.needs_rerender()
             || (!reprocess_queue.is_empty() && reprocess_queue.remove(&entity));
 
@@ -288,6 +299,7 @@ pub fn update_text2d_layout(
             &mut swash_cache,
             text_bounds,
             block.justify,
+            *render_mode,
         ) {
             Err(TextError::NoSuchFont) => {
                 // There was an error processing the text layout.
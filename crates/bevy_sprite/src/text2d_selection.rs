@@ -0,0 +1,144 @@
+//! Pointer selection of [`SelectableText`] on [`Text2d`] entities.
+
+use crate::{Anchor, Text2d};
+use bevy_camera::Camera;
+use bevy_ecs::prelude::*;
+use bevy_math::{prelude::*, Vec3Swizzles};
+use bevy_picking::{
+    events::{Drag, Pointer, Press},
+    pointer::PointerButton,
+};
+use bevy_text::{
+    ComputedTextBlock, CosmicFontSystem, SelectableText, TextBounds, TextLayoutInfo, TextSelection,
+};
+use bevy_time::Time;
+use bevy_transform::prelude::*;
+
+/// The camera through which the [`TextSelection`] of a [`Text2d`] was last pressed.
+///
+/// Inserted when the text is pressed, and used to find the position of the pointer while dragging.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TextSelectionCamera(pub Entity);
+
+/// Returns the position of a point in world space in the coordinates of the [`TextLayoutInfo`].
+fn text_layout_position(
+    layout_info: &TextLayoutInfo,
+    bounds: &TextBounds,
+    anchor: &Anchor,
+    transform: &GlobalTransform,
+    world_position: Vec3,
+) -> Vec2 {
+    let local_position = transform
+        .affine()
+        .inverse()
+        .transform_point3(world_position)
+        .xy();
+    let size = Vec2::new(
+        bounds.width.unwrap_or(layout_info.size.x),
+        bounds.height.unwrap_or(layout_info.size.y),
+    );
+    let top_left = (Anchor::TOP_LEFT.0 - anchor.as_vec()) * size;
+    (local_position - top_left) * Vec2::new(1., -1.) * layout_info.scale_factor
+}
+
+/// Starts a [`TextSelection`] when [`SelectableText`] is pressed with the primary pointer button,
+/// and clears all other selections.
+pub fn text2d_selection_on_press(
+    press: On<Pointer<Press>>,
+    mut commands: Commands,
+    mut text_query: Query<
+        (
+            &TextLayoutInfo,
+            &TextBounds,
+            &Anchor,
+            &GlobalTransform,
+            &mut ComputedTextBlock,
+        ),
+        (With<Text2d>, With<SelectableText>),
+    >,
+    mut selection_query: Query<(Entity, &mut TextSelection)>,
+    mut font_system: ResMut<CosmicFontSystem>,
+    time: Res<Time>,
+) {
+    if press.button != PointerButton::Primary {
+        return;
+    }
+    let Ok((layout_info, bounds, anchor, transform, mut block)) = text_query.get_mut(press.entity)
+    else {
+        return;
+    };
+    let Some(world_position) = press.hit.position else {
+        return;
+    };
+    let position = text_layout_position(layout_info, bounds, anchor, transform, world_position);
+
+    for (entity, mut selection) in &mut selection_query {
+        if entity == press.entity {
+            selection.press(&mut block, &mut font_system, position, time.elapsed());
+        } else if !selection.is_empty() {
+            selection.clear();
+        }
+    }
+    commands
+        .entity(press.entity)
+        .insert(TextSelectionCamera(press.hit.camera));
+}
+
+/// Extends the [`TextSelection`] of [`SelectableText`] while it is dragged with the primary pointer
+/// button.
+pub fn text2d_selection_on_drag(
+    drag: On<Pointer<Drag>>,
+    mut text_query: Query<
+        (
+            &TextLayoutInfo,
+            &TextBounds,
+            &Anchor,
+            &GlobalTransform,
+            &TextSelectionCamera,
+            &mut ComputedTextBlock,
+            &mut TextSelection,
+        ),
+        (With<Text2d>, With<SelectableText>),
+    >,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut font_system: ResMut<CosmicFontSystem>,
+) {
+    if drag.button != PointerButton::Primary {
+        return;
+    }
+    let Ok((layout_info, bounds, anchor, transform, camera, mut block, mut selection)) =
+        text_query.get_mut(drag.entity)
+    else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get(camera.0) else {
+        return;
+    };
+
+    // Intersect the pointer ray with the plane of the text, like the picking backend does.
+    let viewport_min = camera
+        .logical_viewport_rect()
+        .map(|viewport| viewport.min)
+        .unwrap_or_default();
+    let Ok(ray) = camera.viewport_to_world(
+        camera_transform,
+        drag.pointer_location.position - viewport_min,
+    ) else {
+        return;
+    };
+    let Some(distance) = ray.intersect_plane(
+        transform.translation(),
+        InfinitePlane3d::new(transform.back()),
+    ) else {
+        return;
+    };
+
+    let position = text_layout_position(
+        layout_info,
+        bounds,
+        anchor,
+        transform,
+        ray.get_point(distance),
+    );
+    selection.drag(&mut block, &mut font_system, position);
+}
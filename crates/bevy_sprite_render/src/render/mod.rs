@@ -185,7 +185,7 @@ impl SpecializedRenderPipeline for SpritePipeline {
         };
 
         let instance_rate_vertex_buffer_layout = VertexBufferLayout {
-            array_stride: 80,
+            array_stride: 96,
             step_mode: VertexStepMode::Instance,
             attributes: vec![
                 // @location(0) i_model_transpose_col0: vec4<f32>,
@@ -218,6 +218,12 @@ impl SpecializedRenderPipeline for SpritePipeline {
                     offset: 64,
                     shader_location: 4,
                 },
+                // @location(5) i_sdf: vec4<f32>,
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 80,
+                    shader_location: 5,
+                },
             ],
         };
 
@@ -273,6 +279,9 @@ pub struct ExtractedSlice {
     pub offset: Vec2,
     pub rect: Rect,
     pub size: Vec2,
+    /// For text glyphs rendered from a signed distance field, the distance by which the glyph is
+    /// grown, see `bevy_text::sdf_distance`. `None` for regular textures.
+    pub sdf_dilation: Option<f32>,
 }
 
 pub struct ExtractedSprite {
@@ -416,11 +425,18 @@ struct SpriteInstance {
     pub i_model_transpose: [Vec4; 3],
     pub i_color: [f32; 4],
     pub i_uv_offset_scale: [f32; 4],
+    // x: 1 if the texture is a signed distance field, y: the dilation of the signed distance field
+    pub i_sdf: [f32; 4],
 }
 
 impl SpriteInstance {
     #[inline]
-    fn from(transform: &Affine3A, color: &LinearRgba, uv_offset_scale: &Vec4) -> Self {
+    fn from(
+        transform: &Affine3A,
+        color: &LinearRgba,
+        uv_offset_scale: &Vec4,
+        sdf_dilation: Option<f32>,
+    ) -> Self {
         let transpose_model_3x3 = transform.matrix3.transpose();
         Self {
             i_model_transpose: [
@@ -430,6 +446,10 @@ impl SpriteInstance {
             ],
             i_color: color.to_f32_array(),
             i_uv_offset_scale: uv_offset_scale.to_array(),
+            i_sdf: match sdf_dilation {
+                Some(dilation) => [1.0, dilation, 0.0, 0.0],
+                None => [0.0; 4],
+            },
         }
     }
 }
@@ -749,6 +769,7 @@ pub fn prepare_sprite_image_bind_groups(
                             &transform,
                             &extracted_sprite.color,
                             &uv_offset_scale,
+                            None,
                         ));
 
                     current_batch.as_mut().unwrap().get_mut().range.end += 1;
@@ -794,6 +815,7 @@ pub fn prepare_sprite_image_bind_groups(
                                 &transform,
                                 &extracted_sprite.color,
                                 &uv_offset_scale,
+                                slice.sdf_dilation,
                             ));
 
                         current_batch.as_mut().unwrap().get_mut().range.end += 1;
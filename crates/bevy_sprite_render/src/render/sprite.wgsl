@@ -19,12 +19,15 @@ struct VertexInput {
     @location(2) i_model_transpose_col2: vec4<f32>,
     @location(3) i_color: vec4<f32>,
     @location(4) i_uv_offset_scale: vec4<f32>,
+    // x: 1 if the texture is a signed distance field, y: the dilation of the signed distance field
+    @location(5) i_sdf: vec4<f32>,
 }
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) uv: vec2<f32>,
     @location(1) @interpolate(flat) color: vec4<f32>,
+    @location(2) @interpolate(flat) sdf: vec2<f32>,
 };
 
 @vertex
@@ -44,6 +47,7 @@ fn vertex(in: VertexInput) -> VertexOutput {
     )) * vec4<f32>(vertex_position, 1.0);
     out.uv = vec2<f32>(vertex_position.xy) * in.i_uv_offset_scale.zw + in.i_uv_offset_scale.xy;
     out.color = in.i_color;
+    out.sdf = in.i_sdf.xy;
 
     return out;
 }
@@ -53,7 +57,18 @@ fn vertex(in: VertexInput) -> VertexOutput {
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+    let texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
+    // Derivatives require uniform control flow, so compute this before branching.
+    let distance_width = fwidth(texture_color.a);
+
+    var color = in.color * texture_color;
+    if in.sdf.x > 0.0 {
+        // Glyph from a signed distance field, with the edge at 0.5, grown by the dilation.
+        let edge = 0.5 - in.sdf.y;
+        // Antialias over about one pixel on screen.
+        let w = max(0.7 * distance_width, 1e-4);
+        color = vec4(in.color.rgb, in.color.a * smoothstep(edge - w, edge + w, texture_color.a));
+    }
 
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
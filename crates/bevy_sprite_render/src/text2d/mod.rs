@@ -3,7 +3,7 @@ use crate::{
 };
 use bevy_asset::{AssetId, Assets};
 use bevy_camera::visibility::ViewVisibility;
-use bevy_color::LinearRgba;
+use bevy_color::{Alpha, LinearRgba};
 use bevy_ecs::{
     entity::Entity,
     query::Has,
@@ -15,8 +15,9 @@ use bevy_render::sync_world::TemporaryRenderEntity;
 use bevy_render::Extract;
 use bevy_sprite::{Anchor, Text2dShadow};
 use bevy_text::{
-    ComputedTextBlock, PositionedGlyph, Strikethrough, StrikethroughColor, TextBackgroundColor,
-    TextBounds, TextColor, TextLayoutInfo, Underline, UnderlineColor,
+    sdf_distance, ComputedTextBlock, InlineImage, PositionedGlyph, Strikethrough,
+    StrikethroughColor, TextBackgroundColor, TextBounds, TextColor, TextLayoutInfo, TextOutline,
+    TextRenderMode, TextSelection, TextSelectionColor, Underline, UnderlineColor,
 };
 use bevy_transform::prelude::GlobalTransform;
 
@@ -37,6 +38,9 @@ pub fn extract_text2d_sprite(
             &Anchor,
             Option<&Text2dShadow>,
             &GlobalTransform,
+            &TextRenderMode,
+            Option<&TextOutline>,
+            Option<(&TextSelection, &TextSelectionColor)>,
         )>,
     >,
     text_colors: Extract<Query<&TextColor>>,
@@ -50,6 +54,7 @@ pub fn extract_text2d_sprite(
             Option<&UnderlineColor>,
         )>,
     >,
+    inline_image_query: Extract<Query<(&InlineImage, &TextColor)>>,
 ) {
     let mut start = extracted_slices.slices.len();
     let mut end = start + 1;
@@ -63,6 +68,9 @@ pub fn extract_text2d_sprite(
         anchor,
         maybe_shadow,
         global_transform,
+        render_mode,
+        maybe_outline,
+        maybe_selection,
     ) in text2d_query.iter()
     {
         let scaling = GlobalTransform::from_scale(
@@ -78,6 +86,7 @@ pub fn extract_text2d_sprite(
         );
 
         let top_left = (Anchor::TOP_LEFT.0 - anchor.as_vec()) * size;
+        let sdf = *render_mode == TextRenderMode::Sdf;
 
         for run in text_layout_info.run_geometry.iter() {
             let section_entity = computed_block.entities()[run.span_index].entity;
@@ -107,6 +116,32 @@ pub fn extract_text2d_sprite(
             });
         }
 
+        if let Some((selection, selection_color)) = maybe_selection {
+            for rect in selection.rects(computed_block) {
+                let render_entity = commands.spawn(TemporaryRenderEntity).id();
+                let offset = Vec2::new(rect.center().x, -rect.center().y);
+                let transform = *global_transform
+                    * GlobalTransform::from_translation(top_left.extend(0.))
+                    * scaling
+                    * GlobalTransform::from_translation(offset.extend(0.));
+                extracted_sprites.sprites.push(ExtractedSprite {
+                    main_entity,
+                    render_entity,
+                    transform,
+                    color: selection_color.0.into(),
+                    image_handle_id: AssetId::default(),
+                    flip_x: false,
+                    flip_y: false,
+                    kind: ExtractedSpriteKind::Single {
+                        anchor: Vec2::ZERO,
+                        rect: None,
+                        scaling_mode: None,
+                        custom_size: Some(rect.size()),
+                    },
+                });
+            }
+        }
+
         if let Some(shadow) = maybe_shadow {
             let shadow_transform = *global_transform
                 * GlobalTransform::from_translation((top_left + shadow.offset).extend(0.))
@@ -117,6 +152,7 @@ pub fn extract_text2d_sprite(
                 i,
                 PositionedGlyph {
                     position,
+                    size,
                     atlas_info,
                     ..
                 },
@@ -130,7 +166,8 @@ pub fn extract_text2d_sprite(
                 extracted_slices.slices.push(ExtractedSlice {
                     offset: Vec2::new(position.x, -position.y),
                     rect,
-                    size: rect.size(),
+                    size: *size,
+                    sdf_dilation: sdf.then_some(0.),
                 });
 
                 if text_layout_info
@@ -213,6 +250,59 @@ pub fn extract_text2d_sprite(
 
         let transform =
             *global_transform * GlobalTransform::from_translation(top_left.extend(0.)) * scaling;
+
+        // Outlines are drawn as glyphs grown by the outline width, below the glyphs.
+        if let Some(outline) = maybe_outline.filter(|_| sdf) {
+            let color = outline.color.into();
+            let width = outline.width * text_layout_info.scale_factor;
+
+            for (
+                i,
+                PositionedGlyph {
+                    position,
+                    size,
+                    atlas_info,
+                    ..
+                },
+            ) in text_layout_info.glyphs.iter().enumerate()
+            {
+                let rect = texture_atlases
+                    .get(atlas_info.texture_atlas)
+                    .unwrap()
+                    .textures[atlas_info.location.glyph_index]
+                    .as_rect();
+                extracted_slices.slices.push(ExtractedSlice {
+                    offset: Vec2::new(position.x, -position.y),
+                    rect,
+                    size: *size,
+                    sdf_dilation: Some(sdf_distance(width, *size, rect.size())),
+                });
+
+                if text_layout_info
+                    .glyphs
+                    .get(i + 1)
+                    .is_none_or(|info| info.atlas_info.texture != atlas_info.texture)
+                {
+                    let render_entity = commands.spawn(TemporaryRenderEntity).id();
+                    extracted_sprites.sprites.push(ExtractedSprite {
+                        main_entity,
+                        render_entity,
+                        transform,
+                        color,
+                        image_handle_id: atlas_info.texture,
+                        flip_x: false,
+                        flip_y: false,
+                        kind: ExtractedSpriteKind::Slices {
+                            indices: start..end,
+                        },
+                    });
+                    start = end;
+                }
+
+                end += 1;
+            }
+        }
+
         let mut color = LinearRgba::WHITE;
         let mut current_span = usize::MAX;
 
@@ -220,6 +310,7 @@ pub fn extract_text2d_sprite(
             i,
             PositionedGlyph {
                 position,
+                size,
                 atlas_info,
                 span_index,
                 ..
@@ -247,7 +338,8 @@ pub fn extract_text2d_sprite(
             extracted_slices.slices.push(ExtractedSlice {
                 offset: Vec2::new(position.x, -position.y),
                 rect,
-                size: rect.size(),
+                size: *size,
+                sdf_dilation: sdf.then_some(0.),
             });
 
             if text_layout_info.glyphs.get(i + 1).is_none_or(|info| {
@@ -340,5 +432,33 @@ pub fn extract_text2d_sprite(
                 });
             }
         }
+
+        for run in text_layout_info.run_geometry.iter() {
+            let section_entity = computed_block.entities()[run.span_index].entity;
+            let Ok((inline_image, text_color)) = inline_image_query.get(section_entity) else {
+                continue;
+            };
+            let render_entity = commands.spawn(TemporaryRenderEntity).id();
+            let offset = Vec2::new(run.bounds.center().x, -run.bounds.center().y);
+            let transform = *global_transform
+                * GlobalTransform::from_translation(top_left.extend(0.))
+                * scaling
+                * GlobalTransform::from_translation(offset.extend(0.));
+            extracted_sprites.sprites.push(ExtractedSprite {
+                main_entity,
+                render_entity,
+                transform,
+                color: LinearRgba::WHITE.with_alpha(text_color.0.alpha()),
+                image_handle_id: inline_image.0.id(),
+                flip_x: false,
+                flip_y: false,
+                kind: ExtractedSpriteKind::Single {
+                    anchor: Vec2::ZERO,
+                    rect: None,
+                    scaling_mode: None,
+                    custom_size: Some(run.bounds.size()),
+                },
+            });
+        }
     }
 }
@@ -4,7 +4,7 @@ use bevy_math::{IVec2, UVec2};
 use bevy_platform::collections::HashMap;
 use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
 
-use crate::{FontSmoothing, GlyphAtlasInfo, GlyphAtlasLocation, TextError};
+use crate::{FontSmoothing, GlyphAtlasInfo, GlyphAtlasLocation, TextError, TextRenderMode};
 
 /// Rasterized glyphs are cached, stored in, and retrieved from, a `FontAtlas`.
 ///
@@ -36,6 +36,21 @@ impl FontAtlas {
         texture_atlases_layout: &mut Assets<TextureAtlasLayout>,
         size: UVec2,
         font_smoothing: FontSmoothing,
+    ) -> FontAtlas {
+        let sampler = match font_smoothing {
+            FontSmoothing::None => ImageSampler::nearest(),
+            FontSmoothing::AntiAliased => ImageSampler::Default,
+        };
+        Self::with_sampler(textures, texture_atlases_layout, size, sampler)
+    }
+
+    /// Create a new [`FontAtlas`] with the given size, whose texture is sampled with `sampler`,
+    /// adding it to the appropriate asset collections.
+    pub fn with_sampler(
+        textures: &mut Assets<Image>,
+        texture_atlases_layout: &mut Assets<TextureAtlasLayout>,
+        size: UVec2,
+        sampler: ImageSampler,
     ) -> FontAtlas {
         let mut image = Image::new_fill(
             size.to_extents(),
@@ -45,9 +60,7 @@ impl FontAtlas {
             // Need to keep this image CPU persistent in order to add additional glyphs later on
             RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
         );
-        if font_smoothing == FontSmoothing::None {
-            image.sampler = ImageSampler::nearest();
-        }
+        image.sampler = sampler;
         let texture = textures.add(image);
         let texture_atlas = texture_atlases_layout.add(TextureAtlasLayout::new_empty(size));
         Self {
@@ -137,14 +150,35 @@ pub fn add_glyph_to_atlas(
 
     let (glyph_texture, offset) =
         get_outlined_glyph_texture(font_system, swash_cache, &physical_glyph, font_smoothing)?;
+    add_glyph_texture_to_atlas(
+        font_atlases,
+        texture_atlases,
+        textures,
+        physical_glyph.cache_key,
+        &glyph_texture,
+        offset,
+        font_smoothing,
+        TextRenderMode::Raster,
+    )
+}
+
+/// Adds the image of a glyph to the first of the given font atlases with space for it, or to a
+/// new font atlas if none of them has space left.
+///
+/// New font atlases for signed distance fields are always sampled with linear filtering, which
+/// their edges rely on to stay smooth.
+pub fn add_glyph_texture_to_atlas(
+    font_atlases: &mut Vec<FontAtlas>,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+    textures: &mut Assets<Image>,
+    cache_key: cosmic_text::CacheKey,
+    glyph_texture: &Image,
+    offset: IVec2,
+    font_smoothing: FontSmoothing,
+    render_mode: TextRenderMode,
+) -> Result<GlyphAtlasInfo, TextError> {
     let mut add_char_to_font_atlas = |atlas: &mut FontAtlas| -> Result<(), TextError> {
-        atlas.add_glyph(
-            textures,
-            texture_atlases,
-            physical_glyph.cache_key,
-            &glyph_texture,
-            offset,
-        )
+        atlas.add_glyph(textures, texture_atlases, cache_key, glyph_texture, offset)
     };
     if !font_atlases
         .iter_mut()
@@ -159,26 +193,22 @@ pub fn add_glyph_to_atlas(
         // Pick the higher of 512 or the smallest power of 2 greater than glyph_max_size
         let containing = (1u32 << (32 - glyph_max_size.leading_zeros())).max(512);
 
-        let mut new_atlas = FontAtlas::new(
-            textures,
-            texture_atlases,
-            UVec2::splat(containing),
-            font_smoothing,
-        );
+        let size = UVec2::splat(containing);
+        let mut new_atlas = match render_mode {
+            TextRenderMode::Raster => {
+                FontAtlas::new(textures, texture_atlases, size, font_smoothing)
+            }
+            TextRenderMode::Sdf => {
+                FontAtlas::with_sampler(textures, texture_atlases, size, ImageSampler::linear())
+            }
+        };
 
-        new_atlas.add_glyph(
-            textures,
-            texture_atlases,
-            physical_glyph.cache_key,
-            &glyph_texture,
-            offset,
-        )?;
+        new_atlas.add_glyph(textures, texture_atlases, cache_key, glyph_texture, offset)?;
 
         font_atlases.push(new_atlas);
     }
 
-    get_glyph_atlas_info(font_atlases, physical_glyph.cache_key)
-        .ok_or(TextError::InconsistentAtlasState)
+    get_glyph_atlas_info(font_atlases, cache_key).ok_or(TextError::InconsistentAtlasState)
 }
 
 /// Get the texture of the glyph as a rendered image, and its offset
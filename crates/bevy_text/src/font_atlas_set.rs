@@ -1,4 +1,4 @@
-use crate::{FontAtlas, FontSmoothing};
+use crate::{FontAtlas, FontSmoothing, TextRenderMode};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::resource::Resource;
 use bevy_platform::collections::HashMap;
@@ -15,6 +15,8 @@ pub struct FontAtlasKey {
     pub font_size_bits: u32,
     /// Antialiasing method
     pub font_smoothing: FontSmoothing,
+    /// Whether the atlases contain rasterized glyphs or signed distance fields
+    pub render_mode: TextRenderMode,
 }
 
 /// Set of rasterized fonts stored in [`FontAtlas`]es.
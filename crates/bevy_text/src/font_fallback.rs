@@ -0,0 +1,468 @@
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    reflect::ReflectResource,
+    resource::Resource,
+    system::{Local, Query, Res, ResMut},
+};
+use bevy_log::warn;
+use bevy_platform::{
+    collections::{HashMap, HashSet},
+    sync::{LazyLock, Mutex, PoisonError},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use cosmic_text::{Fallback, PlatformFallback};
+use unicode_script::Script;
+
+use crate::{ComputedTextBlock, CosmicFontSystem};
+
+/// Font families used for characters that the font of a [`TextFont`](crate::TextFont) has no
+/// glyph for, such as CJK ideographs, emoji or Arabic in user names.
+///
+/// When a font lacks a glyph, the families declared for the script of the character are tried
+/// first, then the [`common`](Self::common) families, and finally the fallback fonts of the
+/// platform. Fallback fonts have to be available in the font database, either loaded as
+/// [`Font`](crate::Font) assets or with [`load_system_fonts`](Self::load_system_fonts).
+///
+/// Changing this resource rebuilds the [`CosmicFontSystem`] and lays out all text again.
+///
+/// ```
+/// # use bevy_text::FontFallbacks;
+/// let fallbacks = FontFallbacks::default()
+///     .with_common(["Noto Sans", "Noto Color Emoji"])
+///     .with_script("Arab", ["Noto Sans Arabic"])
+///     .with_script("Hani", ["Noto Sans CJK SC"])
+///     .with_script_for_locale("Hani", "ja", ["Noto Sans CJK JP"]);
+/// ```
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default, Debug, Clone, PartialEq)]
+pub struct FontFallbacks {
+    /// Families to use after the script specific families, in order.
+    pub common: Vec<String>,
+    /// Families to use for characters of a script, in order.
+    pub scripts: Vec<ScriptFallback>,
+    /// Families that are never used as a fallback.
+    pub forbidden: Vec<String>,
+    /// Whether to use the fallback families of the platform after the declared families.
+    ///
+    /// Defaults to `true`.
+    pub platform_fallbacks: bool,
+    /// Whether to load the fonts installed on the system into the font database, so that they can
+    /// be used as fallbacks.
+    ///
+    /// Loading the system fonts can take a while, and they aren't unloaded when this is set back to
+    /// `false`. Defaults to `false`.
+    pub load_system_fonts: bool,
+    /// The locale used to select [`ScriptFallback`]s, such as `"ja-JP"`.
+    ///
+    /// If `None`, the locale of the system is used.
+    pub locale: Option<String>,
+}
+
+impl Default for FontFallbacks {
+    fn default() -> Self {
+        Self {
+            common: Vec::new(),
+            scripts: Vec::new(),
+            forbidden: Vec::new(),
+            platform_fallbacks: true,
+            load_system_fonts: false,
+            locale: None,
+        }
+    }
+}
+
+impl FontFallbacks {
+    /// Returns these fallbacks with the given families appended to the [`common`](Self::common)
+    /// families.
+    pub fn with_common(mut self, families: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.common.extend(families.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns these fallbacks with the given families used for a script, such as `"Arab"`.
+    ///
+    /// See [`ScriptFallback::script`] for the script names.
+    pub fn with_script(
+        mut self,
+        script: impl Into<String>,
+        families: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.scripts.push(ScriptFallback {
+            script: script.into(),
+            locale: None,
+            families: families.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Returns these fallbacks with the given families used for a script when the
+    /// [`locale`](Self::locale) is the given locale or one of its regional variants.
+    ///
+    /// These families are tried before the ones declared for the script without a locale.
+    pub fn with_script_for_locale(
+        mut self,
+        script: impl Into<String>,
+        locale: impl Into<String>,
+        families: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.scripts.push(ScriptFallback {
+            script: script.into(),
+            locale: Some(locale.into()),
+            families: families.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Returns these fallbacks with the given families never used as fallbacks.
+    pub fn with_forbidden(mut self, families: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.forbidden.extend(families.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns these fallbacks with the fonts installed on the system loaded.
+    pub fn with_system_fonts(mut self) -> Self {
+        self.load_system_fonts = true;
+        self
+    }
+
+    /// Returns these fallbacks with the given [`locale`](Self::locale).
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+}
+
+/// Font families used for characters of a script.
+///
+/// See [`FontFallbacks`].
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq)]
+pub struct ScriptFallback {
+    /// The four letter [ISO 15924](https://en.wikipedia.org/wiki/ISO_15924) code of the script,
+    /// such as `"Arab"` for Arabic, `"Hani"` for Han ideographs or `"Cyrl"` for Cyrillic.
+    pub script: String,
+    /// If set, these families are only used when the locale of the [`FontFallbacks`] is this
+    /// locale or one of its regional variants, for example `"zh"` matches `"zh-TW"`.
+    pub locale: Option<String>,
+    /// The families to use, in order.
+    pub families: Vec<String>,
+}
+
+impl ScriptFallback {
+    fn matches_locale(&self, locale: &str) -> bool {
+        let Some(expected) = &self.locale else {
+            return true;
+        };
+        locale
+            .get(..expected.len())
+            .is_some_and(|language| language.eq_ignore_ascii_case(expected))
+            && matches!(
+                locale.as_bytes().get(expected.len()),
+                None | Some(b'-' | b'_')
+            )
+    }
+}
+
+/// The fallback lists of [`FontFallbacks`] in the form expected by [`cosmic_text`].
+struct FallbackLists {
+    common: Vec<&'static str>,
+    forbidden: Vec<&'static str>,
+    scripts: HashMap<Script, Vec<&'static str>>,
+    platform_fallbacks: bool,
+}
+
+impl FallbackLists {
+    fn new(fallbacks: &FontFallbacks, locale: &str) -> Self {
+        let platform = fallbacks.platform_fallbacks.then_some(&PlatformFallback);
+
+        let mut common: Vec<_> = fallbacks
+            .common
+            .iter()
+            .map(|family| intern(family))
+            .collect();
+        let mut forbidden: Vec<_> = fallbacks
+            .forbidden
+            .iter()
+            .map(|family| intern(family))
+            .collect();
+        if let Some(platform) = platform {
+            common.extend_from_slice(platform.common_fallback());
+            forbidden.extend_from_slice(platform.forbidden_fallback());
+        }
+
+        let mut scripts = HashMap::<Script, Vec<&'static str>>::default();
+        // Locale specific fallbacks take precedence over the fallbacks for every locale.
+        let locale_specific = fallbacks
+            .scripts
+            .iter()
+            .filter(|fallback| fallback.locale.is_some());
+        let any_locale = fallbacks
+            .scripts
+            .iter()
+            .filter(|fallback| fallback.locale.is_none());
+        for fallback in locale_specific.chain(any_locale) {
+            let Some(script) = Script::from_short_name(&fallback.script) else {
+                warn!("Unknown script `{}` in `FontFallbacks`.", fallback.script);
+                continue;
+            };
+            if fallback.matches_locale(locale) {
+                let families = scripts.entry(script).or_default();
+                families.extend(fallback.families.iter().map(|family| intern(family)));
+            }
+        }
+        if let Some(platform) = platform {
+            for (script, families) in &mut scripts {
+                families.extend_from_slice(platform.script_fallback(*script, locale));
+            }
+        }
+
+        Self {
+            common,
+            forbidden,
+            scripts,
+            platform_fallbacks: fallbacks.platform_fallbacks,
+        }
+    }
+}
+
+impl Fallback for FallbackLists {
+    fn common_fallback(&self) -> &[&'static str] {
+        &self.common
+    }
+
+    fn forbidden_fallback(&self) -> &[&'static str] {
+        &self.forbidden
+    }
+
+    fn script_fallback(&self, script: Script, locale: &str) -> &[&'static str] {
+        match self.scripts.get(&script) {
+            Some(families) => families,
+            None if self.platform_fallbacks => PlatformFallback.script_fallback(script, locale),
+            None => &[],
+        }
+    }
+}
+
+/// Returns a `'static` copy of a family name.
+///
+/// `cosmic_text` requires fallback family names to be `'static`, so they are leaked. Each distinct
+/// name is only leaked once.
+fn intern(family: &str) -> &'static str {
+    static FAMILIES: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+
+    let mut families = FAMILIES.lock().unwrap_or_else(PoisonError::into_inner);
+    match families.get(family) {
+        Some(family) => family,
+        None => {
+            let family: &'static str = String::from(family).leak();
+            families.insert(family);
+            family
+        }
+    }
+}
+
+/// Returns the locale of the system, or `en-US` if it can't be determined.
+pub(crate) fn system_locale() -> String {
+    sys_locale::get_locale().unwrap_or_else(|| String::from("en-US"))
+}
+
+/// Rebuilds the [`CosmicFontSystem`] when the [`FontFallbacks`] change.
+pub fn update_font_fallbacks(
+    fallbacks: Res<FontFallbacks>,
+    mut cosmic_font_system: ResMut<CosmicFontSystem>,
+    mut system_fonts_loaded: Local<bool>,
+    mut text_block_query: Query<&mut ComputedTextBlock>,
+) {
+    // The default font system already uses the default fallbacks.
+    if !fallbacks.is_changed() || (fallbacks.is_added() && *fallbacks == FontFallbacks::default()) {
+        return;
+    }
+
+    let placeholder = cosmic_text::FontSystem::new_with_locale_and_db(
+        String::new(),
+        cosmic_text::fontdb::Database::new(),
+    );
+    let (_, mut db) =
+        core::mem::replace(&mut cosmic_font_system.0, placeholder).into_locale_and_db();
+
+    if fallbacks.load_system_fonts && !*system_fonts_loaded {
+        db.load_system_fonts();
+        *system_fonts_loaded = true;
+    }
+
+    let locale = fallbacks.locale.clone().unwrap_or_else(system_locale);
+    let lists = FallbackLists::new(&fallbacks, &locale);
+    cosmic_font_system.0 =
+        cosmic_text::FontSystem::new_with_locale_and_db_and_fallback(locale, db, lists);
+
+    for mut block in text_block_query.iter_mut() {
+        block.needs_rerender = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Font, FontAtlasSet, FontHinting, Justify, LineBreak, LineHeight, SwashCache, TextBounds,
+        TextFont, TextLayoutInfo, TextPipeline, TextRenderMode,
+    };
+    use bevy_asset::Assets;
+    use bevy_color::Color;
+    use bevy_ecs::{
+        entity::Entity,
+        system::{Query, RunSystemOnce},
+        world::World,
+    };
+    use bevy_image::{Image, TextureAtlasLayout};
+
+    /// Lays out `text` in the Fira Mono family, and returns the font of each run.
+    fn layout_runs(world: &mut World, block: Entity, text: &str) -> Vec<cosmic_text::fontdb::ID> {
+        let text = text.to_owned();
+        world
+            .run_system_once(
+                move |mut pipeline: ResMut<TextPipeline>,
+                      fonts: Res<Assets<Font>>,
+                      mut font_atlas_set: ResMut<FontAtlasSet>,
+                      mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+                      mut textures: ResMut<Assets<Image>>,
+                      mut font_system: ResMut<CosmicFontSystem>,
+                      mut swash_cache: ResMut<SwashCache>,
+                      mut blocks: Query<&mut ComputedTextBlock>| {
+                    let mut computed = blocks.get_mut(block).unwrap();
+                    let text_font = TextFont::default().with_family("Fira Mono");
+                    let spans = [(
+                        block,
+                        0,
+                        text.as_str(),
+                        &text_font,
+                        Color::WHITE,
+                        LineHeight::default(),
+                    )];
+                    pipeline
+                        .update_buffer(
+                            &fonts,
+                            spans.into_iter(),
+                            LineBreak::NoWrap,
+                            Justify::Left,
+                            TextBounds::UNBOUNDED,
+                            1.,
+                            &mut computed,
+                            &mut font_system,
+                            FontHinting::Disabled,
+                        )
+                        .unwrap();
+                    let mut layout_info = TextLayoutInfo::default();
+                    pipeline
+                        .update_text_layout_info(
+                            &mut layout_info,
+                            &mut font_atlas_set,
+                            &mut texture_atlases,
+                            &mut textures,
+                            &mut computed,
+                            &mut font_system,
+                            &mut swash_cache,
+                            TextBounds::UNBOUNDED,
+                            Justify::Left,
+                            TextRenderMode::Raster,
+                        )
+                        .unwrap();
+                    layout_info
+                        .run_geometry
+                        .iter()
+                        .map(|run| run.font_id)
+                        .collect::<Vec<_>>()
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn script_fallbacks_for_locale() {
+        let fallbacks = FontFallbacks {
+            platform_fallbacks: false,
+            ..Default::default()
+        }
+        .with_common(["Noto Sans"])
+        .with_script("Hani", ["Noto Sans CJK SC"])
+        .with_script_for_locale("Hani", "ja", ["Noto Sans CJK JP"])
+        .with_script("Arab", ["Noto Sans Arabic"]);
+
+        let lists = FallbackLists::new(&fallbacks, "ja-JP");
+        assert_eq!(lists.common_fallback(), ["Noto Sans"]);
+        assert_eq!(
+            lists.script_fallback(Script::Han, "ja-JP"),
+            ["Noto Sans CJK JP", "Noto Sans CJK SC"]
+        );
+        assert_eq!(
+            lists.script_fallback(Script::Arabic, "ja-JP"),
+            ["Noto Sans Arabic"]
+        );
+        assert!(lists.script_fallback(Script::Cyrillic, "ja-JP").is_empty());
+
+        let lists = FallbackLists::new(&fallbacks, "jam");
+        assert_eq!(
+            lists.script_fallback(Script::Han, "jam"),
+            ["Noto Sans CJK SC"]
+        );
+    }
+
+    #[test]
+    fn runs_split_at_fallback_fonts() {
+        let mut world = World::new();
+        world.init_resource::<TextPipeline>();
+        world.init_resource::<Assets<Font>>();
+        world.init_resource::<FontAtlasSet>();
+        world.init_resource::<Assets<TextureAtlasLayout>>();
+        world.init_resource::<Assets<Image>>();
+        world.init_resource::<SwashCache>();
+        world.insert_resource(
+            FontFallbacks {
+                platform_fallbacks: false,
+                ..Default::default()
+            }
+            .with_common(["Fira Sans"]),
+        );
+
+        // The subset of Fira Mono has no Cyrillic glyphs, unlike Fira Sans.
+        let mut font_system = CosmicFontSystem::default();
+        let db = font_system.db_mut();
+        db.load_font_data(include_bytes!("FiraMono-subset.ttf").to_vec());
+        db.load_font_data(include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf").to_vec());
+        let font_id = |font_system: &CosmicFontSystem, family: &str| {
+            font_system
+                .db()
+                .faces()
+                .find(|face| face.families.iter().any(|(name, _)| name == family))
+                .unwrap()
+                .id
+        };
+        let mono = font_id(&font_system, "Fira Mono");
+        let sans = font_id(&font_system, "Fira Sans");
+        world.insert_resource(font_system);
+        world.run_system_once(update_font_fallbacks).unwrap();
+
+        let block = world.spawn(ComputedTextBlock::default()).id();
+        assert_eq!(
+            layout_runs(&mut world, block, "abc Жж def"),
+            [mono, sans, mono]
+        );
+
+        // Forbidding the fallback family lays the text out again with the missing glyphs of
+        // Fira Mono.
+        world
+            .get_mut::<ComputedTextBlock>(block)
+            .unwrap()
+            .needs_rerender = false;
+        world
+            .resource_mut::<FontFallbacks>()
+            .forbidden
+            .push("Fira Sans".into());
+        world.run_system_once(update_font_fallbacks).unwrap();
+        assert!(world
+            .get::<ComputedTextBlock>(block)
+            .unwrap()
+            .needs_rerender());
+        assert_eq!(layout_runs(&mut world, block, "abc Жж def"), [mono]);
+    }
+}
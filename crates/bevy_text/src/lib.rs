@@ -36,9 +36,13 @@ mod error;
 mod font;
 mod font_atlas;
 mod font_atlas_set;
+mod font_fallback;
 mod font_loader;
 mod glyph;
+mod markup;
 mod pipeline;
+mod sdf;
+mod selection;
 mod text;
 mod text_access;
 
@@ -48,9 +52,13 @@ pub use error::*;
 pub use font::*;
 pub use font_atlas::*;
 pub use font_atlas_set::*;
+pub use font_fallback::*;
 pub use font_loader::*;
 pub use glyph::*;
+pub use markup::*;
 pub use pipeline::*;
+pub use sdf::*;
+pub use selection::*;
 pub use text::*;
 pub use text_access::*;
 
@@ -60,8 +68,9 @@ pub use text_access::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        Font, FontHinting, FontSmoothing, FontSource, FontStyle, FontWeight, FontWidth, Justify,
-        LineBreak, Strikethrough, StrikethroughColor, TextColor, TextError, TextFont, TextLayout,
+        Font, FontFallbacks, FontHinting, FontSmoothing, FontSource, FontStyle, FontWeight,
+        FontWidth, Justify, LineBreak, RichText, SelectableText, Strikethrough, StrikethroughColor,
+        TextColor, TextError, TextFont, TextLayout, TextOutline, TextRenderMode, TextSelection,
         TextSpan, Underline, UnderlineColor,
     };
 }
@@ -69,6 +78,7 @@ pub mod prelude {
 use bevy_app::prelude::*;
 use bevy_asset::AssetApp;
 use bevy_ecs::prelude::*;
+use bevy_input::{keyboard::KeyCode, ButtonInput, InputSystems};
 
 /// The raw data for the default font used by `bevy_text`
 #[cfg(feature = "default_font")]
@@ -92,11 +102,21 @@ impl Plugin for TextPlugin {
             .init_resource::<FontAtlasSet>()
             .init_resource::<TextPipeline>()
             .init_resource::<CosmicFontSystem>()
+            .init_resource::<FontFallbacks>()
             .init_resource::<SwashCache>()
             .init_resource::<TextIterScratch>()
             .add_systems(
                 PostUpdate,
-                load_font_assets_into_fontdb_system.after(AssetEventSystems),
+                (update_font_fallbacks, load_font_assets_into_fontdb_system)
+                    .chain()
+                    .after(AssetEventSystems),
+            )
+            .add_systems(
+                PreUpdate,
+                copy_text_selection.after(InputSystems).run_if(
+                    resource_exists::<ButtonInput<KeyCode>>
+                        .and(resource_exists::<bevy_window::Clipboard>),
+                ),
             )
             .add_systems(Last, trim_cosmic_cache);
 
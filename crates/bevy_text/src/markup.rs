@@ -0,0 +1,521 @@
+use alloc::borrow::ToOwned;
+use core::str::FromStr;
+
+use bevy_asset::{AssetServer, Handle};
+use bevy_color::{Color, Srgba};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    component::Component,
+    hierarchy::ChildOf,
+    reflect::ReflectComponent,
+    relationship::RelatedSpawner,
+    spawn::{SpawnWith, SpawnableList},
+};
+use bevy_image::Image;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use thiserror::Error;
+
+use crate::{FontStyle, FontWeight, Strikethrough, TextColor, TextFont, TextSpan, Underline};
+
+/// The text of the span spawned for an inline image, which reserves room for the image in the
+/// text layout.
+const INLINE_IMAGE_PLACEHOLDER: &str = "\u{2003}";
+
+/// Errors that can occur when parsing rich text markup with [`RichText::parse`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum MarkupError {
+    /// A `[` was not followed by a matching `]`.
+    #[error("tag at byte {position} is missing a closing `]`")]
+    UnterminatedTag {
+        /// The byte offset of the `[` in the markup.
+        position: usize,
+    },
+    /// The tag is not one of the supported tags.
+    #[error("unknown tag `{tag}` at byte {position}")]
+    UnknownTag {
+        /// The name of the tag.
+        tag: String,
+        /// The byte offset of the tag in the markup.
+        position: usize,
+    },
+    /// The tag requires a value, such as `[color=#ff0000]`, but none was given.
+    #[error("tag `{tag}` at byte {position} requires a value")]
+    MissingValue {
+        /// The name of the tag.
+        tag: String,
+        /// The byte offset of the tag in the markup.
+        position: usize,
+    },
+    /// The value of the tag could not be parsed.
+    #[error("invalid value `{value}` for tag `{tag}` at byte {position}")]
+    InvalidValue {
+        /// The name of the tag.
+        tag: String,
+        /// The value that could not be parsed.
+        value: String,
+        /// The byte offset of the tag in the markup.
+        position: usize,
+    },
+    /// A closing tag was found while no tag was open.
+    #[error("closing tag `{tag}` at byte {position} has no matching opening tag")]
+    UnexpectedClosingTag {
+        /// The name of the tag.
+        tag: String,
+        /// The byte offset of the tag in the markup.
+        position: usize,
+    },
+    /// A closing tag doesn't match the innermost open tag.
+    #[error("closing tag `{found}` at byte {position} does not match the open tag `{expected}`")]
+    MismatchedClosingTag {
+        /// The name of the innermost open tag.
+        expected: String,
+        /// The name of the closing tag.
+        found: String,
+        /// The byte offset of the closing tag in the markup.
+        position: usize,
+    },
+    /// The markup ended while a tag was still open.
+    #[error("tag `{tag}` is never closed")]
+    UnclosedTag {
+        /// The name of the tag.
+        tag: String,
+    },
+}
+
+/// A run of text with a single style, produced by parsing markup with [`RichText::parse`].
+///
+/// Style attributes that are `None` are left at the base [`TextFont`] and [`TextColor`] passed to
+/// [`RichText::spans`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkupSpan {
+    /// The text of the span. For inline images this is empty.
+    pub text: String,
+    /// The font weight, set by `[b]`.
+    pub weight: Option<FontWeight>,
+    /// The font style, set by `[i]`.
+    pub style: Option<FontStyle>,
+    /// The font size, set by `[size=N]`.
+    pub font_size: Option<f32>,
+    /// The text color, set by `[color=#rrggbb]`.
+    pub color: Option<Color>,
+    /// Whether the text is underlined, set by `[u]`.
+    pub underline: bool,
+    /// Whether the text is struck through, set by `[s]`.
+    pub strikethrough: bool,
+    /// The target of the link the span belongs to, set by `[url]`.
+    pub link: Option<String>,
+    /// The asset path of the inline image, set by `[img]`.
+    pub image: Option<String>,
+}
+
+impl MarkupSpan {
+    /// Returns the [`TextFont`] of this span, derived from `base`.
+    pub fn text_font(&self, base: &TextFont) -> TextFont {
+        let mut font = base.clone();
+        if let Some(weight) = self.weight {
+            font.weight = weight;
+        }
+        if let Some(style) = self.style {
+            font.style = style;
+        }
+        if let Some(font_size) = self.font_size {
+            font.font_size = font_size;
+        }
+        font
+    }
+}
+
+/// Text with mixed styles, parsed from a small BBCode-like markup language.
+///
+/// The following tags are supported, and may be nested:
+///
+/// | Tag                          | Effect                                                    |
+/// |------------------------------|-----------------------------------------------------------|
+/// | `[b]bold[/b]`                | [`FontWeight::BOLD`]                                      |
+/// | `[i]italic[/i]`              | [`FontStyle::Italic`]                                     |
+/// | `[u]underline[/u]`           | [`Underline`]                                             |
+/// | `[s]strikethrough[/s]`       | [`Strikethrough`]                                         |
+/// | `[color=#ff8000]text[/color]`| [`TextColor`], from a hex color                           |
+/// | `[size=24]text[/size]`       | [`TextFont::font_size`]                                   |
+/// | `[url=target]text[/url]`     | [`TextLink`]; `[url]target[/url]` uses the text as target |
+/// | `[img]path/to/image.png[/img]` | [`InlineImage`]                                         |
+///
+/// Tag names are case-insensitive, and values may be wrapped in double quotes. A literal `[`, `]`
+/// or `\` is written by escaping it with a backslash.
+///
+/// The parsed text is turned into entities with [`RichText::spans`], which spawns one [`TextSpan`]
+/// per [`MarkupSpan`] as children of a `Text` or `Text2d` entity:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_color::palettes::css::WHITE;
+/// # use bevy_text::{RichText, TextFont};
+/// # let mut world = World::new();
+/// let text = RichText::parse("[b]Bold[/b], [color=#ff0000]red[/color] and [url=docs]a link[/url]")
+///     .unwrap();
+/// world.spawn(
+///     // (Text::default(), ...) or (Text2d::default(), ...)
+///     Children::spawn(text.spans(TextFont::from_font_size(20.0), WHITE)),
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichText {
+    /// The styled runs of text, in order.
+    pub spans: Vec<MarkupSpan>,
+}
+
+/// A tag that has been opened but not yet closed, and the style to restore when it is.
+struct OpenTag {
+    name: String,
+    previous: MarkupSpan,
+}
+
+impl RichText {
+    /// Parses rich text markup. See [`RichText`] for the supported tags.
+    pub fn parse(markup: &str) -> Result<Self, MarkupError> {
+        let mut spans = Vec::new();
+        let mut style = MarkupSpan::default();
+        let mut open_tags: Vec<OpenTag> = Vec::new();
+        let mut text = String::new();
+        let mut rest = markup;
+
+        while let Some(c) = rest.chars().next() {
+            let position = markup.len() - rest.len();
+            match c {
+                '\\' => {
+                    rest = &rest[1..];
+                    match rest.chars().next() {
+                        Some(escaped @ ('[' | ']' | '\\')) => {
+                            text.push(escaped);
+                            rest = &rest[1..];
+                        }
+                        _ => text.push('\\'),
+                    }
+                }
+                '[' => {
+                    let end = rest
+                        .find(']')
+                        .ok_or(MarkupError::UnterminatedTag { position })?;
+                    let tag = &rest[1..end];
+                    rest = &rest[end + 1..];
+                    flush_text(&mut spans, &mut text, &style);
+
+                    if let Some(name) = tag.strip_prefix('/') {
+                        let name = name.trim().to_ascii_lowercase();
+                        let Some(open) = open_tags.pop() else {
+                            return Err(MarkupError::UnexpectedClosingTag {
+                                tag: name,
+                                position,
+                            });
+                        };
+                        if open.name != name {
+                            return Err(MarkupError::MismatchedClosingTag {
+                                expected: open.name,
+                                found: name,
+                                position,
+                            });
+                        }
+                        style = open.previous;
+                        continue;
+                    }
+
+                    let (name, value) = match tag.split_once('=') {
+                        Some((name, value)) => (name, Some(unquote(value.trim()))),
+                        None => (tag, None),
+                    };
+                    let name = name.trim().to_ascii_lowercase();
+                    let previous = style.clone();
+                    match name.as_str() {
+                        "b" => style.weight = Some(FontWeight::BOLD),
+                        "i" => style.style = Some(FontStyle::Italic),
+                        "u" => style.underline = true,
+                        "s" => style.strikethrough = true,
+                        "color" => {
+                            let value = require_value(&name, value, position)?;
+                            let color = Srgba::hex(value)
+                                .map_err(|_| invalid_value(&name, value, position))?;
+                            style.color = Some(color.into());
+                        }
+                        "size" => {
+                            let value = require_value(&name, value, position)?;
+                            let size = value
+                                .parse::<f32>()
+                                .ok()
+                                .filter(|size| *size > 0.0)
+                                .ok_or_else(|| invalid_value(&name, value, position))?;
+                            style.font_size = Some(size);
+                        }
+                        "url" => {
+                            let target = match value {
+                                Some(target) => target.to_owned(),
+                                None => raw_content(rest, &name, position)?.to_owned(),
+                            };
+                            style.link = Some(target);
+                        }
+                        "img" => {
+                            let path = raw_content(rest, &name, position)?;
+                            spans.push(MarkupSpan {
+                                image: Some(path.to_owned()),
+                                ..style.clone()
+                            });
+                            rest = &rest[path.len() + "[/img]".len()..];
+                            continue;
+                        }
+                        _ => {
+                            return Err(MarkupError::UnknownTag {
+                                tag: name,
+                                position,
+                            })
+                        }
+                    }
+                    open_tags.push(OpenTag { name, previous });
+                }
+                _ => {
+                    text.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+
+        if let Some(open) = open_tags.pop() {
+            return Err(MarkupError::UnclosedTag { tag: open.name });
+        }
+        flush_text(&mut spans, &mut text, &style);
+        Ok(Self { spans })
+    }
+
+    /// Returns the text without any markup. Inline images are omitted.
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Returns a [`SpawnableList`] that spawns a [`TextSpan`] for each [`MarkupSpan`], for use
+    /// with [`Children::spawn`](bevy_ecs::hierarchy::Children) on a `Text` or `Text2d` entity.
+    ///
+    /// Each span gets a [`TextFont`] derived from `font` and a [`TextColor`] defaulting to `color`,
+    /// as well as [`Underline`], [`Strikethrough`], [`TextLink`] and [`InlineImage`] components
+    /// where the markup calls for them. Inline images are only loaded if the world has an
+    /// [`AssetServer`].
+    pub fn spans(self, font: TextFont, color: impl Into<Color>) -> impl SpawnableList<ChildOf> {
+        let color = color.into();
+        SpawnWith(move |parent: &mut RelatedSpawner<ChildOf>| {
+            for span in self.spans {
+                let text_font = span.text_font(&font);
+                let image = span.image.and_then(|path| {
+                    parent
+                        .world()
+                        .get_resource::<AssetServer>()
+                        .map(|asset_server| asset_server.load(path))
+                });
+                let text = match image {
+                    Some(_) => INLINE_IMAGE_PLACEHOLDER.to_owned(),
+                    None => span.text,
+                };
+                let mut entity = parent.spawn((
+                    TextSpan(text),
+                    text_font,
+                    TextColor(span.color.unwrap_or(color)),
+                ));
+                if span.underline {
+                    entity.insert(Underline);
+                }
+                if span.strikethrough {
+                    entity.insert(Strikethrough);
+                }
+                if let Some(link) = span.link {
+                    entity.insert(TextLink(link));
+                }
+                if let Some(image) = image {
+                    entity.insert(InlineImage(image));
+                }
+            }
+        })
+    }
+}
+
+impl FromStr for RichText {
+    type Err = MarkupError;
+
+    fn from_str(markup: &str) -> Result<Self, Self::Err> {
+        Self::parse(markup)
+    }
+}
+
+fn flush_text(spans: &mut Vec<MarkupSpan>, text: &mut String, style: &MarkupSpan) {
+    if !text.is_empty() {
+        spans.push(MarkupSpan {
+            text: core::mem::take(text),
+            ..style.clone()
+        });
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn require_value<'a>(
+    tag: &str,
+    value: Option<&'a str>,
+    position: usize,
+) -> Result<&'a str, MarkupError> {
+    value.ok_or_else(|| MarkupError::MissingValue {
+        tag: tag.to_owned(),
+        position,
+    })
+}
+
+fn invalid_value(tag: &str, value: &str, position: usize) -> MarkupError {
+    MarkupError::InvalidValue {
+        tag: tag.to_owned(),
+        value: value.to_owned(),
+        position,
+    }
+}
+
+/// Returns the unparsed content between an opening tag and its closing tag, which is matched
+/// case-insensitively like other tags.
+fn raw_content<'a>(rest: &'a str, tag: &str, position: usize) -> Result<&'a str, MarkupError> {
+    let closing = format!("[/{tag}]");
+    let content = rest
+        .as_bytes()
+        .windows(closing.len())
+        .position(|window| window.eq_ignore_ascii_case(closing.as_bytes()))
+        .map(|end| &rest[..end])
+        .ok_or_else(|| MarkupError::UnclosedTag {
+            tag: tag.to_owned(),
+        })?;
+    if content.is_empty() {
+        return Err(MarkupError::MissingValue {
+            tag: tag.to_owned(),
+            position,
+        });
+    }
+    Ok(content)
+}
+
+/// Marks a text span as part of a link, such as one created with the `[url]` markup tag.
+///
+/// Like other text spans, links receive picking events such as
+/// `Pointer<Click>` from the `bevy_ui` and `bevy_sprite` picking backends, which can be used to
+/// follow the link:
+///
+/// ```ignore
+/// fn on_link_click(click: On<Pointer<Click>>, links: Query<&TextLink>) {
+///     if let Ok(link) = links.get(click.entity) {
+///         info!("clicked {}", link.0);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct TextLink(pub String);
+
+/// An image drawn inline with text, in place of the text of the [`TextSpan`] it's added to.
+///
+/// The image is stretched to cover the layout bounds of the span's text, so its size is
+/// controlled through the text. Spans created with the `[img]` markup tag contain a single em
+/// space, which makes the image as wide as the font size and as high as the line.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct InlineImage(pub Handle<Image>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_nested_styles() {
+        let text =
+            RichText::parse("plain [b]bold [i]both[/i][/b] [color=#ff0000]red[/color]").unwrap();
+        let styles: Vec<_> = text
+            .spans
+            .iter()
+            .map(|span| (span.text.as_str(), span.weight, span.style, span.color))
+            .collect();
+        assert_eq!(
+            styles,
+            vec![
+                ("plain ", None, None, None),
+                ("bold ", Some(FontWeight::BOLD), None, None),
+                (
+                    "both",
+                    Some(FontWeight::BOLD),
+                    Some(FontStyle::Italic),
+                    None
+                ),
+                (" ", None, None, None),
+                ("red", None, None, Some(Srgba::RED.into())),
+            ]
+        );
+        assert_eq!(text.plain_text(), "plain bold both red");
+    }
+
+    #[test]
+    fn parse_links_images_and_escapes() {
+        let text = RichText::parse(
+            r#"\[x\] [URL="https://bevy.org"]site[/url] [url]docs[/url][img]icon.png[/img]"#,
+        )
+        .unwrap();
+        assert_eq!(text.spans[0].text, "[x] ");
+        assert_eq!(text.spans[1].text, "site");
+        assert_eq!(text.spans[1].link.as_deref(), Some("https://bevy.org"));
+        assert_eq!(text.spans[3].text, "docs");
+        assert_eq!(text.spans[3].link.as_deref(), Some("docs"));
+        assert_eq!(text.spans[4].image.as_deref(), Some("icon.png"));
+        assert_eq!(text.spans.len(), 5);
+    }
+
+    #[test]
+    fn parse_uppercase_raw_content_tags() {
+        let text = RichText::parse("[IMG]a.png[/IMG][Url]docs[/uRL]").unwrap();
+        assert_eq!(text.spans[0].image.as_deref(), Some("a.png"));
+        assert_eq!(text.spans[1].text, "docs");
+        assert_eq!(text.spans[1].link.as_deref(), Some("docs"));
+        assert_eq!(text.spans.len(), 2);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            RichText::parse("[b]bold"),
+            Err(MarkupError::UnclosedTag {
+                tag: "b".to_owned()
+            })
+        );
+        assert_eq!(
+            RichText::parse("[b][i]x[/b][/i]"),
+            Err(MarkupError::MismatchedClosingTag {
+                expected: "i".to_owned(),
+                found: "b".to_owned(),
+                position: 7,
+            })
+        );
+        assert_eq!(
+            RichText::parse("[size=big]x[/size]"),
+            Err(MarkupError::InvalidValue {
+                tag: "size".to_owned(),
+                value: "big".to_owned(),
+                position: 0,
+            })
+        );
+        assert_eq!(
+            RichText::parse("x[/u]"),
+            Err(MarkupError::UnexpectedClosingTag {
+                tag: "u".to_owned(),
+                position: 1,
+            })
+        );
+        assert!(matches!(
+            RichText::parse("[blink]x[/blink]"),
+            Err(MarkupError::UnknownTag { .. })
+        ));
+        assert!(matches!(
+            RichText::parse("[b"),
+            Err(MarkupError::UnterminatedTag { position: 0 })
+        ));
+    }
+}
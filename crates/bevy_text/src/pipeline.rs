@@ -11,9 +11,11 @@ use bevy_math::{Rect, UVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
 use crate::{
-    add_glyph_to_atlas, error::TextError, get_glyph_atlas_info, ComputedTextBlock, Font,
-    FontAtlasKey, FontAtlasSet, FontHinting, FontSmoothing, FontSource, FontStyle, FontWeight,
-    Justify, LineBreak, LineHeight, PositionedGlyph, TextBounds, TextEntity, TextFont, TextLayout,
+    add_glyph_to_atlas, add_sdf_glyph_to_atlas, error::TextError, font_fallback::system_locale,
+    get_glyph_atlas_info, ComputedTextBlock, Font, FontAtlasKey, FontAtlasSet, FontHinting,
+    FontSmoothing, FontSource, FontStyle, FontWeight, GlyphAtlasInfo, Justify, LineBreak,
+    LineHeight, PositionedGlyph, TextBounds, TextEntity, TextFont, TextLayout, TextRenderMode,
+    SDF_FONT_SIZE,
 };
 use cosmic_text::{Attrs, Buffer, Family, Metrics, Shaping, Wrap};
 
@@ -27,9 +29,9 @@ pub struct CosmicFontSystem(pub cosmic_text::FontSystem);
 
 impl Default for CosmicFontSystem {
     fn default() -> Self {
-        let locale = sys_locale::get_locale().unwrap_or_else(|| String::from("en-US"));
+        let locale = system_locale();
         let db = cosmic_text::fontdb::Database::new();
-        // TODO: consider using `cosmic_text::FontSystem::new()` (load system fonts by default)
+        // System fonts can be loaded with `FontFallbacks::load_system_fonts`.
         Self(cosmic_text::FontSystem::new_with_locale_and_db(locale, db))
     }
 }
@@ -298,6 +300,8 @@ impl TextPipeline {
     }
 
     /// Update [`TextLayoutInfo`] with the new [`PositionedGlyph`] layout.
+    ///
+    /// Glyphs are rasterized or rendered as signed distance fields depending on `render_mode`.
     pub fn update_text_layout_info(
         &mut self,
         layout_info: &mut TextLayoutInfo,
@@ -309,6 +313,7 @@ impl TextPipeline {
         swash_cache: &mut SwashCache,
         bounds: TextBounds,
         justify: Justify,
+        render_mode: TextRenderMode,
     ) -> Result<(), TextError> {
         computed.needs_rerender = false;
 
@@ -331,10 +336,10 @@ impl TextPipeline {
             let mut end: f32 = 0.;
 
             for layout_glyph in run.glyphs {
-                if maybe_run_geometry
-                    .as_ref()
-                    .is_some_and(|run_geometry| run_geometry.span_index != layout_glyph.metadata)
-                {
+                if maybe_run_geometry.as_ref().is_some_and(|run_geometry| {
+                    run_geometry.span_index != layout_glyph.metadata
+                        || run_geometry.font_id != layout_glyph.font_id
+                }) {
                     layout_info
                         .run_geometry
                         .push(maybe_run_geometry.take().unwrap());
@@ -353,6 +358,7 @@ impl TextPipeline {
 
                     maybe_run_geometry = Some(RunGeometry {
                         span_index: layout_glyph.metadata,
+                        font_id: layout_glyph.font_id,
                         bounds: Rect::new(
                             start,
                             run.line_top,
@@ -369,67 +375,32 @@ impl TextPipeline {
                 end = layout_glyph.x + layout_glyph.w;
                 maybe_run_geometry.as_mut().unwrap().bounds.max.x = end;
 
-                let mut temp_glyph;
                 let span_index = layout_glyph.metadata;
-                let font_smoothing = FontSmoothing::AntiAliased;
-
-                let layout_glyph = if font_smoothing == FontSmoothing::None {
-                    // If font smoothing is disabled, round the glyph positions and sizes,
-                    // effectively discarding all subpixel layout.
-                    temp_glyph = layout_glyph.clone();
-                    temp_glyph.x = temp_glyph.x.round();
-                    temp_glyph.y = temp_glyph.y.round();
-                    temp_glyph.w = temp_glyph.w.round();
-                    temp_glyph.x_offset = temp_glyph.x_offset.round();
-                    temp_glyph.y_offset = temp_glyph.y_offset.round();
-                    temp_glyph.line_height_opt = temp_glyph.line_height_opt.map(f32::round);
-
-                    &temp_glyph
-                } else {
-                    layout_glyph
-                };
 
-                let physical_glyph = layout_glyph.physical((0., 0.), 1.);
-
-                let font_atlases = font_atlas_set
-                    .entry(FontAtlasKey {
-                        id: physical_glyph.cache_key.font_id,
-                        font_size_bits: physical_glyph.cache_key.font_size_bits,
-                        font_smoothing,
-                    })
-                    .or_default();
-
-                let atlas_info = get_glyph_atlas_info(font_atlases, physical_glyph.cache_key)
-                    .map(Ok)
-                    .unwrap_or_else(|| {
-                        add_glyph_to_atlas(
-                            font_atlases,
-                            texture_atlases,
-                            textures,
-                            &mut font_system.0,
-                            &mut swash_cache.0,
-                            layout_glyph,
-                            font_smoothing,
-                        )
-                    })?;
-
-                let texture_atlas = texture_atlases.get(atlas_info.texture_atlas).unwrap();
-                let location = atlas_info.location;
-                let glyph_rect = texture_atlas.textures[location.glyph_index];
-                let left = location.offset.x as f32;
-                let top = location.offset.y as f32;
-                let glyph_size = UVec2::new(glyph_rect.width(), glyph_rect.height());
-
-                // offset by half the size because the origin is center
-                let x = glyph_size.x as f32 / 2.0 + left + physical_glyph.x as f32;
-                let y =
-                    run.line_y.round() + physical_glyph.y as f32 - top + glyph_size.y as f32 / 2.0;
-
-                let position = Vec2::new(x, y);
+                let (atlas_info, position, size) = match render_mode {
+                    TextRenderMode::Raster => raster_glyph(
+                        layout_glyph,
+                        run.line_y,
+                        font_atlas_set,
+                        texture_atlases,
+                        textures,
+                        font_system,
+                        swash_cache,
+                    )?,
+                    TextRenderMode::Sdf => sdf_glyph(
+                        layout_glyph,
+                        run.line_y,
+                        font_atlas_set,
+                        texture_atlases,
+                        textures,
+                        font_system,
+                        swash_cache,
+                    )?,
+                };
 
                 let pos_glyph = PositionedGlyph {
                     position,
-                    size: glyph_size.as_vec2(),
+                    size,
                     atlas_info,
                     span_index,
                     byte_index: layout_glyph.start,
@@ -462,7 +433,7 @@ pub struct TextLayoutInfo {
     pub glyphs: Vec<PositionedGlyph>,
     /// Geometry of each text run used to render text decorations like background colors, strikethrough, and underline.
     /// A run in `bevy_text` is a contiguous sequence of glyphs on a line that share the same text attributes like font,
-    /// font size, and line height. A text entity that extends over multiple lines will have multiple corresponding runs.
+    /// font size, and line height, and that are rendered with the same font face. A text entity that extends over multiple lines will have multiple corresponding runs.
     ///
     /// The coordinates are unscaled and relative to the top left corner of the text layout.
     pub run_geometry: Vec<RunGeometry>,
@@ -487,6 +458,13 @@ impl TextLayoutInfo {
 pub struct RunGeometry {
     /// The index of the text entity in [`ComputedTextBlock`] that this run belongs to.
     pub span_index: usize,
+    /// The font face that rendered the glyphs of this run.
+    ///
+    /// This is a fallback font instead of the font of the span's [`TextFont`] when that font has
+    /// no glyphs for the text, see [`FontFallbacks`](crate::FontFallbacks). Details such as the
+    /// family name are available from [`CosmicFontSystem::get_face_details`].
+    #[reflect(ignore, clone)]
+    pub font_id: cosmic_text::fontdb::ID,
     /// Bounding box around the text run
     pub bounds: Rect,
     /// Y position of the strikethrough in the text layout.
@@ -557,6 +535,136 @@ impl TextMeasureInfo {
     }
 }
 
+/// Rasterizes a glyph into the font atlases, and returns its atlas info, position and size.
+fn raster_glyph(
+    layout_glyph: &cosmic_text::LayoutGlyph,
+    line_y: f32,
+    font_atlas_set: &mut FontAtlasSet,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+    textures: &mut Assets<Image>,
+    font_system: &mut CosmicFontSystem,
+    swash_cache: &mut SwashCache,
+) -> Result<(GlyphAtlasInfo, Vec2, Vec2), TextError> {
+    let mut temp_glyph;
+    let font_smoothing = FontSmoothing::AntiAliased;
+
+    let layout_glyph = if font_smoothing == FontSmoothing::None {
+        // If font smoothing is disabled, round the glyph positions and sizes,
+        // effectively discarding all subpixel layout.
+        temp_glyph = layout_glyph.clone();
+        temp_glyph.x = temp_glyph.x.round();
+        temp_glyph.y = temp_glyph.y.round();
+        temp_glyph.w = temp_glyph.w.round();
+        temp_glyph.x_offset = temp_glyph.x_offset.round();
+        temp_glyph.y_offset = temp_glyph.y_offset.round();
+        temp_glyph.line_height_opt = temp_glyph.line_height_opt.map(f32::round);
+
+        &temp_glyph
+    } else {
+        layout_glyph
+    };
+
+    let physical_glyph = layout_glyph.physical((0., 0.), 1.);
+
+    let font_atlases = font_atlas_set
+        .entry(FontAtlasKey {
+            id: physical_glyph.cache_key.font_id,
+            font_size_bits: physical_glyph.cache_key.font_size_bits,
+            font_smoothing,
+            render_mode: TextRenderMode::Raster,
+        })
+        .or_default();
+
+    let atlas_info = get_glyph_atlas_info(font_atlases, physical_glyph.cache_key)
+        .map(Ok)
+        .unwrap_or_else(|| {
+            add_glyph_to_atlas(
+                font_atlases,
+                texture_atlases,
+                textures,
+                &mut font_system.0,
+                &mut swash_cache.0,
+                layout_glyph,
+                font_smoothing,
+            )
+        })?;
+
+    let texture_atlas = texture_atlases.get(atlas_info.texture_atlas).unwrap();
+    let location = atlas_info.location;
+    let glyph_rect = texture_atlas.textures[location.glyph_index];
+    let left = location.offset.x as f32;
+    let top = location.offset.y as f32;
+    let glyph_size = UVec2::new(glyph_rect.width(), glyph_rect.height());
+
+    // offset by half the size because the origin is center
+    let x = glyph_size.x as f32 / 2.0 + left + physical_glyph.x as f32;
+    let y = line_y.round() + physical_glyph.y as f32 - top + glyph_size.y as f32 / 2.0;
+
+    Ok((atlas_info, Vec2::new(x, y), glyph_size.as_vec2()))
+}
+
+/// Adds the signed distance field of a glyph to the font atlases, and returns its atlas info,
+/// position and size.
+fn sdf_glyph(
+    layout_glyph: &cosmic_text::LayoutGlyph,
+    line_y: f32,
+    font_atlas_set: &mut FontAtlasSet,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+    textures: &mut Assets<Image>,
+    font_system: &mut CosmicFontSystem,
+    swash_cache: &mut SwashCache,
+) -> Result<(GlyphAtlasInfo, Vec2, Vec2), TextError> {
+    // Signed distance fields are generated once at `SDF_FONT_SIZE`, without subpixel offsets, and
+    // scaled to the font size.
+    let (cache_key, _, _) = cosmic_text::CacheKey::new(
+        layout_glyph.font_id,
+        layout_glyph.glyph_id,
+        SDF_FONT_SIZE,
+        (0., 0.),
+        layout_glyph.font_weight,
+        layout_glyph.cache_key_flags,
+    );
+
+    let font_atlases = font_atlas_set
+        .entry(FontAtlasKey {
+            id: cache_key.font_id,
+            font_size_bits: cache_key.font_size_bits,
+            font_smoothing: FontSmoothing::AntiAliased,
+            render_mode: TextRenderMode::Sdf,
+        })
+        .or_default();
+
+    let atlas_info = get_glyph_atlas_info(font_atlases, cache_key)
+        .map(Ok)
+        .unwrap_or_else(|| {
+            add_sdf_glyph_to_atlas(
+                font_atlases,
+                texture_atlases,
+                textures,
+                &mut font_system.0,
+                &mut swash_cache.0,
+                cache_key,
+            )
+        })?;
+
+    let texture_atlas = texture_atlases.get(atlas_info.texture_atlas).unwrap();
+    let location = atlas_info.location;
+    let glyph_rect = texture_atlas.textures[location.glyph_index];
+    let scale = layout_glyph.font_size / SDF_FONT_SIZE;
+    let offset = location.offset.as_vec2() * scale;
+    let glyph_size = UVec2::new(glyph_rect.width(), glyph_rect.height()).as_vec2() * scale;
+
+    // offset by half the size because the origin is center
+    let x = glyph_size.x / 2.0
+        + offset.x
+        + layout_glyph.x
+        + layout_glyph.font_size * layout_glyph.x_offset;
+    let y = line_y + layout_glyph.y - layout_glyph.font_size * layout_glyph.y_offset - offset.y
+        + glyph_size.y / 2.0;
+
+    Ok((atlas_info, Vec2::new(x, y), glyph_size))
+}
+
 /// Translates [`TextFont`] to [`Attrs`].
 fn get_attrs<'a>(
     span_index: usize,
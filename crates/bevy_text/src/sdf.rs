@@ -0,0 +1,299 @@
+use bevy_asset::{Assets, RenderAssetUsages};
+use bevy_image::prelude::*;
+use bevy_log::{once, warn};
+use bevy_math::{IVec2, Vec2};
+use cosmic_text::Command;
+use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::{
+    add_glyph_texture_to_atlas, FontAtlas, FontSmoothing, GlyphAtlasInfo, TextError, TextRenderMode,
+};
+
+/// The font size, in pixels, at which the signed distance fields of glyphs rendered with
+/// [`TextRenderMode::Sdf`](crate::TextRenderMode::Sdf) are generated.
+pub const SDF_FONT_SIZE: f32 = 48.;
+
+/// The distance, in pixels at [`SDF_FONT_SIZE`], from the edge of a glyph over which its signed
+/// distance field goes from fully inside to fully outside the glyph.
+///
+/// This limits the width of effects like outlines.
+pub const SDF_SPREAD: f32 = 6.;
+
+/// Converts a distance in pixels around a glyph rendered with
+/// [`TextRenderMode::Sdf`](crate::TextRenderMode::Sdf) into a distance in its signed distance
+/// field, where the edge of the glyph is at `0.5` and [`SDF_SPREAD`] is `0.5` away from it.
+/// Distances beyond the spread are clamped to it.
+///
+/// `glyph_size` is the size of the [`PositionedGlyph`](crate::PositionedGlyph) and `atlas_size`
+/// the size of its signed distance field in the atlas. This is used by renderers to grow glyphs,
+/// for example to draw outlines.
+pub fn sdf_distance(distance: f32, glyph_size: Vec2, atlas_size: Vec2) -> f32 {
+    let scale = glyph_size.y / atlas_size.y;
+    if scale > 0. {
+        (distance / (2. * SDF_SPREAD * scale)).clamp(-0.5, 0.5)
+    } else {
+        0.
+    }
+}
+
+/// Adds the signed distance field of a glyph to the given font atlases.
+///
+/// The glyph is generated at the font size of the `cache_key`, which should be [`SDF_FONT_SIZE`]
+/// with no subpixel offset.
+pub fn add_sdf_glyph_to_atlas(
+    font_atlases: &mut Vec<FontAtlas>,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+    textures: &mut Assets<Image>,
+    font_system: &mut cosmic_text::FontSystem,
+    swash_cache: &mut cosmic_text::SwashCache,
+    cache_key: cosmic_text::CacheKey,
+) -> Result<GlyphAtlasInfo, TextError> {
+    let (glyph_texture, offset) = match swash_cache.get_outline_commands(font_system, cache_key) {
+        Some(commands) => get_sdf_glyph_texture(commands, SDF_SPREAD),
+        None => {
+            once!(warn!(
+                "Glyph {} has no outline and can't be rendered with `TextRenderMode::Sdf`.",
+                cache_key.glyph_id
+            ));
+            get_sdf_glyph_texture(&[], SDF_SPREAD)
+        }
+    };
+
+    add_glyph_texture_to_atlas(
+        font_atlases,
+        texture_atlases,
+        textures,
+        cache_key,
+        &glyph_texture,
+        offset,
+        FontSmoothing::AntiAliased,
+        TextRenderMode::Sdf,
+    )
+}
+
+/// Generates the signed distance field of a glyph outline, and its offset.
+///
+/// The outline is in pixels, with the y axis pointing up. The alpha channel of the image stores the
+/// signed distance to the outline, mapped from `-spread..spread` pixels to `0..255`, so that `128`
+/// lies on the edge and larger values are inside the glyph. The color channels are white, and the
+/// offset is the position of the top left corner of the image relative to the glyph origin, like
+/// for rasterized glyphs.
+pub fn get_sdf_glyph_texture(commands: &[Command], spread: f32) -> (Image, IVec2) {
+    let segments = flatten_outline(commands);
+
+    let (min, max) = segments.iter().flatten().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(min, max), point| (min.min(*point), max.max(*point)),
+    );
+    let (left, top, width, height) = if segments.is_empty() {
+        (0, 0, 0, 0)
+    } else {
+        let left = (min.x - spread).floor() as i32;
+        let right = (max.x + spread).ceil() as i32;
+        let bottom = (min.y - spread).floor() as i32;
+        let top = (max.y + spread).ceil() as i32;
+        (left, top, (right - left) as u32, (top - bottom) as u32)
+    };
+
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let point = Vec2::new(left as f32 + x as f32 + 0.5, top as f32 - y as f32 - 0.5);
+            let distance = signed_distance(&segments, point);
+            let value = (0.5 + distance / (2. * spread)).clamp(0., 1.);
+            data.extend_from_slice(&[255, 255, 255, (value * 255.).round() as u8]);
+        }
+    }
+
+    (
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        ),
+        IVec2::new(left, top),
+    )
+}
+
+/// Approximates an outline with line segments.
+fn flatten_outline(commands: &[Command]) -> Vec<[Vec2; 2]> {
+    // Curves are split into segments of about this length in pixels.
+    const SEGMENT_LENGTH: f32 = 2.;
+
+    fn subdivisions(control_points: &[Vec2]) -> usize {
+        let length: f32 = control_points
+            .windows(2)
+            .map(|points| points[0].distance(points[1]))
+            .sum();
+        ((length / SEGMENT_LENGTH).ceil() as usize).clamp(1, 16)
+    }
+
+    let mut segments = Vec::new();
+    let mut start = Vec2::ZERO;
+    let mut current = Vec2::ZERO;
+    for command in commands {
+        match *command {
+            Command::MoveTo(to) => {
+                if current != start {
+                    segments.push([current, start]);
+                }
+                start = Vec2::from(<[f32; 2]>::from(to));
+                current = start;
+            }
+            Command::LineTo(to) => {
+                let to = Vec2::from(<[f32; 2]>::from(to));
+                segments.push([current, to]);
+                current = to;
+            }
+            Command::QuadTo(control, to) => {
+                let control = Vec2::from(<[f32; 2]>::from(control));
+                let to = Vec2::from(<[f32; 2]>::from(to));
+                let from = current;
+                let n = subdivisions(&[from, control, to]);
+                for i in 1..=n {
+                    let t = i as f32 / n as f32;
+                    let point = from.lerp(control, t).lerp(control.lerp(to, t), t);
+                    segments.push([current, point]);
+                    current = point;
+                }
+            }
+            Command::CurveTo(control1, control2, to) => {
+                let control1 = Vec2::from(<[f32; 2]>::from(control1));
+                let control2 = Vec2::from(<[f32; 2]>::from(control2));
+                let to = Vec2::from(<[f32; 2]>::from(to));
+                let from = current;
+                let n = subdivisions(&[from, control1, control2, to]);
+                for i in 1..=n {
+                    let t = i as f32 / n as f32;
+                    let u = 1. - t;
+                    let point = from * (u * u * u)
+                        + control1 * (3. * u * u * t)
+                        + control2 * (3. * u * t * t)
+                        + to * (t * t * t);
+                    segments.push([current, point]);
+                    current = point;
+                }
+            }
+            Command::Close => {
+                if current != start {
+                    segments.push([current, start]);
+                }
+                current = start;
+            }
+        }
+    }
+    if current != start {
+        segments.push([current, start]);
+    }
+    segments
+}
+
+/// Returns the distance from a point to the closest segment of an outline, positive inside the
+/// outline using the nonzero winding rule and negative outside of it.
+fn signed_distance(segments: &[[Vec2; 2]], point: Vec2) -> f32 {
+    let mut distance_squared = f32::INFINITY;
+    let mut winding = 0;
+    for &[a, b] in segments {
+        let ab = b - a;
+        let t = ((point - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0., 1.);
+        distance_squared = distance_squared.min(point.distance_squared(a + ab * t));
+
+        // Count the crossings of a ray from the point towards positive x.
+        if (a.y <= point.y) != (b.y <= point.y) {
+            let x = a.x + (point.y - a.y) / ab.y * ab.x;
+            if x > point.x {
+                winding += if b.y > a.y { 1 } else { -1 };
+            }
+        }
+    }
+
+    let distance = distance_squared.sqrt();
+    if winding != 0 {
+        distance
+    } else {
+        -distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_image::ImageSampler;
+
+    #[test]
+    fn square_sdf() {
+        let commands = [
+            Command::MoveTo([0., 0.].into()),
+            Command::LineTo([0., 20.].into()),
+            Command::LineTo([20., 20.].into()),
+            Command::LineTo([20., 0.].into()),
+            Command::Close,
+        ];
+        let (image, offset) = get_sdf_glyph_texture(&commands, 4.);
+        assert_eq!(offset, IVec2::new(-4, 24));
+        assert_eq!((image.width(), image.height()), (28, 28));
+
+        let alpha = |x: u32, y: u32| image.data.as_ref().unwrap()[((y * 28 + x) * 4 + 3) as usize];
+        // Deep inside the square.
+        assert_eq!(alpha(14, 14), 255);
+        // Outside the square, further than the spread.
+        assert_eq!(alpha(0, 0), 0);
+        // Half a pixel inside the left edge.
+        assert_eq!(alpha(4, 14), 143);
+        // Half a pixel outside the left edge.
+        assert_eq!(alpha(3, 14), 112);
+    }
+
+    #[test]
+    fn sdf_atlases_are_sampled_linearly() {
+        let mut font_atlases = Vec::new();
+        let mut texture_atlases = Assets::<TextureAtlasLayout>::default();
+        let mut textures = Assets::<Image>::default();
+        let (cache_key, _, _) = cosmic_text::CacheKey::new(
+            cosmic_text::fontdb::ID::dummy(),
+            1,
+            SDF_FONT_SIZE,
+            (0., 0.),
+            cosmic_text::fontdb::Weight::NORMAL,
+            cosmic_text::CacheKeyFlags::empty(),
+        );
+        let commands = [
+            Command::MoveTo([0., 0.].into()),
+            Command::LineTo([10., 0.].into()),
+            Command::LineTo([0., 10.].into()),
+            Command::Close,
+        ];
+        let (glyph_texture, offset) = get_sdf_glyph_texture(&commands, SDF_SPREAD);
+
+        add_glyph_texture_to_atlas(
+            &mut font_atlases,
+            &mut texture_atlases,
+            &mut textures,
+            cache_key,
+            &glyph_texture,
+            offset,
+            FontSmoothing::AntiAliased,
+            TextRenderMode::Sdf,
+        )
+        .unwrap();
+
+        let texture = textures.get(&font_atlases[0].texture).unwrap();
+        assert_eq!(
+            format!("{:?}", texture.sampler),
+            format!("{:?}", ImageSampler::linear())
+        );
+    }
+
+    #[test]
+    fn empty_outline() {
+        let (image, offset) = get_sdf_glyph_texture(&[], SDF_SPREAD);
+        assert_eq!(offset, IVec2::ZERO);
+        assert_eq!((image.width(), image.height()), (0, 0));
+    }
+}
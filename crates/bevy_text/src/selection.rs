@@ -0,0 +1,399 @@
+use core::time::Duration;
+
+use bevy_color::Color;
+use bevy_ecs::{
+    component::Component,
+    query::With,
+    reflect::ReflectComponent,
+    system::{Query, Res, ResMut},
+};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_log::warn;
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_window::Clipboard;
+use cosmic_text::{Cursor, Motion};
+
+use crate::{ComputedTextBlock, CosmicFontSystem};
+
+/// The longest time between two presses for them to count as a double or triple click.
+pub const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The largest distance, in physical pixels, between two presses for them to count as a double or
+/// triple click.
+const MULTI_CLICK_DISTANCE: f32 = 4.;
+
+/// Makes the text of a `Text` or `Text2d` entity selectable with a pointer.
+///
+/// Dragging selects characters, double-clicking selects a word and triple-clicking selects a
+/// line. The selection is stored in the [`TextSelection`] of the entity, highlighted with its
+/// [`TextSelectionColor`], and copied to the [`Clipboard`] with `Ctrl+C` or `Cmd+C`.
+///
+/// Pointer interactions require the picking backend of `bevy_ui` or `bevy_sprite`.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(TextSelection, TextSelectionColor)]
+pub struct SelectableText;
+
+/// The color used to highlight the [`TextSelection`] of [`SelectableText`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct TextSelectionColor(pub Color);
+
+impl Default for TextSelectionColor {
+    fn default() -> Self {
+        Self(Color::srgba(0.2, 0.45, 0.9, 0.4))
+    }
+}
+
+/// A position between two characters in the text of a [`ComputedTextBlock`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[reflect(Debug, Default, Clone, PartialEq, Hash)]
+pub struct TextCursor {
+    /// The index of the line in the text. Lines are separated by line breaks, lines wrapped during
+    /// layout aren't counted separately.
+    pub line: usize,
+    /// The byte index of the position in the line.
+    pub index: usize,
+}
+
+impl TextCursor {
+    /// Creates a cursor at the given byte index of a line.
+    pub const fn new(line: usize, index: usize) -> Self {
+        Self { line, index }
+    }
+}
+
+impl From<Cursor> for TextCursor {
+    fn from(cursor: Cursor) -> Self {
+        Self::new(cursor.line, cursor.index)
+    }
+}
+
+impl From<TextCursor> for Cursor {
+    fn from(cursor: TextCursor) -> Self {
+        Cursor::new(cursor.line, cursor.index)
+    }
+}
+
+/// The selected range of the text of [`SelectableText`].
+///
+/// Positions passed to [`press`](Self::press) and [`drag`](Self::drag) and the rectangles returned
+/// by [`rects`](Self::rects) are in the coordinates of the [`TextLayoutInfo`](crate::TextLayoutInfo):
+/// in physical pixels, relative to the top left corner of the text, with the y axis pointing down.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct TextSelection {
+    /// The position where the selection started.
+    pub anchor: TextCursor,
+    /// The position where the selection ends, which follows the pointer while dragging.
+    pub focus: TextCursor,
+    #[reflect(ignore, clone)]
+    gesture: SelectionGesture,
+}
+
+/// The state of the pointer gesture that is changing a [`TextSelection`].
+#[derive(Clone, Debug, Default)]
+struct SelectionGesture {
+    /// The number of consecutive presses, from 1 to 3.
+    clicks: u32,
+    /// The time and position of the last press.
+    last_press: Option<(Duration, Vec2)>,
+    /// The range selected by the last press, which dragging extends.
+    origin: (TextCursor, TextCursor),
+}
+
+impl TextSelection {
+    /// Creates a selection from `anchor` to `focus`.
+    pub fn new(anchor: TextCursor, focus: TextCursor) -> Self {
+        Self {
+            anchor,
+            focus,
+            gesture: SelectionGesture::default(),
+        }
+    }
+
+    /// Returns `true` if no text is selected.
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.focus
+    }
+
+    /// Returns the start and end of the selection, in text order.
+    pub fn range(&self) -> (TextCursor, TextCursor) {
+        (self.anchor.min(self.focus), self.anchor.max(self.focus))
+    }
+
+    /// Deselects the text.
+    pub fn clear(&mut self) {
+        self.focus = self.anchor;
+    }
+
+    /// Selects all of the text of a block.
+    pub fn select_all(&mut self, block: &ComputedTextBlock) {
+        let lines = &block.buffer.lines;
+        self.anchor = TextCursor::default();
+        self.focus = lines
+            .last()
+            .map(|line| TextCursor::new(lines.len() - 1, line.text().len()))
+            .unwrap_or_default();
+    }
+
+    /// Starts a selection at `position`.
+    ///
+    /// A single press places the selection at the closest position between two characters,
+    /// consecutive presses at the same position within [`MULTI_CLICK_INTERVAL`] select the word
+    /// under `position`, then its line. `now` is the time of the press, typically
+    /// `Time::elapsed`, which presses are compared with.
+    pub fn press(
+        &mut self,
+        block: &mut ComputedTextBlock,
+        font_system: &mut CosmicFontSystem,
+        position: Vec2,
+        now: Duration,
+    ) {
+        let Some(cursor) = block.buffer.hit(position.x, position.y) else {
+            self.clear();
+            return;
+        };
+
+        let gesture = &mut self.gesture;
+        gesture.clicks = match gesture.last_press {
+            Some((time, last_position))
+                if now.saturating_sub(time) <= MULTI_CLICK_INTERVAL
+                    && last_position.distance(position) <= MULTI_CLICK_DISTANCE =>
+            {
+                gesture.clicks % 3 + 1
+            }
+            _ => 1,
+        };
+        gesture.last_press = Some((now, position));
+        gesture.origin = gesture.range_at(block, font_system, cursor);
+        (self.anchor, self.focus) = gesture.origin;
+    }
+
+    /// Extends the selection started by the last [`press`](Self::press) to `position`.
+    ///
+    /// After a double or triple click, the selection is extended by whole words or lines.
+    pub fn drag(
+        &mut self,
+        block: &mut ComputedTextBlock,
+        font_system: &mut CosmicFontSystem,
+        position: Vec2,
+    ) {
+        let Some(cursor) = block.buffer.hit(position.x, position.y) else {
+            return;
+        };
+
+        let (start, end) = self.gesture.range_at(block, font_system, cursor);
+        let (origin_start, origin_end) = self.gesture.origin;
+        if start < origin_start {
+            self.anchor = origin_end;
+            self.focus = start;
+        } else {
+            self.anchor = origin_start;
+            self.focus = end.max(origin_end);
+        }
+    }
+
+    /// Returns the selected text of a block.
+    ///
+    /// Selected line breaks are included.
+    pub fn selected_text(&self, block: &ComputedTextBlock) -> String {
+        let (start, end) = self.range();
+        let mut text = String::new();
+        for (i, line) in block
+            .buffer
+            .lines
+            .iter()
+            .enumerate()
+            .take(end.line + 1)
+            .skip(start.line)
+        {
+            let line_text = line.text();
+            let from = if i == start.line { start.index } else { 0 };
+            let to = if i == end.line {
+                end.index.min(line_text.len())
+            } else {
+                line_text.len()
+            };
+            text.push_str(line_text.get(from..to).unwrap_or_default());
+            if i < end.line {
+                text.push_str(line.ending().as_str());
+            }
+        }
+        text
+    }
+
+    /// Returns the rectangles covering the selected text of a block, one for each line of the
+    /// layout.
+    pub fn rects<'a>(&self, block: &'a ComputedTextBlock) -> impl Iterator<Item = Rect> + 'a {
+        let (start, end) = self.range();
+        let empty = self.is_empty();
+        block
+            .buffer
+            .layout_runs()
+            .filter(move |_| !empty)
+            .filter_map(move |run| {
+                let (x, width) = run.highlight(start.into(), end.into())?;
+                (width > 0.)
+                    .then(|| Rect::new(x, run.line_top, x + width, run.line_top + run.line_height))
+            })
+    }
+}
+
+impl SelectionGesture {
+    /// Returns the range selected around `cursor` by the current number of clicks.
+    fn range_at(
+        &self,
+        block: &mut ComputedTextBlock,
+        font_system: &mut CosmicFontSystem,
+        cursor: Cursor,
+    ) -> (TextCursor, TextCursor) {
+        let buffer = &mut block.buffer;
+        let line_length = buffer
+            .lines
+            .get(cursor.line)
+            .map_or(0, |line| line.text().len());
+        let mut motion = |cursor: Cursor, motion| {
+            buffer
+                .cursor_motion(font_system, cursor, None, motion)
+                .map_or(cursor, |(cursor, _)| cursor)
+        };
+
+        match self.clicks {
+            2 => {
+                // Word motions move to the next line at the ends of lines, which must not happen
+                // when looking for the boundaries of the word around the cursor.
+                let end = if cursor.index < line_length {
+                    motion(cursor, Motion::NextWord)
+                } else {
+                    cursor
+                };
+                let start = if end.index > 0 {
+                    motion(end, Motion::PreviousWord)
+                } else {
+                    end
+                };
+                (start.into(), end.into())
+            }
+            3 => (
+                motion(cursor, Motion::ParagraphStart).into(),
+                motion(cursor, Motion::ParagraphEnd).into(),
+            ),
+            _ => (cursor.into(), cursor.into()),
+        }
+    }
+}
+
+/// Copies the selected text of [`SelectableText`] to the [`Clipboard`] when `Ctrl+C` or `Cmd+C`
+/// is pressed.
+pub fn copy_text_selection(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut clipboard: ResMut<Clipboard>,
+    selections: Query<(&TextSelection, &ComputedTextBlock), With<SelectableText>>,
+) {
+    let modifier = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    if !modifier || !keyboard.just_pressed(KeyCode::KeyC) {
+        return;
+    }
+
+    let Some((selection, block)) = selections
+        .iter()
+        .find(|(selection, _)| !selection.is_empty())
+    else {
+        return;
+    };
+    if let Err(error) = clipboard.set_text(selection.selected_text(block)) {
+        warn!("Failed to copy the selected text: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmic_text::{fontdb, Attrs, Metrics, Shaping};
+
+    use super::*;
+
+    fn block(text: &str) -> (ComputedTextBlock, CosmicFontSystem) {
+        let mut db = fontdb::Database::new();
+        db.load_font_data(include_bytes!("FiraMono-subset.ttf").to_vec());
+        let mut font_system = CosmicFontSystem(cosmic_text::FontSystem::new_with_locale_and_db(
+            String::from("en-US"),
+            db,
+        ));
+
+        let mut block = ComputedTextBlock::default();
+        let buffer = &mut block.buffer;
+        buffer.set_metrics(&mut font_system, Metrics::new(10., 10.));
+        buffer.set_text(
+            &mut font_system,
+            text,
+            &Attrs::new(),
+            Shaping::Advanced,
+            None,
+        );
+        buffer.shape_until_scroll(&mut font_system, false);
+        (block, font_system)
+    }
+
+    #[test]
+    fn press_and_drag() {
+        let (mut block, mut font_system) = block("hello world\nsecond line");
+        let glyph_width = block.buffer.layout_runs().next().unwrap().glyphs[0].w;
+        let at = |column: f32, line: f32| Vec2::new(glyph_width * column, 10. * line + 5.);
+
+        let mut selection = TextSelection::default();
+        selection.press(&mut block, &mut font_system, at(2., 0.), Duration::ZERO);
+        assert!(selection.is_empty());
+        assert_eq!(selection.anchor, TextCursor::new(0, 2));
+
+        selection.drag(&mut block, &mut font_system, at(3., 1.));
+        assert_eq!(selection.selected_text(&block), "llo world\nsec");
+        assert_eq!(selection.rects(&block).count(), 2);
+
+        // Dragging before the anchor selects backwards.
+        selection.drag(&mut block, &mut font_system, at(0., 0.));
+        assert_eq!(selection.focus, TextCursor::new(0, 0));
+        assert_eq!(selection.selected_text(&block), "he");
+    }
+
+    #[test]
+    fn multi_click() {
+        let (mut block, mut font_system) = block("hello world\nsecond line");
+        let glyph_width = block.buffer.layout_runs().next().unwrap().glyphs[0].w;
+        let position = Vec2::new(glyph_width * 8.5, 5.);
+        let mut now = Duration::ZERO;
+        let mut press = |selection: &mut TextSelection, block: &mut ComputedTextBlock, delay| {
+            now += delay;
+            selection.press(block, &mut font_system, position, now);
+        };
+        let quickly = Duration::from_millis(100);
+
+        let mut selection = TextSelection::default();
+        press(&mut selection, &mut block, quickly);
+        press(&mut selection, &mut block, quickly);
+        assert_eq!(selection.selected_text(&block), "world");
+
+        press(&mut selection, &mut block, quickly);
+        assert_eq!(selection.selected_text(&block), "hello world");
+
+        // Presses further apart than the interval start over.
+        press(&mut selection, &mut block, quickly);
+        press(&mut selection, &mut block, MULTI_CLICK_INTERVAL * 2);
+        assert!(selection.is_empty());
+
+        // Dragging after a double click extends the selection by words.
+        press(&mut selection, &mut block, quickly);
+        selection.drag(
+            &mut block,
+            &mut font_system,
+            Vec2::new(glyph_width * 2.5, 15.),
+        );
+        assert_eq!(selection.selected_text(&block), "world\nsecond");
+    }
+}
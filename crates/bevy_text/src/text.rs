@@ -1011,3 +1011,48 @@ impl From<FontHinting> for cosmic_text::Hinting {
         }
     }
 }
+
+/// How the glyphs of a text entity are rendered.
+///
+/// Set this on the root text entity; it applies to all of its spans.
+#[derive(Component, Debug, Copy, Clone, Default, Reflect, PartialEq, Eq, Hash)]
+#[reflect(Component, Default, Debug, Clone, PartialEq, Hash)]
+pub enum TextRenderMode {
+    /// Glyphs are rasterized at the size they are laid out at.
+    ///
+    /// Produces the sharpest text when it's displayed at its laid out size, but every font size
+    /// needs its own glyphs, and the text looks blurry when it's scaled up, for example by zooming
+    /// a 2d camera.
+    #[default]
+    Raster,
+    /// Glyphs are rendered from signed distance fields, which are generated once per glyph at
+    /// [`SDF_FONT_SIZE`](crate::SDF_FONT_SIZE) from the glyph outlines.
+    ///
+    /// The text stays sharp at any font size and scale, and can be drawn with a [`TextOutline`].
+    /// Sharp corners are slightly rounded, and color glyphs such as emoji aren't rendered.
+    Sdf,
+}
+
+/// Draws an outline around the glyphs of a text entity rendered with [`TextRenderMode::Sdf`].
+///
+/// Set this on the root text entity; it applies to all of its spans.
+#[derive(Component, Debug, Copy, Clone, Reflect, PartialEq)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct TextOutline {
+    /// The width of the outline in logical pixels.
+    ///
+    /// Outlines are limited to [`SDF_SPREAD`](crate::SDF_SPREAD) pixels at
+    /// [`SDF_FONT_SIZE`](crate::SDF_FONT_SIZE), scaled with the font size.
+    pub width: f32,
+    /// The color of the outline.
+    pub color: Color,
+}
+
+impl Default for TextOutline {
+    fn default() -> Self {
+        Self {
+            width: 1.,
+            color: Color::BLACK,
+        }
+    }
+}
@@ -13,6 +13,8 @@
 pub mod auto_directional_navigation;
 pub mod interaction_states;
 pub mod measurement;
+pub mod stylesheet;
+pub mod transition;
 pub mod update;
 pub mod widget;
 
@@ -41,6 +43,8 @@ pub use gradients::*;
 pub use interaction_states::{Checkable, Checked, InteractionDisabled, Pressed};
 pub use layout::*;
 pub use measurement::*;
+pub use stylesheet::{StyleClass, StyleSheet, UiStyleSheet};
+pub use transition::{TransitionProperty, UiTransition, UiTransitions};
 pub use ui_node::*;
 pub use ui_transform::*;
 
@@ -168,12 +172,20 @@ impl Plugin for UiPlugin {
                 ui_focus_system.in_set(UiSystems::Focus).after(InputSystems),
             );
 
+        app.add_plugins((stylesheet::StyleSheetPlugin, transition::UiTransitionPlugin));
+
         #[cfg(feature = "bevy_picking")]
         app.add_plugins(picking_backend::UiPickingPlugin)
             .add_systems(
                 First,
                 widget::viewport_picking.in_set(PickingSystems::PostInput),
-            );
+            )
+            .add_systems(
+                PreUpdate,
+                widget::clear_text_selections_on_outside_press.in_set(PickingSystems::PostHover),
+            )
+            .add_observer(widget::text_selection_on_press)
+            .add_observer(widget::text_selection_on_drag);
 
         let ui_layout_system_config = ui_layout_system
             .in_set(UiSystems::Layout)
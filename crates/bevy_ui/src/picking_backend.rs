@@ -33,7 +33,7 @@ use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_text::{ComputedTextBlock, TextLayoutInfo};
 use bevy_window::PrimaryWindow;
 
-use bevy_picking::backend::prelude::*;
+use bevy_picking::{backend::prelude::*, drag_drop::DragPreview};
 
 /// An optional component that marks cameras that should be used in the [`UiPickingPlugin`].
 ///
@@ -79,7 +79,34 @@ pub struct UiPickingPlugin;
 impl Plugin for UiPickingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UiPickingSettings>()
-            .add_systems(PreUpdate, ui_picking.in_set(PickingSystems::Backend));
+            .add_systems(PreUpdate, ui_picking.in_set(PickingSystems::Backend))
+            .add_systems(
+                PreUpdate,
+                position_drag_preview_nodes.in_set(PickingSystems::Last),
+            );
+    }
+}
+
+/// Moves the [`DragPreview`]s that are UI nodes to the position of their pointer.
+///
+/// The position of the pointer is relative to its render target, and is converted to the viewport
+/// of the UI camera of the preview. Previews must be root nodes: the position of a child node is
+/// relative to its parent instead.
+pub fn position_drag_preview_nodes(
+    ui_scale: Res<UiScale>,
+    cameras: Query<&Camera>,
+    mut previews: Query<(&DragPreview, &mut Node, &ComputedUiTargetCamera), Changed<DragPreview>>,
+) {
+    for (preview, mut node, target_camera) in &mut previews {
+        let viewport_position = target_camera
+            .get()
+            .and_then(|camera| cameras.get(camera).ok())
+            .and_then(Camera::logical_viewport_rect)
+            .map_or(Vec2::ZERO, |viewport| viewport.min);
+        let position = (preview.position - viewport_position) / ui_scale.0;
+        node.position_type = PositionType::Absolute;
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
     }
 }
 
@@ -0,0 +1,1007 @@
+//! Stylesheets, which style UI entities by matching selectors rather than by setting components
+//! on every entity by hand.
+//!
+//! A [`StyleSheet`] is an asset written in a small subset of CSS, and is loaded from `.css` files:
+//!
+//! ```css
+//! /* Rules apply in order of specificity, then in the order they are written. */
+//! Button {
+//!     padding: 8px 16px;
+//!     border-radius: 4px;
+//!     background-color: #303030;
+//! }
+//!
+//! Button:hover { background-color: #404040; }
+//! Button:disabled { background-color: rgba(48, 48, 48, 0.5); }
+//! .toolbar Button.danger:pressed { background-color: rgb(200, 40, 40); }
+//! ```
+//!
+//! Add a [`UiStyleSheet`] to a UI entity to style it and all of its descendants. If several
+//! stylesheets apply to an entity, rules from the innermost stylesheet win over rules of equal
+//! specificity from stylesheets further up the hierarchy.
+//!
+//! # Selectors
+//!
+//! - Type selectors, such as `Button` or `Text`, match entities with the corresponding component.
+//!   The available names are listed in [`StyleTypeSelectors`], which can be extended with more
+//!   component types.
+//! - Class selectors, such as `.danger`, match entities whose [`StyleClass`] contains the class.
+//! - State selectors match entities in an interaction state: `:hover` (from [`Interaction`] or, with the
+//!   `bevy_picking` feature, `Hovered`), `:pressed` or `:active` (from [`Pressed`] or
+//!   [`Interaction`]), `:disabled` ([`InteractionDisabled`]), `:checked` ([`Checked`]) and `:focus`
+//!   ([`InputFocus`]).
+//! - `*` matches any entity.
+//!
+//! These can be combined into compound selectors such as `Button.primary:hover`, compound
+//! selectors separated by spaces match descendants (`.toolbar Button`), and selectors separated by
+//! commas share a rule.
+//!
+//! # Properties
+//!
+//! The supported properties are the [`Node`] properties `display`, `position`, `overflow`, `left`,
+//! `right`, `top`, `bottom`, `width`, `height`, `min-width`, `min-height`, `max-width`,
+//! `max-height`, `align-items`, `align-self`, `align-content`, `justify-content`, `margin`,
+//! `padding`, `border-width`, `border-radius`, `flex-direction`, `flex-wrap`, `flex-grow`,
+//! `flex-shrink`, `flex-basis`, `row-gap` and `column-gap`, as well as `background-color`
+//! ([`BackgroundColor`]), `border-color` ([`BorderColor`]) and `color` ([`TextColor`]).
+//! Properties are only applied to entities that already have the component they belong to.
+//!
+//! Values set by a stylesheet override the entity's own values. When no rule sets a property any
+//! more (for example, once a `:hover` rule stops matching), it reverts to the value it had before
+//! the stylesheet first set it, or to the last value written to it by anything else while the
+//! stylesheet was setting it. See [`StyleDeclaration`] for the syntax of each property.
+//!
+//! Entities are only restyled when something selectors can match changes: a stylesheet, a
+//! [`UiStyleSheet`], a [`StyleClass`], an interaction state or the hierarchy. Components that type
+//! selectors refer to are expected to be added along with the entity's [`Node`].
+//!
+//! Since stylesheets are assets, they are hot reloaded like any other asset when the asset server
+//! is watching for changes.
+
+mod parse;
+
+pub use parse::StyleSheetParseError;
+
+use crate::{
+    transition::TransitionState,
+    widget::{Button, ImageNode, Label, Text},
+    AlignContent, AlignItems, AlignSelf, BackgroundColor, BorderColor, BorderRadius, Checked,
+    ComputedNode, Display, FlexDirection, FlexWrap, Interaction, InteractionDisabled,
+    JustifyContent, Node, Overflow, PositionType, Pressed, UiRect, UiSystems, Val,
+};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{
+    io::Reader, Asset, AssetApp, AssetEvent, AssetId, AssetLoader, Assets, Handle, LoadContext,
+};
+use bevy_color::Color;
+use bevy_ecs::{
+    archetype::{Archetype, Archetypes},
+    component::{ComponentId, Components},
+    entity::EntityLocation,
+    prelude::*,
+    query::QueryData,
+    system::SystemParam,
+};
+use bevy_input_focus::InputFocus;
+use bevy_platform::hash::FixedHasher;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use bevy_text::{TextColor, TextSpan};
+use core::{any::TypeId, hash::BuildHasher, mem::discriminant};
+use thiserror::Error;
+
+/// Adds support for [`StyleSheet`]s.
+///
+/// This is included by default in [`UiPlugin`](crate::UiPlugin).
+pub struct StyleSheetPlugin;
+
+impl Plugin for StyleSheetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StyleSheet>()
+            .init_asset_loader::<StyleSheetLoader>()
+            .init_resource::<StyleTypeSelectors>()
+            .add_systems(PostUpdate, apply_style_sheets.in_set(UiSystems::Prepare));
+    }
+}
+
+/// A set of rules that style UI entities. See the [module docs](self) for the syntax.
+#[derive(Asset, TypePath, Debug, Clone, Default, PartialEq)]
+pub struct StyleSheet {
+    rules: Vec<StyleRule>,
+}
+
+impl StyleSheet {
+    /// Parses a stylesheet from its source.
+    pub fn parse(source: &str) -> Result<Self, StyleSheetParseError> {
+        Ok(Self {
+            rules: parse::parse(source)?,
+        })
+    }
+
+    /// Creates a stylesheet from a list of rules.
+    pub fn from_rules(rules: Vec<StyleRule>) -> Self {
+        Self { rules }
+    }
+
+    /// The rules of this stylesheet, in the order they were written.
+    pub fn rules(&self) -> &[StyleRule] {
+        &self.rules
+    }
+}
+
+/// A rule of a [`StyleSheet`], which applies its declarations to entities matching any of its
+/// selectors.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleRule {
+    /// The selectors of this rule, separated by commas in the source.
+    pub selectors: Vec<Selector>,
+    /// The property values this rule sets.
+    pub declarations: Vec<StyleDeclaration>,
+}
+
+/// A selector, made of compound selectors that must match the entity (the last one) and its
+/// ancestors (the others, in order).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selector {
+    /// The compound selectors, separated by spaces in the source.
+    pub compounds: Vec<CompoundSelector>,
+}
+
+impl Selector {
+    /// The specificity of this selector, as the number of class and state selectors and the number
+    /// of type selectors. Rules with more specific selectors take priority.
+    pub fn specificity(&self) -> (usize, usize) {
+        self.compounds
+            .iter()
+            .fold((0, 0), |(classes, types), compound| {
+                (
+                    classes + compound.classes.len() + compound.states.len(),
+                    types + usize::from(compound.type_name.is_some()),
+                )
+            })
+    }
+
+    fn matches(&self, element: &Element, ancestors: &[Element]) -> bool {
+        let Some((last, rest)) = self.compounds.split_last() else {
+            return false;
+        };
+        if !last.matches(element) {
+            return false;
+        }
+        let mut ancestors = ancestors.iter().rev();
+        rest.iter()
+            .rev()
+            .all(|compound| ancestors.any(|ancestor| compound.matches(ancestor)))
+    }
+}
+
+/// A selector that matches a single entity by type, classes and states, such as
+/// `Button.primary:hover`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompoundSelector {
+    /// The name of a type registered in [`StyleTypeSelectors`].
+    pub type_name: Option<String>,
+    /// Classes the entity's [`StyleClass`] must contain.
+    pub classes: Vec<String>,
+    /// States the entity must be in.
+    pub states: Vec<StyleState>,
+}
+
+impl CompoundSelector {
+    fn matches(&self, element: &Element) -> bool {
+        self.type_name.as_deref().is_none_or(|type_name| {
+            element
+                .types
+                .iter()
+                .any(|(name, id)| *name == type_name && element.archetype.contains(*id))
+        }) && self.classes.iter().all(|class| {
+            element
+                .classes
+                .is_some_and(|classes| classes.contains(class))
+        }) && self
+            .states
+            .iter()
+            .all(|state| element.states & state.bit() != 0)
+    }
+}
+
+/// An interaction state that can be matched by a state selector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StyleState {
+    /// `:hover`
+    Hover,
+    /// `:pressed` or `:active`
+    Pressed,
+    /// `:disabled`
+    Disabled,
+    /// `:focus`
+    Focus,
+    /// `:checked`
+    Checked,
+}
+
+impl StyleState {
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A property value set by a [`StyleRule`].
+///
+/// Lengths are written like [`Val`]s (`auto`, `0`, `10px`, `50%`, `10vw`, `10vh`, `10vmin`,
+/// `10vmax`), and properties of box sides and corners accept one to four lengths, as in CSS.
+/// Colors are written as `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb(r, g, b)`,
+/// `rgba(r, g, b, a)` (with channels from 0 to 255 and alpha from 0 to 1), `white`, `black` or
+/// `transparent`. Keywords are written in kebab case, like `space-between` or `column-reverse`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StyleDeclaration {
+    /// `display`
+    Display(Display),
+    /// `position`
+    PositionType(PositionType),
+    /// `overflow`, with one value for both axes or a value for each.
+    Overflow(Overflow),
+    /// `left`
+    Left(Val),
+    /// `right`
+    Right(Val),
+    /// `top`
+    Top(Val),
+    /// `bottom`
+    Bottom(Val),
+    /// `width`
+    Width(Val),
+    /// `height`
+    Height(Val),
+    /// `min-width`
+    MinWidth(Val),
+    /// `min-height`
+    MinHeight(Val),
+    /// `max-width`
+    MaxWidth(Val),
+    /// `max-height`
+    MaxHeight(Val),
+    /// `align-items`
+    AlignItems(AlignItems),
+    /// `align-self`
+    AlignSelf(AlignSelf),
+    /// `align-content`
+    AlignContent(AlignContent),
+    /// `justify-content`
+    JustifyContent(JustifyContent),
+    /// `margin`
+    Margin(UiRect),
+    /// `padding`
+    Padding(UiRect),
+    /// `border-width`
+    Border(UiRect),
+    /// `border-radius`
+    BorderRadius(BorderRadius),
+    /// `flex-direction`
+    FlexDirection(FlexDirection),
+    /// `flex-wrap`
+    FlexWrap(FlexWrap),
+    /// `flex-grow`
+    FlexGrow(f32),
+    /// `flex-shrink`
+    FlexShrink(f32),
+    /// `flex-basis`
+    FlexBasis(Val),
+    /// `row-gap`
+    RowGap(Val),
+    /// `column-gap`
+    ColumnGap(Val),
+    /// `background-color`
+    BackgroundColor(Color),
+    /// `border-color`
+    BorderColor(BorderColor),
+    /// `color`
+    TextColor(Color),
+}
+
+macro_rules! node_properties {
+    ($($variant:ident => $field:ident),* $(,)?) => {
+        /// Returns the current value of this declaration's property, if it is a [`Node`] property.
+        fn read_node(&self, node: &Node) -> Option<Self> {
+            Some(match self {
+                $(Self::$variant(_) => Self::$variant(node.$field),)*
+                _ => return None,
+            })
+        }
+
+        /// Sets this declaration's property on `node`, if it is a [`Node`] property.
+        fn write_node(&self, node: &mut Mut<Node>) {
+            match self {
+                $(Self::$variant(value) if node.$field != *value => node.$field = *value,)*
+                _ => {}
+            }
+        }
+    };
+}
+
+impl StyleDeclaration {
+    node_properties! {
+        Display => display,
+        PositionType => position_type,
+        Overflow => overflow,
+        Left => left,
+        Right => right,
+        Top => top,
+        Bottom => bottom,
+        Width => width,
+        Height => height,
+        MinWidth => min_width,
+        MinHeight => min_height,
+        MaxWidth => max_width,
+        MaxHeight => max_height,
+        AlignItems => align_items,
+        AlignSelf => align_self,
+        AlignContent => align_content,
+        JustifyContent => justify_content,
+        Margin => margin,
+        Padding => padding,
+        Border => border,
+        BorderRadius => border_radius,
+        FlexDirection => flex_direction,
+        FlexWrap => flex_wrap,
+        FlexGrow => flex_grow,
+        FlexShrink => flex_shrink,
+        FlexBasis => flex_basis,
+        RowGap => row_gap,
+        ColumnGap => column_gap,
+    }
+
+    /// Returns `true` if both declarations set the same property.
+    fn same_property(&self, other: &Self) -> bool {
+        discriminant(self) == discriminant(other)
+    }
+
+    /// Returns the current value of this declaration's property on `target`, or `None` if the
+    /// target doesn't have the property's component.
+    fn read(&self, target: &StyleTargetItem) -> Option<Self> {
+        match self {
+            Self::BackgroundColor(_) => target
+                .background_color
+                .as_ref()
+                .map(|color| Self::BackgroundColor(color.0)),
+            Self::BorderColor(_) => target
+                .border_color
+                .as_ref()
+                .map(|color| Self::BorderColor(**color)),
+            Self::TextColor(_) => target
+                .text_color
+                .as_ref()
+                .map(|color| Self::TextColor(color.0)),
+            _ => target.node.as_ref().and_then(|node| self.read_node(node)),
+        }
+    }
+
+    fn write(&self, target: &mut StyleTargetItem) {
+        match self {
+            Self::BackgroundColor(value) => {
+                if let Some(color) = target.background_color.as_mut() {
+                    color.set_if_neq(BackgroundColor(*value));
+                }
+            }
+            Self::BorderColor(value) => {
+                if let Some(color) = target.border_color.as_mut() {
+                    color.set_if_neq(*value);
+                }
+            }
+            Self::TextColor(value) => {
+                if let Some(color) = target.text_color.as_mut() {
+                    color.set_if_neq(TextColor(*value));
+                }
+            }
+            _ => {
+                if let Some(node) = target.node.as_mut() {
+                    self.write_node(node);
+                }
+            }
+        }
+    }
+}
+
+/// Styles this UI entity and its descendants with a [`StyleSheet`].
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct UiStyleSheet(pub Handle<StyleSheet>);
+
+/// The classes of an entity, which are matched by class selectors such as `.primary`.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct StyleClass(pub Vec<String>);
+
+impl StyleClass {
+    /// Creates a [`StyleClass`] from a list of classes separated by whitespace.
+    pub fn new(classes: &str) -> Self {
+        Self(
+            classes
+                .split_whitespace()
+                .map(ToString::to_string)
+                .collect(),
+        )
+    }
+
+    /// Returns `true` if this contains `class`.
+    pub fn contains(&self, class: &str) -> bool {
+        self.0.iter().any(|existing| existing == class)
+    }
+
+    /// Adds `class`, if it isn't already present.
+    pub fn add(&mut self, class: impl Into<String>) {
+        let class = class.into();
+        if !self.contains(&class) {
+            self.0.push(class);
+        }
+    }
+
+    /// Removes `class`, if it is present.
+    pub fn remove(&mut self, class: &str) {
+        self.0.retain(|existing| existing != class);
+    }
+}
+
+/// The component types that type selectors can refer to, by name.
+///
+/// By default, this contains [`Node`], [`Button`], [`Text`], [`TextSpan`], [`ImageNode`] and
+/// [`Label`].
+#[derive(Resource, Debug)]
+pub struct StyleTypeSelectors {
+    types: Vec<(String, TypeId)>,
+}
+
+impl Default for StyleTypeSelectors {
+    fn default() -> Self {
+        let mut selectors = Self { types: Vec::new() };
+        selectors
+            .register::<Node>("Node")
+            .register::<Button>("Button")
+            .register::<Text>("Text")
+            .register::<TextSpan>("TextSpan")
+            .register::<ImageNode>("ImageNode")
+            .register::<Label>("Label");
+        selectors
+    }
+}
+
+impl StyleTypeSelectors {
+    /// Allows type selectors named `name` to match entities with the component `C`.
+    pub fn register<C: Component>(&mut self, name: impl Into<String>) -> &mut Self {
+        self.types.push((name.into(), TypeId::of::<C>()));
+        self
+    }
+}
+
+/// Stores the values a stylesheet has overridden on an entity, so that they can be restored
+/// when no rule sets them any more.
+///
+/// This is added to styled entities by [`apply_style_sheets`].
+#[derive(Component, Debug, Default)]
+pub struct ComputedStyle {
+    /// A hash of the matched rules, used to skip entities whose styles haven't changed.
+    key: u64,
+    /// The values the overridden properties had before a stylesheet first set them, each paired
+    /// with the value the stylesheet set.
+    overridden: Vec<(StyleDeclaration, StyleDeclaration)>,
+}
+
+impl ComputedStyle {
+    fn apply<'a>(
+        &mut self,
+        declarations: impl Iterator<Item = &'a StyleDeclaration>,
+        target: &mut StyleTargetItem,
+    ) {
+        let mut resolved: Vec<&StyleDeclaration> = Vec::new();
+        for declaration in declarations {
+            resolved.retain(|existing| !existing.same_property(declaration));
+            resolved.push(declaration);
+        }
+
+        self.overridden.retain(|(original, _)| {
+            let still_set = resolved
+                .iter()
+                .any(|declaration| declaration.same_property(original));
+            if !still_set {
+                original.write(target);
+            }
+            still_set
+        });
+
+        for declaration in resolved {
+            match self
+                .overridden
+                .iter_mut()
+                .find(|(original, _)| original.same_property(declaration))
+            {
+                Some((_, applied)) => *applied = declaration.clone(),
+                None => {
+                    if let Some(original) = declaration.read(target) {
+                        self.overridden.push((original, declaration.clone()));
+                    }
+                }
+            }
+            declaration.write(target);
+        }
+    }
+
+    /// Keeps overridden values that were changed by something other than a stylesheet as the
+    /// values to restore, and sets the stylesheet's values again.
+    fn recapture(&mut self, target: &mut StyleTargetItem) {
+        for (original, applied) in &mut self.overridden {
+            if let Some(current) = applied.read(target)
+                && current != *applied
+            {
+                *original = current;
+                applied.write(target);
+            }
+        }
+    }
+}
+
+/// An error that occurs when loading a [`StyleSheet`].
+#[derive(Error, Debug)]
+pub enum StyleSheetLoaderError {
+    /// The stylesheet could not be read.
+    #[error("could not read stylesheet: {0}")]
+    Io(#[from] std::io::Error),
+    /// The stylesheet is not valid UTF-8.
+    #[error("stylesheet is not valid UTF-8: {0}")]
+    Utf8(#[from] core::str::Utf8Error),
+    /// The stylesheet could not be parsed.
+    #[error("could not parse stylesheet: {0}")]
+    Parse(#[from] StyleSheetParseError),
+}
+
+/// Loads [`StyleSheet`]s from `.css` files.
+#[derive(Default, TypePath)]
+pub struct StyleSheetLoader;
+
+impl AssetLoader for StyleSheetLoader {
+    type Asset = StyleSheet;
+    type Settings = ();
+    type Error = StyleSheetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<StyleSheet, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(StyleSheet::parse(core::str::from_utf8(&bytes)?)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["css"]
+    }
+}
+
+/// What selectors can match an entity against.
+struct Element<'a> {
+    types: &'a [(&'a str, ComponentId)],
+    archetype: &'a Archetype,
+    classes: Option<&'a StyleClass>,
+    states: u8,
+}
+
+/// Query for the state [`apply_style_sheets`] matches selectors against.
+#[derive(QueryData)]
+pub struct ElementQuery {
+    style_sheet: Option<&'static UiStyleSheet>,
+    class: Option<&'static StyleClass>,
+    interaction: Option<&'static Interaction>,
+    pressed: Has<Pressed>,
+    disabled: Has<InteractionDisabled>,
+    checked: Has<Checked>,
+}
+
+/// Query for the components [`apply_style_sheets`] sets properties on.
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct StyleTarget {
+    node: Option<&'static mut Node>,
+    background_color: Option<&'static mut BackgroundColor>,
+    border_color: Option<&'static mut BorderColor>,
+    text_color: Option<&'static mut TextColor>,
+}
+
+/// Detects changes to anything that [`apply_style_sheets`] matches selectors against.
+#[derive(SystemParam)]
+pub struct StyleChanges<'w, 's> {
+    asset_events: MessageReader<'w, 's, AssetEvent<StyleSheet>>,
+    type_selectors: Res<'w, StyleTypeSelectors>,
+    input_focus: Option<Res<'w, InputFocus>>,
+    changed: Query<
+        'w,
+        's,
+        (),
+        Or<(
+            Changed<UiStyleSheet>,
+            Changed<StyleClass>,
+            Changed<Interaction>,
+            Changed<ChildOf>,
+            Changed<Children>,
+            Added<ComputedNode>,
+            Added<Pressed>,
+            Added<InteractionDisabled>,
+            Added<Checked>,
+        )>,
+    >,
+    #[cfg(feature = "bevy_picking")]
+    hovered: Query<'w, 's, (), Changed<bevy_picking::hover::Hovered>>,
+    removed_style_sheets: RemovedComponents<'w, 's, UiStyleSheet>,
+    removed_classes: RemovedComponents<'w, 's, StyleClass>,
+    removed_interactions: RemovedComponents<'w, 's, Interaction>,
+    removed_pressed: RemovedComponents<'w, 's, Pressed>,
+    removed_disabled: RemovedComponents<'w, 's, InteractionDisabled>,
+    removed_checked: RemovedComponents<'w, 's, Checked>,
+    removed_parents: RemovedComponents<'w, 's, ChildOf>,
+}
+
+impl StyleChanges<'_, '_> {
+    /// Returns `true` if a stylesheet was added, modified or removed, consuming its events.
+    fn style_sheets_changed(&mut self) -> bool {
+        self.asset_events.read().count() > 0
+    }
+
+    /// Returns `true` if entities may match different rules than when this was last called.
+    fn elements_changed(&mut self) -> bool {
+        #[cfg(feature = "bevy_picking")]
+        let hovered = !self.hovered.is_empty();
+        #[cfg(not(feature = "bevy_picking"))]
+        let hovered = false;
+        // Every reader is drained, so that old removals aren't seen again.
+        let removed = [
+            self.removed_style_sheets.read().count(),
+            self.removed_classes.read().count(),
+            self.removed_interactions.read().count(),
+            self.removed_pressed.read().count(),
+            self.removed_disabled.read().count(),
+            self.removed_checked.read().count(),
+            self.removed_parents.read().count(),
+        ];
+        hovered
+            || removed.iter().any(|count| *count > 0)
+            || !self.changed.is_empty()
+            || self.type_selectors.is_changed()
+            || self
+                .input_focus
+                .as_ref()
+                .is_some_and(DetectChanges::is_changed)
+    }
+}
+
+/// Applies [`StyleSheet`] rules to the UI entities they style.
+///
+/// UI trees are only walked when a stylesheet or something selectors can match changes (see
+/// [`StyleChanges`]), and entities are only restyled when the rules that match them change.
+/// Styled values that were changed by other systems since the last run are kept as the values to
+/// restore once no rule sets them, unless the entity is still transitioning.
+pub fn apply_style_sheets(
+    mut commands: Commands,
+    mut changes: StyleChanges,
+    mut generation: Local<u64>,
+    style_sheets: Res<Assets<StyleSheet>>,
+    components: &Components,
+    archetypes: &Archetypes,
+    roots: Query<Entity, (With<Node>, Without<ChildOf>)>,
+    children_query: Query<&Children>,
+    elements: Query<(EntityLocation, ElementQuery)>,
+    #[cfg(feature = "bevy_picking")] hovered_query: Query<&bevy_picking::hover::Hovered>,
+    mut targets: ParamSet<(
+        Query<
+            (&mut ComputedStyle, Option<&TransitionState>, StyleTarget),
+            Or<(
+                Changed<Node>,
+                Changed<BackgroundColor>,
+                Changed<BorderColor>,
+                Changed<TextColor>,
+            )>,
+        >,
+        Query<(Option<&mut ComputedStyle>, StyleTarget)>,
+    )>,
+) {
+    // Values that are transitioning differ from the stylesheet's until the transition finishes,
+    // without having been changed by anything else.
+    for (mut computed, transition, mut target) in &mut targets.p0() {
+        if !transition.is_some_and(TransitionState::is_animating) {
+            computed.recapture(&mut target);
+        }
+    }
+
+    // Any change to a stylesheet restyles every entity, since rules may have changed.
+    let style_sheets_changed = changes.style_sheets_changed();
+    let elements_changed = changes.elements_changed();
+    if style_sheets_changed {
+        *generation += 1;
+    } else if !elements_changed {
+        return;
+    }
+
+    let type_selectors = &changes.type_selectors;
+    let types: Vec<(&str, ComponentId)> = type_selectors
+        .types
+        .iter()
+        .filter_map(|(name, type_id)| Some((name.as_str(), components.get_id(*type_id)?)))
+        .collect();
+    let focused = changes.input_focus.as_ref().and_then(|focus| focus.0);
+
+    let mut stack: Vec<(Entity, usize)> = roots.iter().map(|root| (root, 0)).collect();
+    let mut ancestors: Vec<Element> = Vec::new();
+    let mut scopes: Vec<(usize, AssetId<StyleSheet>)> = Vec::new();
+    let mut matched: Vec<((usize, usize), usize, usize)> = Vec::new();
+    while let Some((entity, depth)) = stack.pop() {
+        ancestors.truncate(depth);
+        scopes.retain(|(scope_depth, _)| *scope_depth < depth);
+        let Ok((location, item)) = elements.get(entity) else {
+            continue;
+        };
+        if let Some(style_sheet) = item.style_sheet {
+            scopes.push((depth, style_sheet.0.id()));
+        }
+
+        #[cfg(feature = "bevy_picking")]
+        let hovered = hovered_query.get(entity).is_ok_and(|hovered| hovered.0);
+        #[cfg(not(feature = "bevy_picking"))]
+        let hovered = false;
+        let mut states = 0;
+        for (state, active) in [
+            (
+                StyleState::Hover,
+                hovered
+                    || matches!(
+                        item.interaction,
+                        Some(Interaction::Hovered | Interaction::Pressed)
+                    ),
+            ),
+            (
+                StyleState::Pressed,
+                item.pressed || item.interaction == Some(&Interaction::Pressed),
+            ),
+            (StyleState::Disabled, item.disabled),
+            (StyleState::Focus, focused == Some(entity)),
+            (StyleState::Checked, item.checked),
+        ] {
+            if active {
+                states |= state.bit();
+            }
+        }
+        let element = Element {
+            types: &types,
+            archetype: &archetypes[location.archetype_id],
+            classes: item.class,
+            states,
+        };
+
+        matched.clear();
+        for (scope_index, (_, id)) in scopes.iter().enumerate() {
+            let Some(style_sheet) = style_sheets.get(*id) else {
+                continue;
+            };
+            for (rule_index, rule) in style_sheet.rules.iter().enumerate() {
+                if let Some(specificity) = rule
+                    .selectors
+                    .iter()
+                    .filter(|selector| selector.matches(&element, &ancestors))
+                    .map(Selector::specificity)
+                    .max()
+                {
+                    matched.push((specificity, scope_index, rule_index));
+                }
+            }
+        }
+        matched.sort();
+        let key = FixedHasher.hash_one((*generation, &scopes, &matched));
+
+        if let Ok((computed, mut target)) = targets.p1().get_mut(entity) {
+            let declarations = matched.iter().flat_map(|(_, scope_index, rule_index)| {
+                let style_sheet = style_sheets.get(scopes[*scope_index].1).unwrap();
+                &style_sheet.rules[*rule_index].declarations
+            });
+            match computed {
+                Some(mut computed) if computed.key != key => {
+                    computed.key = key;
+                    computed.apply(declarations, &mut target);
+                }
+                None if !matched.is_empty() => {
+                    let mut computed = ComputedStyle {
+                        key,
+                        ..Default::default()
+                    };
+                    computed.apply(declarations, &mut target);
+                    commands.entity(entity).insert(computed);
+                }
+                _ => {}
+            }
+        }
+
+        ancestors.push(element);
+        if let Ok(children) = children_query.get(entity) {
+            stack.extend(children.iter().rev().map(|child| (child, depth + 1)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+
+    #[test]
+    fn parse_rules() {
+        let style_sheet = StyleSheet::parse(
+            "/* comment */
+            Button.primary:hover, .toolbar * {
+                width: 50%;
+                padding: 4px 8px;
+                background-color: #ff0000;
+            }",
+        )
+        .unwrap();
+        let rule = &style_sheet.rules()[0];
+        assert_eq!(rule.selectors.len(), 2);
+        assert_eq!(rule.selectors[0].specificity(), (2, 1));
+        assert_eq!(
+            rule.selectors[0].compounds[0],
+            CompoundSelector {
+                type_name: Some("Button".into()),
+                classes: vec!["primary".into()],
+                states: vec![StyleState::Hover],
+            }
+        );
+        assert_eq!(rule.selectors[1].compounds.len(), 2);
+        assert_eq!(
+            rule.declarations,
+            vec![
+                StyleDeclaration::Width(Val::Percent(50.)),
+                StyleDeclaration::Padding(UiRect::axes(Val::Px(8.), Val::Px(4.))),
+                StyleDeclaration::BackgroundColor(Color::srgb(1., 0., 0.)),
+            ]
+        );
+
+        let error = StyleSheet::parse("Button {\n    width: 10pz;\n}").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 12));
+        let error = StyleSheet::parse("Button {\n    heigth: 2px;\n}").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+    }
+
+    #[test]
+    fn apply_and_revert_styles() {
+        let mut app = App::new();
+        app.init_resource::<Assets<StyleSheet>>()
+            .add_message::<AssetEvent<StyleSheet>>()
+            .init_resource::<StyleTypeSelectors>()
+            .add_systems(PostUpdate, apply_style_sheets);
+
+        let style_sheet = StyleSheet::parse(
+            "Node { width: 100px; background-color: #000000; }
+            .panel Button { background-color: #0000ff; }
+            Button:pressed { background-color: #ff0000; }
+            Button.big { width: 200px; }",
+        )
+        .unwrap();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<StyleSheet>>()
+            .add(style_sheet);
+
+        let button = app.world_mut().spawn((Node::default(), Button)).id();
+        let panel = app
+            .world_mut()
+            .spawn((
+                Node::default(),
+                UiStyleSheet(handle),
+                StyleClass::new("panel"),
+            ))
+            .add_child(button)
+            .id();
+        app.update();
+
+        let background = |app: &App, entity| app.world().get::<BackgroundColor>(entity).unwrap().0;
+        let width = |app: &App, entity| app.world().get::<Node>(entity).unwrap().width;
+        assert_eq!(background(&app, panel), Color::srgb(0., 0., 0.));
+        assert_eq!(background(&app, button), Color::srgb(0., 0., 1.));
+        assert_eq!(width(&app, button), Val::Px(100.));
+
+        app.world_mut()
+            .entity_mut(button)
+            .insert((Pressed, StyleClass::new("big")));
+        app.update();
+        assert_eq!(background(&app, button), Color::srgb(1., 0., 0.));
+        assert_eq!(width(&app, button), Val::Px(200.));
+
+        app.world_mut()
+            .entity_mut(button)
+            .remove::<(Pressed, StyleClass)>();
+        app.update();
+        assert_eq!(background(&app, button), Color::srgb(0., 0., 1.));
+        assert_eq!(width(&app, button), Val::Px(100.));
+
+        // Removing the stylesheet restores the original values.
+        app.world_mut().entity_mut(panel).remove::<UiStyleSheet>();
+        app.update();
+        assert_eq!(background(&app, button), BackgroundColor::DEFAULT.0);
+        assert_eq!(width(&app, button), Val::Auto);
+    }
+
+    #[test]
+    fn restyle_on_state_changes() {
+        let mut app = App::new();
+        app.init_resource::<Assets<StyleSheet>>()
+            .add_message::<AssetEvent<StyleSheet>>()
+            .init_resource::<StyleTypeSelectors>()
+            .init_resource::<InputFocus>()
+            .add_systems(PostUpdate, apply_style_sheets);
+
+        let style_sheet = StyleSheet::parse(
+            "Node:focus { width: 10px; }
+            Node:pressed { height: 20px; }",
+        )
+        .unwrap();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<StyleSheet>>()
+            .add(style_sheet);
+        let entity = app
+            .world_mut()
+            .spawn((Node::default(), UiStyleSheet(handle)))
+            .id();
+        app.update();
+        let node = |app: &App| app.world().get::<Node>(entity).unwrap().clone();
+        assert_eq!(
+            (node(&app).width, node(&app).height),
+            (Val::Auto, Val::Auto)
+        );
+
+        app.world_mut().resource_mut::<InputFocus>().set(entity);
+        app.update();
+        assert_eq!(node(&app).width, Val::Px(10.));
+
+        app.world_mut().entity_mut(entity).insert(Pressed);
+        app.update();
+        assert_eq!(node(&app).height, Val::Px(20.));
+
+        app.world_mut().resource_mut::<InputFocus>().clear();
+        app.world_mut().entity_mut(entity).remove::<Pressed>();
+        app.update();
+        assert_eq!(
+            (node(&app).width, node(&app).height),
+            (Val::Auto, Val::Auto)
+        );
+    }
+
+    #[test]
+    fn keep_values_changed_while_styled() {
+        let mut app = App::new();
+        app.init_resource::<Assets<StyleSheet>>()
+            .add_message::<AssetEvent<StyleSheet>>()
+            .init_resource::<StyleTypeSelectors>()
+            .add_systems(PostUpdate, apply_style_sheets);
+
+        let style_sheet =
+            StyleSheet::parse("Node:pressed { width: 200px; height: 100px; }").unwrap();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<StyleSheet>>()
+            .add(style_sheet);
+        let entity = app
+            .world_mut()
+            .spawn((Node::default(), UiStyleSheet(handle), Pressed))
+            .id();
+        app.update();
+        let node = |app: &App| app.world().get::<Node>(entity).unwrap().clone();
+        assert_eq!(node(&app).width, Val::Px(200.));
+
+        // The stylesheet's value still wins while the rule applies, but the new value is restored
+        // once it stops applying.
+        app.world_mut().get_mut::<Node>(entity).unwrap().width = Val::Px(50.);
+        app.update();
+        assert_eq!(node(&app).width, Val::Px(200.));
+
+        app.world_mut().entity_mut(entity).remove::<Pressed>();
+        app.update();
+        assert_eq!(node(&app).width, Val::Px(50.));
+        assert_eq!(node(&app).height, Val::Auto);
+    }
+}
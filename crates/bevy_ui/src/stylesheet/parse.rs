@@ -0,0 +1,417 @@
+use super::{CompoundSelector, Selector, StyleDeclaration, StyleRule, StyleState};
+use crate::{
+    AlignContent, AlignItems, AlignSelf, BorderColor, BorderRadius, Display, FlexDirection,
+    FlexWrap, JustifyContent, Overflow, OverflowAxis, PositionType, UiRect, Val,
+};
+use bevy_color::{Color, Srgba};
+use thiserror::Error;
+
+/// An error that occurred while parsing a [`StyleSheet`](super::StyleSheet).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{line}:{column}: {message}")]
+pub struct StyleSheetParseError {
+    /// The line the error occurred on, starting at 1.
+    pub line: usize,
+    /// The column the error occurred at, starting at 1.
+    pub column: usize,
+    /// A description of what went wrong.
+    pub message: String,
+}
+
+struct Parser<'a> {
+    source: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, offset: usize, message: impl Into<String>) -> StyleSheetParseError {
+        let before = &self.source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map_or(before, |newline| &before[newline + 1..])
+            .chars()
+            .count()
+            + 1;
+        StyleSheetParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the byte offset of `slice`, which must be a subslice of the source.
+    fn offset_of(&self, slice: &str) -> usize {
+        slice.as_ptr() as usize - self.source.as_ptr() as usize
+    }
+
+    fn parse(&self) -> Result<Vec<StyleRule>, StyleSheetParseError> {
+        let mut rules = Vec::new();
+        let mut rest = self.source;
+        loop {
+            let trimmed = rest.trim_start();
+            if trimmed.is_empty() {
+                return Ok(rules);
+            }
+            let Some(open) = trimmed.find('{') else {
+                return Err(self.error(self.offset_of(trimmed), "expected `{` after selector"));
+            };
+            let Some(close) = trimmed[open..].find('}').map(|close| open + close) else {
+                return Err(self.error(self.offset_of(&trimmed[open..]), "unclosed `{`"));
+            };
+            let selectors = self.parse_selectors(&trimmed[..open])?;
+            let declarations = self.parse_declarations(&trimmed[open + 1..close])?;
+            rules.push(StyleRule {
+                selectors,
+                declarations,
+            });
+            rest = &trimmed[close + 1..];
+        }
+    }
+
+    fn parse_selectors(&self, list: &'a str) -> Result<Vec<Selector>, StyleSheetParseError> {
+        list.split(',')
+            .map(|selector| {
+                let compounds = selector
+                    .split_whitespace()
+                    .map(|compound| self.parse_compound(compound))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if compounds.is_empty() {
+                    return Err(self.error(self.offset_of(selector), "expected a selector"));
+                }
+                Ok(Selector { compounds })
+            })
+            .collect()
+    }
+
+    fn parse_compound(&self, compound: &'a str) -> Result<CompoundSelector, StyleSheetParseError> {
+        let mut selector = CompoundSelector::default();
+        // Split before every `.` and `:`, so that each part is a type, class or state.
+        let mut parts = Vec::new();
+        let mut start = 0;
+        for (index, char) in compound.char_indices().skip(1) {
+            if char == '.' || char == ':' {
+                parts.push(&compound[start..index]);
+                start = index;
+            }
+        }
+        parts.push(&compound[start..]);
+
+        for (index, part) in parts.into_iter().enumerate() {
+            let error = |message: &str| self.error(self.offset_of(part), message);
+            if let Some(class) = part.strip_prefix('.') {
+                if !is_identifier(class) {
+                    return Err(error("expected a class name after `.`"));
+                }
+                selector.classes.push(class.to_string());
+            } else if let Some(state) = part.strip_prefix(':') {
+                selector.states.push(match state {
+                    "hover" => StyleState::Hover,
+                    "pressed" | "active" => StyleState::Pressed,
+                    "disabled" => StyleState::Disabled,
+                    "focus" => StyleState::Focus,
+                    "checked" => StyleState::Checked,
+                    _ => return Err(error("unknown state")),
+                });
+            } else if index == 0 && part == "*" {
+                // The universal selector matches everything, so it needs no constraint.
+            } else if index == 0 && is_identifier(part) {
+                selector.type_name = Some(part.to_string());
+            } else {
+                return Err(error("expected a type, class or state selector"));
+            }
+        }
+        Ok(selector)
+    }
+
+    fn parse_declarations(
+        &self,
+        block: &'a str,
+    ) -> Result<Vec<StyleDeclaration>, StyleSheetParseError> {
+        let mut declarations = Vec::new();
+        for declaration in block.split(';') {
+            if declaration.trim().is_empty() {
+                continue;
+            }
+            let Some((name, value)) = declaration.split_once(':') else {
+                return Err(self.error(
+                    self.offset_of(declaration.trim_start()),
+                    "expected `property: value`",
+                ));
+            };
+            let name = name.trim();
+            let value = value.trim();
+            match parse_declaration(name, value) {
+                Some(Some(declaration)) => declarations.push(declaration),
+                Some(None) if value.is_empty() => {
+                    return Err(self.error(self.offset_of(name), "expected a value"));
+                }
+                Some(None) => {
+                    return Err(
+                        self.error(self.offset_of(value), format!("invalid value for `{name}`"))
+                    );
+                }
+                None => {
+                    return Err(
+                        self.error(self.offset_of(name), format!("unknown property `{name}`"))
+                    );
+                }
+            }
+        }
+        Ok(declarations)
+    }
+}
+
+/// Parses the source of a stylesheet into its rules.
+pub(super) fn parse(source: &str) -> Result<Vec<StyleRule>, StyleSheetParseError> {
+    let source = strip_comments(source);
+    Parser { source: &source }.parse()
+}
+
+/// Replaces `/* ... */` comments with whitespace, keeping newlines so that error locations stay
+/// accurate.
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        let comment_end = rest[start + 2..]
+            .find("*/")
+            .map_or(rest.len(), |end| start + 2 + end + 2);
+        for char in rest[start..comment_end].chars() {
+            stripped.push(if char == '\n' { '\n' } else { ' ' });
+        }
+        rest = &rest[comment_end..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|char: char| char.is_ascii_alphabetic() || char == '_' || char == '-')
+        && name
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '-')
+}
+
+/// Returns `None` for unknown properties, and `Some(None)` for invalid values.
+fn parse_declaration(name: &str, value: &str) -> Option<Option<StyleDeclaration>> {
+    use StyleDeclaration as D;
+    Some(match name {
+        "display" => parse_keyword(
+            value,
+            &[
+                ("flex", Display::Flex),
+                ("grid", Display::Grid),
+                ("block", Display::Block),
+                ("none", Display::None),
+            ],
+        )
+        .map(D::Display),
+        "position" => parse_keyword(
+            value,
+            &[
+                ("relative", PositionType::Relative),
+                ("absolute", PositionType::Absolute),
+            ],
+        )
+        .map(D::PositionType),
+        "overflow" => parse_overflow(value).map(D::Overflow),
+        "left" => parse_val(value).map(D::Left),
+        "right" => parse_val(value).map(D::Right),
+        "top" => parse_val(value).map(D::Top),
+        "bottom" => parse_val(value).map(D::Bottom),
+        "width" => parse_val(value).map(D::Width),
+        "height" => parse_val(value).map(D::Height),
+        "min-width" => parse_val(value).map(D::MinWidth),
+        "min-height" => parse_val(value).map(D::MinHeight),
+        "max-width" => parse_val(value).map(D::MaxWidth),
+        "max-height" => parse_val(value).map(D::MaxHeight),
+        "align-items" => parse_keyword(
+            value,
+            &[
+                ("default", AlignItems::Default),
+                ("start", AlignItems::Start),
+                ("end", AlignItems::End),
+                ("flex-start", AlignItems::FlexStart),
+                ("flex-end", AlignItems::FlexEnd),
+                ("center", AlignItems::Center),
+                ("baseline", AlignItems::Baseline),
+                ("stretch", AlignItems::Stretch),
+            ],
+        )
+        .map(D::AlignItems),
+        "align-self" => parse_keyword(
+            value,
+            &[
+                ("auto", AlignSelf::Auto),
+                ("start", AlignSelf::Start),
+                ("end", AlignSelf::End),
+                ("flex-start", AlignSelf::FlexStart),
+                ("flex-end", AlignSelf::FlexEnd),
+                ("center", AlignSelf::Center),
+                ("baseline", AlignSelf::Baseline),
+                ("stretch", AlignSelf::Stretch),
+            ],
+        )
+        .map(D::AlignSelf),
+        "align-content" => parse_keyword(
+            value,
+            &[
+                ("default", AlignContent::Default),
+                ("start", AlignContent::Start),
+                ("end", AlignContent::End),
+                ("flex-start", AlignContent::FlexStart),
+                ("flex-end", AlignContent::FlexEnd),
+                ("center", AlignContent::Center),
+                ("stretch", AlignContent::Stretch),
+                ("space-between", AlignContent::SpaceBetween),
+                ("space-evenly", AlignContent::SpaceEvenly),
+                ("space-around", AlignContent::SpaceAround),
+            ],
+        )
+        .map(D::AlignContent),
+        "justify-content" => parse_keyword(
+            value,
+            &[
+                ("default", JustifyContent::Default),
+                ("start", JustifyContent::Start),
+                ("end", JustifyContent::End),
+                ("flex-start", JustifyContent::FlexStart),
+                ("flex-end", JustifyContent::FlexEnd),
+                ("center", JustifyContent::Center),
+                ("stretch", JustifyContent::Stretch),
+                ("space-between", JustifyContent::SpaceBetween),
+                ("space-evenly", JustifyContent::SpaceEvenly),
+                ("space-around", JustifyContent::SpaceAround),
+            ],
+        )
+        .map(D::JustifyContent),
+        "margin" => parse_rect(value).map(D::Margin),
+        "padding" => parse_rect(value).map(D::Padding),
+        "border-width" => parse_rect(value).map(D::Border),
+        "border-radius" => {
+            parse_vals(value).map(|[top_left, top_right, bottom_right, bottom_left]| {
+                D::BorderRadius(BorderRadius {
+                    top_left,
+                    top_right,
+                    bottom_right,
+                    bottom_left,
+                })
+            })
+        }
+        "flex-direction" => parse_keyword(
+            value,
+            &[
+                ("row", FlexDirection::Row),
+                ("column", FlexDirection::Column),
+                ("row-reverse", FlexDirection::RowReverse),
+                ("column-reverse", FlexDirection::ColumnReverse),
+            ],
+        )
+        .map(D::FlexDirection),
+        "flex-wrap" => parse_keyword(
+            value,
+            &[
+                ("nowrap", FlexWrap::NoWrap),
+                ("wrap", FlexWrap::Wrap),
+                ("wrap-reverse", FlexWrap::WrapReverse),
+            ],
+        )
+        .map(D::FlexWrap),
+        "flex-grow" => value.parse().ok().map(D::FlexGrow),
+        "flex-shrink" => value.parse().ok().map(D::FlexShrink),
+        "flex-basis" => parse_val(value).map(D::FlexBasis),
+        "row-gap" => parse_val(value).map(D::RowGap),
+        "column-gap" => parse_val(value).map(D::ColumnGap),
+        "background-color" => parse_color(value).map(D::BackgroundColor),
+        "border-color" => parse_color(value).map(|color| D::BorderColor(BorderColor::all(color))),
+        "color" => parse_color(value).map(D::TextColor),
+        _ => return None,
+    })
+}
+
+fn parse_keyword<T: Copy>(value: &str, keywords: &[(&str, T)]) -> Option<T> {
+    keywords
+        .iter()
+        .find(|(keyword, _)| *keyword == value)
+        .map(|(_, value)| *value)
+}
+
+fn parse_val(value: &str) -> Option<Val> {
+    if value == "0" {
+        return Some(Val::ZERO);
+    }
+    value.parse().ok()
+}
+
+/// Parses one to four values using the CSS shorthand rules for box sides and corners.
+fn parse_vals(value: &str) -> Option<[Val; 4]> {
+    let vals = value
+        .split_whitespace()
+        .map(parse_val)
+        .collect::<Option<Vec<_>>>()?;
+    Some(match vals[..] {
+        [all] => [all; 4],
+        [vertical, horizontal] => [vertical, horizontal, vertical, horizontal],
+        [top, horizontal, bottom] => [top, horizontal, bottom, horizontal],
+        [top, right, bottom, left] => [top, right, bottom, left],
+        _ => return None,
+    })
+}
+
+fn parse_rect(value: &str) -> Option<UiRect> {
+    let [top, right, bottom, left] = parse_vals(value)?;
+    Some(UiRect {
+        left,
+        right,
+        top,
+        bottom,
+    })
+}
+
+fn parse_overflow(value: &str) -> Option<Overflow> {
+    let axis = |value| {
+        parse_keyword(
+            value,
+            &[
+                ("visible", OverflowAxis::Visible),
+                ("clip", OverflowAxis::Clip),
+                ("hidden", OverflowAxis::Hidden),
+                ("scroll", OverflowAxis::Scroll),
+            ],
+        )
+    };
+    let axes = value
+        .split_whitespace()
+        .map(axis)
+        .collect::<Option<Vec<_>>>()?;
+    match axes[..] {
+        [both] => Some(Overflow { x: both, y: both }),
+        [x, y] => Some(Overflow { x, y }),
+        _ => None,
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        return Srgba::hex(hex).ok().map(Color::from);
+    }
+    match value {
+        "transparent" | "none" => return Some(Color::NONE),
+        "white" => return Some(Color::WHITE),
+        "black" => return Some(Color::BLACK),
+        _ => {}
+    }
+    let (function, arguments) = value.strip_suffix(')')?.split_once('(')?;
+    let arguments = arguments
+        .split(',')
+        .map(|argument| argument.trim().parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match (function.trim(), &arguments[..]) {
+        ("rgb", &[red, green, blue]) => Some(Color::srgb_u8(red as u8, green as u8, blue as u8)),
+        ("rgba", &[red, green, blue, alpha]) => {
+            Some(Color::srgba(red / 255., green / 255., blue / 255., alpha))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,639 @@
+//! Transitions, which animate UI component values toward new values instead of changing them
+//! instantly.
+//!
+//! Add [`UiTransitions`] to an entity to animate changes to some of its properties. Whenever the
+//! value of a transitioned property changes (whether it was set by a system, a
+//! [`StyleSheet`](crate::StyleSheet) or anything else), the change is undone and the property is
+//! animated from its previous value to the new one over the transition's duration, following an
+//! [`EasingCurve`].
+//!
+//! This makes interaction states animate smoothly: a stylesheet rule such as
+//! `Button:hover { background-color: #404040; }` combined with a [`BackgroundColor`] transition
+//! fades the button in and out of its hover color as [`Interaction`](crate::Interaction),
+//! [`Pressed`](crate::Pressed) and the other interaction states change.
+//!
+//! If a property changes again while it is transitioning, the transition is interrupted and a new
+//! one starts from the current, partially animated value, triggering [`TransitionInterrupted`].
+//! When a transition reaches its target value, [`TransitionCompleted`] is triggered.
+//!
+//! [`Val`]s can only be interpolated between values of the same unit. Changes between different
+//! units, such as from [`Val::Auto`] to [`Val::Px`], are applied immediately, and trigger
+//! [`TransitionCompleted`] right away. Properties made of several [`Val`]s, such as
+//! [`Node::margin`], animate the values that can be interpolated and apply the others immediately.
+
+use crate::{
+    BackgroundColor, BorderColor, BorderRadius, Node, UiRect, UiSystems, UiTransform, Val,
+};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_color::{Color, Mix};
+use bevy_ecs::{prelude::*, query::QueryData};
+use bevy_math::{
+    curve::{Curve, EaseFunction, EasingCurve},
+    FloatExt,
+};
+use bevy_text::TextColor;
+use bevy_time::Time;
+use core::{mem::discriminant, time::Duration};
+
+/// Adds support for [`UiTransitions`].
+///
+/// This is included by default in [`UiPlugin`](crate::UiPlugin).
+pub struct UiTransitionPlugin;
+
+impl Plugin for UiTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            animate_ui_transitions
+                .in_set(UiSystems::Prepare)
+                .after(crate::stylesheet::apply_style_sheets),
+        );
+    }
+}
+
+/// The transitions of an entity's properties. See the [module docs](self) for how they work.
+///
+/// ```
+/// # use bevy_ui::transition::{TransitionProperty, UiTransition, UiTransitions};
+/// # use bevy_math::curve::EaseFunction;
+/// # use core::time::Duration;
+/// let transitions = UiTransitions(vec![
+///     UiTransition::new(TransitionProperty::BackgroundColor, Duration::from_millis(150)),
+///     UiTransition::new(TransitionProperty::Width, Duration::from_millis(300))
+///         .with_ease(EaseFunction::BackOut),
+/// ]);
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+#[require(TransitionState)]
+pub struct UiTransitions(pub Vec<UiTransition>);
+
+/// How a single property of an entity is animated when it changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UiTransition {
+    /// The property to animate.
+    pub property: TransitionProperty,
+    /// How long it takes to animate to a new value.
+    pub duration: Duration,
+    /// The easing function applied to the animation's progress.
+    pub ease: EaseFunction,
+}
+
+impl UiTransition {
+    /// Creates a transition of `property` that lasts `duration`, with
+    /// [`EaseFunction::CubicInOut`] easing.
+    pub fn new(property: TransitionProperty, duration: Duration) -> Self {
+        Self {
+            property,
+            duration,
+            ease: EaseFunction::CubicInOut,
+        }
+    }
+
+    /// Sets the easing function of this transition.
+    pub fn with_ease(mut self, ease: EaseFunction) -> Self {
+        self.ease = ease;
+        self
+    }
+}
+
+/// A property that can be animated by a [`UiTransition`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransitionProperty {
+    /// [`BackgroundColor`]
+    BackgroundColor,
+    /// [`BorderColor`]
+    BorderColor,
+    /// [`TextColor`]
+    TextColor,
+    /// [`UiTransform`]
+    UiTransform,
+    /// [`Node::left`]
+    Left,
+    /// [`Node::right`]
+    Right,
+    /// [`Node::top`]
+    Top,
+    /// [`Node::bottom`]
+    Bottom,
+    /// [`Node::width`]
+    Width,
+    /// [`Node::height`]
+    Height,
+    /// [`Node::min_width`]
+    MinWidth,
+    /// [`Node::min_height`]
+    MinHeight,
+    /// [`Node::max_width`]
+    MaxWidth,
+    /// [`Node::max_height`]
+    MaxHeight,
+    /// [`Node::margin`]
+    Margin,
+    /// [`Node::padding`]
+    Padding,
+    /// [`Node::border`]
+    Border,
+    /// [`Node::border_radius`]
+    BorderRadius,
+    /// [`Node::flex_basis`]
+    FlexBasis,
+    /// [`Node::row_gap`]
+    RowGap,
+    /// [`Node::column_gap`]
+    ColumnGap,
+}
+
+/// Triggered on an entity when one of its [`UiTransitions`] reaches its target value.
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+pub struct TransitionCompleted {
+    /// The entity whose property finished transitioning.
+    pub entity: Entity,
+    /// The property that finished transitioning.
+    pub property: TransitionProperty,
+}
+
+/// Triggered on an entity when a property changes while it is still transitioning. A new
+/// transition toward the new value starts from the current value.
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+pub struct TransitionInterrupted {
+    /// The entity whose transition was interrupted.
+    pub entity: Entity,
+    /// The property whose transition was interrupted.
+    pub property: TransitionProperty,
+}
+
+/// A value of a [`TransitionProperty`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TransitionValue {
+    Color(Color),
+    BorderColor(BorderColor),
+    Transform(UiTransform),
+    Val(Val),
+    Rect(UiRect),
+    Radius(BorderRadius),
+}
+
+impl TransitionValue {
+    /// Returns `true` if animating from this value to `target` has any values in between, rather
+    /// than jumping straight to `target`.
+    fn interpolates_to(&self, target: &Self) -> bool {
+        match (self, target) {
+            (Self::Val(start), Self::Val(end)) => vals_interpolate(*start, *end),
+            (Self::Rect(start), Self::Rect(end)) => [
+                (start.left, end.left),
+                (start.right, end.right),
+                (start.top, end.top),
+                (start.bottom, end.bottom),
+            ]
+            .into_iter()
+            .any(|(start, end)| vals_interpolate(start, end)),
+            (Self::Radius(start), Self::Radius(end)) => [
+                (start.top_left, end.top_left),
+                (start.top_right, end.top_right),
+                (start.bottom_right, end.bottom_right),
+                (start.bottom_left, end.bottom_left),
+            ]
+            .into_iter()
+            .any(|(start, end)| vals_interpolate(start, end)),
+            _ => self != target,
+        }
+    }
+
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        match (*self, *target) {
+            (Self::Color(start), Self::Color(end)) => Self::Color(start.mix(&end, t)),
+            (Self::BorderColor(start), Self::BorderColor(end)) => Self::BorderColor(BorderColor {
+                top: start.top.mix(&end.top, t),
+                right: start.right.mix(&end.right, t),
+                bottom: start.bottom.mix(&end.bottom, t),
+                left: start.left.mix(&end.left, t),
+            }),
+            (Self::Transform(start), Self::Transform(end)) => Self::Transform(UiTransform {
+                translation: crate::Val2 {
+                    x: interpolate_val(start.translation.x, end.translation.x, t),
+                    y: interpolate_val(start.translation.y, end.translation.y, t),
+                },
+                scale: start.scale.lerp(end.scale, t),
+                rotation: start.rotation.slerp(end.rotation, t),
+            }),
+            (Self::Val(start), Self::Val(end)) => Self::Val(interpolate_val(start, end, t)),
+            (Self::Rect(start), Self::Rect(end)) => Self::Rect(UiRect {
+                left: interpolate_val(start.left, end.left, t),
+                right: interpolate_val(start.right, end.right, t),
+                top: interpolate_val(start.top, end.top, t),
+                bottom: interpolate_val(start.bottom, end.bottom, t),
+            }),
+            (Self::Radius(start), Self::Radius(end)) => Self::Radius(BorderRadius {
+                top_left: interpolate_val(start.top_left, end.top_left, t),
+                top_right: interpolate_val(start.top_right, end.top_right, t),
+                bottom_right: interpolate_val(start.bottom_right, end.bottom_right, t),
+                bottom_left: interpolate_val(start.bottom_left, end.bottom_left, t),
+            }),
+            _ => *target,
+        }
+    }
+}
+
+/// Returns `true` if `start` and `end` are different values of the same unit.
+fn vals_interpolate(start: Val, end: Val) -> bool {
+    start != end && start != Val::Auto && discriminant(&start) == discriminant(&end)
+}
+
+fn interpolate_val(start: Val, end: Val, t: f32) -> Val {
+    match (start, end) {
+        (Val::Px(start), Val::Px(end)) => Val::Px(start.lerp(end, t)),
+        (Val::Percent(start), Val::Percent(end)) => Val::Percent(start.lerp(end, t)),
+        (Val::Vw(start), Val::Vw(end)) => Val::Vw(start.lerp(end, t)),
+        (Val::Vh(start), Val::Vh(end)) => Val::Vh(start.lerp(end, t)),
+        (Val::VMin(start), Val::VMin(end)) => Val::VMin(start.lerp(end, t)),
+        (Val::VMax(start), Val::VMax(end)) => Val::VMax(start.lerp(end, t)),
+        _ => end,
+    }
+}
+
+/// Query for the components [`animate_ui_transitions`] animates.
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct TransitionTarget {
+    node: Option<&'static mut Node>,
+    background_color: Option<&'static mut BackgroundColor>,
+    border_color: Option<&'static mut BorderColor>,
+    text_color: Option<&'static mut TextColor>,
+    transform: Option<&'static mut UiTransform>,
+}
+
+macro_rules! node_lengths {
+    ($target:ident, $property:ident, $value:ident, { $($variant:ident => $field:ident: $kind:ident),* $(,)? }) => {
+        match ($property, $value) {
+            $(
+                (TransitionProperty::$variant, None) => $target
+                    .node
+                    .as_ref()
+                    .map(|node| TransitionValue::$kind(node.$field)),
+                (TransitionProperty::$variant, Some(TransitionValue::$kind(value))) => {
+                    if let Some(node) = $target.node.as_mut() {
+                        node.$field = value;
+                    }
+                    None
+                }
+            )*
+            _ => None,
+        }
+    };
+}
+
+impl TransitionTargetItem<'_, '_> {
+    /// Reads the current value of `property` if `value` is `None`, or writes `value` otherwise.
+    fn access(
+        &mut self,
+        property: TransitionProperty,
+        value: Option<TransitionValue>,
+    ) -> Option<TransitionValue> {
+        match (property, value) {
+            (TransitionProperty::BackgroundColor, None) => self
+                .background_color
+                .as_ref()
+                .map(|color| TransitionValue::Color(color.0)),
+            (TransitionProperty::BackgroundColor, Some(TransitionValue::Color(value))) => {
+                if let Some(color) = self.background_color.as_mut() {
+                    color.0 = value;
+                }
+                None
+            }
+            (TransitionProperty::BorderColor, None) => self
+                .border_color
+                .as_ref()
+                .map(|color| TransitionValue::BorderColor(**color)),
+            (TransitionProperty::BorderColor, Some(TransitionValue::BorderColor(value))) => {
+                if let Some(color) = self.border_color.as_mut() {
+                    **color = value;
+                }
+                None
+            }
+            (TransitionProperty::TextColor, None) => self
+                .text_color
+                .as_ref()
+                .map(|color| TransitionValue::Color(color.0)),
+            (TransitionProperty::TextColor, Some(TransitionValue::Color(value))) => {
+                if let Some(color) = self.text_color.as_mut() {
+                    color.0 = value;
+                }
+                None
+            }
+            (TransitionProperty::UiTransform, None) => self
+                .transform
+                .as_ref()
+                .map(|transform| TransitionValue::Transform(**transform)),
+            (TransitionProperty::UiTransform, Some(TransitionValue::Transform(value))) => {
+                if let Some(transform) = self.transform.as_mut() {
+                    **transform = value;
+                }
+                None
+            }
+            (property, value) => node_lengths!(self, property, value, {
+                Left => left: Val,
+                Right => right: Val,
+                Top => top: Val,
+                Bottom => bottom: Val,
+                Width => width: Val,
+                Height => height: Val,
+                MinWidth => min_width: Val,
+                MinHeight => min_height: Val,
+                MaxWidth => max_width: Val,
+                MaxHeight => max_height: Val,
+                Margin => margin: Rect,
+                Padding => padding: Rect,
+                Border => border: Rect,
+                BorderRadius => border_radius: Radius,
+                FlexBasis => flex_basis: Val,
+                RowGap => row_gap: Val,
+                ColumnGap => column_gap: Val,
+            }),
+        }
+    }
+
+    fn read(&mut self, property: TransitionProperty) -> Option<TransitionValue> {
+        self.access(property, None)
+    }
+
+    fn write(&mut self, property: TransitionProperty, value: TransitionValue) {
+        self.access(property, Some(value));
+    }
+}
+
+/// The state of the [`UiTransitions`] of an entity, which is added automatically.
+#[derive(Component, Debug, Default)]
+pub struct TransitionState {
+    properties: Vec<PropertyState>,
+}
+
+impl TransitionState {
+    /// Returns `true` if any property is transitioning.
+    pub(crate) fn is_animating(&self) -> bool {
+        self.properties
+            .iter()
+            .any(|property_state| property_state.animation.is_some())
+    }
+}
+
+#[derive(Debug)]
+struct PropertyState {
+    property: TransitionProperty,
+    /// The value that was last seen or written, used to detect changes.
+    current: TransitionValue,
+    animation: Option<Animation>,
+}
+
+#[derive(Debug)]
+struct Animation {
+    start: TransitionValue,
+    end: TransitionValue,
+    elapsed: Duration,
+}
+
+/// Animates properties with [`UiTransitions`] toward their new values when they change.
+pub fn animate_ui_transitions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &UiTransitions,
+        &mut TransitionState,
+        TransitionTarget,
+    )>,
+) {
+    for (entity, transitions, mut state, mut target) in &mut query {
+        let state = &mut *state;
+        state.properties.retain(|property_state| {
+            transitions
+                .0
+                .iter()
+                .any(|transition| transition.property == property_state.property)
+        });
+
+        for transition in &transitions.0 {
+            let Some(value) = target.read(transition.property) else {
+                continue;
+            };
+            let Some(property_state) = state
+                .properties
+                .iter_mut()
+                .find(|property_state| property_state.property == transition.property)
+            else {
+                // Values present when the transition is added aren't animated.
+                state.properties.push(PropertyState {
+                    property: transition.property,
+                    current: value,
+                    animation: None,
+                });
+                continue;
+            };
+
+            if value != property_state.current {
+                if property_state.animation.is_some() {
+                    commands.trigger(TransitionInterrupted {
+                        entity,
+                        property: transition.property,
+                    });
+                }
+                // Values that can't be interpolated, such as lengths of different units, are
+                // applied immediately, so the transition is already complete.
+                if !property_state.current.interpolates_to(&value) {
+                    property_state.current = value;
+                    property_state.animation = None;
+                    commands.trigger(TransitionCompleted {
+                        entity,
+                        property: transition.property,
+                    });
+                    continue;
+                }
+                property_state.animation = Some(Animation {
+                    start: property_state.current,
+                    end: value,
+                    elapsed: Duration::ZERO,
+                });
+            }
+
+            let Some(animation) = property_state.animation.as_mut() else {
+                continue;
+            };
+            animation.elapsed += time.delta();
+            let progress = if transition.duration.is_zero() {
+                1.
+            } else {
+                animation.elapsed.as_secs_f32() / transition.duration.as_secs_f32()
+            };
+            let eased = EasingCurve::new(0., 1., transition.ease).sample_clamped(progress);
+            let value = animation.start.interpolate(&animation.end, eased);
+            target.write(transition.property, value);
+            property_state.current = value;
+
+            if progress >= 1. {
+                // Land exactly on the target, regardless of the easing function.
+                target.write(transition.property, animation.end);
+                property_state.current = animation.end;
+                property_state.animation = None;
+                commands.trigger(TransitionCompleted {
+                    entity,
+                    property: transition.property,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        stylesheet::{apply_style_sheets, StyleTypeSelectors},
+        Pressed, StyleSheet, UiStyleSheet,
+    };
+    use bevy_asset::{AssetEvent, Assets};
+
+    #[derive(Resource, Default)]
+    struct Events(Vec<&'static str>);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Events>()
+            .add_observer(|_: On<TransitionCompleted>, mut events: ResMut<Events>| {
+                events.0.push("completed");
+            })
+            .add_observer(|_: On<TransitionInterrupted>, mut events: ResMut<Events>| {
+                events.0.push("interrupted");
+            })
+            .add_systems(PostUpdate, animate_ui_transitions);
+        app
+    }
+
+    fn advance(app: &mut App, millis: u64) {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        app.update();
+    }
+
+    #[test]
+    fn transition_width() {
+        let mut app = app();
+        let entity = app
+            .world_mut()
+            .spawn((
+                Node {
+                    width: Val::Px(0.),
+                    ..Default::default()
+                },
+                UiTransitions(vec![UiTransition::new(
+                    TransitionProperty::Width,
+                    Duration::from_millis(100),
+                )
+                .with_ease(EaseFunction::Linear)]),
+            ))
+            .id();
+        advance(&mut app, 0);
+        let width = |app: &App| app.world().get::<Node>(entity).unwrap().width;
+
+        app.world_mut().get_mut::<Node>(entity).unwrap().width = Val::Px(100.);
+        advance(&mut app, 50);
+        assert_eq!(width(&app), Val::Px(50.));
+
+        // Interrupt the transition, which restarts from the current value.
+        app.world_mut().get_mut::<Node>(entity).unwrap().width = Val::Px(0.);
+        advance(&mut app, 50);
+        assert_eq!(width(&app), Val::Px(25.));
+        advance(&mut app, 60);
+        assert_eq!(width(&app), Val::Px(0.));
+        assert_eq!(
+            app.world().resource::<Events>().0,
+            vec!["interrupted", "completed"]
+        );
+    }
+
+    #[test]
+    fn transition_between_units() {
+        let mut app = app();
+        let entity = app
+            .world_mut()
+            .spawn((
+                Node {
+                    margin: UiRect::left(Val::Px(10.)),
+                    ..Default::default()
+                },
+                UiTransitions(vec![
+                    UiTransition::new(TransitionProperty::Width, Duration::from_millis(100)),
+                    UiTransition::new(TransitionProperty::Margin, Duration::from_millis(100))
+                        .with_ease(EaseFunction::Linear),
+                ]),
+            ))
+            .id();
+        advance(&mut app, 0);
+        let node = |app: &App| app.world().get::<Node>(entity).unwrap().clone();
+
+        // Changing from `Auto` to a length jumps, and completes straight away.
+        app.world_mut().get_mut::<Node>(entity).unwrap().width = Val::Px(100.);
+        advance(&mut app, 10);
+        assert_eq!(node(&app).width, Val::Px(100.));
+        assert_eq!(app.world().resource::<Events>().0, vec!["completed"]);
+
+        // Sides that can be interpolated are animated, the others jump.
+        app.world_mut().get_mut::<Node>(entity).unwrap().margin = UiRect {
+            left: Val::Px(20.),
+            top: Val::Percent(10.),
+            ..UiRect::DEFAULT
+        };
+        advance(&mut app, 50);
+        assert_eq!(node(&app).margin.left, Val::Px(15.));
+        assert_eq!(node(&app).margin.top, Val::Percent(10.));
+        assert_eq!(app.world().resource::<Events>().0.len(), 1);
+        advance(&mut app, 50);
+        assert_eq!(node(&app).margin.left, Val::Px(20.));
+        assert_eq!(app.world().resource::<Events>().0.len(), 2);
+    }
+
+    #[test]
+    fn transition_style_sheet_states() {
+        let mut app = app();
+        app.init_resource::<Assets<StyleSheet>>()
+            .add_message::<AssetEvent<StyleSheet>>()
+            .init_resource::<StyleTypeSelectors>()
+            .add_systems(
+                PostUpdate,
+                apply_style_sheets.before(animate_ui_transitions),
+            );
+        let style_sheet = StyleSheet::parse(
+            "Node { background-color: #000000; }
+            Node:pressed { background-color: #ffffff; }",
+        )
+        .unwrap();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<StyleSheet>>()
+            .add(style_sheet);
+        let entity = app
+            .world_mut()
+            .spawn((
+                Node::default(),
+                UiStyleSheet(handle),
+                UiTransitions(vec![UiTransition::new(
+                    TransitionProperty::BackgroundColor,
+                    Duration::from_millis(100),
+                )
+                .with_ease(EaseFunction::Linear)]),
+            ))
+            .id();
+        let background = |app: &App| app.world().get::<BackgroundColor>(entity).unwrap().0;
+
+        // Values are only animated once the transition has seen the entity, so the first
+        // stylesheet values are applied immediately.
+        advance(&mut app, 100);
+        assert_eq!(background(&app), Color::srgb(0., 0., 0.));
+
+        app.world_mut().entity_mut(entity).insert(Pressed);
+        advance(&mut app, 50);
+        assert_eq!(background(&app), Color::srgb(0.5, 0.5, 0.5));
+        advance(&mut app, 50);
+        assert_eq!(background(&app), Color::srgb(1., 1., 1.));
+    }
+}
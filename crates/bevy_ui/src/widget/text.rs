@@ -21,7 +21,8 @@ use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_text::{
     ComputedTextBlock, CosmicFontSystem, Font, FontAtlasSet, FontHinting, LineBreak, LineHeight,
     SwashCache, TextBounds, TextColor, TextError, TextFont, TextLayout, TextLayoutInfo,
-    TextMeasureInfo, TextPipeline, TextReader, TextRoot, TextSpanAccess, TextWriter,
+    TextMeasureInfo, TextPipeline, TextReader, TextRenderMode, TextRoot, TextSpanAccess,
+    TextWriter,
 };
 use taffy::style::AvailableSpace;
 use tracing::error;
@@ -106,7 +107,8 @@ impl Default for TextNodeFlags {
     ContentSize,
     // Disable hinting.
     // UI text is normally pixel-aligned, but with hinting enabled sometimes the text bounds are miscalculated slightly.
-    FontHinting::Disabled
+    FontHinting::Disabled,
+    TextRenderMode
 )]
 pub struct Text(pub String);
 
@@ -336,12 +338,15 @@ pub fn text_system(
         &mut TextLayoutInfo,
         &mut TextNodeFlags,
         &mut ComputedTextBlock,
+        Ref<TextRenderMode>,
     )>,
     mut font_system: ResMut<CosmicFontSystem>,
     mut swash_cache: ResMut<SwashCache>,
 ) {
-    for (node, block, mut text_layout_info, mut text_flags, mut computed) in &mut text_query {
-        if node.is_changed() || text_flags.needs_recompute {
+    for (node, block, mut text_layout_info, mut text_flags, mut computed, render_mode) in
+        &mut text_query
+    {
+        if node.is_changed() || render_mode.is_changed() || text_flags.needs_recompute {
             // Skip the text node if it is waiting for a new measure func
             if text_flags.needs_measure_fn {
                 continue;
@@ -365,6 +370,7 @@ pub fn text_system(
                 &mut swash_cache,
                 physical_node_size,
                 block.justify,
+                *render_mode,
             ) {
                 Err(TextError::NoSuchFont | TextError::DegenerateScaleFactor) => {
                     // There was an error processing the text layout, try again next frame
@@ -392,3 +398,240 @@ pub fn text_system(
         }
     }
 }
+
+/// Returns the position of a pointer in the coordinates of the [`TextLayoutInfo`] of a text node.
+///
+/// `viewport_min` is the top left corner of the viewport of the node's camera in physical pixels,
+/// which UI coordinates are relative to.
+#[cfg(feature = "bevy_picking")]
+fn text_layout_position(
+    node: &ComputedNode,
+    transform: &crate::UiGlobalTransform,
+    target: &ComputedUiRenderTargetInfo,
+    ui_scale: &crate::UiScale,
+    viewport_min: Vec2,
+    pointer_position: Vec2,
+) -> Option<Vec2> {
+    let local_position = transform
+        .try_inverse()?
+        .transform_point2(pointer_position * target.scale_factor() / ui_scale.0 - viewport_min);
+    Some(local_position + 0.5 * node.size())
+}
+
+/// Returns the top left corner of the viewport of the camera a UI node is rendered with, in
+/// physical pixels, like the UI picking backend.
+#[cfg(feature = "bevy_picking")]
+fn camera_viewport_min(
+    target_camera: &crate::ComputedUiTargetCamera,
+    cameras: &Query<&bevy_camera::Camera>,
+) -> Vec2 {
+    target_camera
+        .get()
+        .and_then(|camera| cameras.get(camera).ok())
+        .and_then(bevy_camera::Camera::physical_viewport_rect)
+        .map_or(Vec2::ZERO, |viewport| viewport.min.as_vec2())
+}
+
+#[cfg(feature = "bevy_picking")]
+/// Starts a [`TextSelection`](bevy_text::TextSelection) when [`SelectableText`](bevy_text::SelectableText)
+/// is pressed with the primary pointer button, and clears all other selections.
+pub fn text_selection_on_press(
+    press: bevy_ecs::observer::On<bevy_picking::events::Pointer<bevy_picking::events::Press>>,
+    mut text_query: Query<
+        (
+            &ComputedNode,
+            &crate::UiGlobalTransform,
+            &ComputedUiRenderTargetInfo,
+            &crate::ComputedUiTargetCamera,
+            &mut ComputedTextBlock,
+        ),
+        (With<Text>, With<bevy_text::SelectableText>),
+    >,
+    cameras: Query<&bevy_camera::Camera>,
+    mut selection_query: Query<(Entity, &mut bevy_text::TextSelection)>,
+    mut font_system: ResMut<CosmicFontSystem>,
+    ui_scale: Res<crate::UiScale>,
+    time: Res<bevy_time::Time>,
+) {
+    if press.button != bevy_picking::pointer::PointerButton::Primary {
+        return;
+    }
+    let Ok((node, transform, target, target_camera, mut block)) = text_query.get_mut(press.entity)
+    else {
+        return;
+    };
+    let Some(position) = text_layout_position(
+        node,
+        transform,
+        target,
+        &ui_scale,
+        camera_viewport_min(target_camera, &cameras),
+        press.pointer_location.position,
+    ) else {
+        return;
+    };
+
+    for (entity, mut selection) in &mut selection_query {
+        if entity == press.entity {
+            selection.press(&mut block, &mut font_system, position, time.elapsed());
+        } else if !selection.is_empty() {
+            selection.clear();
+        }
+    }
+}
+
+#[cfg(feature = "bevy_picking")]
+/// Clears all [`TextSelection`](bevy_text::TextSelection)s when the primary pointer button is
+/// pressed outside of any [`SelectableText`](bevy_text::SelectableText).
+pub fn clear_text_selections_on_outside_press(
+    mut pointer_inputs: bevy_ecs::message::MessageReader<bevy_picking::pointer::PointerInput>,
+    hover_map: Res<bevy_picking::hover::HoverMap>,
+    selectable: Query<(), With<bevy_text::SelectableText>>,
+    parents: Query<&bevy_ecs::hierarchy::ChildOf>,
+    mut selection_query: Query<&mut bevy_text::TextSelection>,
+) {
+    for input in pointer_inputs.read() {
+        if !input.button_just_pressed(bevy_picking::pointer::PointerButton::Primary) {
+            continue;
+        }
+        // Spans of selectable text are hovered as well as the text itself.
+        let on_selectable = hover_map
+            .get(&input.pointer_id)
+            .into_iter()
+            .flat_map(|hovered| hovered.keys())
+            .any(|&entity| {
+                core::iter::once(entity)
+                    .chain(parents.iter_ancestors(entity))
+                    .any(|entity| selectable.contains(entity))
+            });
+        if on_selectable {
+            continue;
+        }
+        for mut selection in &mut selection_query {
+            if !selection.is_empty() {
+                selection.clear();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bevy_picking")]
+/// Extends the [`TextSelection`](bevy_text::TextSelection) of [`SelectableText`](bevy_text::SelectableText)
+/// while it is dragged with the primary pointer button.
+pub fn text_selection_on_drag(
+    drag: bevy_ecs::observer::On<bevy_picking::events::Pointer<bevy_picking::events::Drag>>,
+    mut text_query: Query<
+        (
+            &ComputedNode,
+            &crate::UiGlobalTransform,
+            &ComputedUiRenderTargetInfo,
+            &crate::ComputedUiTargetCamera,
+            &mut ComputedTextBlock,
+            &mut bevy_text::TextSelection,
+        ),
+        (With<Text>, With<bevy_text::SelectableText>),
+    >,
+    cameras: Query<&bevy_camera::Camera>,
+    mut font_system: ResMut<CosmicFontSystem>,
+    ui_scale: Res<crate::UiScale>,
+) {
+    if drag.button != bevy_picking::pointer::PointerButton::Primary {
+        return;
+    }
+    let Ok((node, transform, target, target_camera, mut block, mut selection)) =
+        text_query.get_mut(drag.entity)
+    else {
+        return;
+    };
+    if let Some(position) = text_layout_position(
+        node,
+        transform,
+        target,
+        &ui_scale,
+        camera_viewport_min(target_camera, &cameras),
+        drag.pointer_location.position,
+    ) {
+        selection.drag(&mut block, &mut font_system, position);
+    }
+}
+
+#[cfg(all(test, feature = "bevy_picking"))]
+mod tests {
+    use super::*;
+    use crate::{UiGlobalTransform, UiScale};
+    use bevy_app::{App, Update};
+    use bevy_camera::NormalizedRenderTarget;
+    use bevy_picking::{
+        backend::HitData,
+        hover::HoverMap,
+        pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput},
+    };
+    use bevy_platform::collections::HashMap;
+    use bevy_text::{SelectableText, TextCursor, TextSelection};
+
+    #[test]
+    fn text_layout_position_is_relative_to_viewport() {
+        let node = ComputedNode {
+            size: Vec2::new(100., 20.),
+            ..Default::default()
+        };
+        let transform = UiGlobalTransform::from_translation(Vec2::new(50., 10.));
+        let target = ComputedUiRenderTargetInfo {
+            scale_factor: 2.,
+            ..Default::default()
+        };
+        // The camera's viewport starts 100 physical pixels from the left of the window.
+        let position = text_layout_position(
+            &node,
+            &transform,
+            &target,
+            &UiScale(1.),
+            Vec2::new(100., 0.),
+            Vec2::new(60., 15.),
+        );
+        assert_eq!(position, Some(Vec2::new(20., 30.)));
+    }
+
+    #[test]
+    fn press_outside_selectable_text_clears_selections() {
+        let mut app = App::new();
+        app.add_message::<PointerInput>()
+            .init_resource::<HoverMap>()
+            .add_systems(Update, clear_text_selections_on_outside_press);
+        let text = app
+            .world_mut()
+            .spawn((
+                SelectableText,
+                TextSelection::new(TextCursor::new(0, 0), TextCursor::new(0, 3)),
+            ))
+            .id();
+        let press = |app: &mut App, hovered: Option<Entity>| {
+            let hit = HitData::new(Entity::PLACEHOLDER, 0., None, None);
+            app.world_mut().resource_mut::<HoverMap>().0 = HashMap::from_iter([(
+                PointerId::Mouse,
+                hovered
+                    .into_iter()
+                    .map(|entity| (entity, hit.clone()))
+                    .collect(),
+            )]);
+            app.world_mut().write_message(PointerInput::new(
+                PointerId::Mouse,
+                Location {
+                    target: NormalizedRenderTarget::None {
+                        width: 800,
+                        height: 600,
+                    },
+                    position: Vec2::ZERO,
+                },
+                PointerAction::Press(PointerButton::Primary),
+            ));
+            app.update();
+            app.world().get::<TextSelection>(text).unwrap().is_empty()
+        };
+
+        // Pressing the text itself leaves the selection to its observer.
+        assert!(!press(&mut app, Some(text)));
+        let other = app.world_mut().spawn_empty().id();
+        assert!(press(&mut app, Some(other)));
+    }
+}
@@ -63,8 +63,9 @@ use gradient::GradientPlugin;
 
 use bevy_platform::collections::{HashMap, HashSet};
 use bevy_text::{
-    ComputedTextBlock, PositionedGlyph, Strikethrough, StrikethroughColor, TextBackgroundColor,
-    TextColor, TextLayoutInfo, Underline, UnderlineColor,
+    sdf_distance, ComputedTextBlock, InlineImage, PositionedGlyph, Strikethrough,
+    StrikethroughColor, TextBackgroundColor, TextColor, TextLayoutInfo, TextOutline,
+    TextRenderMode, TextSelection, TextSelectionColor, Underline, UnderlineColor,
 };
 use bevy_transform::components::GlobalTransform;
 use box_shadow::BoxShadowPlugin;
@@ -246,6 +247,10 @@ impl Plugin for UiRenderPlugin {
                     extract_uinode_borders.in_set(RenderUiSystems::ExtractBorders),
                     extract_viewport_nodes.in_set(RenderUiSystems::ExtractViewportNodes),
                     extract_text_decorations.in_set(RenderUiSystems::ExtractTextBackgrounds),
+                    extract_text_selections
+                        .in_set(RenderUiSystems::ExtractTextBackgrounds)
+                        .after(extract_text_decorations),
+                    extract_text_inline_images.in_set(RenderUiSystems::ExtractText),
                     extract_text_shadows.in_set(RenderUiSystems::ExtractTextShadows),
                     extract_text_sections.in_set(RenderUiSystems::ExtractText),
                     #[cfg(feature = "bevy_ui_debug")]
@@ -390,7 +395,14 @@ pub enum ExtractedUiItem {
 pub struct ExtractedGlyph {
     pub color: LinearRgba,
     pub translation: Vec2,
+    /// The region of the glyph in the atlas texture.
     pub rect: Rect,
+    /// The size of the glyph on screen, which differs from the size of `rect` for signed distance
+    /// field glyphs.
+    pub size: Vec2,
+    /// For glyphs rendered from a signed distance field, the distance by which the glyph is grown,
+    /// see [`sdf_distance`]. `None` for rasterized glyphs.
+    pub sdf_dilation: Option<f32>,
 }
 
 #[derive(Resource, Default)]
@@ -914,6 +926,8 @@ pub fn extract_text_sections(
             &ComputedTextBlock,
             &TextColor,
             &TextLayoutInfo,
+            Option<&TextRenderMode>,
+            Option<&TextOutline>,
         )>,
     >,
     text_styles: Extract<Query<&TextColor>>,
@@ -933,6 +947,8 @@ pub fn extract_text_sections(
         computed_block,
         text_color,
         text_layout_info,
+        render_mode,
+        outline,
     ) in &uinode_query
     {
         // Skip if not visible or if size is set to zero (e.g. when a parent is set to `Display::None`)
@@ -946,61 +962,79 @@ pub fn extract_text_sections(
 
         let transform = Affine2::from(*transform) * Affine2::from_translation(-0.5 * uinode.size());
 
-        let mut color = text_color.0.to_linear();
-
-        let mut current_span_index = 0;
+        let sdf = render_mode == Some(&TextRenderMode::Sdf);
+        // Outlines are drawn first, as glyphs grown by the outline width, below the glyphs.
+        let outline = outline.filter(|_| sdf).map(|outline| {
+            (
+                LinearRgba::from(outline.color),
+                outline.width / uinode.inverse_scale_factor(),
+            )
+        });
 
-        for (
-            i,
-            PositionedGlyph {
-                position,
-                atlas_info,
-                span_index,
-                ..
-            },
-        ) in text_layout_info.glyphs.iter().enumerate()
-        {
-            if current_span_index != *span_index
-                && let Some(span_entity) =
-                    computed_block.entities().get(*span_index).map(|t| t.entity)
+        for outline_pass in outline.into_iter().map(Some).chain([None]) {
+            let (mut color, dilation) = outline_pass
+                .map(|(color, width)| (color, Some(width)))
+                .unwrap_or((text_color.0.to_linear(), None));
+
+            let mut current_span_index = 0;
+
+            for (
+                i,
+                PositionedGlyph {
+                    position,
+                    size,
+                    atlas_info,
+                    span_index,
+                    ..
+                },
+            ) in text_layout_info.glyphs.iter().enumerate()
             {
-                color = text_styles
-                    .get(span_entity)
-                    .map(|text_color| LinearRgba::from(text_color.0))
-                    .unwrap_or_default();
-                current_span_index = *span_index;
-            }
-
-            let rect = texture_atlases
-                .get(atlas_info.texture_atlas)
-                .unwrap()
-                .textures[atlas_info.location.glyph_index]
-                .as_rect();
-            extracted_uinodes.glyphs.push(ExtractedGlyph {
-                color,
-                translation: *position,
-                rect,
-            });
+                if outline_pass.is_none()
+                    && current_span_index != *span_index
+                    && let Some(span_entity) =
+                        computed_block.entities().get(*span_index).map(|t| t.entity)
+                {
+                    color = text_styles
+                        .get(span_entity)
+                        .map(|text_color| LinearRgba::from(text_color.0))
+                        .unwrap_or_default();
+                    current_span_index = *span_index;
+                }
 
-            if text_layout_info
-                .glyphs
-                .get(i + 1)
-                .is_none_or(|info| info.atlas_info.texture != atlas_info.texture)
-            {
-                extracted_uinodes.uinodes.push(ExtractedUiNode {
-                    z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
-                    render_entity: commands.spawn(TemporaryRenderEntity).id(),
-                    image: atlas_info.texture,
-                    clip: clip.map(|clip| clip.clip),
-                    extracted_camera_entity,
-                    item: ExtractedUiItem::Glyphs { range: start..end },
-                    main_entity: entity.into(),
-                    transform,
+                let rect = texture_atlases
+                    .get(atlas_info.texture_atlas)
+                    .unwrap()
+                    .textures[atlas_info.location.glyph_index]
+                    .as_rect();
+                extracted_uinodes.glyphs.push(ExtractedGlyph {
+                    color,
+                    translation: *position,
+                    rect,
+                    size: *size,
+                    sdf_dilation: sdf
+                        .then(|| sdf_distance(dilation.unwrap_or(0.), *size, rect.size())),
                 });
-                start = end;
-            }
 
-            end += 1;
+                if text_layout_info
+                    .glyphs
+                    .get(i + 1)
+                    .is_none_or(|info| info.atlas_info.texture != atlas_info.texture)
+                {
+                    extracted_uinodes.uinodes.push(ExtractedUiNode {
+                        z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
+                        render_entity: commands.spawn(TemporaryRenderEntity).id(),
+                        image: atlas_info.texture,
+                        clip: clip.map(|clip| clip.clip),
+                        extracted_camera_entity,
+                        item: ExtractedUiItem::Glyphs { range: start..end },
+                        main_entity: entity.into(),
+                        transform,
+                    });
+                    start = end;
+                }
+
+                end += 1;
+            }
         }
     }
 }
@@ -1020,6 +1054,7 @@ pub fn extract_text_shadows(
             &TextLayoutInfo,
             &TextShadow,
             &ComputedTextBlock,
+            Option<&TextRenderMode>,
         )>,
     >,
     text_decoration_query: Extract<Query<(Has<Strikethrough>, Has<Underline>)>>,
@@ -1039,6 +1074,7 @@ pub fn extract_text_shadows(
         text_layout_info,
         shadow,
         computed_block,
+        render_mode,
     ) in &uinode_query
     {
         // Skip if not visible or if size is set to zero (e.g. when a parent is set to `Display::None`)
@@ -1054,11 +1090,13 @@ pub fn extract_text_shadows(
             * Affine2::from_translation(
                 -0.5 * uinode.size() + shadow.offset / uinode.inverse_scale_factor(),
             );
+        let sdf = render_mode == Some(&TextRenderMode::Sdf);
 
         for (
             i,
             PositionedGlyph {
                 position,
+                size,
                 atlas_info,
                 span_index,
                 ..
@@ -1074,6 +1112,8 @@ pub fn extract_text_shadows(
                 color: shadow.color.into(),
                 translation: *position,
                 rect,
+                size: *size,
+                sdf_dilation: sdf.then_some(0.),
             });
 
             if text_layout_info.glyphs.get(i + 1).is_none_or(|info| {
@@ -1305,6 +1345,156 @@ pub fn extract_text_decorations(
     }
 }
 
+/// Extracts the highlighted rectangles of the [`TextSelection`] of text nodes.
+pub fn extract_text_selections(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    uinode_query: Extract<
+        Query<(
+            Entity,
+            &ComputedNode,
+            &ComputedTextBlock,
+            &UiGlobalTransform,
+            &InheritedVisibility,
+            Option<&CalculatedClip>,
+            &ComputedUiTargetCamera,
+            &TextSelection,
+            &TextSelectionColor,
+        )>,
+    >,
+    camera_map: Extract<UiCameraMap>,
+) {
+    let mut camera_mapper = camera_map.get_mapper();
+    for (
+        entity,
+        uinode,
+        computed_block,
+        global_transform,
+        inherited_visibility,
+        clip,
+        camera,
+        selection,
+        selection_color,
+    ) in &uinode_query
+    {
+        // Skip if not visible or if size is set to zero (e.g. when a parent is set to `Display::None`)
+        if !inherited_visibility.get() || uinode.is_empty() || selection.is_empty() {
+            continue;
+        }
+
+        let Some(extracted_camera_entity) = camera_mapper.map(camera) else {
+            continue;
+        };
+
+        let transform =
+            Affine2::from(global_transform) * Affine2::from_translation(-0.5 * uinode.size());
+
+        for rect in selection.rects(computed_block) {
+            extracted_uinodes.uinodes.push(ExtractedUiNode {
+                z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
+                render_entity: commands.spawn(TemporaryRenderEntity).id(),
+                clip: clip.map(|clip| clip.clip),
+                image: AssetId::default(),
+                extracted_camera_entity,
+                transform: transform * Affine2::from_translation(rect.center()),
+                item: ExtractedUiItem::Node {
+                    color: selection_color.0.to_linear(),
+                    rect: Rect {
+                        min: Vec2::ZERO,
+                        max: rect.size(),
+                    },
+                    atlas_scaling: None,
+                    flip_x: false,
+                    flip_y: false,
+                    border: BorderRect::ZERO,
+                    border_radius: ResolvedBorderRadius::ZERO,
+                    node_type: NodeType::Rect,
+                },
+                main_entity: entity.into(),
+            });
+        }
+    }
+}
+
+/// Extracts the [`InlineImage`]s of text spans, drawn over the layout bounds of each span's text.
+pub fn extract_text_inline_images(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    uinode_query: Extract<
+        Query<(
+            Entity,
+            &ComputedNode,
+            &ComputedTextBlock,
+            &UiGlobalTransform,
+            &InheritedVisibility,
+            Option<&CalculatedClip>,
+            &ComputedUiTargetCamera,
+            &TextLayoutInfo,
+        )>,
+    >,
+    inline_image_query: Extract<Query<(&InlineImage, &TextColor)>>,
+    camera_map: Extract<UiCameraMap>,
+) {
+    let mut camera_mapper = camera_map.get_mapper();
+    for (
+        entity,
+        uinode,
+        computed_block,
+        global_transform,
+        inherited_visibility,
+        clip,
+        camera,
+        text_layout_info,
+    ) in &uinode_query
+    {
+        // Skip if not visible or if size is set to zero (e.g. when a parent is set to `Display::None`)
+        if !inherited_visibility.get() || uinode.is_empty() {
+            continue;
+        }
+
+        let Some(extracted_camera_entity) = camera_mapper.map(camera) else {
+            continue;
+        };
+
+        let transform =
+            Affine2::from(global_transform) * Affine2::from_translation(-0.5 * uinode.size());
+
+        for run in text_layout_info.run_geometry.iter() {
+            let section_entity = computed_block.entities()[run.span_index].entity;
+            let Ok((inline_image, text_color)) = inline_image_query.get(section_entity) else {
+                continue;
+            };
+
+            // Inline images are tinted by the alpha of the span's text color only, so that they
+            // fade together with the text around them.
+            let color = LinearRgba::WHITE.with_alpha(text_color.0.alpha());
+
+            extracted_uinodes.uinodes.push(ExtractedUiNode {
+                z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
+                render_entity: commands.spawn(TemporaryRenderEntity).id(),
+                clip: clip.map(|clip| clip.clip),
+                image: inline_image.0.id(),
+                extracted_camera_entity,
+                transform: transform * Affine2::from_translation(run.bounds.center()),
+                item: ExtractedUiItem::Node {
+                    color,
+                    rect: Rect {
+                        min: Vec2::ZERO,
+                        max: run.bounds.size(),
+                    },
+                    atlas_scaling: None,
+                    flip_x: false,
+                    flip_y: false,
+                    border: BorderRect::ZERO,
+                    border_radius: ResolvedBorderRadius::ZERO,
+                    node_type: NodeType::Rect,
+                },
+                main_entity: entity.into(),
+            });
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
 struct UiVertex {
@@ -1375,6 +1565,8 @@ pub mod shader_flags {
     pub const BORDER_RIGHT: u32 = 1024;
     pub const BORDER_BOTTOM: u32 = 2048;
     pub const BORDER_ALL: u32 = BORDER_LEFT + BORDER_TOP + BORDER_RIGHT + BORDER_BOTTOM;
+    /// The texture is a signed distance field, see [`bevy_text::TextRenderMode::Sdf`]
+    pub const SDF: u32 = 4096;
 }
 
 pub fn queue_uinodes(
@@ -1732,14 +1924,16 @@ pub fn prepare_uinodes(
 
                         for glyph in &extracted_uinodes.glyphs[range.clone()] {
                             let color = glyph.color.to_f32_array();
-                            let glyph_rect = glyph.rect;
-                            let rect_size = glyph_rect.size();
+                            let rect_size = glyph.size;
+                            // Signed distance field glyphs are scaled from their size in the atlas.
+                            let uv_scale =
+                                glyph.rect.size() / glyph.size.max(Vec2::splat(f32::EPSILON));
 
                             // Specify the corners of the glyph
                             let positions = QUAD_VERTEX_POSITIONS.map(|pos| {
                                 extracted_uinode
                                     .transform
-                                    .transform_point2(glyph.translation + pos * glyph_rect.size())
+                                    .transform_point2(glyph.translation + pos * rect_size)
                                     .extend(0.)
                             });
 
@@ -1785,32 +1979,31 @@ pub fn prepare_uinodes(
                             }
 
                             let uvs = [
-                                Vec2::new(
-                                    glyph.rect.min.x + positions_diff[0].x,
-                                    glyph.rect.min.y + positions_diff[0].y,
-                                ),
-                                Vec2::new(
-                                    glyph.rect.max.x + positions_diff[1].x,
-                                    glyph.rect.min.y + positions_diff[1].y,
-                                ),
-                                Vec2::new(
-                                    glyph.rect.max.x + positions_diff[2].x,
-                                    glyph.rect.max.y + positions_diff[2].y,
-                                ),
-                                Vec2::new(
-                                    glyph.rect.min.x + positions_diff[3].x,
-                                    glyph.rect.max.y + positions_diff[3].y,
-                                ),
+                                glyph.rect.min + positions_diff[0] * uv_scale,
+                                Vec2::new(glyph.rect.max.x, glyph.rect.min.y)
+                                    + positions_diff[1] * uv_scale,
+                                glyph.rect.max + positions_diff[2] * uv_scale,
+                                Vec2::new(glyph.rect.min.x, glyph.rect.max.y)
+                                    + positions_diff[3] * uv_scale,
                             ]
                             .map(|pos| pos / atlas_extent);
 
+                            // Signed distance field glyphs store their dilation in the otherwise
+                            // unused radius.
+                            let (sdf_flag, radius) = match glyph.sdf_dilation {
+                                Some(dilation) => (shader_flags::SDF, [dilation, 0.0, 0.0, 0.0]),
+                                None => (0, [0.0; 4]),
+                            };
+
                             for i in 0..4 {
                                 ui_meta.vertices.push(UiVertex {
                                     position: positions_clipped[i].into(),
                                     uv: uvs[i].into(),
                                     color,
-                                    flags: shader_flags::TEXTURED | shader_flags::CORNERS[i],
-                                    radius: [0.0; 4],
+                                    flags: shader_flags::TEXTURED
+                                        | sdf_flag
+                                        | shader_flags::CORNERS[i],
+                                    radius,
                                     border: [0.0; 4],
                                     size: rect_size.into(),
                                     point: [0.0; 2],
@@ -11,6 +11,8 @@ const BORDER_TOP: u32 = 512u;
 const BORDER_RIGHT: u32 = 1024u;
 const BORDER_BOTTOM: u32 = 2048u;
 const BORDER_ANY: u32 = BORDER_LEFT + BORDER_TOP + BORDER_RIGHT + BORDER_BOTTOM;
+// must align with SDF shader_flag from bevy_ui/render/mod.rs
+const SDF: u32 = 4096u;
 
 fn enabled(flags: u32, mask: u32) -> bool {
     return (flags & mask) != 0u;
@@ -210,9 +212,26 @@ fn draw_uinode_background(
     return vec4(color.rgb, saturate(color.a * t));
 }
 
+// Draws a glyph from a signed distance field, where `distance` is the sampled distance with the 
+// edge of the glyph at 0.5, and `dilation` grows the glyph, for example to draw an outline.
+fn draw_sdf_glyph(color: vec4<f32>, distance: f32, distance_width: f32, dilation: f32) -> vec4<f32> {
+    let edge = 0.5 - dilation;
+    // Antialias over about one pixel on screen.
+    let w = max(0.7 * distance_width, 1e-4);
+    let t = smoothstep(edge - w, edge + w, distance);
+    return vec4(color.rgb, saturate(color.a * t));
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
+    // Derivatives require uniform control flow, so compute this before branching.
+    let distance_width = fwidth(texture_color.a);
+
+    if enabled(in.flags, SDF) {
+        // For signed distance field glyphs the dilation is stored in the radius.
+        return draw_sdf_glyph(in.color, texture_color.a, distance_width, in.radius.x);
+    }
 
     // Only use the color sampled from the texture if the `TEXTURED` flag is enabled. 
     // This allows us to draw both textured and untextured shapes together in the same batch.
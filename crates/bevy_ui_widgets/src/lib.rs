@@ -21,19 +21,29 @@
 mod button;
 mod checkbox;
 mod menu;
+mod number_input;
 mod observe;
 pub mod popover;
 mod radio;
 mod scrollbar;
+mod select;
 mod slider;
+mod tabs;
+mod tree;
+mod virtual_list;
 
 pub use button::*;
 pub use checkbox::*;
 pub use menu::*;
+pub use number_input::*;
 pub use observe::*;
 pub use radio::*;
 pub use scrollbar::*;
+pub use select::*;
 pub use slider::*;
+pub use tabs::*;
+pub use tree::*;
+pub use virtual_list::*;
 
 use bevy_app::{PluginGroup, PluginGroupBuilder};
 use bevy_ecs::{entity::Entity, event::EntityEvent};
@@ -51,9 +61,14 @@ impl PluginGroup for UiWidgetsPlugins {
             .add(ButtonPlugin)
             .add(CheckboxPlugin)
             .add(MenuPlugin)
+            .add(NumberInputPlugin)
             .add(RadioGroupPlugin)
             .add(ScrollbarPlugin)
+            .add(SelectPlugin)
             .add(SliderPlugin)
+            .add(TabsPlugin)
+            .add(TreeViewPlugin)
+            .add(VirtualListPlugin)
     }
 }
 
@@ -73,3 +88,61 @@ pub struct ValueChange<T> {
     /// The new value.
     pub value: T,
 }
+
+#[cfg(test)]
+mod test_utils {
+    use crate::ValueChange;
+    use bevy_app::{App, Plugin};
+    use bevy_ecs::prelude::*;
+    use bevy_input::{
+        keyboard::{Key, KeyCode, KeyboardInput, NativeKey},
+        ButtonState, InputPlugin,
+    };
+    use bevy_input_focus::{InputDispatchPlugin, InputFocus};
+    use bevy_window::{PrimaryWindow, Window};
+
+    /// The [`ValueChange`] events received by a test app, as source and value.
+    #[derive(Resource)]
+    pub(crate) struct ValueChanges<T>(pub Vec<(Entity, T)>);
+
+    /// Creates an app with `plugin` that dispatches keyboard input to the focused entity and
+    /// records [`ValueChange<T>`] events.
+    pub(crate) fn test_app<T: Clone + Send + Sync + 'static>(plugin: impl Plugin) -> App {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputDispatchPlugin, plugin));
+        record_value_changes::<T>(&mut app);
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        app.update();
+        app
+    }
+
+    /// Records the [`ValueChange<T>`] events received by `app`, for widgets that emit more than
+    /// one kind of value.
+    pub(crate) fn record_value_changes<T: Clone + Send + Sync + 'static>(app: &mut App) {
+        app.insert_resource(ValueChanges::<T>(Vec::new()))
+            .add_observer(
+                |change: On<ValueChange<T>>, mut changes: ResMut<ValueChanges<T>>| {
+                    changes.0.push((change.source, change.value.clone()));
+                },
+            );
+    }
+
+    /// Presses `key_code` while `entity` has focus.
+    pub(crate) fn press_key(app: &mut App, entity: Entity, key_code: KeyCode) {
+        app.world_mut().resource_mut::<InputFocus>().set(entity);
+        app.world_mut().write_message(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+
+    /// Returns the values of the [`ValueChange<T>`] events received since the last call.
+    pub(crate) fn take_value_changes<T: Send + Sync + 'static>(app: &mut App) -> Vec<(Entity, T)> {
+        core::mem::take(&mut app.world_mut().resource_mut::<ValueChanges<T>>().0)
+    }
+}
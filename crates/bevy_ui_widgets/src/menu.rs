@@ -101,7 +101,7 @@ pub struct MenuPopup {
 }
 
 /// Component that defines a menu item.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Default, Clone)]
 #[require(AccessibilityNode(accesskit::Node::new(Role::MenuItem)))]
 pub struct MenuItem;
 
@@ -0,0 +1,260 @@
+use accesskit::Role;
+use bevy_a11y::AccessibilityNode;
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    observer::On,
+    query::Has,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res},
+};
+use bevy_input::keyboard::{KeyCode, KeyboardInput};
+use bevy_input::ButtonState;
+use bevy_input_focus::FocusedInput;
+use bevy_picking::events::{Drag, DragEnd, DragStart, Pointer};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::{InteractionDisabled, UiScale};
+
+use crate::{SliderPrecision, SliderRange, SliderStep, SliderValue, ValueChange};
+
+/// A headless numeric input field, also known as a "drag value". The value can be changed by
+/// dragging horizontally anywhere on the widget, or with the arrow keys while it has focus.
+///
+/// The number input shares its value components with [`Slider`](crate::Slider): the value is
+/// stored in [`SliderValue`], and can be constrained by [`SliderRange`] (which defaults to all
+/// finite values), [`SliderStep`] and [`SliderPrecision`]. Unlike a slider, the range doesn't
+/// need to be bounded, since the distance dragged is not relative to the size of the widget.
+///
+/// Like the slider, the number input does not update its own value: it emits a
+/// [`ValueChange<f32>`] event with the new value, and [`slider_self_update`](crate::slider_self_update)
+/// can be used as an observer to update it automatically.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[require(
+    AccessibilityNode(accesskit::Node::new(Role::SpinButton)),
+    NumberInputDragState,
+    SliderValue,
+    SliderRange = SliderRange::new(f32::MIN, f32::MAX),
+    SliderStep
+)]
+pub struct NumberInput {
+    /// How much the value changes for each logical pixel dragged, in multiples of the
+    /// [`SliderStep`].
+    pub drag_speed: f32,
+}
+
+impl Default for NumberInput {
+    fn default() -> Self {
+        Self { drag_speed: 0.1 }
+    }
+}
+
+/// Component used to manage the state of a number input during dragging.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct NumberInputDragState {
+    /// Whether the number input is currently being dragged.
+    pub dragging: bool,
+
+    /// The value of the number input when dragging started.
+    offset: f32,
+}
+
+fn number_input_on_drag_start(
+    mut drag_start: On<Pointer<DragStart>>,
+    mut q_input: Query<(
+        &NumberInput,
+        &SliderValue,
+        &mut NumberInputDragState,
+        Has<InteractionDisabled>,
+    )>,
+) {
+    if let Ok((_, value, mut drag, disabled)) = q_input.get_mut(drag_start.entity) {
+        drag_start.propagate(false);
+        if !disabled {
+            drag.dragging = true;
+            drag.offset = value.0;
+        }
+    }
+}
+
+fn number_input_on_drag(
+    mut event: On<Pointer<Drag>>,
+    q_input: Query<(
+        &NumberInput,
+        &SliderRange,
+        &SliderStep,
+        Option<&SliderPrecision>,
+        &NumberInputDragState,
+        Has<InteractionDisabled>,
+    )>,
+    mut commands: Commands,
+    ui_scale: Res<UiScale>,
+) {
+    if let Ok((input, range, step, precision, drag, disabled)) = q_input.get(event.entity) {
+        event.propagate(false);
+        if drag.dragging && !disabled {
+            let distance = event.distance.x / ui_scale.0;
+            let new_value = drag.offset + distance * input.drag_speed * step.0;
+            commands.trigger(ValueChange {
+                source: event.entity,
+                value: range.clamp(
+                    precision
+                        .map(|prec| prec.round(new_value))
+                        .unwrap_or(new_value),
+                ),
+            });
+        }
+    }
+}
+
+fn number_input_on_drag_end(
+    mut drag_end: On<Pointer<DragEnd>>,
+    mut q_input: Query<(&NumberInput, &mut NumberInputDragState)>,
+) {
+    if let Ok((_, mut drag)) = q_input.get_mut(drag_end.entity) {
+        drag_end.propagate(false);
+        if drag.dragging {
+            drag.dragging = false;
+        }
+    }
+}
+
+fn number_input_on_key_input(
+    mut focused_input: On<FocusedInput<KeyboardInput>>,
+    q_input: Query<(
+        &NumberInput,
+        &SliderValue,
+        &SliderRange,
+        &SliderStep,
+        Has<InteractionDisabled>,
+    )>,
+    mut commands: Commands,
+) {
+    if let Ok((_, value, range, step, disabled)) = q_input.get(focused_input.focused_entity) {
+        let input_event = &focused_input.input;
+        if !disabled && input_event.state == ButtonState::Pressed {
+            let new_value = match input_event.key_code {
+                KeyCode::ArrowDown | KeyCode::ArrowLeft => range.clamp(value.0 - step.0),
+                KeyCode::ArrowUp | KeyCode::ArrowRight => range.clamp(value.0 + step.0),
+                _ => {
+                    return;
+                }
+            };
+            focused_input.propagate(false);
+            commands.trigger(ValueChange {
+                source: focused_input.focused_entity,
+                value: new_value,
+            });
+        }
+    }
+}
+
+/// Plugin that adds the observers for the [`NumberInput`] widget.
+pub struct NumberInputPlugin;
+
+impl Plugin for NumberInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(number_input_on_drag_start)
+            .add_observer(number_input_on_drag)
+            .add_observer(number_input_on_drag_end)
+            .add_observer(number_input_on_key_input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{press_key, take_value_changes, test_app};
+    use bevy_camera::NormalizedRenderTarget;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::Vec2;
+    use bevy_picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    };
+
+    #[test]
+    fn test_number_input_keyboard_steps() {
+        let mut app = test_app::<f32>(NumberInputPlugin);
+        let input = app
+            .world_mut()
+            .spawn((
+                NumberInput::default(),
+                SliderValue(8.),
+                SliderRange::new(0., 10.),
+                SliderStep(4.),
+            ))
+            .id();
+
+        let press = |app: &mut App, key_code| {
+            press_key(app, input, key_code);
+            take_value_changes::<f32>(app)
+        };
+        // Steps are clamped to the range.
+        assert_eq!(press(&mut app, KeyCode::ArrowUp), vec![(input, 10.)]);
+        assert_eq!(press(&mut app, KeyCode::ArrowDown), vec![(input, 4.)]);
+        app.world_mut().entity_mut(input).insert(SliderValue(2.));
+        assert_eq!(press(&mut app, KeyCode::ArrowLeft), vec![(input, 0.)]);
+        assert_eq!(press(&mut app, KeyCode::ArrowRight), vec![(input, 6.)]);
+        assert_eq!(press(&mut app, KeyCode::KeyA), vec![]);
+
+        app.world_mut()
+            .entity_mut(input)
+            .insert(InteractionDisabled);
+        assert_eq!(press(&mut app, KeyCode::ArrowUp), vec![]);
+    }
+
+    #[test]
+    fn test_number_input_drag() {
+        let mut app = test_app::<f32>(NumberInputPlugin);
+        app.init_resource::<UiScale>();
+        let input = app
+            .world_mut()
+            .spawn((
+                NumberInput { drag_speed: 0.5 },
+                SliderValue(1.),
+                SliderRange::new(-5., 5.),
+                SliderStep(0.1),
+                SliderPrecision(1),
+            ))
+            .id();
+
+        let location = Location {
+            target: NormalizedRenderTarget::None {
+                width: 100,
+                height: 100,
+            },
+            position: Vec2::ZERO,
+        };
+        let hit = HitData::new(Entity::PLACEHOLDER, 0., None, None);
+        app.world_mut().trigger(Pointer::new(
+            PointerId::Mouse,
+            location.clone(),
+            DragStart {
+                button: PointerButton::Primary,
+                hit: hit.clone(),
+            },
+            input,
+        ));
+        let drag = |app: &mut App, distance: f32| {
+            app.world_mut().trigger(Pointer::new(
+                PointerId::Mouse,
+                location.clone(),
+                Drag {
+                    button: PointerButton::Primary,
+                    distance: Vec2::new(distance, 0.),
+                    delta: Vec2::ZERO,
+                },
+                input,
+            ));
+            app.update();
+            take_value_changes::<f32>(app)
+        };
+        // Each pixel changes the value by half a step, rounded to the precision.
+        assert_eq!(drag(&mut app, 11.), vec![(input, 1.6)]);
+        assert_eq!(drag(&mut app, -30.), vec![(input, -0.5)]);
+        // The value is relative to the start of the drag, and clamped to the range.
+        assert_eq!(drag(&mut app, 200.), vec![(input, 5.)]);
+    }
+}
@@ -0,0 +1,244 @@
+//! Standard widget components for select boxes, also known as dropdowns.
+
+use accesskit::Role;
+use bevy_a11y::AccessibilityNode;
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    hierarchy::ChildOf,
+    observer::On,
+    query::{Has, With},
+    reflect::ReflectComponent,
+    system::{Commands, Query},
+};
+use bevy_input::keyboard::{KeyCode, KeyboardInput};
+use bevy_input::ButtonState;
+use bevy_input_focus::FocusedInput;
+use bevy_picking::events::{Pointer, Press};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::InteractionDisabled;
+
+use crate::{Activate, MenuAction, MenuEvent, MenuItem, MenuPopup, ValueChange};
+
+/// Headless widget implementation for a "select" box, which lets the user pick one of a list of
+/// options from a popup menu.
+///
+/// The select works like a [`MenuButton`](crate::MenuButton): pressing it, or pressing `Enter` or
+/// `Space` while it has focus, sends a [`MenuEvent`] with [`MenuAction::Toggle`]. It is the
+/// responsibility of the app to respond to the [`MenuEvent`]s by spawning or despawning the popup:
+/// a [`MenuPopup`] that is a child of the select, typically positioned with a
+/// [`Popover`](crate::popover::Popover), and which contains a [`SelectOption`] for each option.
+///
+/// The selected option is stored as an index in the [`SelectValue`] component. Like the other
+/// widgets, the select does not update it itself; instead, it emits a [`ValueChange<usize>`] event
+/// with the index of the new option when an option is activated, or when the arrow keys, `Home` or
+/// `End` are used while the select has focus.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::ComboBox)), SelectValue)]
+pub struct Select {
+    /// The number of options, used for changing the selection with the keyboard while the popup
+    /// is closed.
+    pub option_count: usize,
+}
+
+/// A component which stores the index of the selected option of a [`Select`].
+#[derive(Component, Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[component(immutable)]
+pub struct SelectValue(pub usize);
+
+/// Component that defines an option in the popup of a [`Select`], containing the index of the
+/// option. When activated, the enclosing select emits a [`ValueChange<usize>`] with this index.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+#[require(MenuItem)]
+pub struct SelectOption(pub usize);
+
+fn select_on_key_input(
+    mut ev: On<FocusedInput<KeyboardInput>>,
+    q_select: Query<(&Select, &SelectValue, Has<InteractionDisabled>)>,
+    mut commands: Commands,
+) {
+    let Ok((select, value, disabled)) = q_select.get(ev.focused_entity) else {
+        return;
+    };
+    let event = &ev.event().input;
+    if disabled || event.state != ButtonState::Pressed {
+        return;
+    }
+    let repeat = event.repeat;
+    let last = select.option_count.saturating_sub(1);
+    let new_value = match event.key_code {
+        KeyCode::Enter | KeyCode::Space => {
+            ev.propagate(false);
+            if !repeat {
+                commands.trigger(MenuEvent {
+                    source: ev.focused_entity,
+                    action: MenuAction::Toggle,
+                });
+            }
+            return;
+        }
+        KeyCode::ArrowUp | KeyCode::ArrowLeft => value.0.saturating_sub(1),
+        KeyCode::ArrowDown | KeyCode::ArrowRight => (value.0 + 1).min(last),
+        KeyCode::Home => 0,
+        KeyCode::End => last,
+        _ => return,
+    };
+    ev.propagate(false);
+    if select.option_count > 0 && new_value != value.0 {
+        commands.trigger(ValueChange {
+            source: ev.focused_entity,
+            value: new_value,
+        });
+    }
+}
+
+fn select_on_pointer_press(
+    mut press: On<Pointer<Press>>,
+    q_select: Query<Has<InteractionDisabled>, With<Select>>,
+    q_popup: Query<(), With<MenuPopup>>,
+    q_parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let Ok(disabled) = q_select.get(press.entity) else {
+        return;
+    };
+    // Ignore presses that bubbled up from the popup.
+    let target = press.original_event_target();
+    if q_popup.contains(target)
+        || q_parents
+            .iter_ancestors(target)
+            .take_while(|ancestor| *ancestor != press.entity)
+            .any(|ancestor| q_popup.contains(ancestor))
+    {
+        return;
+    }
+    press.propagate(false);
+    if !disabled {
+        commands.trigger(MenuEvent {
+            source: press.entity,
+            action: MenuAction::Toggle,
+        });
+    }
+}
+
+fn select_option_on_activate(
+    activate: On<Activate>,
+    q_option: Query<&SelectOption>,
+    q_select: Query<(), With<Select>>,
+    q_parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let Ok(option) = q_option.get(activate.entity) else {
+        return;
+    };
+    if let Some(select) = q_parents
+        .iter_ancestors(activate.entity)
+        .find(|ancestor| q_select.contains(*ancestor))
+    {
+        commands.trigger(ValueChange {
+            source: select,
+            value: option.0,
+        });
+    }
+}
+
+/// Observer function which updates the select value in response to a [`ValueChange`] event.
+/// This can be used to make the select automatically update its own state, as opposed to
+/// managing the select state externally.
+pub fn select_self_update(value_change: On<ValueChange<usize>>, mut commands: Commands) {
+    commands
+        .entity(value_change.source)
+        .insert(SelectValue(value_change.value));
+}
+
+/// Plugin that adds the observers for the [`Select`] widget.
+pub struct SelectPlugin;
+
+impl Plugin for SelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(select_on_key_input)
+            .add_observer(select_on_pointer_press)
+            .add_observer(select_option_on_activate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{press_key, take_value_changes, test_app};
+    use bevy_ecs::{entity::Entity, resource::Resource, system::ResMut};
+
+    #[derive(Resource, Default)]
+    struct Toggles(usize);
+
+    #[test]
+    fn test_select_keyboard_navigation() {
+        let mut app = test_app::<usize>(SelectPlugin);
+        app.init_resource::<Toggles>().add_observer(
+            |menu: On<MenuEvent>, mut toggles: ResMut<Toggles>| {
+                if matches!(menu.action, MenuAction::Toggle) {
+                    toggles.0 += 1;
+                }
+            },
+        );
+        let select = app
+            .world_mut()
+            .spawn((Select { option_count: 3 }, SelectValue(1)))
+            .id();
+
+        let press = |app: &mut App, key_code| {
+            press_key(app, select, key_code);
+            take_value_changes::<usize>(app)
+        };
+        assert_eq!(press(&mut app, KeyCode::ArrowDown), vec![(select, 2)]);
+        assert_eq!(press(&mut app, KeyCode::ArrowUp), vec![(select, 0)]);
+        assert_eq!(press(&mut app, KeyCode::End), vec![(select, 2)]);
+        assert_eq!(press(&mut app, KeyCode::Home), vec![(select, 0)]);
+
+        // The value is clamped to the options, and doesn't wrap around.
+        app.world_mut().entity_mut(select).insert(SelectValue(2));
+        assert_eq!(press(&mut app, KeyCode::ArrowDown), vec![]);
+        app.world_mut().entity_mut(select).insert(SelectValue(0));
+        assert_eq!(press(&mut app, KeyCode::ArrowUp), vec![]);
+
+        assert_eq!(press(&mut app, KeyCode::Enter), vec![]);
+        assert_eq!(app.world().resource::<Toggles>().0, 1);
+
+        app.world_mut()
+            .entity_mut(select)
+            .insert(InteractionDisabled);
+        assert_eq!(press(&mut app, KeyCode::ArrowDown), vec![]);
+        assert_eq!(press(&mut app, KeyCode::Space), vec![]);
+        assert_eq!(app.world().resource::<Toggles>().0, 1);
+    }
+
+    #[test]
+    fn test_select_option_activation() {
+        let mut app = test_app::<usize>(SelectPlugin);
+        app.add_observer(select_self_update);
+        let select = app
+            .world_mut()
+            .spawn((Select { option_count: 2 }, SelectValue(0)))
+            .id();
+        let popup = app
+            .world_mut()
+            .spawn((MenuPopup::default(), ChildOf(select)))
+            .id();
+        let option = app
+            .world_mut()
+            .spawn((SelectOption(1), ChildOf(popup)))
+            .id();
+        let unrelated: Entity = app.world_mut().spawn(SelectOption(0)).id();
+
+        app.world_mut().trigger(Activate { entity: option });
+        app.world_mut().trigger(Activate { entity: unrelated });
+        app.update();
+        assert_eq!(take_value_changes::<usize>(&mut app), vec![(select, 1)]);
+        assert_eq!(
+            app.world().get::<SelectValue>(select),
+            Some(&SelectValue(1))
+        );
+    }
+}
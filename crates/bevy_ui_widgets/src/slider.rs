@@ -207,7 +207,7 @@ impl Default for SliderStep {
 pub struct SliderPrecision(pub i32);
 
 impl SliderPrecision {
-    fn round(&self, value: f32) -> f32 {
+    pub(crate) fn round(&self, value: f32) -> f32 {
         let factor = ops::powf(10.0_f32, self.0 as f32);
         (value * factor).round() / factor
     }
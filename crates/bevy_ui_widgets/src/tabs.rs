@@ -0,0 +1,260 @@
+//! Standard widget components for tab bars and tab panels.
+
+use accesskit::Role;
+use bevy_a11y::AccessibilityNode;
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    observer::On,
+    query::{Has, With},
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query},
+};
+use bevy_input::keyboard::{KeyCode, KeyboardInput};
+use bevy_input::ButtonState;
+use bevy_input_focus::FocusedInput;
+use bevy_picking::events::{Click, Pointer};
+use bevy_reflect::Reflect;
+use bevy_ui::{Checkable, Checked, Display, InteractionDisabled, Node, UiSystems};
+
+use crate::ValueChange;
+
+/// Headless widget implementation for a tab bar. This component groups multiple [`Tab`]
+/// components together, and behaves much like a [`RadioGroup`](crate::RadioGroup): the active
+/// tab is the one that is [`Checked`], and the output of the tab bar is a [`ValueChange<Entity>`]
+/// event whose payload is the entity id of the newly selected tab. This event is emitted whenever
+/// a tab is clicked, or when the left and right arrow keys, `Home` or `End` are used while the tab
+/// bar is focused.
+///
+/// The tab bar doesn't set the [`Checked`] states directly, that is presumed to happen by the app
+/// or via some external data-binding scheme.
+#[derive(Component, Debug, Default)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::TabList)))]
+pub struct TabBar;
+
+/// Headless widget implementation for a tab within a [`TabBar`]. The active tab should be marked
+/// with [`Checked`].
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::Tab)), Checkable)]
+pub struct Tab;
+
+/// Component for the content of a tab. The panel is displayed while its [`Tab`] is [`Checked`],
+/// and its [`Node::display`] is set to [`Display::None`] otherwise.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::TabPanel)))]
+pub struct TabPanel {
+    /// The tab that shows this panel.
+    pub tab: Entity,
+}
+
+/// Stores the display mode of a [`TabPanel`] while it is hidden.
+#[derive(Component, Debug, Clone, Copy)]
+struct HiddenTabPanel(Display);
+
+fn tab_bar_on_key_input(
+    mut ev: On<FocusedInput<KeyboardInput>>,
+    q_bar: Query<(), With<TabBar>>,
+    q_tab: Query<(Has<Checked>, Has<InteractionDisabled>), With<Tab>>,
+    q_children: Query<&Children>,
+    mut commands: Commands,
+) {
+    if !q_bar.contains(ev.focused_entity) {
+        return;
+    }
+    let event = &ev.event().input;
+    if event.state != ButtonState::Pressed
+        || event.repeat
+        || !matches!(
+            event.key_code,
+            KeyCode::ArrowLeft | KeyCode::ArrowRight | KeyCode::Home | KeyCode::End
+        )
+    {
+        return;
+    }
+    let key_code = event.key_code;
+    ev.propagate(false);
+
+    // Find all tab descendants that are not disabled
+    let tabs = q_children
+        .iter_descendants(ev.focused_entity)
+        .filter_map(|child_id| match q_tab.get(child_id) {
+            Ok((checked, false)) => Some((child_id, checked)),
+            Ok((_, true)) | Err(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if tabs.is_empty() {
+        return;
+    }
+    let current_index = tabs.iter().position(|(_, checked)| *checked);
+    let next_index = match (key_code, current_index) {
+        (KeyCode::ArrowLeft, Some(0) | None) => tabs.len() - 1,
+        (KeyCode::ArrowLeft, Some(index)) => index - 1,
+        (KeyCode::ArrowRight, Some(index)) if index + 1 < tabs.len() => index + 1,
+        (KeyCode::ArrowRight, _) | (KeyCode::Home, _) => 0,
+        _ => tabs.len() - 1,
+    };
+    if current_index != Some(next_index) {
+        commands.trigger(ValueChange {
+            source: ev.focused_entity,
+            value: tabs[next_index].0,
+        });
+    }
+}
+
+fn tab_on_click(
+    mut ev: On<Pointer<Click>>,
+    q_tab: Query<(Has<InteractionDisabled>, Has<Checked>), With<Tab>>,
+    q_bar: Query<(), With<TabBar>>,
+    q_parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let Ok((disabled, checked)) = q_tab.get(ev.entity) else {
+        return;
+    };
+    ev.propagate(false);
+    if disabled || checked {
+        return;
+    }
+    if let Some(bar) = q_parents
+        .iter_ancestors(ev.entity)
+        .find(|ancestor| q_bar.contains(*ancestor))
+    {
+        commands.trigger(ValueChange {
+            source: bar,
+            value: ev.entity,
+        });
+    }
+}
+
+fn update_tab_panels(
+    mut q_panels: Query<(Entity, &TabPanel, &mut Node, Option<&HiddenTabPanel>)>,
+    q_tab: Query<Has<Checked>, With<Tab>>,
+    mut commands: Commands,
+) {
+    for (entity, panel, mut node, hidden) in q_panels.iter_mut() {
+        let active = q_tab.get(panel.tab).unwrap_or(false);
+        match (active, hidden) {
+            (true, Some(hidden)) => {
+                node.display = hidden.0;
+                commands.entity(entity).remove::<HiddenTabPanel>();
+            }
+            (false, None) => {
+                commands.entity(entity).insert(HiddenTabPanel(node.display));
+                node.display = Display::None;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Plugin that adds the observers and systems for the [`TabBar`] widget.
+pub struct TabsPlugin;
+
+impl Plugin for TabsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(tab_bar_on_key_input)
+            .add_observer(tab_on_click)
+            .add_systems(PostUpdate, update_tab_panels.before(UiSystems::Layout));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{press_key, take_value_changes, test_app};
+    use bevy_camera::NormalizedRenderTarget;
+    use bevy_math::Vec2;
+    use bevy_picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    };
+    use core::time::Duration;
+
+    fn spawn_tabs(app: &mut App) -> (Entity, [Entity; 3]) {
+        let bar = app.world_mut().spawn(TabBar).id();
+        let tabs = [(); 3].map(|_| app.world_mut().spawn((Tab, ChildOf(bar))).id());
+        (bar, tabs)
+    }
+
+    #[test]
+    fn test_tab_bar_keyboard_navigation() {
+        let mut app = test_app::<Entity>(TabsPlugin);
+        let (bar, [first, second, third]) = spawn_tabs(&mut app);
+        app.world_mut().entity_mut(second).insert(Checked);
+
+        let press = |app: &mut App, key_code| {
+            press_key(app, bar, key_code);
+            take_value_changes::<Entity>(app)
+        };
+        assert_eq!(press(&mut app, KeyCode::ArrowRight), vec![(bar, third)]);
+        assert_eq!(press(&mut app, KeyCode::ArrowLeft), vec![(bar, first)]);
+        assert_eq!(press(&mut app, KeyCode::Home), vec![(bar, first)]);
+        assert_eq!(press(&mut app, KeyCode::End), vec![(bar, third)]);
+
+        // The arrow keys wrap around, skipping disabled tabs.
+        app.world_mut().entity_mut(second).remove::<Checked>();
+        app.world_mut().entity_mut(third).insert(Checked);
+        assert_eq!(press(&mut app, KeyCode::ArrowRight), vec![(bar, first)]);
+        app.world_mut()
+            .entity_mut(first)
+            .insert(InteractionDisabled);
+        assert_eq!(press(&mut app, KeyCode::ArrowRight), vec![(bar, second)]);
+        assert_eq!(press(&mut app, KeyCode::End), vec![]);
+    }
+
+    #[test]
+    fn test_tab_click_and_panels() {
+        let mut app = test_app::<Entity>(TabsPlugin);
+        let (bar, [first, second, _]) = spawn_tabs(&mut app);
+        app.world_mut().entity_mut(first).insert(Checked);
+        let panel = |app: &mut App, tab| {
+            app.world_mut()
+                .spawn((
+                    Node {
+                        display: Display::Grid,
+                        ..Default::default()
+                    },
+                    TabPanel { tab },
+                ))
+                .id()
+        };
+        let first_panel = panel(&mut app, first);
+        let second_panel = panel(&mut app, second);
+        app.update();
+        let display = |app: &App, panel| app.world().get::<Node>(panel).unwrap().display;
+        assert_eq!(display(&app, first_panel), Display::Grid);
+        assert_eq!(display(&app, second_panel), Display::None);
+
+        let click = |app: &mut App, tab| {
+            let location = Location {
+                target: NormalizedRenderTarget::None {
+                    width: 100,
+                    height: 100,
+                },
+                position: Vec2::ZERO,
+            };
+            let event = Click {
+                button: PointerButton::Primary,
+                hit: HitData::new(Entity::PLACEHOLDER, 0., None, None),
+                duration: Duration::ZERO,
+            };
+            app.world_mut()
+                .trigger(Pointer::new(PointerId::Mouse, location, event, tab));
+            app.update();
+            take_value_changes::<Entity>(app)
+        };
+        assert_eq!(click(&mut app, first), vec![]);
+        assert_eq!(click(&mut app, second), vec![(bar, second)]);
+
+        app.world_mut().entity_mut(first).remove::<Checked>();
+        app.world_mut().entity_mut(second).insert(Checked);
+        app.update();
+        assert_eq!(display(&app, first_panel), Display::None);
+        assert_eq!(display(&app, second_panel), Display::Grid);
+    }
+}
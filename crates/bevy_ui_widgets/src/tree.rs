@@ -0,0 +1,455 @@
+//! Standard widget components for tree views.
+
+use accesskit::Role;
+use bevy_a11y::AccessibilityNode;
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    observer::On,
+    query::{Has, With},
+    reflect::ReflectComponent,
+    system::{Commands, Query},
+};
+use bevy_input::keyboard::{KeyCode, KeyboardInput};
+use bevy_input::ButtonState;
+use bevy_input_focus::FocusedInput;
+use bevy_picking::events::{Click, Pointer};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::{Checked, InteractionDisabled};
+
+use crate::{Activate, ValueChange};
+
+/// Headless widget implementation for a collapsible tree view, such as an outliner or a file
+/// browser. The tree contains [`TreeItem`]s, which may be nested to any depth: the child items of
+/// an item are the [`TreeItem`]s among its descendants that are not nested inside another item.
+///
+/// Like the other widgets, the tree view uses external state management:
+/// - The selected item is the one marked with [`Checked`]. The tree emits a
+///   [`ValueChange<Entity>`] event whose payload is the entity id of the item to select when an
+///   item is clicked, or when the arrow keys, `Home` or `End` are used while the tree is focused.
+/// - Items whose children are shown are marked with [`Expanded`]. The tree emits a
+///   [`ValueChange<bool>`] event on an item when it should be expanded or collapsed, either by
+///   clicking its [`TreeItemToggle`], or with the right and left arrow keys.
+/// - Pressing `Enter` while the tree is focused emits [`Activate`] on the selected item.
+///
+/// The tree doesn't hide the children of collapsed items; that is presumed to happen by the app,
+/// for instance by setting their display mode to [`Display::None`](bevy_ui::Display::None). Keyboard
+/// navigation skips the children of collapsed items regardless.
+#[derive(Component, Debug, Default)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::Tree)))]
+pub struct TreeView;
+
+/// Headless widget implementation for an item within a [`TreeView`].
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::TreeItem)))]
+pub struct TreeItem;
+
+/// Component that indicates that a [`TreeItem`] is expanded, showing its child items.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct Expanded;
+
+/// Marker component for the descendant of a [`TreeItem`] that expands or collapses it when
+/// clicked, such as a disclosure triangle.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct TreeItemToggle;
+
+/// Appends the items below `entity` that are visible (not inside a collapsed item) to `items`, in
+/// depth-first order.
+fn collect_visible_items(
+    entity: Entity,
+    q_children: &Query<&Children>,
+    q_item: &Query<(Has<Expanded>, Has<InteractionDisabled>), With<TreeItem>>,
+    items: &mut Vec<Entity>,
+) {
+    let Ok(children) = q_children.get(entity) else {
+        return;
+    };
+    for &child in children {
+        match q_item.get(child) {
+            Ok((expanded, disabled)) => {
+                if !disabled {
+                    items.push(child);
+                }
+                if expanded {
+                    collect_visible_items(child, q_children, q_item, items);
+                }
+            }
+            Err(_) => collect_visible_items(child, q_children, q_item, items),
+        }
+    }
+}
+
+fn tree_on_key_input(
+    mut ev: On<FocusedInput<KeyboardInput>>,
+    q_tree: Query<(), With<TreeView>>,
+    q_item: Query<(Has<Expanded>, Has<InteractionDisabled>), With<TreeItem>>,
+    q_checked: Query<(), (With<TreeItem>, With<Checked>)>,
+    q_children: Query<&Children>,
+    q_parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let tree = ev.focused_entity;
+    if !q_tree.contains(tree) {
+        return;
+    }
+    let event = &ev.event().input;
+    if event.state != ButtonState::Pressed {
+        return;
+    }
+    let key_code = event.key_code;
+    let repeat = event.repeat;
+
+    let mut items = Vec::new();
+    collect_visible_items(tree, &q_children, &q_item, &mut items);
+    let current = items.iter().position(|item| q_checked.contains(*item));
+    let select = |commands: &mut Commands, index: usize| {
+        if current != Some(index) {
+            commands.trigger(ValueChange {
+                source: tree,
+                value: items[index],
+            });
+        }
+    };
+
+    match key_code {
+        KeyCode::ArrowUp => {
+            ev.propagate(false);
+            if !items.is_empty() {
+                select(
+                    &mut commands,
+                    current.map_or(0, |index| index.saturating_sub(1)),
+                );
+            }
+        }
+        KeyCode::ArrowDown => {
+            ev.propagate(false);
+            if !items.is_empty() {
+                select(
+                    &mut commands,
+                    current.map_or(0, |index| (index + 1).min(items.len() - 1)),
+                );
+            }
+        }
+        KeyCode::Home => {
+            ev.propagate(false);
+            if !items.is_empty() {
+                select(&mut commands, 0);
+            }
+        }
+        KeyCode::End => {
+            ev.propagate(false);
+            if !items.is_empty() {
+                select(&mut commands, items.len() - 1);
+            }
+        }
+        KeyCode::ArrowRight => {
+            ev.propagate(false);
+            let Some(index) = current else {
+                return;
+            };
+            let item = items[index];
+            let has_children = q_children
+                .iter_descendants(item)
+                .any(|descendant| q_item.contains(descendant));
+            match q_item.get(item) {
+                Ok((false, _)) if has_children => {
+                    commands.trigger(ValueChange {
+                        source: item,
+                        value: true,
+                    });
+                }
+                // Move to the first child.
+                Ok((true, _))
+                    if items.get(index + 1).is_some_and(|next| {
+                        q_parents
+                            .iter_ancestors(*next)
+                            .any(|ancestor| ancestor == item)
+                    }) =>
+                {
+                    select(&mut commands, index + 1);
+                }
+                _ => {}
+            }
+        }
+        KeyCode::ArrowLeft => {
+            ev.propagate(false);
+            let Some(index) = current else {
+                return;
+            };
+            let item = items[index];
+            if let Ok((true, _)) = q_item.get(item) {
+                commands.trigger(ValueChange {
+                    source: item,
+                    value: false,
+                });
+            } else if let Some(parent) = q_parents
+                .iter_ancestors(item)
+                .take_while(|ancestor| *ancestor != tree)
+                .find(|ancestor| q_item.contains(*ancestor))
+                && let Some(parent_index) = items.iter().position(|item| *item == parent)
+            {
+                select(&mut commands, parent_index);
+            }
+        }
+        KeyCode::Enter => {
+            ev.propagate(false);
+            if let Some(index) = current
+                && !repeat
+            {
+                commands.trigger(Activate {
+                    entity: items[index],
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+fn tree_item_on_click(
+    mut ev: On<Pointer<Click>>,
+    q_item: Query<(Has<Expanded>, Has<InteractionDisabled>, Has<Checked>), With<TreeItem>>,
+    q_toggle: Query<(), With<TreeItemToggle>>,
+    q_tree: Query<(), With<TreeView>>,
+    q_parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let item = ev.entity;
+    let Ok((expanded, disabled, checked)) = q_item.get(item) else {
+        return;
+    };
+    ev.propagate(false);
+    if disabled {
+        return;
+    }
+
+    let target = ev.original_event_target();
+    let on_toggle = q_toggle.contains(target)
+        || q_parents
+            .iter_ancestors(target)
+            .take_while(|ancestor| *ancestor != item)
+            .any(|ancestor| q_toggle.contains(ancestor));
+    if on_toggle {
+        commands.trigger(ValueChange {
+            source: item,
+            value: !expanded,
+        });
+    } else if !checked
+        && let Some(tree) = q_parents
+            .iter_ancestors(item)
+            .find(|ancestor| q_tree.contains(*ancestor))
+    {
+        commands.trigger(ValueChange {
+            source: tree,
+            value: item,
+        });
+    }
+}
+
+/// Plugin that adds the observers for the [`TreeView`] widget.
+pub struct TreeViewPlugin;
+
+impl Plugin for TreeViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(tree_on_key_input)
+            .add_observer(tree_item_on_click);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{press_key, record_value_changes, take_value_changes, test_app};
+    use bevy_camera::NormalizedRenderTarget;
+    use bevy_ecs::{
+        children,
+        resource::Resource,
+        system::{ResMut, RunSystemOnce},
+        world::World,
+    };
+    use bevy_math::Vec2;
+    use bevy_picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    };
+    use core::time::Duration;
+
+    /// The items activated in a test app.
+    #[derive(Resource, Default)]
+    struct Activated(Vec<Entity>);
+
+    /// Creates an app with the tree view observers that records the events they emit, and spawns
+    /// a tree with an expanded item with two children, followed by a collapsed item with one
+    /// child.
+    fn tree_app() -> (App, Entity, [Entity; 5]) {
+        let mut app = test_app::<Entity>(TreeViewPlugin);
+        record_value_changes::<bool>(&mut app);
+        app.init_resource::<Activated>().add_observer(
+            |activate: On<Activate>, mut activated: ResMut<Activated>| {
+                activated.0.push(activate.entity);
+            },
+        );
+        let tree = app
+            .world_mut()
+            .spawn((
+                TreeView,
+                children![
+                    (TreeItem, Expanded, children![TreeItem, TreeItem]),
+                    (TreeItem, children![TreeItem]),
+                ],
+            ))
+            .id();
+        let children = |app: &App, entity| -> Vec<Entity> {
+            app.world().get::<Children>(entity).unwrap().to_vec()
+        };
+        let top = children(&app, tree);
+        let expanded = children(&app, top[0]);
+        let collapsed = children(&app, top[1]);
+        (
+            app,
+            tree,
+            [top[0], expanded[0], expanded[1], top[1], collapsed[0]],
+        )
+    }
+
+    fn select(app: &mut App, item: Entity) {
+        let world = app.world_mut();
+        let mut q_checked = world.query_filtered::<Entity, With<Checked>>();
+        let checked: Vec<Entity> = q_checked.iter(world).collect();
+        for entity in checked {
+            world.entity_mut(entity).remove::<Checked>();
+        }
+        world.entity_mut(item).insert(Checked);
+    }
+
+    #[test]
+    fn test_visible_items_skip_collapsed() {
+        let mut world = World::new();
+        let tree = world
+            .spawn((
+                TreeView,
+                children![
+                    (TreeItem, Expanded, children![TreeItem, TreeItem]),
+                    (TreeItem, children![TreeItem]),
+                    (TreeItem, InteractionDisabled),
+                ],
+            ))
+            .id();
+        let items =
+            world
+                .run_system_once(
+                    move |q_children: Query<&Children>,
+                          q_item: Query<
+                        (Has<Expanded>, Has<InteractionDisabled>),
+                        With<TreeItem>,
+                    >| {
+                        let mut items = Vec::new();
+                        collect_visible_items(tree, &q_children, &q_item, &mut items);
+                        items
+                    },
+                )
+                .unwrap();
+
+        let top: Vec<Entity> = world.entity(tree).get::<Children>().unwrap().to_vec();
+        let nested: Vec<Entity> = world.entity(top[0]).get::<Children>().unwrap().to_vec();
+        assert_eq!(items, vec![top[0], nested[0], nested[1], top[1]]);
+    }
+
+    #[test]
+    fn test_tree_keyboard_selection() {
+        let (mut app, tree, [first, first_a, first_b, second, _]) = tree_app();
+        select(&mut app, first_a);
+
+        let press = |app: &mut App, key_code| {
+            press_key(app, tree, key_code);
+            take_value_changes::<Entity>(app)
+        };
+        assert_eq!(press(&mut app, KeyCode::ArrowUp), vec![(tree, first)]);
+        assert_eq!(press(&mut app, KeyCode::ArrowDown), vec![(tree, first_b)]);
+        assert_eq!(press(&mut app, KeyCode::Home), vec![(tree, first)]);
+        // The child of the collapsed item is skipped.
+        assert_eq!(press(&mut app, KeyCode::End), vec![(tree, second)]);
+
+        select(&mut app, second);
+        assert_eq!(press(&mut app, KeyCode::ArrowDown), vec![]);
+        assert_eq!(press(&mut app, KeyCode::ArrowUp), vec![(tree, first_b)]);
+    }
+
+    #[test]
+    fn test_tree_keyboard_expansion() {
+        let (mut app, tree, [first, first_a, _, second, _]) = tree_app();
+
+        // Right expands a collapsed item, and moves to the first child of an expanded one.
+        select(&mut app, second);
+        press_key(&mut app, tree, KeyCode::ArrowRight);
+        assert_eq!(take_value_changes::<bool>(&mut app), vec![(second, true)]);
+        assert_eq!(take_value_changes::<Entity>(&mut app), vec![]);
+        select(&mut app, first);
+        press_key(&mut app, tree, KeyCode::ArrowRight);
+        assert_eq!(take_value_changes::<bool>(&mut app), vec![]);
+        assert_eq!(
+            take_value_changes::<Entity>(&mut app),
+            vec![(tree, first_a)]
+        );
+
+        // Left collapses an expanded item, and moves to the parent of a collapsed one.
+        press_key(&mut app, tree, KeyCode::ArrowLeft);
+        assert_eq!(take_value_changes::<bool>(&mut app), vec![(first, false)]);
+        assert_eq!(take_value_changes::<Entity>(&mut app), vec![]);
+        select(&mut app, first_a);
+        press_key(&mut app, tree, KeyCode::ArrowLeft);
+        assert_eq!(take_value_changes::<bool>(&mut app), vec![]);
+        assert_eq!(take_value_changes::<Entity>(&mut app), vec![(tree, first)]);
+    }
+
+    #[test]
+    fn test_tree_enter_activates_selected_item() {
+        let (mut app, tree, [_, first_a, ..]) = tree_app();
+        press_key(&mut app, tree, KeyCode::Enter);
+        assert!(app.world().resource::<Activated>().0.is_empty());
+
+        select(&mut app, first_a);
+        press_key(&mut app, tree, KeyCode::Enter);
+        assert_eq!(app.world().resource::<Activated>().0, vec![first_a]);
+    }
+
+    #[test]
+    fn test_tree_item_click() {
+        let (mut app, tree, [first, .., second, _]) = tree_app();
+        select(&mut app, first);
+        let toggle = app
+            .world_mut()
+            .spawn((TreeItemToggle, ChildOf(second)))
+            .id();
+
+        let click = |app: &mut App, target| {
+            let location = Location {
+                target: NormalizedRenderTarget::None {
+                    width: 100,
+                    height: 100,
+                },
+                position: Vec2::ZERO,
+            };
+            let event = Click {
+                button: PointerButton::Primary,
+                hit: HitData::new(Entity::PLACEHOLDER, 0., None, None),
+                duration: Duration::ZERO,
+            };
+            app.world_mut()
+                .trigger(Pointer::new(PointerId::Mouse, location, event, target));
+            app.update();
+            (
+                take_value_changes::<Entity>(app),
+                take_value_changes::<bool>(app),
+            )
+        };
+        assert_eq!(click(&mut app, first), (vec![], vec![]));
+        assert_eq!(click(&mut app, second), (vec![(tree, second)], vec![]));
+        // Clicking the toggle expands the item instead of selecting it.
+        assert_eq!(click(&mut app, toggle), (vec![], vec![(second, true)]));
+    }
+}
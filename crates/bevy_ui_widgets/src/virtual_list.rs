@@ -0,0 +1,672 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    hierarchy::{ChildOf, Children},
+    observer::On,
+    query::{With, Without},
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query, Res},
+};
+use bevy_input::keyboard::{KeyCode, KeyboardInput};
+use bevy_input::ButtonState;
+use bevy_input_focus::{FocusedInput, InputFocus};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_ui::{ComputedNode, Node, PositionType, ScrollPosition, UiSystems, Val};
+
+/// A headless virtualized list, which displays a large number of items while only keeping
+/// entities for the items that are visible.
+///
+/// The entity with this component is the scrolling container: give it a [`Node`] with a fixed
+/// size and vertical [`Overflow::scroll_y`](bevy_ui::Overflow::scroll_y). The list spawns a
+/// content child that is as tall as all of the items together, so that [`Scrollbar`](crate::Scrollbar)s
+/// targeting the list work as usual. Within it, it spawns an entity with a [`VirtualListItem`] for
+/// each item that is visible (plus [`overscan`](Self::overscan) rows on either side), and
+/// reassigns these entities to other items as the [`ScrollPosition`] changes, rather than
+/// despawning them.
+///
+/// Whenever an item entity is assigned to an item, a [`VirtualListItemChanged`] event is triggered
+/// on it. The app is responsible for filling the entity with the item's content in response,
+/// replacing the content of the item it previously showed.
+///
+/// Items are laid out in [`columns`](Self::columns) of equal width, and each row of items is as
+/// tall as its tallest item. Rows that haven't been displayed yet are assumed to be
+/// [`estimated_row_height`](Self::estimated_row_height) tall, and are measured once they have
+/// been laid out. The scroll position is adjusted when rows above the visible area turn out to
+/// have a different height than estimated, so that the visible items don't jump.
+///
+/// For keyboard and tab navigation:
+/// - Item entities are kept in the same order as the items, so tab navigation between focusable
+///   items follows the list order.
+/// - The item containing the [`InputFocus`] is never reassigned, and is scrolled into view when
+///   it receives focus.
+/// - When the list or one of its items has focus, the arrow keys scroll to the previous or next
+///   row, page up and page down scroll by the height of the list, and home and end scroll to
+///   either end.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[require(VirtualListState)]
+pub struct VirtualList {
+    /// The number of items in the list.
+    pub item_count: usize,
+    /// The number of items in each row. Must be at least 1.
+    pub columns: usize,
+    /// The height assumed for rows that haven't been measured yet, in logical pixels.
+    pub estimated_row_height: f32,
+    /// The number of rows to keep entities for above and below the visible rows.
+    pub overscan: usize,
+}
+
+impl VirtualList {
+    /// Creates a list with a single column of `item_count` items, and two rows of overscan.
+    pub fn new(item_count: usize, estimated_row_height: f32) -> Self {
+        Self {
+            item_count,
+            columns: 1,
+            estimated_row_height,
+            overscan: 2,
+        }
+    }
+
+    /// Lays out the items in a grid with `columns` items per row.
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the number of rows to keep entities for above and below the visible rows.
+    pub fn with_overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    fn row_count(&self) -> usize {
+        self.item_count.div_ceil(self.columns.max(1))
+    }
+}
+
+/// Component used to manage the state of a [`VirtualList`]. This is inserted automatically.
+#[derive(Component, Debug, Default)]
+pub struct VirtualListState {
+    /// The child that contains the item entities.
+    content: Option<Entity>,
+    /// The measured height of each item, if it has been laid out.
+    item_heights: Vec<Option<f32>>,
+    /// The height of each row.
+    rows: RowHeights,
+    /// The item count, column count and estimated row height `rows` was built for, or `None` if
+    /// it must be rebuilt.
+    layout: Option<(usize, usize, f32)>,
+    /// The first row that was visible in the last update.
+    first_visible_row: usize,
+}
+
+impl VirtualListState {
+    /// Returns the offset of the top of `row` from the top of the list, in logical pixels.
+    ///
+    /// `row` may be the row count, in which case this is the height of the whole list.
+    pub fn row_offset(&self, row: usize) -> Option<f32> {
+        (row <= self.rows.len()).then(|| self.rows.offset(row))
+    }
+
+    /// Returns the total height of the list's content, in logical pixels.
+    pub fn content_height(&self) -> f32 {
+        self.rows.offset(self.rows.len())
+    }
+
+    /// Returns the row at the offset `y` from the top of the list, or the row count if `y` is
+    /// below the last row.
+    pub fn row_at(&self, y: f32) -> usize {
+        self.rows.count_up_to(y, true)
+    }
+
+    /// Forgets the measured heights of all items, for instance after their contents changed.
+    /// Items are measured again when they are next displayed.
+    pub fn clear_measurements(&mut self) {
+        self.item_heights.fill(None);
+        self.layout = None;
+    }
+
+    /// Returns the height of `row`: the height of its tallest measured item, or the estimate if
+    /// none of its items have been measured.
+    fn measured_row_height(&self, row: usize, columns: usize, estimate: f32) -> f32 {
+        let end = ((row + 1) * columns).min(self.item_heights.len());
+        self.item_heights[(row * columns).min(end)..end]
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(f32::max)
+            .unwrap_or(estimate)
+    }
+}
+
+/// The heights of the rows of a [`VirtualList`], stored in a Fenwick tree so that the offset of a
+/// row, and the row at an offset, are found without summing up all of the rows above it.
+#[derive(Debug, Default)]
+struct RowHeights {
+    heights: Vec<f32>,
+    /// Each node `i` (1-based) holds the sum of the heights of the `i & -i` rows ending at row `i`.
+    tree: Vec<f32>,
+}
+
+impl RowHeights {
+    fn from_heights(heights: Vec<f32>) -> Self {
+        let mut tree = heights.clone();
+        for i in 1..=tree.len() {
+            let parent = i + (i & i.wrapping_neg());
+            if parent <= tree.len() {
+                tree[parent - 1] += tree[i - 1];
+            }
+        }
+        Self { heights, tree }
+    }
+
+    fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    fn set(&mut self, row: usize, height: f32) {
+        let delta = height - self.heights[row];
+        if delta == 0. {
+            return;
+        }
+        self.heights[row] = height;
+        let mut i = row + 1;
+        while i <= self.tree.len() {
+            self.tree[i - 1] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Returns the sum of the heights of the rows above `row`.
+    fn offset(&self, row: usize) -> f32 {
+        let mut sum = 0.;
+        let mut i = row.min(self.tree.len());
+        while i > 0 {
+            sum += self.tree[i - 1];
+            i &= i - 1;
+        }
+        sum
+    }
+
+    /// Returns the number of rows whose bottom edge is above `y`, or at `y` if `inclusive`.
+    fn count_up_to(&self, y: f32, inclusive: bool) -> usize {
+        let mut count = 0;
+        let mut remaining = y;
+        let mut step = self.tree.len().checked_ilog2().map_or(0, |log| 1 << log);
+        while step > 0 {
+            if count + step <= self.tree.len() {
+                let sum = self.tree[count + step - 1];
+                if sum < remaining || (inclusive && sum == remaining) {
+                    count += step;
+                    remaining -= sum;
+                }
+            }
+            step >>= 1;
+        }
+        count
+    }
+}
+
+/// Marker for the content child of a [`VirtualList`], which contains the item entities.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualListContent;
+
+/// An entity that displays an item of a [`VirtualList`]. The list positions these entities; the
+/// app fills them with content when it receives [`VirtualListItemChanged`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct VirtualListItem {
+    /// The [`VirtualList`] entity this item belongs to.
+    pub list: Entity,
+    /// The index of the item this entity displays.
+    pub index: usize,
+}
+
+/// Triggered on a [`VirtualListItem`] entity when it is assigned to an item, either because it
+/// was just spawned or because it was reassigned from an item that scrolled out of view.
+#[derive(Copy, Clone, Debug, PartialEq, EntityEvent)]
+pub struct VirtualListItemChanged {
+    /// The item entity.
+    pub entity: Entity,
+    /// The [`VirtualList`] entity.
+    pub list: Entity,
+    /// The index of the item the entity now displays.
+    pub index: usize,
+}
+
+/// Returns the range of rows that overlap the vertical span `top..bottom`, extended by
+/// `overscan` rows on either side.
+fn visible_rows(
+    rows: &RowHeights,
+    top: f32,
+    bottom: f32,
+    overscan: usize,
+) -> (usize, core::ops::Range<usize>) {
+    let count = rows.len();
+    // The first row whose bottom edge is below `top`.
+    let first = rows.count_up_to(top, true);
+    // The number of rows whose top edge is above `bottom`: the rows whose bottom edge is above
+    // it, and the row that contains it.
+    let end = if bottom > 0. {
+        (rows.count_up_to(bottom, false) + 1).min(count)
+    } else {
+        0
+    }
+    .max(first + 1);
+    (
+        first.min(count),
+        first.saturating_sub(overscan)..(end + overscan).min(count),
+    )
+}
+
+/// Returns the item that contains `entity`, if any.
+fn find_item(
+    mut entity: Entity,
+    q_items: &Query<(&mut VirtualListItem, &mut Node, &ComputedNode), Without<VirtualList>>,
+    q_parents: &Query<&ChildOf>,
+) -> Option<(Entity, VirtualListItem)> {
+    loop {
+        if let Ok((item, _, _)) = q_items.get(entity) {
+            return Some((entity, *item));
+        }
+        entity = q_parents.get(entity).ok()?.parent();
+    }
+}
+
+fn update_virtual_lists(
+    mut commands: Commands,
+    input_focus: Option<Res<InputFocus>>,
+    mut q_lists: Query<(
+        Entity,
+        &VirtualList,
+        &mut VirtualListState,
+        &mut ScrollPosition,
+        &ComputedNode,
+    )>,
+    mut q_items: Query<(&mut VirtualListItem, &mut Node, &ComputedNode), Without<VirtualList>>,
+    mut q_content: Query<&mut Node, (With<VirtualListContent>, Without<VirtualListItem>)>,
+    q_children: Query<&Children>,
+    q_parents: Query<&ChildOf>,
+) {
+    let focused_item = input_focus
+        .as_ref()
+        .and_then(|focus| focus.0)
+        .and_then(|focus| find_item(focus, &q_items, &q_parents));
+    let focus_changed = input_focus.is_some_and(|focus| focus.is_changed());
+
+    for (list_entity, list, mut state, mut scroll_position, list_node) in &mut q_lists {
+        let state = &mut *state;
+        let columns = list.columns.max(1);
+        let content = match state.content {
+            Some(content) if q_content.contains(content) => content,
+            _ => {
+                let content = commands
+                    .spawn((
+                        VirtualListContent,
+                        Node {
+                            width: Val::Percent(100.),
+                            ..Default::default()
+                        },
+                        ChildOf(list_entity),
+                    ))
+                    .id();
+                state.content = Some(content);
+                content
+            }
+        };
+
+        // Lay out the rows, keeping the first visible row in place if rows above it change. All
+        // of the rows are only laid out again when the shape of the list changes, otherwise only
+        // the rows with new measurements are updated.
+        let previous_anchor = state.row_offset(state.first_visible_row);
+        let layout = (list.item_count, columns, list.estimated_row_height);
+        if state.layout != Some(layout) {
+            state.item_heights.resize(list.item_count, None);
+            let heights = (0..list.row_count())
+                .map(|row| state.measured_row_height(row, columns, list.estimated_row_height))
+                .collect();
+            state.rows = RowHeights::from_heights(heights);
+            state.layout = Some(layout);
+        }
+
+        // Measure the items that have been laid out.
+        let existing: Vec<Entity> = q_children
+            .get(content)
+            .map(|children| children.iter().copied().collect())
+            .unwrap_or_default();
+        for &entity in &existing {
+            if let Ok((item, _, node)) = q_items.get(entity)
+                && item.index < list.item_count
+                && node.size().y > 0.
+            {
+                let height = Some(node.size().y * node.inverse_scale_factor);
+                if state.item_heights[item.index] != height {
+                    state.item_heights[item.index] = height;
+                    let row = item.index / columns;
+                    let row_height =
+                        state.measured_row_height(row, columns, list.estimated_row_height);
+                    state.rows.set(row, row_height);
+                }
+            }
+        }
+
+        if let Some(previous_anchor) = previous_anchor
+            && let Some(anchor) = state.row_offset(state.first_visible_row)
+        {
+            scroll_position.y += anchor - previous_anchor;
+        }
+
+        let visible_height =
+            (list_node.size().y - list_node.scrollbar_size.y) * list_node.inverse_scale_factor;
+        let max_scroll = (state.content_height() - visible_height).max(0.);
+
+        // Scroll the focused item into view when it receives focus.
+        let pinned = focused_item.filter(|(_, item)| item.list == list_entity);
+        if focus_changed && let Some((_, item)) = pinned {
+            let row = item.index / columns;
+            if let (Some(top), Some(bottom)) = (state.row_offset(row), state.row_offset(row + 1)) {
+                if top < scroll_position.y {
+                    scroll_position.y = top;
+                } else if bottom > scroll_position.y + visible_height {
+                    scroll_position.y = bottom - visible_height;
+                }
+            }
+        }
+        let top = scroll_position.y.clamp(0., max_scroll);
+        if scroll_position.y != top {
+            scroll_position.y = top;
+        }
+
+        let (first_visible_row, realized_rows) =
+            visible_rows(&state.rows, top, top + visible_height, list.overscan);
+        state.first_visible_row = first_visible_row;
+        let realized =
+            realized_rows.start * columns..(realized_rows.end * columns).min(list.item_count);
+
+        // Keep the entities of items that are still realized, and reassign the others.
+        let mut assigned: Vec<(usize, Entity)> = Vec::new();
+        let mut pool: Vec<Entity> = Vec::new();
+        for &entity in &existing {
+            let Ok((item, _, _)) = q_items.get(entity) else {
+                continue;
+            };
+            let is_pinned = pinned.is_some_and(|(pinned, _)| pinned == entity);
+            if item.index < list.item_count && (realized.contains(&item.index) || is_pinned) {
+                assigned.push((item.index, entity));
+            } else {
+                pool.push(entity);
+            }
+        }
+        let item_position = |index: usize| {
+            let column = index % columns;
+            (
+                Val::Px(state.rows.offset(index / columns)),
+                Val::Percent(column as f32 * 100. / columns as f32),
+                Val::Percent(100. / columns as f32),
+            )
+        };
+        for index in realized {
+            if assigned.iter().any(|(assigned, _)| *assigned == index) {
+                continue;
+            }
+            let item = VirtualListItem {
+                list: list_entity,
+                index,
+            };
+            let entity = match pool.pop() {
+                Some(entity) => {
+                    if let Ok((mut existing, _, _)) = q_items.get_mut(entity) {
+                        *existing = item;
+                    }
+                    entity
+                }
+                None => {
+                    // New entities aren't visible to the queries until the commands are
+                    // applied, so they are positioned when spawned.
+                    let (top, left, width) = item_position(index);
+                    commands
+                        .spawn((
+                            item,
+                            Node {
+                                position_type: PositionType::Absolute,
+                                top,
+                                left,
+                                width,
+                                ..Default::default()
+                            },
+                            ChildOf(content),
+                        ))
+                        .id()
+                }
+            };
+            assigned.push((index, entity));
+            commands.trigger(VirtualListItemChanged {
+                entity,
+                list: list_entity,
+                index,
+            });
+        }
+        for entity in pool {
+            commands.entity(entity).despawn();
+        }
+
+        // Position the items.
+        for &(index, entity) in &assigned {
+            let Ok((_, mut node, _)) = q_items.get_mut(entity) else {
+                continue;
+            };
+            let (top, left, width) = item_position(index);
+            if node.position_type != PositionType::Absolute
+                || node.top != top
+                || node.left != left
+                || node.width != width
+            {
+                node.position_type = PositionType::Absolute;
+                node.top = top;
+                node.left = left;
+                node.width = width;
+            }
+        }
+
+        if let Ok(mut content_node) = q_content.get_mut(content) {
+            let height = Val::Px(state.content_height());
+            if content_node.height != height {
+                content_node.height = height;
+            }
+        }
+
+        // Keep the entities in item order, which tab navigation relies on.
+        assigned.sort_by_key(|(index, _)| *index);
+        let ordered: Vec<Entity> = assigned.iter().map(|(_, entity)| *entity).collect();
+        if ordered != existing {
+            commands.entity(content).replace_children(&ordered);
+        }
+    }
+}
+
+fn virtual_list_on_key_input(
+    mut focused_input: On<FocusedInput<KeyboardInput>>,
+    mut q_lists: Query<(&VirtualListState, &mut ScrollPosition, &ComputedNode), With<VirtualList>>,
+) {
+    let Ok((state, mut scroll_position, node)) = q_lists.get_mut(focused_input.focused_entity)
+    else {
+        return;
+    };
+    let input_event = &focused_input.input;
+    if input_event.state != ButtonState::Pressed {
+        return;
+    }
+    let visible_height = (node.size().y - node.scrollbar_size.y) * node.inverse_scale_factor;
+    let Some(new_position) = key_scroll_position(
+        state,
+        input_event.key_code,
+        scroll_position.y,
+        visible_height,
+    ) else {
+        return;
+    };
+    focused_input.propagate(false);
+    scroll_position.y = new_position;
+}
+
+/// Returns the scroll position a key press scrolls a list to, or `None` if the key doesn't
+/// scroll.
+fn key_scroll_position(
+    state: &VirtualListState,
+    key_code: KeyCode,
+    position: f32,
+    visible_height: f32,
+) -> Option<f32> {
+    let max_scroll = (state.content_height() - visible_height).max(0.);
+    let row = state.row_at(position);
+    let new_position = match key_code {
+        // Scroll to the top of the first visible row if it's partially hidden, otherwise to
+        // the row above it.
+        KeyCode::ArrowUp => match state.row_offset(row) {
+            Some(top) if top < position => top,
+            _ => state.row_offset(row.saturating_sub(1))?,
+        },
+        KeyCode::ArrowDown => state.row_offset(row + 1).unwrap_or(max_scroll),
+        KeyCode::PageUp => position - visible_height,
+        KeyCode::PageDown => position + visible_height,
+        KeyCode::Home => 0.,
+        KeyCode::End => max_scroll,
+        _ => return None,
+    };
+    Some(new_position.clamp(0., max_scroll))
+}
+
+/// Plugin that adds the systems and observers for the [`VirtualList`] widget.
+pub struct VirtualListPlugin;
+
+impl Plugin for VirtualListPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(virtual_list_on_key_input)
+            .add_systems(PostUpdate, update_virtual_lists.before(UiSystems::Layout));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn test_visible_rows() {
+        let rows = RowHeights::from_heights(vec![10., 20., 10., 10.]);
+        assert_eq!(visible_rows(&rows, 0., 15., 0), (0, 0..2));
+        assert_eq!(visible_rows(&rows, 10., 30., 0), (1, 1..2));
+        assert_eq!(visible_rows(&rows, 12., 35., 1), (1, 0..4));
+        assert_eq!(visible_rows(&rows, 0., 0., 0), (0, 0..1));
+        assert_eq!(visible_rows(&RowHeights::default(), 0., 10., 1), (0, 0..0));
+    }
+
+    #[test]
+    fn test_row_heights() {
+        let mut rows = RowHeights::from_heights(vec![10.; 7]);
+        assert_eq!(
+            (0..=7).map(|row| rows.offset(row)).collect::<Vec<_>>(),
+            [0., 10., 20., 30., 40., 50., 60., 70.]
+        );
+        rows.set(2, 30.);
+        assert_eq!(
+            (0..=7).map(|row| rows.offset(row)).collect::<Vec<_>>(),
+            [0., 10., 20., 50., 60., 70., 80., 90.]
+        );
+        assert_eq!(rows.count_up_to(20., true), 2);
+        assert_eq!(rows.count_up_to(20., false), 1);
+        assert_eq!(rows.count_up_to(49., true), 2);
+        assert_eq!(rows.count_up_to(1000., true), 7);
+    }
+
+    #[test]
+    fn test_key_scroll_position() {
+        let state = VirtualListState {
+            rows: RowHeights::from_heights(vec![10., 30., 20., 10., 10., 10.]),
+            ..Default::default()
+        };
+        // The rows are at 0, 10, 40, 60, 70 and 80, and the list is 90 tall.
+        let scroll = |key_code, position| key_scroll_position(&state, key_code, position, 20.);
+        assert_eq!(scroll(KeyCode::ArrowDown, 0.), Some(10.));
+        assert_eq!(scroll(KeyCode::ArrowDown, 10.), Some(40.));
+        assert_eq!(scroll(KeyCode::ArrowDown, 15.), Some(40.));
+        assert_eq!(scroll(KeyCode::ArrowUp, 40.), Some(10.));
+        assert_eq!(scroll(KeyCode::ArrowUp, 15.), Some(10.));
+        assert_eq!(scroll(KeyCode::ArrowUp, 0.), Some(0.));
+        // Scrolling is clamped to the end of the list.
+        assert_eq!(scroll(KeyCode::ArrowDown, 65.), Some(70.));
+        assert_eq!(scroll(KeyCode::End, 0.), Some(70.));
+        assert_eq!(scroll(KeyCode::KeyA, 0.), None);
+    }
+
+    #[test]
+    fn test_items_are_recycled() {
+        let mut app = App::new();
+        app.add_systems(PostUpdate, update_virtual_lists);
+        let list = app
+            .world_mut()
+            .spawn((
+                Node::default(),
+                ComputedNode {
+                    size: Vec2::new(100., 100.),
+                    inverse_scale_factor: 1.,
+                    ..Default::default()
+                },
+                VirtualList::new(1000, 20.).with_overscan(1),
+            ))
+            .id();
+
+        let items = |app: &mut App| {
+            let mut items = app
+                .world_mut()
+                .query::<(Entity, &VirtualListItem)>()
+                .iter(app.world())
+                .map(|(entity, item)| (item.index, entity))
+                .collect::<Vec<_>>();
+            items.sort();
+            items
+        };
+
+        // The scroll position is clamped using the content height, which is only known after the
+        // first update.
+        app.update();
+        let first = items(&mut app);
+        assert_eq!(
+            first.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+            (0..6).collect::<Vec<_>>()
+        );
+
+        app.world_mut().get_mut::<ScrollPosition>(list).unwrap().y = 500.;
+        app.update();
+        let scrolled = items(&mut app);
+        assert_eq!(
+            scrolled.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+            (24..31).collect::<Vec<_>>()
+        );
+        // All of the previous entities were reused.
+        assert!(first
+            .iter()
+            .all(|(_, entity)| scrolled.iter().any(|(_, scrolled)| scrolled == entity)));
+
+        // Items spawned for new rows are positioned right away.
+        app.world_mut()
+            .get_mut::<VirtualList>(list)
+            .unwrap()
+            .overscan = 3;
+        app.update();
+        let node = app
+            .world_mut()
+            .query::<(&VirtualListItem, &Node)>()
+            .iter(app.world())
+            .find(|(item, _)| item.index == 32)
+            .map(|(_, node)| node.clone())
+            .unwrap();
+        assert_eq!(node.top, Val::Px(32. * 20.));
+        assert_eq!(node.width, Val::Percent(100.));
+    }
+}
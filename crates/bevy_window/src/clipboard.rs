@@ -0,0 +1,335 @@
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use bevy_ecs::{
+    message::{Message, MessageWriter},
+    resource::Resource,
+    system::Res,
+};
+use bevy_platform::sync::{Mutex, PoisonError};
+use thiserror::Error;
+
+#[cfg(feature = "bevy_image")]
+use bevy_image::Image;
+
+/// Provides access to a clipboard.
+///
+/// The [`Clipboard`] resource forwards reads and writes to a provider. The default provider is a
+/// [`MemoryClipboard`], which windowing backends may replace with the clipboard of the platform.
+pub trait ClipboardProvider: Send + Sync + 'static {
+    /// Returns the text stored in the clipboard.
+    ///
+    /// Providers that can only read the clipboard asynchronously return
+    /// [`ClipboardError::Unsupported`], and implement [`request_text`](Self::request_text) instead.
+    fn get_text(&mut self) -> Result<String, ClipboardError>;
+
+    /// Replaces the content of the clipboard with the given text.
+    fn set_text(&mut self, text: String) -> Result<(), ClipboardError>;
+
+    /// Requests the text stored in the clipboard, which is sent to `response` once it has been
+    /// read.
+    ///
+    /// By default, the text is read with [`get_text`](Self::get_text) and sent immediately.
+    fn request_text(&mut self, response: ClipboardResponse) {
+        response.send(self.get_text());
+    }
+
+    /// Returns the image stored in the clipboard.
+    #[cfg(feature = "bevy_image")]
+    fn get_image(&mut self) -> Result<Image, ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+
+    /// Replaces the content of the clipboard with the given image.
+    #[cfg(feature = "bevy_image")]
+    fn set_image(&mut self, image: &Image) -> Result<(), ClipboardError> {
+        let _ = image;
+        Err(ClipboardError::Unsupported)
+    }
+}
+
+/// An error when reading or writing the [`Clipboard`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// The clipboard doesn't contain content of the requested kind.
+    #[error("the clipboard doesn't contain content of the requested kind")]
+    ContentNotAvailable,
+    /// The clipboard can't be accessed.
+    #[error("the clipboard can't be accessed")]
+    Unavailable,
+    /// The operation isn't supported by the clipboard of this platform.
+    #[error("the operation isn't supported by the clipboard of this platform")]
+    Unsupported,
+    /// The content couldn't be converted to or from the format of the clipboard.
+    #[error("the content couldn't be converted to or from the format of the clipboard")]
+    ConversionFailed,
+    /// An error reported by the clipboard of the platform.
+    #[error("clipboard error: {0}")]
+    Other(String),
+}
+
+/// Identifies a read of the clipboard started with [`Clipboard::request_text`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ClipboardRequest(u64);
+
+/// Sent when the text requested with [`Clipboard::request_text`] has been read.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardTextReceived {
+    /// The request that was answered.
+    pub request: ClipboardRequest,
+    /// The text stored in the clipboard, or why it couldn't be read.
+    pub result: Result<String, ClipboardError>,
+}
+
+/// Receives the text read for a [`ClipboardRequest`].
+///
+/// Passed to [`ClipboardProvider::request_text`], and can be moved to another task or callback to
+/// respond asynchronously.
+#[derive(Debug)]
+pub struct ClipboardResponse {
+    request: ClipboardRequest,
+    received: Arc<Mutex<Vec<ClipboardTextReceived>>>,
+}
+
+impl ClipboardResponse {
+    /// Returns the request answered by this response.
+    pub fn request(&self) -> ClipboardRequest {
+        self.request
+    }
+
+    /// Sends the result of the request, as a [`ClipboardTextReceived`] message.
+    pub fn send(self, result: Result<String, ClipboardError>) {
+        self.received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(ClipboardTextReceived {
+                request: self.request,
+                result,
+            });
+    }
+}
+
+/// A resource to copy content to and paste content from a clipboard.
+///
+/// Reading the clipboard is asynchronous on some platforms, such as the web. To read text on every
+/// platform, use [`request_text`](Self::request_text) and wait for the matching
+/// [`ClipboardTextReceived`] message.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::Clipboard;
+/// fn copy_score(mut clipboard: ResMut<Clipboard>) {
+///     if let Err(error) = clipboard.set_text("Score: 42") {
+///         eprintln!("Failed to copy the score: {error}");
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct Clipboard {
+    provider: Box<dyn ClipboardProvider>,
+    next_request: u64,
+    received: Arc<Mutex<Vec<ClipboardTextReceived>>>,
+}
+
+impl Clipboard {
+    /// Creates a clipboard backed by the given provider.
+    pub fn new(provider: impl ClipboardProvider) -> Self {
+        Self {
+            provider: Box::new(provider),
+            next_request: 0,
+            received: Arc::default(),
+        }
+    }
+
+    /// Replaces the provider backing this clipboard.
+    pub fn set_provider(&mut self, provider: impl ClipboardProvider) {
+        self.provider = Box::new(provider);
+    }
+
+    /// Returns the text stored in the clipboard.
+    ///
+    /// This fails with [`ClipboardError::Unsupported`] on platforms where the clipboard can only be
+    /// read asynchronously, see [`request_text`](Self::request_text).
+    pub fn get_text(&mut self) -> Result<String, ClipboardError> {
+        self.provider.get_text()
+    }
+
+    /// Replaces the content of the clipboard with the given text.
+    pub fn set_text(&mut self, text: impl Into<String>) -> Result<(), ClipboardError> {
+        self.provider.set_text(text.into())
+    }
+
+    /// Requests the text stored in the clipboard.
+    ///
+    /// The text is sent in a [`ClipboardTextReceived`] message with the returned request, during
+    /// the next [`PreUpdate`](bevy_app::PreUpdate) after it has been read.
+    pub fn request_text(&mut self) -> ClipboardRequest {
+        let request = ClipboardRequest(self.next_request);
+        self.next_request += 1;
+        self.provider.request_text(ClipboardResponse {
+            request,
+            received: self.received.clone(),
+        });
+        request
+    }
+
+    /// Returns the image stored in the clipboard.
+    #[cfg(feature = "bevy_image")]
+    pub fn get_image(&mut self) -> Result<Image, ClipboardError> {
+        self.provider.get_image()
+    }
+
+    /// Replaces the content of the clipboard with the given image.
+    #[cfg(feature = "bevy_image")]
+    pub fn set_image(&mut self, image: &Image) -> Result<(), ClipboardError> {
+        self.provider.set_image(image)
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new(MemoryClipboard::default())
+    }
+}
+
+impl core::fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Clipboard").finish_non_exhaustive()
+    }
+}
+
+/// A [`ClipboardProvider`] that keeps its content in memory, without sharing it with other
+/// applications.
+///
+/// This is the default provider of the [`Clipboard`], and is useful for headless apps and tests.
+#[derive(Default, Debug, Clone)]
+pub struct MemoryClipboard {
+    /// The text stored in the clipboard.
+    pub text: Option<String>,
+    /// The image stored in the clipboard.
+    #[cfg(feature = "bevy_image")]
+    pub image: Option<Image>,
+}
+
+impl PartialEq for MemoryClipboard {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "bevy_image")]
+        {
+            let image_eq = match (&self.image, &other.image) {
+                (Some(image), Some(other_image)) => {
+                    image.texture_descriptor.size == other_image.texture_descriptor.size
+                        && image.texture_descriptor.format == other_image.texture_descriptor.format
+                        && image.data == other_image.data
+                }
+                (None, None) => true,
+                _ => false,
+            };
+            if !image_eq {
+                return false;
+            }
+        }
+        self.text == other.text
+    }
+}
+
+impl Eq for MemoryClipboard {}
+
+impl ClipboardProvider for MemoryClipboard {
+    fn get_text(&mut self) -> Result<String, ClipboardError> {
+        self.text.clone().ok_or(ClipboardError::ContentNotAvailable)
+    }
+
+    fn set_text(&mut self, text: String) -> Result<(), ClipboardError> {
+        self.text = Some(text);
+        #[cfg(feature = "bevy_image")]
+        {
+            self.image = None;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "bevy_image")]
+    fn get_image(&mut self) -> Result<Image, ClipboardError> {
+        self.image.clone().ok_or(ClipboardError::ContentNotAvailable)
+    }
+
+    #[cfg(feature = "bevy_image")]
+    fn set_image(&mut self, image: &Image) -> Result<(), ClipboardError> {
+        self.image = Some(image.clone());
+        self.text = None;
+        Ok(())
+    }
+}
+
+/// Sends a [`ClipboardTextReceived`] message for each request of the [`Clipboard`] that has been
+/// answered.
+pub fn send_clipboard_messages(
+    clipboard: Res<Clipboard>,
+    mut text_received: MessageWriter<ClipboardTextReceived>,
+) {
+    let mut received = clipboard
+        .received
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    text_received.write_batch(received.drain(..));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, PreUpdate};
+    use bevy_ecs::message::Messages;
+
+    #[test]
+    fn request_text() {
+        let mut app = App::new();
+        app.init_resource::<Clipboard>()
+            .add_message::<ClipboardTextReceived>()
+            .add_systems(PreUpdate, send_clipboard_messages);
+
+        let mut clipboard = app.world_mut().resource_mut::<Clipboard>();
+        let empty = clipboard.request_text();
+        clipboard.set_text("hello").unwrap();
+        let hello = clipboard.request_text();
+        app.update();
+
+        let messages = app.world().resource::<Messages<ClipboardTextReceived>>();
+        let received: Vec<_> = messages.iter_current_update_messages().cloned().collect();
+        assert_eq!(
+            received,
+            [
+                ClipboardTextReceived {
+                    request: empty,
+                    result: Err(ClipboardError::ContentNotAvailable),
+                },
+                ClipboardTextReceived {
+                    request: hello,
+                    result: Ok(String::from("hello")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn memory_clipboard_eq() {
+        let mut a = MemoryClipboard::default();
+        let mut b = MemoryClipboard::default();
+        assert_eq!(a, b);
+        a.set_text("hello".into()).unwrap();
+        assert_ne!(a, b);
+        b.set_text("hello".into()).unwrap();
+        assert_eq!(a, b);
+
+        #[cfg(feature = "bevy_image")]
+        {
+            let image = Image::default();
+            a.set_image(&image).unwrap();
+            assert_ne!(a, b);
+            b.set_image(&image).unwrap();
+            assert_eq!(a, b);
+
+            let mut other = image.clone();
+            other.data.as_mut().unwrap()[0] = 0;
+            b.set_image(&other).unwrap();
+            assert_ne!(a, b);
+        }
+    }
+}
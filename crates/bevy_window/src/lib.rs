@@ -17,6 +17,7 @@ extern crate std;
 
 extern crate alloc;
 
+mod clipboard;
 mod cursor;
 mod event;
 mod monitor;
@@ -26,6 +27,7 @@ mod window;
 
 pub use crate::raw_handle::*;
 
+pub use clipboard::*;
 pub use cursor::*;
 pub use event::*;
 pub use monitor::*;
@@ -38,7 +40,7 @@ pub use window::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MonitorSelection,
+        Clipboard, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MonitorSelection,
         VideoModeSelection, Window, WindowMoved, WindowPlugin, WindowPosition,
         WindowResizeConstraints,
     };
@@ -122,7 +124,10 @@ impl Plugin for WindowPlugin {
             .add_message::<FileDragAndDrop>()
             .add_message::<WindowMoved>()
             .add_message::<WindowThemeChanged>()
-            .add_message::<AppLifecycle>();
+            .add_message::<AppLifecycle>()
+            .add_message::<ClipboardTextReceived>()
+            .init_resource::<Clipboard>()
+            .add_systems(PreUpdate, send_clipboard_messages);
 
         if let Some(primary_window) = &self.primary_window {
             let mut entity_commands = app.world_mut().spawn(primary_window.clone());
@@ -0,0 +1,162 @@
+//! Backs the [`Clipboard`] resource with the clipboard of the platform.
+//!
+//! Desktop platforms use [`arboard`], and the web uses the asynchronous clipboard API of the
+//! browser. Other platforms keep the in-memory clipboard of `bevy_window`.
+
+use bevy_app::{App, Plugin};
+use bevy_window::Clipboard;
+
+/// A [`Plugin`] that replaces the provider of the [`Clipboard`] with the clipboard of the platform.
+pub(crate) struct WinitClipboardPlugin;
+
+impl Plugin for WinitClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        cfg_if::cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                app.world_mut()
+                    .get_resource_or_init::<Clipboard>()
+                    .set_provider(web::WebClipboard);
+            } else if #[cfg(not(any(target_os = "android", target_os = "ios")))] {
+                match arboard::Clipboard::new() {
+                    Ok(clipboard) => {
+                        app.world_mut()
+                            .get_resource_or_init::<Clipboard>()
+                            .set_provider(desktop::SystemClipboard(clipboard.into()));
+                    }
+                    Err(error) => {
+                        tracing::warn!(
+                            "Failed to access the system clipboard, an in-memory clipboard is used instead: {error}"
+                        );
+                    }
+                }
+            } else {
+                let _ = app;
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+mod desktop {
+    use std::sync::{Mutex, PoisonError};
+
+    use bevy_window::{ClipboardError, ClipboardProvider};
+
+    /// A [`ClipboardProvider`] using the clipboard of the operating system.
+    pub(super) struct SystemClipboard(pub(super) Mutex<arboard::Clipboard>);
+
+    impl SystemClipboard {
+        fn clipboard(&mut self) -> &mut arboard::Clipboard {
+            self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl ClipboardProvider for SystemClipboard {
+        fn get_text(&mut self) -> Result<String, ClipboardError> {
+            self.clipboard().get_text().map_err(clipboard_error)
+        }
+
+        fn set_text(&mut self, text: String) -> Result<(), ClipboardError> {
+            self.clipboard().set_text(text).map_err(clipboard_error)
+        }
+
+        #[cfg(feature = "clipboard_image")]
+        fn get_image(&mut self) -> Result<bevy_image::Image, ClipboardError> {
+            use bevy_asset::RenderAssetUsages;
+            use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
+
+            let image = self.clipboard().get_image().map_err(clipboard_error)?;
+            Ok(bevy_image::Image::new(
+                Extent3d {
+                    width: image.width as u32,
+                    height: image.height as u32,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                image.bytes.into_owned(),
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::default(),
+            ))
+        }
+
+        #[cfg(feature = "clipboard_image")]
+        fn set_image(&mut self, image: &bevy_image::Image) -> Result<(), ClipboardError> {
+            use wgpu_types::TextureFormat;
+
+            // The clipboard stores images as 8 bit RGBA.
+            let (TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb, Some(data)) =
+                (image.texture_descriptor.format, &image.data)
+            else {
+                return Err(ClipboardError::ConversionFailed);
+            };
+            self.clipboard()
+                .set_image(arboard::ImageData {
+                    width: image.width() as usize,
+                    height: image.height() as usize,
+                    bytes: data.into(),
+                })
+                .map_err(clipboard_error)
+        }
+    }
+
+    fn clipboard_error(error: arboard::Error) -> ClipboardError {
+        match error {
+            arboard::Error::ContentNotAvailable => ClipboardError::ContentNotAvailable,
+            arboard::Error::ClipboardNotSupported => ClipboardError::Unsupported,
+            arboard::Error::ClipboardOccupied => ClipboardError::Unavailable,
+            arboard::Error::ConversionFailure => ClipboardError::ConversionFailed,
+            error => ClipboardError::Other(error.to_string()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use bevy_window::{ClipboardError, ClipboardProvider, ClipboardResponse};
+    use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+    /// A [`ClipboardProvider`] using the asynchronous clipboard API of the browser.
+    ///
+    /// The clipboard can only be read with [`ClipboardProvider::request_text`].
+    pub(super) struct WebClipboard;
+
+    fn clipboard() -> Result<web_sys::Clipboard, ClipboardError> {
+        web_sys::window()
+            .map(|window| window.navigator().clipboard())
+            .ok_or(ClipboardError::Unavailable)
+    }
+
+    impl ClipboardProvider for WebClipboard {
+        fn get_text(&mut self) -> Result<String, ClipboardError> {
+            Err(ClipboardError::Unsupported)
+        }
+
+        fn set_text(&mut self, text: String) -> Result<(), ClipboardError> {
+            let promise = clipboard()?.write_text(&text);
+            spawn_local(async move {
+                if let Err(error) = JsFuture::from(promise).await {
+                    tracing::warn!("Failed to write to the clipboard: {error:?}");
+                }
+            });
+            Ok(())
+        }
+
+        fn request_text(&mut self, response: ClipboardResponse) {
+            let promise = match clipboard() {
+                Ok(clipboard) => clipboard.read_text(),
+                Err(error) => {
+                    response.send(Err(error));
+                    return;
+                }
+            };
+            spawn_local(async move {
+                // Reading fails when the user denies the permission to read the clipboard.
+                let result = match JsFuture::from(promise).await {
+                    Ok(text) => text.as_string().ok_or(ClipboardError::ConversionFailed),
+                    Err(_) => Err(ClipboardError::Unavailable),
+                };
+                response.send(result);
+            });
+        }
+    }
+}
@@ -42,6 +42,8 @@ use crate::{
 };
 
 pub mod accessibility;
+#[cfg(feature = "system_clipboard")]
+mod clipboard;
 pub mod converters;
 mod cursor;
 mod state;
@@ -148,6 +150,8 @@ impl Plugin for WinitPlugin {
 
         app.add_plugins(AccessKitPlugin);
         app.add_plugins(cursor::WinitCursorPlugin);
+        #[cfg(feature = "system_clipboard")]
+        app.add_plugins(clipboard::WinitClipboardPlugin);
 
         app.add_observer(
             |_window: On<Add, Window>, event_loop_proxy: Res<EventLoopProxyWrapper>| -> Result {